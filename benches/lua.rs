@@ -61,6 +61,9 @@ fn bench_add_fields(c: &mut Criterion) {
             Transform::Synchronous(_t) => {
                 unreachable!("no sync transform used in these benches");
             }
+            Transform::MultiOutputTask(_t) => {
+                unreachable!("no multi-output task transform used in these benches");
+            }
             Transform::Task(t) => t.transform_events(Box::pin(rx)),
         };
 
@@ -137,6 +140,9 @@ fn bench_field_filter(c: &mut Criterion) {
             Transform::Synchronous(_t) => {
                 unreachable!("no sync transform used in these benches");
             }
+            Transform::MultiOutputTask(_t) => {
+                unreachable!("no multi-output task transform used in these benches");
+            }
             Transform::Task(t) => t.transform_events(Box::pin(rx)),
         };
 
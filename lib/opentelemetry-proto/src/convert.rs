@@ -5,14 +5,26 @@ use ordered_float::NotNan;
 use std::collections::BTreeMap;
 use vector_core::{
     config::{log_schema, LegacyKey, LogNamespace},
-    event::{Event, LogEvent},
+    event::{
+        metric::{Bucket, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricTags, MetricValue, TraceEvent,
+    },
 };
 use vrl::value::Value;
 
 use super::proto::{
-    common::v1::{any_value::Value as PBValue, KeyValue},
+    common::v1::{any_value::Value as PBValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, SeverityNumber},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        AggregationTemporality, DataPointFlags, ExponentialHistogramDataPoint, HistogramDataPoint,
+        Metric as PBMetric, NumberDataPoint, ResourceMetrics, SummaryDataPoint,
+    },
     resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        ResourceSpans, Span, Status,
+    },
 };
 
 const SOURCE_NAME: &str = "opentelemetry";
@@ -26,6 +38,12 @@ pub const SEVERITY_NUMBER_KEY: &str = "severity_number";
 pub const OBSERVED_TIMESTAMP_KEY: &str = "observed_timestamp";
 pub const DROPPED_ATTRIBUTES_COUNT_KEY: &str = "dropped_attributes_count";
 pub const FLAGS_KEY: &str = "flags";
+pub const SCOPE_KEY: &str = "scope";
+
+/// The prefix of the tags holding resource attributes on converted metrics.
+pub const RESOURCE_TAG_PREFIX: &str = "resource.";
+/// The prefix of the tags holding the instrumentation scope on converted metrics.
+pub const SCOPE_TAG_PREFIX: &str = "scope.";
 
 impl ResourceLogs {
     pub fn into_event_iter(self, log_namespace: LogNamespace) -> impl Iterator<Item = Event> {
@@ -221,3 +239,439 @@ impl ResourceLog {
         log.into()
     }
 }
+
+impl ResourceMetrics {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let mut resource_tags = MetricTags::default();
+        if let Some(resource) = self.resource {
+            insert_attribute_tags(&mut resource_tags, RESOURCE_TAG_PREFIX, resource.attributes);
+        }
+
+        self.scope_metrics
+            .into_iter()
+            .flat_map(move |scope_metrics| {
+                let mut tags = resource_tags.clone();
+                if let Some(scope) = scope_metrics.scope {
+                    insert_scope_tags(&mut tags, scope);
+                }
+
+                scope_metrics
+                    .metrics
+                    .into_iter()
+                    .flat_map(move |metric| metric.into_metrics(&tags))
+            })
+            .map(Event::Metric)
+    }
+}
+
+fn insert_attribute_tags(tags: &mut MetricTags, prefix: &str, attributes: Vec<KeyValue>) {
+    for kv in attributes {
+        if let Some(value) = kv.value.and_then(|av| av.value) {
+            tags.replace(format!("{}{}", prefix, kv.key), tag_value(value));
+        }
+    }
+}
+
+fn insert_scope_tags(tags: &mut MetricTags, scope: InstrumentationScope) {
+    if !scope.name.is_empty() {
+        tags.replace(format!("{}name", SCOPE_TAG_PREFIX), scope.name);
+    }
+    if !scope.version.is_empty() {
+        tags.replace(format!("{}version", SCOPE_TAG_PREFIX), scope.version);
+    }
+    insert_attribute_tags(tags, SCOPE_TAG_PREFIX, scope.attributes);
+}
+
+fn tag_value(value: PBValue) -> String {
+    match value {
+        PBValue::StringValue(v) => v,
+        PBValue::BoolValue(v) => v.to_string(),
+        PBValue::IntValue(v) => v.to_string(),
+        PBValue::DoubleValue(v) => v.to_string(),
+        PBValue::BytesValue(v) => hex::encode(v),
+        value @ (PBValue::ArrayValue(_) | PBValue::KvlistValue(_)) => {
+            Value::from(value).to_string()
+        }
+    }
+}
+
+fn timestamp(time_unix_nano: u64) -> Option<DateTime<Utc>> {
+    (time_unix_nano > 0).then(|| Utc.timestamp_nanos(time_unix_nano as i64))
+}
+
+/// Returns `true` if the data point is flagged as having no recorded value, which OTLP uses as a
+/// staleness marker.
+fn has_no_recorded_value(flags: u32) -> bool {
+    flags & DataPointFlags::NoRecordedValueMask as u32 != 0
+}
+
+fn metric_kind(aggregation_temporality: i32) -> MetricKind {
+    if aggregation_temporality == AggregationTemporality::Delta as i32 {
+        MetricKind::Incremental
+    } else {
+        MetricKind::Absolute
+    }
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/metrics/data-model.md
+impl PBMetric {
+    fn into_metrics(self, tags: &MetricTags) -> Vec<Metric> {
+        let name = self.name;
+        let new_metric = |kind, value, attributes: Vec<KeyValue>, time_unix_nano| {
+            let mut tags = tags.clone();
+            insert_attribute_tags(&mut tags, "", attributes);
+            Metric::new(name.clone(), kind, value)
+                .with_tags((!tags.is_empty()).then_some(tags))
+                .with_timestamp(timestamp(time_unix_nano))
+        };
+
+        match self.data {
+            Some(Data::Gauge(gauge)) => gauge
+                .data_points
+                .into_iter()
+                .filter_map(|point| {
+                    let value = number_value(&point)?;
+                    Some(new_metric(
+                        MetricKind::Absolute,
+                        MetricValue::Gauge { value },
+                        point.attributes,
+                        point.time_unix_nano,
+                    ))
+                })
+                .collect(),
+            Some(Data::Sum(sum)) => {
+                let kind = metric_kind(sum.aggregation_temporality);
+                sum.data_points
+                    .into_iter()
+                    .filter_map(|point| {
+                        let value = number_value(&point)?;
+                        // Only monotonic sums can be represented as counters, as these can't
+                        // decrease.
+                        let value = if sum.is_monotonic {
+                            MetricValue::Counter { value }
+                        } else {
+                            MetricValue::Gauge { value }
+                        };
+                        Some(new_metric(
+                            kind,
+                            value,
+                            point.attributes,
+                            point.time_unix_nano,
+                        ))
+                    })
+                    .collect()
+            }
+            Some(Data::Histogram(histogram)) => {
+                let kind = metric_kind(histogram.aggregation_temporality);
+                histogram
+                    .data_points
+                    .into_iter()
+                    .filter(|point| !has_no_recorded_value(point.flags))
+                    .map(|point| {
+                        let value = histogram_value(&point);
+                        new_metric(kind, value, point.attributes, point.time_unix_nano)
+                    })
+                    .collect()
+            }
+            Some(Data::ExponentialHistogram(histogram)) => {
+                let kind = metric_kind(histogram.aggregation_temporality);
+                histogram
+                    .data_points
+                    .into_iter()
+                    .filter(|point| !has_no_recorded_value(point.flags))
+                    .map(|point| {
+                        let value = exponential_histogram_value(&point);
+                        new_metric(kind, value, point.attributes, point.time_unix_nano)
+                    })
+                    .collect()
+            }
+            Some(Data::Summary(summary)) => summary
+                .data_points
+                .into_iter()
+                .filter(|point| !has_no_recorded_value(point.flags))
+                .map(|point| {
+                    let value = summary_value(&point);
+                    // Summaries are always cumulative.
+                    new_metric(
+                        MetricKind::Absolute,
+                        value,
+                        point.attributes,
+                        point.time_unix_nano,
+                    )
+                })
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn number_value(point: &NumberDataPoint) -> Option<f64> {
+    if has_no_recorded_value(point.flags) {
+        return None;
+    }
+
+    match point.value? {
+        number_data_point::Value::AsDouble(value) => Some(value),
+        number_data_point::Value::AsInt(value) => Some(value as f64),
+    }
+}
+
+fn histogram_value(point: &HistogramDataPoint) -> MetricValue {
+    // There is one more bucket than there are bounds, the last one being unbounded.
+    let upper_limits = point
+        .explicit_bounds
+        .iter()
+        .copied()
+        .chain(std::iter::once(f64::INFINITY));
+    let buckets = upper_limits
+        .zip(point.bucket_counts.iter())
+        .map(|(upper_limit, count)| Bucket {
+            upper_limit,
+            count: *count,
+        })
+        .collect();
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+/// Converts an exponential histogram into an aggregated histogram whose buckets have the
+/// exponential bucket boundaries.
+///
+/// Negative buckets hold values by their absolute value, so they are converted in reverse order and
+/// their upper limit is the lower boundary of the absolute range. Values in the zero bucket are
+/// counted in a bucket with an upper limit of zero.
+fn exponential_histogram_value(point: &ExponentialHistogramDataPoint) -> MetricValue {
+    let base = 2f64.powf(2f64.powi(-point.scale));
+    let mut buckets = Vec::new();
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.negative
+    {
+        buckets.extend(
+            bucket_counts
+                .iter()
+                .enumerate()
+                .rev()
+                .map(|(i, count)| Bucket {
+                    upper_limit: -base.powi(offset + i as i32),
+                    count: *count,
+                }),
+        );
+    }
+
+    buckets.push(Bucket {
+        upper_limit: 0.0,
+        count: point.zero_count,
+    });
+
+    if let Some(Buckets {
+        offset,
+        bucket_counts,
+    }) = &point.positive
+    {
+        buckets.extend(bucket_counts.iter().enumerate().map(|(i, count)| Bucket {
+            upper_limit: base.powi(offset + i as i32 + 1),
+            count: *count,
+        }));
+    }
+
+    MetricValue::AggregatedHistogram {
+        buckets,
+        count: point.count,
+        sum: point.sum.unwrap_or_default(),
+    }
+}
+
+fn summary_value(point: &SummaryDataPoint) -> MetricValue {
+    MetricValue::AggregatedSummary {
+        quantiles: point
+            .quantile_values
+            .iter()
+            .map(|quantile| Quantile {
+                quantile: quantile.quantile,
+                value: quantile.value,
+            })
+            .collect(),
+        count: point.count,
+        sum: point.sum,
+    }
+}
+
+impl ResourceSpans {
+    pub fn into_event_iter(self) -> impl Iterator<Item = Event> {
+        let resource = self.resource;
+        let now = Utc::now();
+
+        self.scope_spans.into_iter().flat_map(move |scope_spans| {
+            let resource = resource.clone();
+            let scope = scope_spans.scope;
+            scope_spans.spans.into_iter().map(move |span| {
+                ResourceSpan {
+                    resource: resource.clone(),
+                    scope: scope.clone(),
+                    span,
+                }
+                .into_event(now)
+            })
+        })
+    }
+}
+
+struct ResourceSpan {
+    resource: Option<Resource>,
+    scope: Option<InstrumentationScope>,
+    span: Span,
+}
+
+fn nanos_into_timestamp(time_unix_nano: u64) -> Value {
+    Value::Timestamp(Utc.timestamp_nanos(time_unix_nano as i64))
+}
+
+fn hex_id(id: Vec<u8>) -> Value {
+    Value::Bytes(Bytes::from(hex::encode(id)))
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/trace/api.md#span
+impl ResourceSpan {
+    fn into_event(self, now: DateTime<Utc>) -> Event {
+        let span = self.span;
+        let mut trace = BTreeMap::new();
+
+        trace.insert(TRACE_ID_KEY.to_owned(), hex_id(span.trace_id));
+        trace.insert(SPAN_ID_KEY.to_owned(), hex_id(span.span_id));
+        trace.insert("parent_span_id".to_owned(), hex_id(span.parent_span_id));
+        trace.insert("trace_state".to_owned(), span.trace_state.into());
+        trace.insert("name".to_owned(), span.name.into());
+        trace.insert("kind".to_owned(), span.kind.into());
+        trace.insert(
+            "start_time_unix_nano".to_owned(),
+            nanos_into_timestamp(span.start_time_unix_nano),
+        );
+        trace.insert(
+            "end_time_unix_nano".to_owned(),
+            nanos_into_timestamp(span.end_time_unix_nano),
+        );
+        if !span.attributes.is_empty() {
+            trace.insert(
+                ATTRIBUTES_KEY.to_owned(),
+                kv_list_into_value(span.attributes),
+            );
+        }
+        trace.insert(
+            DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+            span.dropped_attributes_count.into(),
+        );
+        if !span.events.is_empty() {
+            trace.insert(
+                "events".to_owned(),
+                Value::Array(span.events.into_iter().map(Into::into).collect()),
+            );
+        }
+        trace.insert(
+            "dropped_events_count".to_owned(),
+            span.dropped_events_count.into(),
+        );
+        if !span.links.is_empty() {
+            trace.insert(
+                "links".to_owned(),
+                Value::Array(span.links.into_iter().map(Into::into).collect()),
+            );
+        }
+        trace.insert(
+            "dropped_links_count".to_owned(),
+            span.dropped_links_count.into(),
+        );
+        trace.insert(
+            "status".to_owned(),
+            Value::from(span.status.unwrap_or_default()),
+        );
+
+        if let Some(resource) = self.resource {
+            if !resource.attributes.is_empty() {
+                trace.insert(
+                    RESOURCE_KEY.to_owned(),
+                    kv_list_into_value(resource.attributes),
+                );
+            }
+        }
+        if let Some(scope) = self.scope {
+            trace.insert(SCOPE_KEY.to_owned(), scope.into());
+        }
+        trace.insert("ingest_timestamp".to_owned(), Value::Timestamp(now));
+
+        TraceEvent::from(trace).into()
+    }
+}
+
+impl From<SpanEvent> for Value {
+    fn from(event: SpanEvent) -> Self {
+        Value::Object(BTreeMap::from([
+            ("name".to_owned(), event.name.into()),
+            (
+                "time_unix_nano".to_owned(),
+                nanos_into_timestamp(event.time_unix_nano),
+            ),
+            (
+                ATTRIBUTES_KEY.to_owned(),
+                kv_list_into_value(event.attributes),
+            ),
+            (
+                DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+                event.dropped_attributes_count.into(),
+            ),
+        ]))
+    }
+}
+
+impl From<Link> for Value {
+    fn from(link: Link) -> Self {
+        Value::Object(BTreeMap::from([
+            (TRACE_ID_KEY.to_owned(), hex_id(link.trace_id)),
+            (SPAN_ID_KEY.to_owned(), hex_id(link.span_id)),
+            ("trace_state".to_owned(), link.trace_state.into()),
+            (
+                ATTRIBUTES_KEY.to_owned(),
+                kv_list_into_value(link.attributes),
+            ),
+            (
+                DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+                link.dropped_attributes_count.into(),
+            ),
+        ]))
+    }
+}
+
+impl From<Status> for Value {
+    fn from(status: Status) -> Self {
+        Value::Object(BTreeMap::from([
+            ("message".to_owned(), status.message.into()),
+            ("code".to_owned(), status.code.into()),
+        ]))
+    }
+}
+
+impl From<InstrumentationScope> for Value {
+    fn from(scope: InstrumentationScope) -> Self {
+        let mut value = BTreeMap::from([
+            ("name".to_owned(), scope.name.into()),
+            ("version".to_owned(), scope.version.into()),
+            (
+                DROPPED_ATTRIBUTES_COUNT_KEY.to_owned(),
+                scope.dropped_attributes_count.into(),
+            ),
+        ]);
+        if !scope.attributes.is_empty() {
+            value.insert(
+                ATTRIBUTES_KEY.to_owned(),
+                kv_list_into_value(scope.attributes),
+            );
+        }
+        Value::Object(value)
+    }
+}
@@ -31,6 +31,7 @@ pub enum Transform {
     Function(Box<dyn FunctionTransform>),
     Synchronous(Box<dyn SyncTransform>),
     Task(Box<dyn TaskTransform<EventArray>>),
+    MultiOutputTask(Box<dyn MultiOutputTaskTransform>),
 }
 
 impl Transform {
@@ -81,6 +82,17 @@ impl Transform {
         Transform::Task(Box::new(WrapEventTask(v)))
     }
 
+    /// Create a new task transform that can write to multiple outputs.
+    ///
+    /// Like [`SyncTransform`], the outputs must be known in advance and returned via
+    /// `TransformConfig::outputs`.
+    ///
+    /// **Note:** You should prefer to implement [`SyncTransform`] over this
+    /// where possible.
+    pub fn multi_output_task(v: impl MultiOutputTaskTransform + 'static) -> Self {
+        Transform::MultiOutputTask(Box::new(v))
+    }
+
     /// Transmute the inner transform into a task transform.
     ///
     /// # Panics
@@ -139,6 +151,19 @@ pub trait TaskTransform<T: EventContainer + 'static>: Send + 'static {
     }
 }
 
+/// Task transforms that write to multiple outputs.
+///
+/// Each item of the returned stream is a set of buffers, created from the given outputs, whose
+/// events are sent to their respective outputs. Like with [`SyncTransform`], attempting to send
+/// to any output not registered in advance is considered a bug and will cause a panic.
+pub trait MultiOutputTaskTransform: Send + 'static {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = EventArray> + Send>>,
+        outputs: Vec<config::TransformOutput>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>>;
+}
+
 /// Broader than the simple [`FunctionTransform`], this trait allows transforms to write to
 /// multiple outputs. Those outputs must be known in advanced and returned via
 /// `TransformConfig::outputs`. Attempting to send to any output not registered in advance is
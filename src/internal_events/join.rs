@@ -0,0 +1,16 @@
+use vector_core::{config::OutputId, internal_event::InternalEvent};
+
+#[derive(Debug)]
+pub struct JoinUnknownInput<'a> {
+    pub input: Option<&'a OutputId>,
+}
+
+impl<'a> InternalEvent for JoinUnknownInput<'a> {
+    fn emit(self) {
+        warn!(
+            message = "Received event from an input that is neither the left nor the right input. Sending it to the unmatched output.",
+            input = ?self.input.map(ToString::to_string),
+            internal_log_rate_limit = true,
+        );
+    }
+}
@@ -3,7 +3,7 @@ use vector_core::{internal_event::InternalEvent, update_counter};
 use vrl::path::OwnedTargetPath;
 
 use vector_common::{
-    internal_event::{error_stage, error_type},
+    internal_event::{error_stage, error_type, ComponentEventsDropped, UNINTENTIONAL},
     json_size::JsonSize,
 };
 
@@ -111,6 +111,62 @@ impl InternalEvent for KafkaReadError {
     }
 }
 
+#[derive(Debug)]
+pub struct KafkaSchemaRegistryError {
+    pub error: crate::kafka::schema_registry::SchemaRegistryError,
+    /// If set to true, the message couldn't be decoded and was dropped, rather than retried.
+    pub drop_event: bool,
+}
+
+impl InternalEvent for KafkaSchemaRegistryError {
+    fn emit(self) {
+        error!(
+            message = "Failed to decode message with the schema registry.",
+            error = %self.error,
+            error_code = "kafka_schema_registry",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_schema_registry",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+        if self.drop_event {
+            emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+                count: 1,
+                reason: "Failed to decode message with the schema registry.",
+            });
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct KafkaTransactionError {
+    pub error: rdkafka::error::KafkaError,
+}
+
+impl InternalEvent for KafkaTransactionError {
+    fn emit(self) {
+        error!(
+            message = "Kafka transaction failed.",
+            error = %self.error,
+            error_code = "kafka_transaction",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "kafka_transaction",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+}
+
 #[derive(Debug)]
 pub struct KafkaStatisticsReceived<'a> {
     pub statistics: &'a rdkafka::Statistics,
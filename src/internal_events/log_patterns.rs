@@ -0,0 +1,56 @@
+use std::{io::Error, path::Path};
+
+use metrics::counter;
+use vector_common::internal_event::{error_stage, error_type};
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct LogPatternsLoadError<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
+impl<'a> InternalEvent for LogPatternsLoadError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed loading persisted log patterns, starting without them.",
+            path = %self.path.display(),
+            error = %self.error,
+            error_code = "loading_patterns",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::PROCESSING,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "loading_patterns",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct LogPatternsPersistError<'a> {
+    pub path: &'a Path,
+    pub error: Error,
+}
+
+impl<'a> InternalEvent for LogPatternsPersistError<'a> {
+    fn emit(self) {
+        error!(
+            message = "Failed persisting log patterns.",
+            path = %self.path.display(),
+            error = %self.error,
+            error_code = "persisting_patterns",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "persisting_patterns",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
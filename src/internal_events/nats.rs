@@ -0,0 +1,29 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+use vrl::path::OwnedTargetPath;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct NatsHeaderExtractionError<'a> {
+    pub header_field: &'a OwnedTargetPath,
+}
+
+impl InternalEvent for NatsHeaderExtractionError<'_> {
+    fn emit(self) {
+        error!(
+            message = "Failed to extract header. Value should be a map of String -> Bytes.",
+            error_code = "extracting_header",
+            error_type = error_type::PARSER_FAILED,
+            stage = error_stage::PROCESSING,
+            header_field = self.header_field.to_string(),
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "extracting_header",
+            "error_type" => error_type::PARSER_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+    }
+}
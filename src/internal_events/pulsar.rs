@@ -56,3 +56,51 @@ impl<F: std::fmt::Display> InternalEvent for PulsarPropertyExtractionError<F> {
         );
     }
 }
+
+#[derive(Debug)]
+pub struct PulsarReceiveError {
+    pub error: vector_common::Error,
+}
+
+impl InternalEvent for PulsarReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Failed to read message.",
+            error = %self.error,
+            error_code = "reading_message",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "reading_message",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
+
+#[derive(Debug)]
+pub struct PulsarAckError {
+    pub error: vector_common::Error,
+}
+
+impl InternalEvent for PulsarAckError {
+    fn emit(self) {
+        error!(
+            message = "Unable to acknowledge message.",
+            error = %self.error,
+            error_code = "pulsar_ack_error",
+            error_type = error_type::ACKNOWLEDGMENT_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "pulsar_ack_error",
+            "error_type" => error_type::ACKNOWLEDGMENT_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct RedactionsPerformed<'a> {
+    pub detector: &'a str,
+    pub count: usize,
+}
+
+impl<'a> InternalEvent for RedactionsPerformed<'a> {
+    fn emit(self) {
+        trace!(
+            message = "Redacted sensitive values.",
+            detector = self.detector,
+            count = self.count,
+        );
+        counter!(
+            "redactions_total", self.count as u64,
+            "detector" => self.detector.to_string(),
+        );
+    }
+}
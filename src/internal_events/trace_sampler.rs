@@ -0,0 +1,16 @@
+use vector_common::internal_event::{ComponentEventsDropped, Count, Registered, INTENTIONAL};
+
+use crate::register;
+
+vector_common::registered_event! (
+    TraceSamplerEventsDropped => {
+        events_dropped: Registered<ComponentEventsDropped<'static, INTENTIONAL>>
+            = register!(ComponentEventsDropped::<INTENTIONAL>::from(
+                "Trace not kept by any sampling policy."
+            )),
+    }
+
+    fn emit(&self, data: Count) {
+        self.events_dropped.emit(data);
+    }
+);
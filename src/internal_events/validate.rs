@@ -0,0 +1,36 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type, ComponentEventsDropped, INTENTIONAL};
+
+#[derive(Debug)]
+pub struct ValidationFailed {
+    pub reason: &'static str,
+    pub error_count: usize,
+    /// If set to true, the validate transform has dropped the event instead of rerouting it.
+    pub event_dropped: bool,
+}
+
+impl InternalEvent for ValidationFailed {
+    fn emit(self) {
+        warn!(
+            message = "Event failed validation.",
+            reason = self.reason,
+            error_count = self.error_count,
+            error_type = error_type::CONDITION_FAILED,
+            stage = error_stage::PROCESSING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_type" => error_type::CONDITION_FAILED,
+            "stage" => error_stage::PROCESSING,
+        );
+        if self.event_dropped {
+            emit!(ComponentEventsDropped::<INTENTIONAL> {
+                count: 1,
+                reason: "Event failed validation.",
+            });
+        }
+    }
+}
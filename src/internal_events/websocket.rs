@@ -89,3 +89,31 @@ impl InternalEvent for WsConnectionError {
         Some("WsConnectionError")
     }
 }
+
+#[derive(Debug)]
+pub struct WsReceiveError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsReceiveError {
+    fn emit(self) {
+        error!(
+            message = "Error receiving from WebSocket server.",
+            error = %self.error,
+            error_code = "ws_receive_error",
+            error_type = error_type::READER_FAILED,
+            stage = error_stage::RECEIVING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_receive_error",
+            "error_type" => error_type::READER_FAILED,
+            "stage" => error_stage::RECEIVING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsReceiveError")
+    }
+}
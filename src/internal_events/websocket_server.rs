@@ -0,0 +1,94 @@
+use std::{error::Error, fmt::Debug, net::SocketAddr};
+
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+use vector_common::internal_event::{error_stage, error_type};
+
+#[derive(Debug)]
+pub struct WsListenerConnectionEstablished {
+    pub client: SocketAddr,
+}
+
+impl InternalEvent for WsListenerConnectionEstablished {
+    fn emit(self) {
+        debug!(message = "WebSocket client connected.", client = %self.client);
+        counter!("connection_established_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionEstablished")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerConnectionShutdown {
+    pub client: SocketAddr,
+}
+
+impl InternalEvent for WsListenerConnectionShutdown {
+    fn emit(self) {
+        debug!(message = "WebSocket client disconnected.", client = %self.client);
+        counter!("connection_shutdown_total", 1);
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionShutdown")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerConnectionError {
+    pub error: Box<dyn Error + Send + Sync>,
+}
+
+impl InternalEvent for WsListenerConnectionError {
+    fn emit(self) {
+        error!(
+            message = "Failed to accept WebSocket client.",
+            error = %self.error,
+            error_code = "ws_listener_connection_failed",
+            error_type = error_type::CONNECTION_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_listener_connection_failed",
+            "error_type" => error_type::CONNECTION_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerConnectionError")
+    }
+}
+
+#[derive(Debug)]
+pub struct WsListenerSendError {
+    pub error: tokio_tungstenite::tungstenite::Error,
+}
+
+impl InternalEvent for WsListenerSendError {
+    fn emit(self) {
+        error!(
+            message = "WebSocket message send error.",
+            error = %self.error,
+            error_code = "ws_listener_send_error",
+            error_type = error_type::WRITER_FAILED,
+            stage = error_stage::SENDING,
+            internal_log_rate_limit = true,
+        );
+        counter!(
+            "component_errors_total", 1,
+            "error_code" => "ws_listener_send_error",
+            "error_type" => error_type::WRITER_FAILED,
+            "stage" => error_stage::SENDING,
+        );
+    }
+
+    fn name(&self) -> Option<&'static str> {
+        Some("WsListenerSendError")
+    }
+}
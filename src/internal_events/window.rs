@@ -0,0 +1,16 @@
+use vector_common::internal_event::{ComponentEventsDropped, Count, Registered, INTENTIONAL};
+
+use crate::register;
+
+vector_common::registered_event! (
+    WindowEventsDropped => {
+        events_dropped: Registered<ComponentEventsDropped<'static, INTENTIONAL>>
+            = register!(ComponentEventsDropped::<INTENTIONAL>::from(
+                "Events fell outside of a triggered window."
+            )),
+    }
+
+    fn emit(&self, data: Count) {
+        self.events_dropped.emit(data);
+    }
+);
@@ -0,0 +1,569 @@
+//! Support for the [Confluent Schema Registry][registry] and its wire format.
+//!
+//! Messages in the wire format start with a magic byte, followed by the ID of their schema in the
+//! registry as a big-endian 32-bit integer, and the payload encoded with that schema.
+//!
+//! [registry]: https://docs.confluent.io/platform/current/schema-registry/index.html
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{Arc, Mutex},
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
+use http::{header::CONTENT_TYPE, Method, Request, StatusCode, Uri};
+use hyper::Body;
+use ordered_float::NotNan;
+use percent_encoding::{AsciiSet, CONTROLS};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vrl::value::Value;
+
+use crate::{
+    config::ProxyConfig,
+    http::{Auth, HttpClient, HttpError},
+    tls::{TlsConfig, TlsSettings},
+};
+
+/// The first byte of messages in the Confluent wire format.
+const MAGIC_BYTE: u8 = 0;
+
+/// The length of the magic byte and schema ID that prefix the payload.
+const HEADER_LEN: usize = 5;
+
+/// The characters escaped in subject names, which are used as a segment of the request paths.
+const PATH_SEGMENT: &AsciiSet = &CONTROLS
+    .add(b' ')
+    .add(b'"')
+    .add(b'#')
+    .add(b'%')
+    .add(b'/')
+    .add(b'<')
+    .add(b'>')
+    .add(b'?')
+    .add(b'`')
+    .add(b'{')
+    .add(b'}');
+
+const CONTENT_TYPE_SCHEMA_REGISTRY: &str = "application/vnd.schemaregistry.v1+json";
+
+#[derive(Debug, Snafu)]
+pub enum SchemaRegistryError {
+    #[snafu(display("Invalid schema registry URL {:?}: {}", url, source))]
+    InvalidUrl {
+        url: String,
+        source: http::uri::InvalidUri,
+    },
+    #[snafu(display("Schema registry request failed: {}", source))]
+    Request { source: HttpError },
+    #[snafu(display("Failed to read the schema registry response: {}", source))]
+    ReadResponse { source: hyper::Error },
+    #[snafu(display("Schema registry responded with {}: {}", status, body))]
+    UnexpectedStatus { status: StatusCode, body: String },
+    #[snafu(display("Invalid schema registry response: {}", source))]
+    ParseResponse { source: serde_json::Error },
+    #[snafu(display("Unsupported schema type {:?} for schema {}.", schema_type, id))]
+    UnsupportedSchemaType { id: u32, schema_type: String },
+    #[snafu(display("Invalid Avro schema {}: {}", id, source))]
+    InvalidAvroSchema { id: u32, source: apache_avro::Error },
+    #[snafu(display("Failed to decode Avro payload: {}", source))]
+    AvroDecode { source: apache_avro::Error },
+    #[snafu(display("Failed to decode JSON payload: {}", source))]
+    JsonDecode { source: serde_json::Error },
+    #[snafu(display("Invalid Protobuf message indexes."))]
+    ProtobufMessageIndexes,
+}
+
+impl SchemaRegistryError {
+    /// Returns whether the error is caused by the registry being unavailable, in which case the
+    /// request may succeed when retried.
+    pub fn is_retriable(&self) -> bool {
+        match self {
+            Self::Request { .. } | Self::ReadResponse { .. } => true,
+            Self::UnexpectedStatus { status, .. } => {
+                status.is_server_error() || *status == StatusCode::TOO_MANY_REQUESTS
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Configuration for the Confluent Schema Registry.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct SchemaRegistryConfig {
+    /// The URL of the schema registry.
+    #[configurable(metadata(docs::examples = "http://localhost:8081"))]
+    pub url: String,
+
+    #[configurable(derived)]
+    pub auth: Option<Auth>,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsConfig>,
+}
+
+impl SchemaRegistryConfig {
+    pub fn build(&self, proxy: &ProxyConfig) -> crate::Result<SchemaRegistryClient> {
+        self.url
+            .parse::<Uri>()
+            .context(InvalidUrlSnafu { url: &self.url })?;
+        let tls = TlsSettings::from_options(&self.tls)?;
+        let client = HttpClient::new(tls, proxy)?;
+
+        Ok(SchemaRegistryClient {
+            client,
+            url: self.url.trim_end_matches('/').to_owned(),
+            auth: self.auth.clone(),
+            schemas: Default::default(),
+            subjects: Default::default(),
+        })
+    }
+}
+
+/// The type of a schema in the registry.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum SchemaType {
+    Avro,
+    Json,
+    Protobuf,
+}
+
+/// A schema fetched from the registry.
+#[derive(Debug)]
+pub enum RegisteredSchema {
+    Avro(apache_avro::Schema),
+    Json,
+    Protobuf,
+}
+
+/// The payload of a message, decoded according to its schema in the registry.
+#[derive(Debug, PartialEq)]
+pub enum DecodedPayload {
+    /// The payload was fully decoded with its schema.
+    Value(Value),
+
+    /// The payload must be decoded by the configured decoder.
+    ///
+    /// This is the case for messages that are not in the wire format, and for Protobuf messages,
+    /// whose schemas are not compiled by the client.
+    Raw(Bytes),
+}
+
+#[derive(Deserialize)]
+struct SchemaResponse {
+    schema: String,
+    #[serde(rename = "schemaType", default)]
+    schema_type: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SchemaRequest<'a> {
+    schema: &'a str,
+    #[serde(rename = "schemaType")]
+    schema_type: SchemaType,
+}
+
+#[derive(Deserialize)]
+struct IdResponse {
+    id: u32,
+}
+
+/// A client of the schema registry, caching the schemas and IDs it resolves.
+#[derive(Clone)]
+pub struct SchemaRegistryClient {
+    client: HttpClient,
+    url: String,
+    auth: Option<Auth>,
+    schemas: Arc<Mutex<HashMap<u32, Arc<RegisteredSchema>>>>,
+    subjects: Arc<Mutex<HashMap<String, u32>>>,
+}
+
+impl SchemaRegistryClient {
+    /// Returns the schema with the given ID.
+    pub async fn schema(&self, id: u32) -> Result<Arc<RegisteredSchema>, SchemaRegistryError> {
+        if let Some(schema) = self.schemas.lock().unwrap().get(&id) {
+            return Ok(Arc::clone(schema));
+        }
+
+        let response: SchemaResponse = self
+            .request(Method::GET, &format!("/schemas/ids/{}", id), None)
+            .await?;
+        // The schema type is omitted for Avro schemas.
+        let schema = match response.schema_type.as_deref() {
+            None | Some("AVRO") => RegisteredSchema::Avro(
+                apache_avro::Schema::parse_str(&response.schema)
+                    .context(InvalidAvroSchemaSnafu { id })?,
+            ),
+            Some("JSON") => RegisteredSchema::Json,
+            Some("PROTOBUF") => RegisteredSchema::Protobuf,
+            Some(schema_type) => {
+                return Err(SchemaRegistryError::UnsupportedSchemaType {
+                    id,
+                    schema_type: schema_type.to_owned(),
+                })
+            }
+        };
+
+        let schema = Arc::new(schema);
+        self.schemas.lock().unwrap().insert(id, Arc::clone(&schema));
+        Ok(schema)
+    }
+
+    /// Returns the ID of `schema` under `subject`, registering it first if `register` is set.
+    pub async fn schema_id(
+        &self,
+        subject: &str,
+        schema: &str,
+        schema_type: SchemaType,
+        register: bool,
+    ) -> Result<u32, SchemaRegistryError> {
+        if let Some(id) = self.subjects.lock().unwrap().get(subject) {
+            return Ok(*id);
+        }
+
+        let subject_path = percent_encoding::utf8_percent_encode(subject, PATH_SEGMENT);
+        let path = if register {
+            format!("/subjects/{}/versions", subject_path)
+        } else {
+            format!("/subjects/{}", subject_path)
+        };
+        let body = serde_json::to_vec(&SchemaRequest {
+            schema,
+            schema_type,
+        })
+        .expect("Serializing a schema request can't fail.");
+        let response: IdResponse = self.request(Method::POST, &path, Some(body)).await?;
+
+        self.subjects
+            .lock()
+            .unwrap()
+            .insert(subject.to_owned(), response.id);
+        Ok(response.id)
+    }
+
+    /// Decodes a message payload, resolving its schema if it is in the wire format.
+    pub async fn decode(&self, payload: &[u8]) -> Result<DecodedPayload, SchemaRegistryError> {
+        let Some((id, mut data)) = split_wire_format(payload) else {
+            return Ok(DecodedPayload::Raw(Bytes::copy_from_slice(payload)));
+        };
+
+        match &*self.schema(id).await? {
+            RegisteredSchema::Avro(schema) => {
+                let value = apache_avro::from_avro_datum(schema, &mut data, None)
+                    .context(AvroDecodeSnafu)?;
+                Ok(DecodedPayload::Value(avro_to_vrl(value)))
+            }
+            RegisteredSchema::Json => serde_json::from_slice::<serde_json::Value>(data)
+                .map(|value| DecodedPayload::Value(Value::from(value)))
+                .context(JsonDecodeSnafu),
+            RegisteredSchema::Protobuf => skip_message_indexes(data)
+                .map(|data| DecodedPayload::Raw(Bytes::copy_from_slice(data))),
+        }
+    }
+
+    async fn request<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<Vec<u8>>,
+    ) -> Result<T, SchemaRegistryError> {
+        let uri = format!("{}{}", self.url, path);
+        let mut request = Request::builder()
+            .method(method)
+            .uri(uri.as_str())
+            .header(CONTENT_TYPE, CONTENT_TYPE_SCHEMA_REGISTRY)
+            .body(body.map(Body::from).unwrap_or_else(Body::empty))
+            .expect("Schema registry requests are always valid.");
+        if let Some(auth) = &self.auth {
+            auth.apply(&mut request);
+        }
+
+        let response = self.client.send(request).await.context(RequestSnafu)?;
+        let status = response.status();
+        let body = hyper::body::to_bytes(response.into_body())
+            .await
+            .context(ReadResponseSnafu)?;
+        if !status.is_success() {
+            return Err(SchemaRegistryError::UnexpectedStatus {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            });
+        }
+        serde_json::from_slice(&body).context(ParseResponseSnafu)
+    }
+}
+
+/// Splits a payload in the wire format into its schema ID and data.
+pub fn split_wire_format(payload: &[u8]) -> Option<(u32, &[u8])> {
+    if payload.len() < HEADER_LEN || payload[0] != MAGIC_BYTE {
+        return None;
+    }
+    let id = u32::from_be_bytes(payload[1..HEADER_LEN].try_into().unwrap());
+    Some((id, &payload[HEADER_LEN..]))
+}
+
+/// Prefixes a payload with the wire format header for the schema with the given ID.
+pub fn encode_wire_format(id: u32, payload: &[u8]) -> Bytes {
+    let mut buffer = BytesMut::with_capacity(HEADER_LEN + payload.len());
+    buffer.put_u8(MAGIC_BYTE);
+    buffer.put_u32(id);
+    buffer.put_slice(payload);
+    buffer.freeze()
+}
+
+/// Skips the indexes of the message type that precede Protobuf payloads.
+///
+/// The indexes are encoded as a zig-zag varint count followed by that many zig-zag varints, with
+/// a single zero byte standing for the first message type of the schema.
+fn skip_message_indexes(mut data: &[u8]) -> Result<&[u8], SchemaRegistryError> {
+    fn read_varint(data: &mut &[u8]) -> Result<i64, SchemaRegistryError> {
+        let mut value = 0_u64;
+        for shift in (0..64).step_by(7) {
+            let (&byte, rest) = data
+                .split_first()
+                .ok_or(SchemaRegistryError::ProtobufMessageIndexes)?;
+            *data = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok((value >> 1) as i64 ^ -((value & 1) as i64));
+            }
+        }
+        Err(SchemaRegistryError::ProtobufMessageIndexes)
+    }
+
+    let count = read_varint(&mut data)?;
+    if count < 0 {
+        return Err(SchemaRegistryError::ProtobufMessageIndexes);
+    }
+    for _ in 0..count {
+        read_varint(&mut data)?;
+    }
+    Ok(data)
+}
+
+fn avro_to_vrl(value: apache_avro::types::Value) -> Value {
+    use apache_avro::types::Value as AvroValue;
+    use chrono::{TimeZone, Utc};
+
+    match value {
+        AvroValue::Null => Value::Null,
+        AvroValue::Boolean(v) => Value::from(v),
+        AvroValue::Int(v) | AvroValue::Date(v) | AvroValue::TimeMillis(v) => Value::from(v),
+        AvroValue::Long(v) | AvroValue::TimeMicros(v) => Value::from(v),
+        AvroValue::Float(v) => NotNan::new(f64::from(v)).map_or(Value::Null, Value::Float),
+        AvroValue::Double(v) => NotNan::new(v).map_or(Value::Null, Value::Float),
+        AvroValue::Bytes(v) | AvroValue::Fixed(_, v) => Value::from(Bytes::from(v)),
+        AvroValue::String(v) | AvroValue::Enum(_, v) => Value::from(v),
+        AvroValue::Uuid(v) => Value::from(v.to_string()),
+        AvroValue::Union(_, v) => avro_to_vrl(*v),
+        AvroValue::Array(v) => Value::Array(v.into_iter().map(avro_to_vrl).collect()),
+        AvroValue::Map(v) => Value::Object(
+            v.into_iter()
+                .map(|(key, value)| (key, avro_to_vrl(value)))
+                .collect(),
+        ),
+        AvroValue::Record(v) => Value::Object(
+            v.into_iter()
+                .map(|(key, value)| (key, avro_to_vrl(value)))
+                .collect::<BTreeMap<_, _>>(),
+        ),
+        AvroValue::TimestampMillis(v) | AvroValue::LocalTimestampMillis(v) => Utc
+            .timestamp_millis_opt(v)
+            .single()
+            .map_or(Value::Null, Value::from),
+        AvroValue::TimestampMicros(v) | AvroValue::LocalTimestampMicros(v) => {
+            Value::from(Utc.timestamp_nanos(v.saturating_mul(1000)))
+        }
+        // Decimals and durations are kept in their binary representation.
+        other => {
+            apache_avro::from_value::<serde_json::Value>(&other).map_or(Value::Null, Value::from)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::Response;
+    use vrl::btreemap;
+
+    use super::*;
+    use crate::test_util::http::spawn_blackhole_http_server;
+
+    const AVRO_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "Log",
+        "namespace": "com.example",
+        "fields": [
+            {"name": "message", "type": "string"},
+            {"name": "count", "type": "long"}
+        ]
+    }"#;
+
+    async fn registry(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let authorized = request.headers().get(http::header::AUTHORIZATION).is_some();
+        let body = match (request.method(), request.uri().path()) {
+            _ if !authorized => {
+                return Ok(Response::builder()
+                    .status(StatusCode::UNAUTHORIZED)
+                    .body(Body::empty())
+                    .unwrap())
+            }
+            (&Method::GET, "/schemas/ids/1") => {
+                serde_json::json!({ "schema": AVRO_SCHEMA }).to_string()
+            }
+            (&Method::GET, "/schemas/ids/2") => {
+                serde_json::json!({ "schema": "{}", "schemaType": "JSON" }).to_string()
+            }
+            (&Method::GET, "/schemas/ids/3") => {
+                serde_json::json!({ "schema": "syntax = \"proto3\";", "schemaType": "PROTOBUF" })
+                    .to_string()
+            }
+            (&Method::POST, "/subjects/logs-value/versions") => r#"{"id": 1}"#.to_owned(),
+            _ => {
+                return Ok(Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from(r#"{"error_code": 40403}"#))
+                    .unwrap())
+            }
+        };
+        Ok(Response::new(Body::from(body)))
+    }
+
+    async fn client(auth: bool) -> SchemaRegistryClient {
+        let uri = spawn_blackhole_http_server(registry).await;
+        SchemaRegistryConfig {
+            url: uri.to_string(),
+            auth: auth.then(|| Auth::Basic {
+                user: "user".to_owned(),
+                password: "password".to_owned().into(),
+            }),
+            tls: None,
+        }
+        .build(&ProxyConfig::default())
+        .unwrap()
+    }
+
+    #[test]
+    fn wire_format_round_trips() {
+        let payload = encode_wire_format(42, b"data");
+        assert_eq!(&payload[..], b"\0\0\0\0\x2adata");
+        assert_eq!(split_wire_format(&payload), Some((42, &b"data"[..])));
+        assert_eq!(split_wire_format(b"{\"message\":1}"), None);
+        assert_eq!(split_wire_format(b"\0\0"), None);
+    }
+
+    #[test]
+    fn skips_protobuf_message_indexes() {
+        assert_eq!(skip_message_indexes(b"\0data").unwrap(), b"data");
+        // Two indexes, 1 and 0.
+        assert_eq!(skip_message_indexes(b"\x04\x02\0data").unwrap(), b"data");
+        assert!(skip_message_indexes(b"\x04\x02").is_err());
+    }
+
+    #[tokio::test]
+    async fn decodes_avro_payloads() {
+        let client = client(true).await;
+
+        let schema = apache_avro::Schema::parse_str(AVRO_SCHEMA).unwrap();
+        let mut record = apache_avro::types::Record::new(&schema).unwrap();
+        record.put("message", "hello");
+        record.put("count", 3_i64);
+        let datum = apache_avro::to_avro_datum(&schema, record).unwrap();
+
+        let decoded = client.decode(&encode_wire_format(1, &datum)).await.unwrap();
+        assert_eq!(
+            decoded,
+            DecodedPayload::Value(Value::from(btreemap! {
+                "message" => "hello",
+                "count" => 3,
+            }))
+        );
+        assert_eq!(client.schemas.lock().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn decodes_json_and_protobuf_payloads() {
+        let client = client(true).await;
+
+        let decoded = client
+            .decode(&encode_wire_format(2, br#"{"message": "hello"}"#))
+            .await
+            .unwrap();
+        assert_eq!(
+            decoded,
+            DecodedPayload::Value(Value::from(btreemap! { "message" => "hello" }))
+        );
+
+        let decoded = client
+            .decode(&encode_wire_format(3, b"\0proto"))
+            .await
+            .unwrap();
+        assert_eq!(decoded, DecodedPayload::Raw(Bytes::from("proto")));
+
+        let decoded = client.decode(b"plain").await.unwrap();
+        assert_eq!(decoded, DecodedPayload::Raw(Bytes::from("plain")));
+    }
+
+    #[tokio::test]
+    async fn registers_and_caches_subjects() {
+        let client = client(true).await;
+
+        for _ in 0..2 {
+            let id = client
+                .schema_id("logs-value", AVRO_SCHEMA, SchemaType::Avro, true)
+                .await
+                .unwrap();
+            assert_eq!(id, 1);
+        }
+        assert_eq!(client.subjects.lock().unwrap().len(), 1);
+
+        let error = client
+            .schema_id("unknown-value", AVRO_SCHEMA, SchemaType::Avro, false)
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            SchemaRegistryError::UnexpectedStatus {
+                status: StatusCode::NOT_FOUND,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn sends_credentials() {
+        let client = client(false).await;
+
+        let error = client.schema(1).await.unwrap_err();
+        assert!(matches!(
+            error,
+            SchemaRegistryError::UnexpectedStatus {
+                status: StatusCode::UNAUTHORIZED,
+                ..
+            }
+        ));
+    }
+
+    #[tokio::test]
+    async fn retries_only_when_unavailable() {
+        let client = client(true).await;
+
+        let error = client.schema(42).await.unwrap_err();
+        assert!(!error.is_retriable());
+        let error = client
+            .decode(&encode_wire_format(1, b"\xff"))
+            .await
+            .unwrap_err();
+        assert!(!error.is_retriable());
+
+        let error = SchemaRegistryError::UnexpectedStatus {
+            status: StatusCode::SERVICE_UNAVAILABLE,
+            body: String::new(),
+        };
+        assert!(error.is_retriable());
+    }
+}
@@ -82,12 +82,16 @@ pub mod kafka;
 pub mod kubernetes;
 pub mod line_agg;
 pub mod list;
+#[cfg(any(feature = "sources-mqtt", feature = "sinks-mqtt"))]
+pub(crate) mod mqtt;
 #[cfg(any(feature = "sources-nats", feature = "sinks-nats"))]
 pub(crate) mod nats;
 pub mod net;
 #[allow(unreachable_pub)]
 pub(crate) mod proto;
 pub mod providers;
+#[cfg(any(feature = "sources-pulsar", feature = "sinks-pulsar"))]
+pub(crate) mod pulsar;
 pub mod secrets;
 pub mod serde;
 #[cfg(windows)]
@@ -117,6 +121,8 @@ pub mod types;
 pub mod unit_test;
 pub(crate) mod utilization;
 pub mod validate;
+#[cfg(any(feature = "sources-websocket", feature = "sinks-websocket"))]
+pub(crate) mod websocket;
 #[cfg(windows)]
 pub mod vector_windows;
 
@@ -0,0 +1,284 @@
+//! Functionality supporting both the `[crate::sources::mqtt]` source and `[crate::sinks::mqtt]` sink.
+use std::{io, time::Duration};
+
+use rand::{thread_rng, Rng};
+use rand_distr::Alphanumeric;
+use rumqttc::{
+    tokio_rustls::rustls::{self, Certificate, ClientConfig, PrivateKey, RootCertStore},
+    MqttOptions, QoS, Transport,
+};
+use rustls_pemfile::Item;
+use snafu::{ResultExt, Snafu};
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+
+use crate::tls::{MaybeTlsSettings, TlsConfig, TlsEnableableConfig, TlsError, TlsSettings};
+
+/// The largest packet, in bytes, that is sent to or accepted from the broker.
+const MAX_PACKET_SIZE: usize = 1024 * 1024;
+
+#[derive(Debug, Snafu)]
+pub(crate) enum MqttConfigError {
+    #[snafu(display("MQTT TLS Config Error: {}", source))]
+    Tls { source: TlsError },
+    #[snafu(display("MQTT TLS Config Error: `tls.{}` can't be disabled", option))]
+    UnsupportedTlsOption { option: &'static str },
+    #[snafu(display("MQTT TLS Config Error: could not load certificates: {}", source))]
+    Certificates { source: io::Error },
+    #[snafu(display("MQTT TLS Config Error: invalid CA certificate"))]
+    InvalidCaCertificate,
+    #[snafu(display("MQTT TLS Config Error: no supported private key found in `tls.key_file`"))]
+    MissingPrivateKey,
+    #[snafu(display("MQTT TLS Config Error: invalid client certificate or key: {}", source))]
+    InvalidIdentity { source: rustls::Error },
+    #[snafu(display(
+        "MQTT Config Error: `client_id` must be set when `clean_session` is disabled"
+    ))]
+    MissingClientId,
+    #[snafu(display("MQTT Config Error: `client_id` must not be empty or start with a space"))]
+    InvalidClientId,
+}
+
+/// MQTT connection options.
+#[configurable_component]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+pub(crate) struct MqttConnectionConfig {
+    /// The MQTT broker's domain name or IP address.
+    #[configurable(metadata(docs::examples = "mosquitto.local"))]
+    #[configurable(metadata(docs::examples = "127.0.0.1"))]
+    #[derivative(Default(value = "\"127.0.0.1\".to_owned()"))]
+    pub(crate) host: String,
+
+    /// The TCP port of the MQTT broker.
+    #[serde(default = "default_port")]
+    #[derivative(Default(value = "default_port()"))]
+    pub(crate) port: u16,
+
+    /// The username used to authenticate with the broker.
+    #[configurable(metadata(docs::examples = "vector"))]
+    pub(crate) user: Option<String>,
+
+    /// The password used to authenticate with the broker.
+    #[configurable(metadata(docs::examples = "${MQTT_PASSWORD}"))]
+    pub(crate) password: Option<SensitiveString>,
+
+    /// The client identifier presented to the broker.
+    ///
+    /// The broker associates persistent sessions with this identifier, so it must be set when
+    /// `clean_session` is disabled. If omitted, a random identifier prefixed with `vector-` is
+    /// generated on every connection.
+    #[configurable(metadata(docs::examples = "vector-edge-01"))]
+    pub(crate) client_id: Option<String>,
+
+    /// The interval, in seconds, between keep-alive pings sent to the broker.
+    #[serde(default = "default_keep_alive_secs")]
+    #[derivative(Default(value = "default_keep_alive_secs()"))]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Keep Alive Interval"))]
+    pub(crate) keep_alive_secs: u16,
+
+    /// Whether to start a clean session on every connection.
+    ///
+    /// When disabled, the broker keeps the subscriptions and undelivered messages of the session
+    /// while the client is disconnected, and resumes them on reconnection.
+    #[serde(default = "crate::serde::default_true")]
+    #[derivative(Default(value = "true"))]
+    pub(crate) clean_session: bool,
+
+    #[configurable(derived)]
+    pub(crate) tls: Option<TlsEnableableConfig>,
+}
+
+const fn default_port() -> u16 {
+    1883
+}
+
+const fn default_keep_alive_secs() -> u16 {
+    60
+}
+
+impl MqttConnectionConfig {
+    /// Builds the client options used to connect to the broker.
+    pub(crate) fn build_options(&self) -> Result<MqttOptions, MqttConfigError> {
+        let client_id = match &self.client_id {
+            Some(client_id) if client_id.is_empty() || client_id.starts_with(' ') => {
+                return Err(MqttConfigError::InvalidClientId)
+            }
+            Some(client_id) => client_id.clone(),
+            None if !self.clean_session => return Err(MqttConfigError::MissingClientId),
+            None => {
+                let suffix = thread_rng()
+                    .sample_iter(&Alphanumeric)
+                    .take(16)
+                    .map(char::from)
+                    .collect::<String>();
+                format!("vector-{}", suffix)
+            }
+        };
+
+        let mut options = MqttOptions::new(client_id, &self.host, self.port);
+        options
+            .set_keep_alive(Duration::from_secs(self.keep_alive_secs.into()))
+            .set_clean_session(self.clean_session)
+            .set_max_packet_size(MAX_PACKET_SIZE, MAX_PACKET_SIZE);
+
+        if let Some(user) = &self.user {
+            let password = self
+                .password
+                .as_ref()
+                .map(|password| password.inner().to_owned())
+                .unwrap_or_default();
+            options.set_credentials(user, password);
+        }
+
+        let tls = MaybeTlsSettings::from_config(&self.tls, false).context(TlsSnafu)?;
+        if let (Some(config), Some(settings)) = (&self.tls, tls.tls()) {
+            let client_config = tls_client_config(&config.options, settings)?;
+            options.set_transport(Transport::tls_with_config(client_config.into()));
+        }
+
+        Ok(options)
+    }
+}
+
+/// Builds the rustls configuration used to connect to the broker, trusting the system certificates
+/// along with the configured CA.
+fn tls_client_config(
+    config: &TlsConfig,
+    settings: &TlsSettings,
+) -> Result<ClientConfig, MqttConfigError> {
+    // The broker's certificate and hostname are always verified by rustls
+    if config.verify_certificate == Some(false) {
+        return Err(MqttConfigError::UnsupportedTlsOption {
+            option: "verify_certificate",
+        });
+    }
+    if config.verify_hostname == Some(false) {
+        return Err(MqttConfigError::UnsupportedTlsOption {
+            option: "verify_hostname",
+        });
+    }
+
+    let mut roots = RootCertStore::empty();
+    for certificate in rustls_native_certs::load_native_certs().context(CertificatesSnafu)? {
+        // Invalid system certificates are skipped, as other rustls clients do
+        _ = roots.add(&Certificate(certificate.0));
+    }
+    for pem in settings.authorities_pem() {
+        for certificate in rustls_pemfile::certs(&mut pem.as_slice()).context(CertificatesSnafu)? {
+            roots
+                .add(&Certificate(certificate))
+                .map_err(|_| MqttConfigError::InvalidCaCertificate)?;
+        }
+    }
+
+    let builder = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(roots);
+    let mut client_config = match settings.identity_pem() {
+        Some((crt, key)) => {
+            let chain = rustls_pemfile::certs(&mut crt.as_slice())
+                .context(CertificatesSnafu)?
+                .into_iter()
+                .map(Certificate)
+                .collect();
+            builder
+                .with_single_cert(chain, private_key(&key)?)
+                .context(InvalidIdentitySnafu)?
+        }
+        None => builder.with_no_client_auth(),
+    };
+    if let Some(protocols) = &config.alpn_protocols {
+        client_config.alpn_protocols = protocols
+            .iter()
+            .map(|protocol| protocol.as_bytes().to_vec())
+            .collect();
+    }
+
+    Ok(client_config)
+}
+
+/// Reads the first private key of a PEM document, whether it's an RSA, EC or PKCS#8 key.
+fn private_key(pem: &[u8]) -> Result<PrivateKey, MqttConfigError> {
+    rustls_pemfile::read_all(&mut &pem[..])
+        .context(CertificatesSnafu)?
+        .into_iter()
+        .find_map(|item| match item {
+            Item::RSAKey(key) | Item::ECKey(key) | Item::PKCS8Key(key) => Some(PrivateKey(key)),
+            _ => None,
+        })
+        .ok_or(MqttConfigError::MissingPrivateKey)
+}
+
+/// Supported Quality of Service levels for MQTT.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum MqttQoS {
+    /// At most once delivery (QoS 0).
+    ///
+    /// Messages are sent without any acknowledgement and may be lost.
+    AtMostOnce,
+
+    /// At least once delivery (QoS 1).
+    ///
+    /// Messages are acknowledged with `PUBACK` and may be delivered more than once.
+    #[derivative(Default)]
+    AtLeastOnce,
+
+    /// Exactly once delivery (QoS 2).
+    ///
+    /// Messages are acknowledged through the `PUBREC`/`PUBREL`/`PUBCOMP` handshake.
+    ExactlyOnce,
+}
+
+impl From<MqttQoS> for QoS {
+    fn from(qos: MqttQoS) -> Self {
+        match qos {
+            MqttQoS::AtMostOnce => QoS::AtMostOnce,
+            MqttQoS::AtLeastOnce => QoS::AtLeastOnce,
+            MqttQoS::ExactlyOnce => QoS::ExactlyOnce,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tls::{TEST_PEM_CA_PATH, TEST_PEM_CLIENT_CRT_PATH, TEST_PEM_CLIENT_KEY_PATH};
+
+    fn config(tls: &str) -> MqttConnectionConfig {
+        toml::from_str(&format!(
+            "host = \"localhost\"\n[tls]\nenabled = true\n{tls}"
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn builds_tls_transport_with_client_identity() {
+        let config = config(&format!(
+            r#"
+            ca_file = "{TEST_PEM_CA_PATH}"
+            crt_file = "{TEST_PEM_CLIENT_CRT_PATH}"
+            key_file = "{TEST_PEM_CLIENT_KEY_PATH}"
+            alpn_protocols = ["mqtt"]
+            "#
+        ));
+
+        let options = config.build_options().unwrap();
+        assert!(matches!(options.transport(), Transport::Tls(_)));
+    }
+
+    #[test]
+    fn rejects_disabled_verification() {
+        for option in ["verify_certificate", "verify_hostname"] {
+            let config = config(&format!("{option} = false"));
+            assert!(matches!(
+                config.build_options(),
+                Err(MqttConfigError::UnsupportedTlsOption { .. })
+            ));
+        }
+    }
+}
@@ -9,7 +9,7 @@ use vector_config::configurable_component;
 use vrl::value::Kind;
 
 use crate::{
-    kafka::{KafkaAuthConfig, KafkaCompression},
+    kafka::{schema_registry::SchemaRegistryConfig, KafkaAuthConfig, KafkaCompression},
     serde::json::to_string,
     sinks::{
         kafka::sink::{healthcheck, KafkaSink},
@@ -111,6 +111,19 @@ pub struct KafkaSinkConfig {
     #[configurable(metadata(docs::examples = "headers"))]
     pub headers_key: Option<ConfigTargetPath>,
 
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    #[serde(default)]
+    pub transaction: KafkaTransactionConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[configurable(metadata(docs::advanced))]
+    pub schema_registry: Option<KafkaSchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(
         default,
@@ -120,6 +133,98 @@ pub struct KafkaSinkConfig {
     pub acknowledgements: AcknowledgementsConfig,
 }
 
+/// Transactional delivery options.
+///
+/// When enabled, events are grouped into batches according to the `batch` settings, and each batch
+/// is produced within a single Kafka transaction. The transaction is only committed once every
+/// message in the batch has been delivered, and the events are acknowledged once the commit
+/// succeeds. If any message fails, the transaction is aborted and the whole batch is retried
+/// according to the retry settings of `request`. Batches that can never be delivered, such as when
+/// a message is too large or the topic is not writable, are rejected without being retried.
+///
+/// Together with consumers reading with `isolation.level` set to `read_committed`, this provides
+/// exactly-once delivery into Kafka.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct KafkaTransactionConfig {
+    /// Whether or not to produce events within Kafka transactions.
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// The `transactional.id` used by the producer.
+    ///
+    /// Required when transactions are enabled. The ID must be unique to each Vector instance
+    /// running the sink, and should stay the same across restarts so that the broker can fence off
+    /// transactions left behind by a previous run of that instance. When running several replicas,
+    /// include a value specific to each of them, such as `vector-${HOSTNAME}`. Two instances with
+    /// the same ID fence each other off, and only one of them can produce at a time. For that
+    /// reason, no default is derived from the ID of the component, which replicas share.
+    #[configurable(metadata(docs::examples = "vector-kafka-sink-0"))]
+    #[configurable(metadata(docs::examples = "vector-${HOSTNAME}"))]
+    pub transactional_id: Option<String>,
+
+    /// The maximum time, in milliseconds, a transaction can remain open before the broker aborts it.
+    #[serde_as(as = "Option<serde_with::DurationMilliSeconds<u64>>")]
+    #[configurable(metadata(docs::examples = 60000))]
+    #[configurable(metadata(docs::human_name = "Transaction Timeout"))]
+    pub timeout_ms: Option<Duration>,
+}
+
+impl KafkaTransactionConfig {
+    /// Returns the `transactional.id` to use, if transactions are enabled.
+    pub(crate) fn transactional_id(&self) -> crate::Result<Option<&str>> {
+        if !self.enabled {
+            return Ok(None);
+        }
+        match &self.transactional_id {
+            Some(id) => Ok(Some(id)),
+            None => Err(
+                "`transaction.transactional_id` must be set when transactions are enabled.".into(),
+            ),
+        }
+    }
+}
+
+/// Confluent Schema Registry options.
+///
+/// When set, the schema of the `avro` encoding is registered in, or looked up from, the registry,
+/// and every message is prefixed with the magic byte and ID of the schema, as expected by consumers
+/// of the Confluent wire format.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct KafkaSchemaRegistryConfig {
+    #[configurable(derived)]
+    #[serde(flatten)]
+    pub registry: SchemaRegistryConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub subject_name_strategy: SubjectNameStrategy,
+
+    /// Whether or not to register the schema under its subject.
+    ///
+    /// If disabled, the schema must already be registered under the subject, and its ID is looked
+    /// up instead.
+    #[serde(default = "crate::serde::default_true")]
+    pub auto_register_schemas: bool,
+}
+
+/// The strategy used to name the subject the schema is registered under.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum SubjectNameStrategy {
+    /// The subject is named after the topic, as `<topic>-value`.
+    #[derivative(Default)]
+    TopicName,
+
+    /// The subject is named after the fully-qualified name of the Avro record.
+    RecordName,
+}
+
 const fn default_socket_timeout_ms() -> Duration {
     Duration::from_millis(60000) // default in librdkafka
 }
@@ -225,6 +330,13 @@ impl KafkaSinkConfig {
                 );
                 client_config.set(key, &value.to_string());
             }
+
+            if let Some(transactional_id) = self.transaction.transactional_id()? {
+                client_config.set("transactional.id", transactional_id);
+                if let Some(timeout) = self.transaction.timeout_ms {
+                    client_config.set("transaction.timeout.ms", &timeout.as_millis().to_string());
+                }
+            }
         }
 
         for (key, value) in self.librdkafka_options.iter() {
@@ -250,6 +362,9 @@ impl GenerateConfig for KafkaSinkConfig {
             message_timeout_ms: default_message_timeout_ms(),
             librdkafka_options: Default::default(),
             headers_key: None,
+            transaction: Default::default(),
+            request: Default::default(),
+            schema_registry: None,
             acknowledgements: Default::default(),
         })
         .unwrap()
@@ -259,8 +374,9 @@ impl GenerateConfig for KafkaSinkConfig {
 #[async_trait::async_trait]
 #[typetag::serde(name = "kafka")]
 impl SinkConfig for KafkaSinkConfig {
-    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
-        let sink = KafkaSink::new(self.clone())?;
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        self.transaction.transactional_id()?;
+        let sink = KafkaSink::new(self.clone(), cx.proxy())?;
         let hc = healthcheck(self.clone()).boxed();
         Ok((VectorSink::from_event_streamsink(sink), hc))
     }
@@ -285,4 +401,38 @@ mod tests {
     fn generate_config() {
         KafkaSinkConfig::generate_config();
     }
+
+    #[test]
+    fn transactional_id_is_required_with_transactions() {
+        let mut transaction = KafkaTransactionConfig::default();
+        assert_eq!(transaction.transactional_id().unwrap(), None);
+
+        transaction.enabled = true;
+        assert!(transaction.transactional_id().is_err());
+
+        transaction.transactional_id = Some("custom".to_owned());
+        assert_eq!(transaction.transactional_id().unwrap(), Some("custom"));
+    }
+
+    #[test]
+    fn transactional_id_is_set_on_producer_only() {
+        let config: KafkaSinkConfig = toml::from_str(
+            r#"
+            bootstrap_servers = "localhost:9092"
+            topic = "logs"
+            encoding.codec = "json"
+            transaction.enabled = true
+            transaction.transactional_id = "custom"
+            transaction.timeout_ms = 10000
+            "#,
+        )
+        .unwrap();
+
+        let producer = config.to_rdkafka(KafkaRole::Producer).unwrap();
+        assert_eq!(producer.get("transactional.id"), Some("custom"));
+        assert_eq!(producer.get("transaction.timeout.ms"), Some("10000"));
+
+        let consumer = config.to_rdkafka(KafkaRole::Consumer).unwrap();
+        assert_eq!(consumer.get("transactional.id"), None);
+    }
 }
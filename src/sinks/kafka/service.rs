@@ -11,18 +11,24 @@ use bytes::Bytes;
 use rdkafka::{
     error::KafkaError,
     message::OwnedHeaders,
-    producer::{FutureProducer, FutureRecord},
+    producer::{FutureProducer, FutureRecord, Producer},
     types::RDKafkaErrorCode,
 };
 
-use crate::{kafka::KafkaStatisticsContext, sinks::prelude::*};
+use crate::{
+    internal_events::KafkaTransactionError,
+    kafka::KafkaStatisticsContext,
+    sinks::{prelude::*, util::retries::ExponentialBackoff},
+};
 
+#[derive(Clone)]
 pub struct KafkaRequest {
     pub body: Bytes,
     pub metadata: KafkaRequestMetadata,
     pub request_metadata: RequestMetadata,
 }
 
+#[derive(Clone)]
 pub struct KafkaRequestMetadata {
     pub finalizers: EventFinalizers,
     pub key: Option<Bytes>,
@@ -50,6 +56,12 @@ impl DriverResponse for KafkaResponse {
     }
 }
 
+impl KafkaRequest {
+    fn raw_byte_size(&self) -> usize {
+        self.body.len() + self.metadata.key.as_ref().map_or(0, |x| x.len())
+    }
+}
+
 impl Finalizable for KafkaRequest {
     fn take_finalizers(&mut self) -> EventFinalizers {
         std::mem::take(&mut self.metadata.finalizers)
@@ -99,6 +111,52 @@ impl KafkaService {
             records_blocked: Arc::new(AtomicUsize::new(0)),
         }
     }
+
+    /// Enqueues the record of the request on the producer and waits for its delivery.
+    async fn produce(&self, request: &KafkaRequest) -> Result<(), KafkaError> {
+        let mut record = FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
+        if let Some(key) = &request.metadata.key {
+            record = record.key(&key[..]);
+        }
+        if let Some(timestamp) = request.metadata.timestamp_millis {
+            record = record.timestamp(timestamp);
+        }
+        if let Some(headers) = &request.metadata.headers {
+            record = record.headers(headers.clone());
+        }
+
+        // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
+        // records that fail to be enqueued on the producer.
+        let mut blocked_state: Option<BlockedRecordState> = None;
+        loop {
+            match self.kafka_producer.send_result(record) {
+                // Record was successfully enqueued on the producer.
+                Ok(fut) => {
+                    // Drop the blocked state (if any), as the producer is no longer blocked.
+                    drop(blocked_state.take());
+                    return fut
+                        .await
+                        .expect("producer unexpectedly dropped")
+                        .map(|_| ())
+                        .map_err(|(err, _)| err);
+                }
+                // Producer queue is full.
+                Err((
+                    KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
+                    original_record,
+                )) => {
+                    if blocked_state.is_none() {
+                        blocked_state =
+                            Some(BlockedRecordState::new(Arc::clone(&self.records_blocked)));
+                    }
+                    record = original_record;
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                }
+                // A different error occurred.
+                Err((err, _)) => return Err(err),
+            };
+        }
+    }
 }
 
 impl Service<KafkaRequest> for KafkaService {
@@ -120,58 +178,229 @@ impl Service<KafkaRequest> for KafkaService {
         let this = self.clone();
 
         Box::pin(async move {
-            let raw_byte_size =
-                request.body.len() + request.metadata.key.as_ref().map_or(0, |x| x.len());
-            let event_byte_size = request
-                .request_metadata
-                .into_events_estimated_json_encoded_byte_size();
-
-            let mut record =
-                FutureRecord::to(&request.metadata.topic).payload(request.body.as_ref());
-            if let Some(key) = &request.metadata.key {
-                record = record.key(&key[..]);
-            }
-            if let Some(timestamp) = request.metadata.timestamp_millis {
-                record = record.timestamp(timestamp);
-            }
-            if let Some(headers) = request.metadata.headers {
-                record = record.headers(headers);
+            let raw_byte_size = request.raw_byte_size();
+            this.produce(&request).await?;
+
+            Ok(KafkaResponse {
+                event_byte_size: request
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                raw_byte_size,
+            })
+        })
+    }
+}
+
+/// A batch of requests produced within a single Kafka transaction.
+#[derive(Clone)]
+pub struct KafkaTransactionRequest {
+    pub requests: Vec<KafkaRequest>,
+    pub finalizers: EventFinalizers,
+    pub request_metadata: RequestMetadata,
+}
+
+impl KafkaTransactionRequest {
+    pub fn new(mut requests: Vec<KafkaRequest>) -> Self {
+        let mut finalizers = EventFinalizers::default();
+        for request in &mut requests {
+            finalizers.merge(request.take_finalizers());
+        }
+        let request_metadata = RequestMetadata::from_batch(
+            requests
+                .iter()
+                .map(|request| request.request_metadata.clone()),
+        );
+
+        Self {
+            requests,
+            finalizers,
+            request_metadata,
+        }
+    }
+}
+
+impl Finalizable for KafkaTransactionRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        std::mem::take(&mut self.finalizers)
+    }
+}
+
+impl MetaDescriptive for KafkaTransactionRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.request_metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.request_metadata
+    }
+}
+
+/// Produces each batch of requests within a Kafka transaction.
+///
+/// The transaction is only committed once every record in the batch has been delivered. On
+/// failure, the transaction is aborted before the error is returned, so that the whole batch can be
+/// retried according to [`KafkaTransactionRetryLogic`]. The producer only supports one open
+/// transaction at a time, so this service must not be called concurrently.
+#[derive(Clone)]
+pub struct KafkaTransactionService {
+    service: KafkaService,
+
+    /// The timeout of the blocking transaction operations.
+    timeout: Duration,
+}
+
+impl KafkaTransactionService {
+    pub(crate) const fn new(service: KafkaService, timeout: Duration) -> Self {
+        Self { service, timeout }
+    }
+
+    /// Initializes the transactions of the producer, fencing off any transaction left open by a
+    /// previous producer using the same `transactional.id`.
+    pub(crate) async fn init_transactions(&self) -> Result<(), KafkaError> {
+        let mut backoff = fresh_backoff();
+        loop {
+            let producer = self.service.kafka_producer.clone();
+            let timeout = self.timeout;
+            match tokio::task::spawn_blocking(move || producer.init_transactions(timeout))
+                .await
+                .expect("transaction initialization panicked")
+            {
+                Ok(()) => return Ok(()),
+                Err(KafkaError::Transaction(error)) if error.is_retriable() => {
+                    emit!(KafkaTransactionError {
+                        error: KafkaError::Transaction(error)
+                    });
+                    tokio::time::sleep(backoff.next().unwrap()).await;
+                }
+                Err(error) => return Err(error),
             }
+        }
+    }
 
-            // Manually poll [FutureProducer::send_result] instead of [FutureProducer::send] to track
-            // records that fail to be enqueued on the producer.
-            let mut blocked_state: Option<BlockedRecordState> = None;
-            loop {
-                match this.kafka_producer.send_result(record) {
-                    // Record was successfully enqueued on the producer.
-                    Ok(fut) => {
-                        // Drop the blocked state (if any), as the producer is no longer blocked.
-                        drop(blocked_state.take());
-                        return fut
-                            .await
-                            .expect("producer unexpectedly dropped")
-                            .map(|_| KafkaResponse {
-                                event_byte_size,
-                                raw_byte_size,
-                            })
-                            .map_err(|(err, _)| err);
-                    }
-                    // Producer queue is full.
-                    Err((
-                        KafkaError::MessageProduction(RDKafkaErrorCode::QueueFull),
-                        original_record,
-                    )) => {
-                        if blocked_state.is_none() {
-                            blocked_state =
-                                Some(BlockedRecordState::new(Arc::clone(&this.records_blocked)));
-                        }
-                        record = original_record;
-                        tokio::time::sleep(Duration::from_millis(100)).await;
-                    }
-                    // A different error occurred.
-                    Err((err, _)) => return Err(err),
-                };
+    async fn produce_transaction(&self, requests: &[KafkaRequest]) -> Result<(), KafkaError> {
+        let producer = self.service.kafka_producer.clone();
+        tokio::task::spawn_blocking(move || producer.begin_transaction())
+            .await
+            .expect("transaction begin panicked")?;
+        future::try_join_all(requests.iter().map(|request| self.service.produce(request))).await?;
+
+        let producer = self.service.kafka_producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || producer.commit_transaction(timeout))
+            .await
+            .expect("transaction commit panicked")
+    }
+
+    async fn abort_transaction(&self) -> Result<(), KafkaError> {
+        let producer = self.service.kafka_producer.clone();
+        let timeout = self.timeout;
+        tokio::task::spawn_blocking(move || producer.abort_transaction(timeout))
+            .await
+            .expect("transaction abort panicked")
+    }
+}
+
+const fn fresh_backoff() -> ExponentialBackoff {
+    ExponentialBackoff::from_millis(2)
+        .factor(250)
+        .max_delay(Duration::from_secs(60))
+}
+
+impl Service<KafkaTransactionRequest> for KafkaTransactionService {
+    type Response = KafkaResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Service::<KafkaRequest>::poll_ready(&mut self.service, cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: KafkaTransactionRequest) -> Self::Future {
+        let this = self.clone();
+
+        Box::pin(async move {
+            let raw_byte_size = request
+                .requests
+                .iter()
+                .map(KafkaRequest::raw_byte_size)
+                .sum();
+
+            if let Err(error) = this.produce_transaction(&request.requests).await {
+                // A fatal error leaves no transaction to abort, as the producer can no longer be
+                // used.
+                if !is_fatal(&error) {
+                    this.abort_transaction().await?;
+                }
+                return Err(error.into());
             }
+
+            Ok(KafkaResponse {
+                event_byte_size: request
+                    .request_metadata
+                    .into_events_estimated_json_encoded_byte_size(),
+                raw_byte_size,
+            })
         })
     }
 }
+
+fn is_fatal(error: &KafkaError) -> bool {
+    matches!(error, KafkaError::Transaction(error) if error.is_fatal())
+}
+
+/// Retries the transactions that failed with a transient error.
+///
+/// Transactions are not retried when the producer can no longer be used, such as when it was
+/// fenced by another producer using the same `transactional.id`, nor when the records of the batch
+/// can never be delivered, such as when they are too large or the topic is not writable.
+#[derive(Clone, Debug, Default)]
+pub struct KafkaTransactionRetryLogic;
+
+impl RetryLogic for KafkaTransactionRetryLogic {
+    type Error = KafkaError;
+    type Response = KafkaResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        !is_fatal(error)
+            && !matches!(
+                error.rdkafka_error_code(),
+                Some(
+                    RDKafkaErrorCode::MessageSizeTooLarge
+                        | RDKafkaErrorCode::InvalidMessageSize
+                        | RDKafkaErrorCode::InvalidMessage
+                        | RDKafkaErrorCode::RecordListTooLarge
+                        | RDKafkaErrorCode::InvalidRecord
+                        | RDKafkaErrorCode::InvalidTopic
+                        | RDKafkaErrorCode::UnknownTopic
+                        | RDKafkaErrorCode::UnknownTopicOrPartition
+                        | RDKafkaErrorCode::TopicAuthorizationFailed
+                        | RDKafkaErrorCode::ClusterAuthorizationFailed
+                        | RDKafkaErrorCode::TransactionalIdAuthorizationFailed
+                        | RDKafkaErrorCode::PolicyViolation
+                )
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retries_transient_errors_only() {
+        let logic = KafkaTransactionRetryLogic;
+
+        assert!(logic.is_retriable_error(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::RequestTimedOut
+        )));
+        assert!(logic.is_retriable_error(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::NotLeaderForPartition
+        )));
+        assert!(!logic.is_retriable_error(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::MessageSizeTooLarge
+        )));
+        assert!(!logic.is_retriable_error(&KafkaError::MessageProduction(
+            RDKafkaErrorCode::TopicAuthorizationFailed
+        )));
+    }
+}
@@ -1,3 +1,6 @@
+use std::sync::Arc;
+
+use codecs::encoding::SerializerConfig;
 use rdkafka::{
     consumer::{BaseConsumer, Consumer},
     error::KafkaError,
@@ -8,11 +11,24 @@ use snafu::{ResultExt, Snafu};
 use tokio::time::Duration;
 use vrl::path::OwnedTargetPath;
 
-use super::config::{KafkaRole, KafkaSinkConfig};
+use super::config::{KafkaRole, KafkaSchemaRegistryConfig, KafkaSinkConfig, SubjectNameStrategy};
 use crate::{
-    kafka::KafkaStatisticsContext,
-    sinks::kafka::{request_builder::KafkaRequestBuilder, service::KafkaService},
-    sinks::prelude::*,
+    config::ProxyConfig,
+    internal_events::KafkaTransactionError,
+    kafka::{
+        schema_registry::{
+            encode_wire_format, SchemaRegistryClient, SchemaRegistryError, SchemaType,
+        },
+        KafkaStatisticsContext,
+    },
+    sinks::kafka::{
+        request_builder::KafkaRequestBuilder,
+        service::{
+            KafkaRequest, KafkaService, KafkaTransactionRequest, KafkaTransactionRetryLogic,
+            KafkaTransactionService,
+        },
+    },
+    sinks::{prelude::*, util::TowerRequestSettings},
 };
 
 #[derive(Debug, Snafu)]
@@ -22,6 +38,12 @@ pub(super) enum BuildError {
     KafkaCreateFailed { source: KafkaError },
     #[snafu(display("invalid topic template: {}", source))]
     TopicTemplate { source: TemplateParseError },
+    #[snafu(display("the schema registry requires the `avro` encoding"))]
+    SchemaRegistryEncoding,
+    #[snafu(display("invalid avro schema: {}", source))]
+    AvroSchema { source: apache_avro::Error },
+    #[snafu(display("the `record_name` subject name strategy requires a named avro schema"))]
+    UnnamedAvroSchema,
 }
 
 pub struct KafkaSink {
@@ -31,6 +53,67 @@ pub struct KafkaSink {
     topic: Template,
     key_field: Option<OwnedTargetPath>,
     headers_key: Option<OwnedTargetPath>,
+
+    schema_registry: Option<Arc<SchemaRegistryEncoder>>,
+
+    /// The batching of the events produced within each transaction, if transactions are enabled.
+    transaction_batch: Option<BatcherSettings>,
+    transaction_timeout: Duration,
+    /// The retry settings of failed transactions.
+    transaction_request: TowerRequestSettings,
+}
+
+// Default for `transaction.timeout.ms` in librdkafka.
+const DEFAULT_TRANSACTION_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Prefixes the encoded events with the ID of their schema in the registry.
+struct SchemaRegistryEncoder {
+    client: SchemaRegistryClient,
+    schema: String,
+    /// The subject of the schema, unless it is named after the topic.
+    record_subject: Option<String>,
+    register: bool,
+}
+
+impl SchemaRegistryEncoder {
+    fn new(
+        config: &KafkaSchemaRegistryConfig,
+        encoding: &SerializerConfig,
+        proxy: &ProxyConfig,
+    ) -> crate::Result<Self> {
+        let SerializerConfig::Avro { avro } = encoding else {
+            return Err(BuildError::SchemaRegistryEncoding.into());
+        };
+        let record_subject = match config.subject_name_strategy {
+            SubjectNameStrategy::TopicName => None,
+            SubjectNameStrategy::RecordName => {
+                let schema =
+                    apache_avro::Schema::parse_str(&avro.schema).context(AvroSchemaSnafu)?;
+                let name = schema.name().ok_or(BuildError::UnnamedAvroSchema)?;
+                Some(name.fullname(None))
+            }
+        };
+
+        Ok(Self {
+            client: config.registry.build(proxy)?,
+            schema: avro.schema.clone(),
+            record_subject,
+            register: config.auto_register_schemas,
+        })
+    }
+
+    async fn encode(&self, request: &mut KafkaRequest) -> Result<(), SchemaRegistryError> {
+        let subject = match &self.record_subject {
+            Some(subject) => subject.clone(),
+            None => format!("{}-value", request.metadata.topic),
+        };
+        let id = self
+            .client
+            .schema_id(&subject, &self.schema, SchemaType::Avro, self.register)
+            .await?;
+        request.body = encode_wire_format(id, &request.body);
+        Ok(())
+    }
 }
 
 pub(crate) fn create_producer(
@@ -43,12 +126,24 @@ pub(crate) fn create_producer(
 }
 
 impl KafkaSink {
-    pub(crate) fn new(config: KafkaSinkConfig) -> crate::Result<Self> {
+    pub(crate) fn new(config: KafkaSinkConfig, proxy: &ProxyConfig) -> crate::Result<Self> {
         let producer_config = config.to_rdkafka(KafkaRole::Producer)?;
         let producer = create_producer(producer_config)?;
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build()?;
         let encoder = Encoder::<()>::new(serializer);
+        let schema_registry = config
+            .schema_registry
+            .as_ref()
+            .map(|registry| {
+                SchemaRegistryEncoder::new(registry, config.encoding.config(), proxy).map(Arc::new)
+            })
+            .transpose()?;
+        let transaction_batch = if config.transaction.enabled {
+            Some(config.batch.into_batcher_settings()?)
+        } else {
+            None
+        };
 
         Ok(KafkaSink {
             headers_key: config.headers_key.map(|key| key.0),
@@ -57,6 +152,13 @@ impl KafkaSink {
             service: KafkaService::new(producer),
             topic: config.topic,
             key_field: config.key_field.map(|key| key.0),
+            schema_registry,
+            transaction_batch,
+            transaction_timeout: config
+                .transaction
+                .timeout_ms
+                .unwrap_or(DEFAULT_TRANSACTION_TIMEOUT),
+            transaction_request: config.request.unwrap_with(&TowerRequestConfig::default()),
         })
     }
 
@@ -67,7 +169,7 @@ impl KafkaSink {
             encoder: (self.transformer, self.encoder),
         };
 
-        input
+        let requests = input
             .filter_map(|event| {
                 // Compute the topic.
                 future::ready(
@@ -85,19 +187,66 @@ impl KafkaSink {
                 )
             })
             .request_builder(default_request_builder_concurrency_limit(), request_builder)
-            .filter_map(|request| async {
-                match request {
-                    Err(error) => {
-                        emit!(SinkRequestBuildError { error });
-                        None
+            .filter_map(|request| {
+                let schema_registry = self.schema_registry.clone();
+                async move {
+                    let mut request = match request {
+                        Err(error) => {
+                            emit!(SinkRequestBuildError { error });
+                            return None;
+                        }
+                        Ok(req) => req,
+                    };
+                    if let Some(schema_registry) = schema_registry {
+                        if let Err(error) = schema_registry.encode(&mut request).await {
+                            request
+                                .take_finalizers()
+                                .update_status(EventStatus::Rejected);
+                            emit!(SinkRequestBuildError { error });
+                            return None;
+                        }
                     }
-                    Ok(req) => Some(req),
+                    Some(request)
                 }
-            })
-            .into_driver(self.service)
-            .protocol("kafka")
-            .run()
-            .await
+            });
+
+        match self.transaction_batch {
+            None => {
+                requests
+                    .into_driver(self.service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+            Some(batch_settings) => {
+                let service = KafkaTransactionService::new(self.service, self.transaction_timeout);
+                if let Err(error) = service.init_transactions().await {
+                    emit!(KafkaTransactionError { error });
+                    return Err(());
+                }
+
+                // The producer can only have one transaction open at a time, so a failed
+                // transaction is retried before the next one begins.
+                let service = ServiceBuilder::new()
+                    .concurrency_limit(1)
+                    .retry(
+                        self.transaction_request
+                            .retry_policy(KafkaTransactionRetryLogic),
+                    )
+                    .service(service);
+
+                requests
+                    .batched(
+                        batch_settings
+                            .into_item_size_config(|request: &KafkaRequest| request.body.len()),
+                    )
+                    .map(KafkaTransactionRequest::new)
+                    .into_driver(service)
+                    .protocol("kafka")
+                    .run()
+                    .await
+            }
+        }
     }
 }
 
@@ -134,3 +283,100 @@ impl StreamSink<Event> for KafkaSink {
         self.run_inner(input).await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use bytes::Bytes;
+    use codecs::{encoding::AvroSerializerConfig, JsonSerializerConfig};
+    use http::{Request, Response};
+    use hyper::Body;
+
+    use super::*;
+    use crate::{
+        kafka::schema_registry::SchemaRegistryConfig, sinks::kafka::service::KafkaRequestMetadata,
+        test_util::http::spawn_blackhole_http_server,
+    };
+
+    const AVRO_SCHEMA: &str = r#"{
+        "type": "record",
+        "name": "Log",
+        "namespace": "com.example",
+        "fields": [{"name": "message", "type": "string"}]
+    }"#;
+
+    async fn registry(request: Request<Body>) -> Result<Response<Body>, Infallible> {
+        let body = match request.uri().path() {
+            "/subjects/logs-value/versions" => r#"{"id": 7}"#,
+            "/subjects/com.example.Log/versions" => r#"{"id": 8}"#,
+            _ => return Ok(Response::builder().status(404).body(Body::empty()).unwrap()),
+        };
+        Ok(Response::new(Body::from(body)))
+    }
+
+    async fn encoder(strategy: SubjectNameStrategy) -> SchemaRegistryEncoder {
+        let uri = spawn_blackhole_http_server(registry).await;
+        let config = KafkaSchemaRegistryConfig {
+            registry: SchemaRegistryConfig {
+                url: uri.to_string(),
+                auth: None,
+                tls: None,
+            },
+            subject_name_strategy: strategy,
+            auto_register_schemas: true,
+        };
+        let encoding: SerializerConfig = AvroSerializerConfig::new(AVRO_SCHEMA.to_owned()).into();
+        SchemaRegistryEncoder::new(&config, &encoding, &ProxyConfig::default()).unwrap()
+    }
+
+    fn request(topic: &str) -> KafkaRequest {
+        KafkaRequest {
+            body: Bytes::from("datum"),
+            metadata: KafkaRequestMetadata {
+                finalizers: Default::default(),
+                key: None,
+                timestamp_millis: None,
+                headers: None,
+                topic: topic.to_owned(),
+            },
+            request_metadata: Default::default(),
+        }
+    }
+
+    #[tokio::test]
+    async fn prefixes_payloads_with_topic_subject_schema_id() {
+        let encoder = encoder(SubjectNameStrategy::TopicName).await;
+
+        let mut logs = request("logs");
+        encoder.encode(&mut logs).await.unwrap();
+        assert_eq!(logs.body, encode_wire_format(7, b"datum"));
+
+        let mut other = request("other");
+        assert!(encoder.encode(&mut other).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn prefixes_payloads_with_record_subject_schema_id() {
+        let encoder = encoder(SubjectNameStrategy::RecordName).await;
+
+        let mut other = request("other");
+        encoder.encode(&mut other).await.unwrap();
+        assert_eq!(other.body, encode_wire_format(8, b"datum"));
+    }
+
+    #[test]
+    fn schema_registry_requires_avro_encoding() {
+        let config = KafkaSchemaRegistryConfig {
+            registry: SchemaRegistryConfig {
+                url: "http://localhost:8081".to_owned(),
+                auth: None,
+                tls: None,
+            },
+            subject_name_strategy: SubjectNameStrategy::TopicName,
+            auto_register_schemas: true,
+        };
+        let encoding: SerializerConfig = JsonSerializerConfig::default().into();
+        assert!(SchemaRegistryEncoder::new(&config, &encoding, &ProxyConfig::default()).is_err());
+    }
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use codecs::JsonSerializerConfig;
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, Packet};
+use snafu::ResultExt;
+
+use crate::{
+    mqtt::{MqttConnectionConfig, MqttQoS},
+    sinks::prelude::*,
+};
+
+use super::{sink::MqttSink, ConfigSnafu, ConnectSnafu, MqttError};
+
+/// The capacity of the channel between the client and its event loop.
+const REQUEST_CHANNEL_CAPACITY: usize = 1024;
+
+/// How long the healthcheck waits for the broker to accept the connection.
+const HEALTHCHECK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Configuration for the `mqtt` sink.
+#[configurable_component(sink("mqtt", "Publish observability data to topics on an MQTT broker."))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSinkConfig {
+    #[serde(flatten)]
+    pub(super) connection: MqttConnectionConfig,
+
+    /// The MQTT topic to publish messages to.
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(
+        docs::examples = "vector",
+        docs::examples = "devices/{{ host }}/logs"
+    ))]
+    pub(super) topic: Template,
+
+    /// The Quality of Service level used to publish messages.
+    ///
+    /// With `at_least_once`, events are only marked as delivered once the broker has answered with
+    /// `PUBACK`. With `exactly_once`, they are marked as delivered once the broker has answered
+    /// with `PUBCOMP`.
+    #[serde(default)]
+    pub(super) quality_of_service: MqttQoS,
+
+    /// Whether the broker should retain the last message published to each topic.
+    #[serde(default)]
+    pub(super) retain: bool,
+
+    #[configurable(derived)]
+    pub(super) encoding: EncodingConfig,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub(super) request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+impl GenerateConfig for MqttSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            connection: MqttConnectionConfig::default(),
+            topic: Template::try_from("vector").unwrap(),
+            quality_of_service: MqttQoS::default(),
+            retain: false,
+            encoding: JsonSerializerConfig::default().into(),
+            request: Default::default(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SinkConfig for MqttSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let sink = MqttSink::new(self.clone())?;
+        let healthcheck = healthcheck(self.clone()).boxed();
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type() & DataType::Log)
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+impl MqttSinkConfig {
+    pub(super) fn connect(&self) -> Result<(AsyncClient, EventLoop), MqttError> {
+        let options = self.connection.build_options().context(ConfigSnafu)?;
+        Ok(AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY))
+    }
+}
+
+async fn healthcheck(config: MqttSinkConfig) -> crate::Result<()> {
+    let (client, mut eventloop) = config.connect()?;
+
+    let connected = async {
+        loop {
+            match eventloop.poll().await.context(ConnectSnafu)? {
+                MqttEvent::Incoming(Packet::ConnAck(_)) => return Ok::<_, MqttError>(()),
+                _ => continue,
+            }
+        }
+    };
+    let result = tokio::time::timeout(HEALTHCHECK_TIMEOUT, connected)
+        .await
+        .unwrap_or(Err(MqttError::ConnectTimeout));
+
+    _ = client.try_disconnect();
+    result.map_err(Into::into)
+}
@@ -0,0 +1,139 @@
+use std::time::Duration;
+
+use codecs::TextSerializerConfig;
+use rumqttc::{AsyncClient, Event as MqttPacketEvent, MqttOptions, Packet, QoS};
+
+use super::{config::MqttSinkConfig, sink::MqttSink};
+use crate::{
+    event::{BatchNotifier, BatchStatus},
+    mqtt::{MqttConnectionConfig, MqttQoS},
+    sinks::prelude::*,
+    test_util::{
+        components::{run_and_assert_sink_compliance, SINK_TAGS},
+        random_lines_with_stream, random_string, trace_init,
+    },
+};
+
+fn mqtt_host() -> String {
+    std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn make_config(topic: &str, quality_of_service: MqttQoS) -> MqttSinkConfig {
+    MqttSinkConfig {
+        connection: MqttConnectionConfig {
+            host: mqtt_host(),
+            ..Default::default()
+        },
+        topic: Template::try_from(topic).unwrap(),
+        quality_of_service,
+        retain: false,
+        encoding: TextSerializerConfig::default().into(),
+        request: Default::default(),
+        acknowledgements: Default::default(),
+    }
+}
+
+async fn publish_and_check(config: MqttSinkConfig, subscribe_to: &str) {
+    // Subscribe with a separate client before publishing, then check that every message
+    // published by the sink was received.
+    let options = MqttOptions::new(format!("consumer-{}", random_string(10)), mqtt_host(), 1883);
+    let (consumer, mut eventloop) = AsyncClient::new(options, 16);
+    consumer
+        .subscribe(subscribe_to, QoS::ExactlyOnce)
+        .await
+        .unwrap();
+    loop {
+        if let MqttPacketEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+            break;
+        }
+    }
+
+    let num_events = 10;
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    let (input, events) = random_lines_with_stream(100, num_events, Some(batch));
+
+    let sink = VectorSink::from_event_streamsink(MqttSink::new(config).unwrap());
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let mut output = Vec::new();
+    while output.len() < num_events {
+        let event = tokio::time::timeout(Duration::from_secs(5), eventloop.poll())
+            .await
+            .expect("timed out waiting for messages")
+            .unwrap();
+        if let MqttPacketEvent::Incoming(Packet::Publish(publish)) = event {
+            output.push(String::from_utf8_lossy(&publish.payload).to_string());
+        }
+    }
+
+    assert_eq!(output, input);
+}
+
+#[tokio::test]
+async fn mqtt_sink_at_most_once() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    publish_and_check(make_config(&topic, MqttQoS::AtMostOnce), &topic).await;
+}
+
+#[tokio::test]
+async fn mqtt_sink_at_least_once() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    publish_and_check(make_config(&topic, MqttQoS::AtLeastOnce), &topic).await;
+}
+
+#[tokio::test]
+async fn mqtt_sink_exactly_once() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    publish_and_check(make_config(&topic, MqttQoS::ExactlyOnce), &topic).await;
+}
+
+#[tokio::test]
+async fn mqtt_sink_templated_topic() {
+    trace_init();
+
+    let prefix = format!("test-{}", random_string(10));
+    let config = make_config(
+        &format!("{}/{{{{ message }}}}", prefix),
+        MqttQoS::AtLeastOnce,
+    );
+    // Every event is published to a topic named after its message.
+    let options = MqttOptions::new(format!("consumer-{}", random_string(10)), mqtt_host(), 1883);
+    let (consumer, mut eventloop) = AsyncClient::new(options, 16);
+    consumer
+        .subscribe(format!("{}/+", prefix), QoS::AtLeastOnce)
+        .await
+        .unwrap();
+    loop {
+        if let MqttPacketEvent::Incoming(Packet::SubAck(_)) = eventloop.poll().await.unwrap() {
+            break;
+        }
+    }
+
+    let (input, events) = random_lines_with_stream(10, 5, None);
+    let sink = VectorSink::from_event_streamsink(MqttSink::new(config).unwrap());
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    let mut topics = Vec::new();
+    while topics.len() < input.len() {
+        let event = tokio::time::timeout(Duration::from_secs(5), eventloop.poll())
+            .await
+            .expect("timed out waiting for messages")
+            .unwrap();
+        if let MqttPacketEvent::Incoming(Packet::Publish(publish)) = event {
+            topics.push(publish.topic);
+        }
+    }
+
+    let expected = input
+        .iter()
+        .map(|line| format!("{}/{}", prefix, line))
+        .collect::<Vec<_>>();
+    assert_eq!(topics, expected);
+}
@@ -0,0 +1,34 @@
+//! `MQTT` sink
+//! Publishes data to topics on an MQTT 3.1.1 broker.
+
+use snafu::Snafu;
+
+use crate::mqtt::MqttConfigError;
+
+mod config;
+#[cfg(feature = "mqtt-integration-tests")]
+#[cfg(test)]
+mod integration_tests;
+mod request_builder;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+#[derive(Debug, Snafu)]
+pub(super) enum MqttError {
+    #[snafu(display("invalid encoding: {}", source))]
+    Encoding {
+        source: codecs::encoding::BuildError,
+    },
+    #[snafu(display("{}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("MQTT Connect Error: {}", source))]
+    Connect { source: rumqttc::ConnectionError },
+    #[snafu(display("MQTT Connect Error: timed out waiting for CONNACK"))]
+    ConnectTimeout,
+    #[snafu(display("MQTT Publish Error: {}", source))]
+    Publish { source: rumqttc::ClientError },
+    #[snafu(display("MQTT Publish Error: the connection was closed before the broker acknowledged the message"))]
+    Acknowledgement,
+}
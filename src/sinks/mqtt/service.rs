@@ -0,0 +1,134 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use rumqttc::{AsyncClient, QoS};
+use tokio::sync::oneshot;
+
+use crate::sinks::prelude::*;
+
+use super::{request_builder::MqttRequest, MqttError};
+
+/// Tracks publishes handed to the event loop until the broker acknowledges them.
+///
+/// The event loop assigns packet identifiers in the order publishes are sent through the client,
+/// so the senders waiting for an identifier are queued in that same order and matched up when the
+/// event loop reports the outgoing publish.
+#[derive(Default)]
+pub(super) struct PendingAcks {
+    unassigned: VecDeque<oneshot::Sender<()>>,
+    in_flight: HashMap<u16, oneshot::Sender<()>>,
+}
+
+impl PendingAcks {
+    /// Records that the event loop has written a publish with the given packet identifier.
+    pub(super) fn outgoing(&mut self, pkid: u16) {
+        // QoS 0 publishes have no packet identifier and are complete once written.
+        if pkid == 0 {
+            if let Some(sender) = self.unassigned.pop_front() {
+                _ = sender.send(());
+            }
+            return;
+        }
+
+        // Publishes retransmitted after a reconnection keep their packet identifier.
+        if self.in_flight.contains_key(&pkid) {
+            return;
+        }
+
+        if let Some(sender) = self.unassigned.pop_front() {
+            self.in_flight.insert(pkid, sender);
+        }
+    }
+
+    /// Records that the broker has acknowledged the publish with the given packet identifier,
+    /// through `PUBACK` for QoS 1 or `PUBCOMP` for QoS 2.
+    pub(super) fn acknowledged(&mut self, pkid: u16) {
+        if let Some(sender) = self.in_flight.remove(&pkid) {
+            _ = sender.send(());
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct MqttService {
+    pub(super) client: AsyncClient,
+    pub(super) qos: QoS,
+    pub(super) retain: bool,
+    pub(super) pending: Arc<Mutex<PendingAcks>>,
+    /// Serializes handing publishes to the client, so that they reach the event loop in the same
+    /// order as their senders are queued in `pending`.
+    pub(super) publish_lock: Arc<tokio::sync::Mutex<()>>,
+}
+
+pub(super) struct MqttResponse {
+    metadata: RequestMetadata,
+}
+
+impl DriverResponse for MqttResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        self.metadata.events_estimated_json_encoded_byte_size()
+    }
+
+    fn bytes_sent(&self) -> Option<usize> {
+        Some(self.metadata.request_encoded_size())
+    }
+}
+
+impl Service<MqttRequest> for MqttService {
+    type Response = MqttResponse;
+
+    type Error = MqttError;
+
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, req: MqttRequest) -> Self::Future {
+        let service = self.clone();
+
+        Box::pin(async move {
+            let (sender, receiver) = oneshot::channel();
+
+            {
+                let _guard = service.publish_lock.lock().await;
+                service
+                    .pending
+                    .lock()
+                    .expect("mutex poisoned")
+                    .unassigned
+                    .push_back(sender);
+
+                if let Err(source) = service
+                    .client
+                    .publish_bytes(req.topic, service.qos, service.retain, req.body)
+                    .await
+                {
+                    // The publish never reached the event loop, so its sender is still the last
+                    // one queued.
+                    service
+                        .pending
+                        .lock()
+                        .expect("mutex poisoned")
+                        .unassigned
+                        .pop_back();
+                    return Err(MqttError::Publish { source });
+                }
+            }
+
+            receiver.await.map_err(|_| MqttError::Acknowledgement)?;
+
+            Ok(MqttResponse {
+                metadata: req.metadata,
+            })
+        })
+    }
+}
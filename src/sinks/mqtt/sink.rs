@@ -0,0 +1,181 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use rumqttc::{AsyncClient, Event as MqttPacketEvent, EventLoop, Outgoing, Packet, QoS};
+use snafu::ResultExt;
+use vector_common::internal_event::error_stage;
+
+use crate::{internal_events::MqttConnectionError, sinks::prelude::*};
+
+use super::{
+    config::MqttSinkConfig,
+    request_builder::{MqttEncoder, MqttRequestBuilder},
+    service::{MqttResponse, MqttService, PendingAcks},
+    EncodingSnafu, MqttError,
+};
+
+/// How long to wait before polling the event loop again after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// How long the event loop is given to send `DISCONNECT` once all requests have completed.
+const DISCONNECT_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// The default number of publishes awaiting acknowledgement at once, matching the client's
+/// default in-flight window.
+const DEFAULT_CONCURRENCY: usize = 100;
+
+pub(super) struct MqttEvent {
+    pub(super) event: Event,
+    pub(super) topic: String,
+}
+
+pub(super) struct MqttSink {
+    request: TowerRequestConfig,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    client: AsyncClient,
+    eventloop: EventLoop,
+    topic: Template,
+    qos: QoS,
+    retain: bool,
+}
+
+impl MqttSink {
+    pub(super) fn new(config: MqttSinkConfig) -> Result<Self, MqttError> {
+        let (client, eventloop) = config.connect()?;
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build().context(EncodingSnafu)?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(MqttSink {
+            request: config.request,
+            transformer,
+            encoder,
+            client,
+            eventloop,
+            topic: config.topic,
+            qos: config.quality_of_service.into(),
+            retain: config.retain,
+        })
+    }
+
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let request = self.request.unwrap_with(&TowerRequestConfig {
+            concurrency: Concurrency::Fixed(DEFAULT_CONCURRENCY),
+            ..Default::default()
+        });
+
+        let pending = Arc::new(Mutex::new(PendingAcks::default()));
+        let mut eventloop = tokio::spawn(drive_eventloop(self.eventloop, Arc::clone(&pending)));
+
+        let request_builder = MqttRequestBuilder {
+            encoder: MqttEncoder {
+                encoder: self.encoder.clone(),
+                transformer: self.transformer.clone(),
+            },
+        };
+
+        let service = ServiceBuilder::new()
+            .settings(request, MqttRetryLogic)
+            .service(MqttService {
+                client: self.client.clone(),
+                qos: self.qos,
+                retain: self.retain,
+                pending,
+                publish_lock: Default::default(),
+            });
+
+        let topic = self.topic;
+        let result = input
+            .filter_map(|event| std::future::ready(make_mqtt_event(&topic, event)))
+            .request_builder(default_request_builder_concurrency_limit(), request_builder)
+            .filter_map(|request| async move {
+                match request {
+                    Err(e) => {
+                        error!("Failed to build MQTT request: {:?}.", e);
+                        None
+                    }
+                    Ok(req) => Some(req),
+                }
+            })
+            .into_driver(service)
+            .protocol("mqtt")
+            .run()
+            .await;
+
+        _ = self.client.try_disconnect();
+        if tokio::time::timeout(DISCONNECT_TIMEOUT, &mut eventloop)
+            .await
+            .is_err()
+        {
+            eventloop.abort();
+        }
+
+        result
+    }
+}
+
+fn make_mqtt_event(topic: &Template, event: Event) -> Option<MqttEvent> {
+    let topic = topic
+        .render_string(&event)
+        .map_err(|missing_keys| {
+            emit!(TemplateRenderingError {
+                error: missing_keys,
+                field: Some("topic"),
+                drop_event: true,
+            });
+        })
+        .ok()?;
+
+    Some(MqttEvent { event, topic })
+}
+
+/// Drives the connection to the broker, resolving pending publishes as the broker acknowledges
+/// them. The client reconnects on the next poll after an error.
+async fn drive_eventloop(mut eventloop: EventLoop, pending: Arc<Mutex<PendingAcks>>) {
+    loop {
+        match eventloop.poll().await {
+            Ok(MqttPacketEvent::Outgoing(Outgoing::Publish(pkid))) => {
+                pending.lock().expect("mutex poisoned").outgoing(pkid)
+            }
+            Ok(MqttPacketEvent::Incoming(Packet::PubAck(ack))) => pending
+                .lock()
+                .expect("mutex poisoned")
+                .acknowledged(ack.pkid),
+            Ok(MqttPacketEvent::Incoming(Packet::PubComp(comp))) => pending
+                .lock()
+                .expect("mutex poisoned")
+                .acknowledged(comp.pkid),
+            Ok(MqttPacketEvent::Outgoing(Outgoing::Disconnect)) => break,
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError {
+                    error,
+                    stage: error_stage::SENDING,
+                });
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl StreamSink<Event> for MqttSink {
+    async fn run(mut self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct MqttRetryLogic;
+
+impl RetryLogic for MqttRetryLogic {
+    type Error = MqttError;
+    type Response = MqttResponse;
+
+    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
+        true
+    }
+}
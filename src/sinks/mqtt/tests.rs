@@ -0,0 +1,6 @@
+use super::config::MqttSinkConfig;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<MqttSinkConfig>();
+}
@@ -1,5 +1,6 @@
 use codecs::JsonSerializerConfig;
 use futures_util::TryFutureExt;
+use lookup::lookup_v2::ConfigTargetPath;
 use snafu::ResultExt;
 use vector_core::tls::TlsEnableableConfig;
 
@@ -69,6 +70,49 @@ pub struct NatsSinkConfig {
     #[configurable(derived)]
     #[serde(default)]
     pub(super) request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    pub(super) jetstream: Option<NatsJetStreamConfig>,
+
+    /// The log field name to use for the NATS message headers.
+    ///
+    /// The field must contain a map of string values. If omitted, no headers are written.
+    #[configurable(metadata(docs::advanced))]
+    #[configurable(metadata(docs::examples = "headers"))]
+    pub(super) headers_key: Option<ConfigTargetPath>,
+}
+
+/// Options for publishing messages through [NATS JetStream][nats_jetstream].
+///
+/// When set, each message is published to JetStream and the sink waits for the server's
+/// acknowledgement (`PubAck`) before considering the message delivered. Failed or timed out
+/// acknowledgements are retried according to the `request` settings.
+///
+/// [nats_jetstream]: https://docs.nats.io/nats-concepts/jetstream
+#[configurable_component]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub(super) struct NatsJetStreamConfig {
+    /// The template used to set the `Nats-Msg-Id` header of each message.
+    ///
+    /// JetStream uses this identifier to discard duplicate messages published within the
+    /// stream's duplicate window, which makes retried publishes idempotent.
+    ///
+    /// If omitted, no message ID is set.
+    #[configurable(metadata(docs::templateable))]
+    #[configurable(metadata(
+        docs::examples = "{{ id }}",
+        docs::examples = "{{ host }}-{{ offset }}"
+    ))]
+    pub(super) message_id: Option<Template>,
+
+    /// The [JetStream domain][nats_jetstream_domain] to publish to.
+    ///
+    /// If omitted, the default domain of the account is used.
+    ///
+    /// [nats_jetstream_domain]: https://docs.nats.io/running-a-nats-service/configuration/leafnodes/jetstream_leafnodes
+    #[configurable(metadata(docs::examples = "hub"))]
+    pub(super) domain: Option<String>,
 }
 
 fn default_name() -> String {
@@ -86,6 +130,8 @@ impl GenerateConfig for NatsSinkConfig {
             tls: None,
             url: "nats://127.0.0.1:4222".into(),
             request: Default::default(),
+            jetstream: None,
+            headers_key: None,
         })
         .unwrap()
     }
@@ -123,6 +169,18 @@ impl NatsSinkConfig {
 
         options.connect(&self.url).await.context(ConnectSnafu)
     }
+
+    pub(super) fn jetstream_context(
+        &self,
+        client: async_nats::Client,
+    ) -> Option<async_nats::jetstream::Context> {
+        self.jetstream
+            .as_ref()
+            .map(|jetstream| match &jetstream.domain {
+                Some(domain) => async_nats::jetstream::with_domain(client, domain),
+                None => async_nats::jetstream::new(client),
+            })
+    }
 }
 
 async fn healthcheck(config: NatsSinkConfig) -> crate::Result<()> {
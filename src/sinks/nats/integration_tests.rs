@@ -1,15 +1,20 @@
 use codecs::TextSerializerConfig;
 use std::time::Duration;
 
-use super::{config::NatsSinkConfig, sink::NatsSink, NatsError};
+use super::{
+    config::{NatsJetStreamConfig, NatsSinkConfig},
+    sink::NatsSink,
+    NatsError,
+};
 use crate::{
+    event::{BatchNotifier, BatchStatus},
     nats::{
         NatsAuthConfig, NatsAuthCredentialsFile, NatsAuthNKey, NatsAuthToken, NatsAuthUserPassword,
     },
     sinks::prelude::*,
     test_util::{
         components::{run_and_assert_sink_compliance, SINK_TAGS},
-        random_lines_with_stream, random_string, trace_init,
+        generate_lines_with_stream, random_lines_with_stream, random_string, trace_init,
     },
     tls::TlsEnableableConfig,
 };
@@ -78,6 +83,8 @@ async fn nats_no_auth() {
         tls: None,
         auth: None,
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -110,6 +117,8 @@ async fn nats_userpass_auth_valid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     publish_and_check(conf)
@@ -139,6 +148,8 @@ async fn nats_userpass_auth_invalid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -170,6 +181,8 @@ async fn nats_token_auth_valid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -201,6 +214,8 @@ async fn nats_token_auth_invalid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -233,6 +248,8 @@ async fn nats_nkey_auth_valid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -265,6 +282,8 @@ async fn nats_nkey_auth_invalid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -298,6 +317,8 @@ async fn nats_tls_valid() {
         }),
         auth: None,
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -325,6 +346,8 @@ async fn nats_tls_invalid() {
         tls: None,
         auth: None,
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -360,6 +383,8 @@ async fn nats_tls_client_cert_valid() {
         }),
         auth: None,
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -393,6 +418,8 @@ async fn nats_tls_client_cert_invalid() {
         }),
         auth: None,
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -430,6 +457,8 @@ async fn nats_tls_jwt_auth_valid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -467,6 +496,8 @@ async fn nats_tls_jwt_auth_invalid() {
             },
         }),
         request: Default::default(),
+        jetstream: None,
+        headers_key: None,
     };
 
     let r = publish_and_check(conf).await;
@@ -476,3 +507,90 @@ async fn nats_tls_jwt_auth_invalid() {
         r
     );
 }
+
+#[tokio::test]
+async fn nats_jetstream_message_id_dedup() {
+    trace_init();
+
+    let subject = format!("test-{}", random_string(10));
+    let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+        .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+    let conf = NatsSinkConfig {
+        acknowledgements: Default::default(),
+        encoding: TextSerializerConfig::default().into(),
+        connection_name: "".to_owned(),
+        subject: Template::try_from(subject.as_str()).unwrap(),
+        url,
+        tls: None,
+        auth: None,
+        request: Default::default(),
+        jetstream: Some(NatsJetStreamConfig {
+            message_id: Some(Template::try_from("{{ message }}").unwrap()),
+            domain: None,
+        }),
+        headers_key: None,
+    };
+
+    // Create the stream capturing the subject before publishing, JetStream
+    // rejects publishes to subjects without a stream.
+    let client = conf.connect().await.expect("failed to connect");
+    let jetstream = async_nats::jetstream::new(client);
+    let mut stream = jetstream
+        .create_stream(async_nats::jetstream::stream::Config {
+            name: subject.clone(),
+            subjects: vec![subject.clone()],
+            ..Default::default()
+        })
+        .await
+        .expect("failed to create stream");
+
+    // Every line is sent twice, the duplicates must be discarded by the
+    // server based on the `Nats-Msg-Id` header.
+    let num_events = 10;
+    let (_input, events) =
+        generate_lines_with_stream(|index| format!("line-{}", index % 5), num_events, None);
+
+    let sink = NatsSink::new(conf).await.expect("failed to build sink");
+    let sink = VectorSink::from_event_streamsink(sink);
+    run_and_assert_sink_compliance(sink, events, &SINK_TAGS).await;
+
+    let info = stream.info().await.expect("failed to fetch stream info");
+    assert_eq!(info.state.messages, 5);
+}
+
+#[tokio::test]
+async fn nats_jetstream_no_stream() {
+    trace_init();
+
+    let subject = format!("test-{}", random_string(10));
+    let url = std::env::var("NATS_JETSTREAM_ADDRESS")
+        .unwrap_or_else(|_| String::from("nats://localhost:4222"));
+
+    let conf = NatsSinkConfig {
+        acknowledgements: Default::default(),
+        encoding: TextSerializerConfig::default().into(),
+        connection_name: "".to_owned(),
+        subject: Template::try_from(subject.as_str()).unwrap(),
+        url,
+        tls: None,
+        auth: None,
+        request: TowerRequestConfig {
+            retry_attempts: Some(0),
+            ..Default::default()
+        },
+        jetstream: Some(NatsJetStreamConfig::default()),
+        headers_key: None,
+    };
+
+    // Without a stream bound to the subject the publish is never acknowledged,
+    // so the events must be rejected rather than reported as delivered.
+    let (batch, receiver) = BatchNotifier::new_with_receiver();
+    let (_input, events) = random_lines_with_stream(100, 10, Some(batch));
+
+    let sink = NatsSink::new(conf).await.expect("failed to build sink");
+    let sink = VectorSink::from_event_streamsink(sink);
+    sink.run(events).await.expect("sink failed");
+
+    assert_eq!(receiver.await, BatchStatus::Rejected);
+}
@@ -1,5 +1,6 @@
 //! `NATS` sink
 //! Publishes data using [NATS](nats.io)(Neural Autonomic Transport System).
+//! Optionally publishes through JetStream and waits for the server acknowledgement.
 
 use snafu::Snafu;
 
@@ -27,4 +28,8 @@ enum NatsError {
     Connect { source: async_nats::ConnectError },
     #[snafu(display("NATS Server Error: {}", source))]
     ServerError { source: async_nats::Error },
+    #[snafu(display("NATS JetStream Publish Error: {}", source))]
+    Publish {
+        source: async_nats::jetstream::context::PublishError,
+    },
 }
@@ -1,10 +1,12 @@
 use std::io;
 
+use async_nats::HeaderMap;
 use bytes::{Bytes, BytesMut};
 use tokio_util::codec::Encoder as _;
 use vector_core::config::telemetry;
+use vrl::path::OwnedTargetPath;
 
-use crate::sinks::prelude::*;
+use crate::{internal_events::NatsHeaderExtractionError, sinks::prelude::*};
 
 use super::sink::NatsEvent;
 
@@ -39,17 +41,22 @@ impl encoding::Encoder<Event> for NatsEncoder {
 
 pub(super) struct NatsMetadata {
     subject: String,
+    message_id: Option<String>,
+    headers: Option<HeaderMap>,
     finalizers: EventFinalizers,
 }
 
 pub(super) struct NatsRequestBuilder {
     pub(super) encoder: NatsEncoder,
+    pub(super) headers_key: Option<OwnedTargetPath>,
 }
 
 #[derive(Clone)]
 pub(super) struct NatsRequest {
     pub(super) bytes: Bytes,
     pub(super) subject: String,
+    pub(super) message_id: Option<String>,
+    pub(super) headers: Option<HeaderMap>,
     finalizers: EventFinalizers,
     pub(super) metadata: RequestMetadata,
 }
@@ -94,6 +101,8 @@ impl RequestBuilder<NatsEvent> for NatsRequestBuilder {
 
         let metadata = NatsMetadata {
             subject: input.subject,
+            message_id: input.message_id,
+            headers: get_headers(&input.event, self.headers_key.as_ref()),
             finalizers: input.event.take_finalizers(),
         };
 
@@ -110,8 +119,69 @@ impl RequestBuilder<NatsEvent> for NatsRequestBuilder {
         NatsRequest {
             bytes: body,
             subject: nats_metadata.subject,
+            message_id: nats_metadata.message_id,
+            headers: nats_metadata.headers,
             finalizers: nats_metadata.finalizers,
             metadata,
         }
     }
 }
+
+fn get_headers(event: &Event, headers_key: Option<&OwnedTargetPath>) -> Option<HeaderMap> {
+    let headers_key = headers_key?;
+    let headers = event.maybe_as_log()?.get(headers_key)?;
+
+    match headers {
+        Value::Object(headers_map) => {
+            let mut header_map = HeaderMap::new();
+            for (key, value) in headers_map {
+                if let Value::Bytes(value_bytes) = value {
+                    header_map.insert(key.as_str(), String::from_utf8_lossy(value_bytes).as_ref());
+                } else {
+                    emit!(NatsHeaderExtractionError {
+                        header_field: headers_key
+                    });
+                }
+            }
+            Some(header_map)
+        }
+        _ => {
+            emit!(NatsHeaderExtractionError {
+                header_field: headers_key
+            });
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+
+    #[test]
+    fn nats_get_headers() {
+        let headers_key = OwnedTargetPath::try_from("headers".to_string()).unwrap();
+        let mut header_values = BTreeMap::new();
+        header_values.insert("a-key".to_string(), Value::Bytes(Bytes::from("a-value")));
+        header_values.insert("b-key".to_string(), Value::Bytes(Bytes::from("b-value")));
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert(&headers_key, header_values);
+
+        let headers = get_headers(&event, Some(&headers_key)).unwrap();
+        assert_eq!(headers.get("a-key").unwrap().as_str(), "a-value");
+        assert_eq!(headers.get("b-key").unwrap().as_str(), "b-value");
+    }
+
+    #[test]
+    fn nats_get_headers_not_a_map() {
+        let headers_key = OwnedTargetPath::try_from("headers".to_string()).unwrap();
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert(&headers_key, "not a map");
+
+        assert!(get_headers(&event, Some(&headers_key)).is_none());
+    }
+}
@@ -3,15 +3,17 @@ use std::{
     task::{Context, Poll},
 };
 
-use futures_util::TryFutureExt;
+use async_nats::jetstream::context::Publish;
+use snafu::ResultExt;
 
 use crate::sinks::prelude::*;
 
-use super::{request_builder::NatsRequest, NatsError};
+use super::{request_builder::NatsRequest, NatsError, PublishSnafu};
 
 #[derive(Clone)]
 pub(super) struct NatsService {
     pub(super) connection: Arc<async_nats::Client>,
+    pub(super) jetstream: Option<Arc<async_nats::jetstream::Context>>,
 }
 
 pub(super) struct NatsResponse {
@@ -45,19 +47,55 @@ impl Service<NatsRequest> for NatsService {
 
     fn call(&mut self, req: NatsRequest) -> Self::Future {
         let connection = Arc::clone(&self.connection);
+        let jetstream = self.jetstream.clone();
 
         Box::pin(async move {
-            match connection
-                .publish(req.subject, req.bytes)
-                .map_err(async_nats::Error::from)
-                .and_then(|_| connection.flush().map_err(Into::into))
-                .await
-            {
-                Err(error) => Err(NatsError::ServerError { source: error }),
-                Ok(_) => Ok(NatsResponse {
-                    metadata: req.metadata,
-                }),
+            match jetstream {
+                Some(jetstream) => {
+                    let mut publish = Publish::build().payload(req.bytes);
+                    if let Some(headers) = req.headers {
+                        publish = publish.headers(headers);
+                    }
+                    if let Some(message_id) = req.message_id {
+                        publish = publish.message_id(message_id);
+                    }
+
+                    let ack = jetstream
+                        .send_publish(req.subject, publish)
+                        .await
+                        .context(PublishSnafu)?
+                        .await
+                        .context(PublishSnafu)?;
+
+                    if ack.duplicate {
+                        debug!(
+                            message = "Message was discarded by JetStream as a duplicate.",
+                            stream = %ack.stream,
+                            sequence = ack.sequence,
+                        );
+                    }
+                }
+                None => {
+                    async {
+                        match req.headers {
+                            Some(headers) => {
+                                connection
+                                    .publish_with_headers(req.subject, headers, req.bytes)
+                                    .await?
+                            }
+                            None => connection.publish(req.subject, req.bytes).await?,
+                        }
+                        connection.flush().await?;
+                        Ok::<_, async_nats::Error>(())
+                    }
+                    .await
+                    .map_err(|error| NatsError::ServerError { source: error })?;
+                }
             }
+
+            Ok(NatsResponse {
+                metadata: req.metadata,
+            })
         })
     }
 }
@@ -1,6 +1,8 @@
 use std::sync::Arc;
 
+use async_nats::jetstream::context::PublishErrorKind;
 use snafu::ResultExt;
+use vrl::path::OwnedTargetPath;
 
 use crate::sinks::prelude::*;
 
@@ -14,6 +16,7 @@ use super::{
 pub(super) struct NatsEvent {
     pub(super) event: Event,
     pub(super) subject: String,
+    pub(super) message_id: Option<String>,
 }
 
 pub(super) struct NatsSink {
@@ -21,7 +24,10 @@ pub(super) struct NatsSink {
     transformer: Transformer,
     encoder: Encoder<()>,
     connection: Arc<async_nats::Client>,
+    jetstream: Option<Arc<async_nats::jetstream::Context>>,
     subject: Template,
+    message_id: Option<Template>,
+    headers_key: Option<OwnedTargetPath>,
 }
 
 impl NatsSink {
@@ -38,23 +44,50 @@ impl NatsSink {
             })
             .ok()?;
 
-        Some(NatsEvent { event, subject })
+        let message_id = match &self.message_id {
+            Some(message_id) => Some(
+                message_id
+                    .render_string(&event)
+                    .map_err(|missing_keys| {
+                        emit!(TemplateRenderingError {
+                            error: missing_keys,
+                            field: Some("jetstream.message_id"),
+                            drop_event: true,
+                        });
+                    })
+                    .ok()?,
+            ),
+            None => None,
+        };
+
+        Some(NatsEvent {
+            event,
+            subject,
+            message_id,
+        })
     }
 
     pub(super) async fn new(config: NatsSinkConfig) -> Result<Self, NatsError> {
-        let connection = Arc::new(config.connect().await?);
+        let client = config.connect().await?;
+        let jetstream = config.jetstream_context(client.clone()).map(Arc::new);
+        let connection = Arc::new(client);
         let transformer = config.encoding.transformer();
         let serializer = config.encoding.build().context(EncodingSnafu)?;
         let encoder = Encoder::<()>::new(serializer);
         let request = config.request;
         let subject = config.subject;
+        let message_id = config.jetstream.and_then(|jetstream| jetstream.message_id);
+        let headers_key = config.headers_key.map(|key| key.0);
 
         Ok(NatsSink {
             request,
             connection,
+            jetstream,
             transformer,
             encoder,
             subject,
+            message_id,
+            headers_key,
         })
     }
 
@@ -69,12 +102,14 @@ impl NatsSink {
                 encoder: self.encoder.clone(),
                 transformer: self.transformer.clone(),
             },
+            headers_key: self.headers_key.clone(),
         };
 
         let service = ServiceBuilder::new()
             .settings(request, NatsRetryLogic)
             .service(NatsService {
                 connection: Arc::clone(&self.connection),
+                jetstream: self.jetstream.clone(),
             });
 
         input
@@ -110,7 +145,15 @@ impl RetryLogic for NatsRetryLogic {
     type Error = NatsError;
     type Response = NatsResponse;
 
-    fn is_retriable_error(&self, _error: &Self::Error) -> bool {
-        true
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        match error {
+            // A mismatched expected sequence or message ID is reported by the server for every
+            // attempt, so retrying the publish cannot succeed.
+            NatsError::Publish { source } => !matches!(
+                source.kind(),
+                PublishErrorKind::WrongLastMessageId | PublishErrorKind::WrongLastSequence
+            ),
+            _ => true,
+        }
     }
 }
@@ -0,0 +1,190 @@
+use futures::{future, FutureExt};
+use http::Uri;
+use indexmap::IndexMap;
+use tower::ServiceBuilder;
+use vector_config::configurable_component;
+
+use super::{
+    service::{OpenTelemetryRetryLogic, OpenTelemetryService, Transport},
+    sink::OpenTelemetrySink,
+};
+use crate::{
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    http::{build_proxy_connector, HttpClient},
+    sinks::{
+        util::{
+            http::validate_headers, BatchConfig, RealtimeSizeBasedDefaultBatchSettings,
+            ServiceBuilderExt, TowerRequestConfig,
+        },
+        Healthcheck, VectorSink,
+    },
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// The transport protocol and payload encoding used to export data.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OtlpProtocol {
+    /// OTLP/gRPC.
+    ///
+    /// The endpoint is the address of the collector's gRPC server, which conventionally listens on
+    /// port 4317.
+    #[default]
+    Grpc,
+
+    /// OTLP/HTTP with binary protobuf payloads.
+    ///
+    /// Logs, metrics, and traces are sent to the `/v1/logs`, `/v1/metrics`, and `/v1/traces`
+    /// paths of the endpoint, which conventionally listens on port 4318.
+    HttpProtobuf,
+
+    /// OTLP/HTTP with JSON payloads.
+    ///
+    /// Logs, metrics, and traces are sent to the `/v1/logs`, `/v1/metrics`, and `/v1/traces`
+    /// paths of the endpoint, which conventionally listens on port 4318.
+    HttpJson,
+}
+
+/// Configuration for the `opentelemetry` sink.
+#[configurable_component(sink(
+    "opentelemetry",
+    "Deliver logs, metrics, and traces to an OpenTelemetry collector or any OTLP-compatible backend."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OpenTelemetryConfig {
+    /// The OTLP endpoint to which to connect.
+    ///
+    /// If the endpoint has no scheme, `http` or `https` is used depending on whether TLS is
+    /// enabled. When using one of the HTTP protocols, the signal-specific path (for example
+    /// `/v1/logs`) is appended to the path of the endpoint.
+    #[configurable(validation(format = "uri"))]
+    #[configurable(metadata(docs::examples = "http://localhost:4317"))]
+    #[configurable(metadata(docs::examples = "https://otlp.example.com:4318"))]
+    endpoint: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    protocol: OtlpProtocol,
+
+    /// Whether or not to compress requests.
+    ///
+    /// If set to `true`, requests are compressed with [`gzip`][gzip_docs].
+    ///
+    /// [gzip_docs]: https://www.gzip.org/
+    #[serde(default)]
+    compression: bool,
+
+    /// Additional headers to send with each request.
+    ///
+    /// With the gRPC protocol, these are sent as request metadata. This is typically used to
+    /// authenticate against the backend.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An HTTP request header or gRPC metadata entry."
+    ))]
+    #[configurable(metadata(docs::examples = "headers_examples()"))]
+    headers: IndexMap<String, String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    batch: BatchConfig<RealtimeSizeBasedDefaultBatchSettings>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    request: TowerRequestConfig,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    acknowledgements: AcknowledgementsConfig,
+}
+
+fn headers_examples() -> IndexMap<String, String> {
+    IndexMap::from_iter([(
+        "Authorization".to_owned(),
+        "Bearer ${OTLP_TOKEN}".to_owned(),
+    )])
+}
+
+impl GenerateConfig for OpenTelemetryConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"endpoint = "http://localhost:4317"
+            protocol = "grpc""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "opentelemetry")]
+impl SinkConfig for OpenTelemetryConfig {
+    async fn build(&self, cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let endpoint = endpoint_with_default_scheme(&self.endpoint, tls.is_tls())?;
+        let headers = validate_headers(&self.headers)?;
+
+        let transport = match self.protocol {
+            OtlpProtocol::Grpc => {
+                let proxy = build_proxy_connector(tls, cx.proxy())?;
+                let client = hyper::Client::builder().http2_only(true).build(proxy);
+                Transport::grpc(client, endpoint.clone(), headers, self.compression)
+            }
+            OtlpProtocol::HttpProtobuf | OtlpProtocol::HttpJson => {
+                let client = HttpClient::new(tls, cx.proxy())?;
+                Transport::Http {
+                    client,
+                    endpoint: endpoint.clone(),
+                    headers,
+                    json: self.protocol == OtlpProtocol::HttpJson,
+                    compression: self.compression,
+                }
+            }
+        };
+
+        let service = OpenTelemetryService::new(transport, &endpoint);
+        let request_settings = self.request.unwrap_with(&TowerRequestConfig::default());
+        let service = ServiceBuilder::new()
+            .settings(request_settings, OpenTelemetryRetryLogic)
+            .service(service);
+
+        let sink = OpenTelemetrySink {
+            service,
+            batch_settings: self.batch.into_batcher_settings()?,
+        };
+
+        // OTLP has no health check endpoint, so the connection is only exercised by the first
+        // export request.
+        let healthcheck = future::ok(()).boxed();
+
+        Ok((VectorSink::from_event_streamsink(sink), healthcheck))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+/// Like most OTLP exporters, defaults the scheme of the endpoint to `http` or `https` depending on
+/// whether TLS is enabled.
+fn endpoint_with_default_scheme(endpoint: &str, tls: bool) -> crate::Result<Uri> {
+    let uri: Uri = endpoint.parse()?;
+    if uri.scheme().is_some() {
+        return Ok(uri);
+    }
+
+    let scheme = if tls { "https" } else { "http" };
+    Ok(format!("{}://{}", scheme, endpoint).parse()?)
+}
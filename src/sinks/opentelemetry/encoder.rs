@@ -0,0 +1,720 @@
+//! Conversion of Vector events into OTLP export requests.
+//!
+//! This is the inverse of the conversions done by the `opentelemetry` source, so that events
+//! received from one OTLP endpoint round-trip to another. Records are grouped by resource, and then
+//! by instrumentation scope, as OTLP expects.
+
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use lookup::{event_path, metadata_path};
+use opentelemetry_proto::{
+    convert::{
+        ATTRIBUTES_KEY, DROPPED_ATTRIBUTES_COUNT_KEY, FLAGS_KEY, OBSERVED_TIMESTAMP_KEY,
+        RESOURCE_KEY, RESOURCE_TAG_PREFIX, SCOPE_KEY, SCOPE_TAG_PREFIX, SEVERITY_NUMBER_KEY,
+        SEVERITY_TEXT_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
+    },
+    proto::{
+        collector::{
+            logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+            trace::v1::ExportTraceServiceRequest,
+        },
+        common::v1::{
+            any_value, AnyValue, ArrayValue, InstrumentationScope, KeyValue, KeyValueList,
+        },
+        logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+        metrics::v1::{
+            metric::Data, number_data_point, summary_data_point::ValueAtQuantile,
+            AggregationTemporality, Gauge, Histogram, HistogramDataPoint, Metric as PBMetric,
+            NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+        },
+        resource::v1::Resource,
+        trace::v1::{
+            span::{Event as SpanEvent, Link},
+            ResourceSpans, ScopeSpans, Span, Status,
+        },
+    },
+};
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
+use vector_core::{
+    config::{log_schema, LogNamespace},
+    event::{
+        metric::{Bucket, MetricSketch, Quantile},
+        Event, LogEvent, Metric, MetricKind, MetricValue, TraceEvent,
+    },
+    metrics::AgentDDSketch,
+};
+use vrl::value::Value;
+
+use crate::sinks::util::encode_namespace;
+
+/// The name of the source whose metadata holds OTLP log fields with the `Vector` log namespace.
+const SOURCE_NAME: &str = "opentelemetry";
+
+/// OTLP has no equivalent of distributions or sketches, so these are sent as summaries with the
+/// following quantiles.
+const SUMMARY_QUANTILES: [f64; 5] = [0.5, 0.75, 0.9, 0.95, 0.99];
+
+pub(super) fn encode_logs(events: Vec<Event>) -> ExportLogsServiceRequest {
+    let now = Utc::now();
+    let mut resource_logs = Vec::<ResourceLogs>::new();
+
+    for event in events {
+        let (resource, scope, log_record) = log_record(event.into_log(), now);
+
+        let index = resource_logs
+            .iter()
+            .position(|logs| logs.resource == resource)
+            .unwrap_or_else(|| {
+                resource_logs.push(ResourceLogs {
+                    resource,
+                    scope_logs: Vec::new(),
+                    schema_url: String::new(),
+                });
+                resource_logs.len() - 1
+            });
+        let scope_logs = &mut resource_logs[index].scope_logs;
+
+        let index = scope_logs
+            .iter()
+            .position(|logs| logs.scope == scope)
+            .unwrap_or_else(|| {
+                scope_logs.push(ScopeLogs {
+                    scope,
+                    log_records: Vec::new(),
+                    schema_url: String::new(),
+                });
+                scope_logs.len() - 1
+            });
+        scope_logs[index].log_records.push(log_record);
+    }
+
+    ExportLogsServiceRequest { resource_logs }
+}
+
+pub(super) fn encode_metrics(events: Vec<Event>) -> ExportMetricsServiceRequest {
+    let now = Utc::now();
+    let mut resource_metrics = Vec::<ResourceMetrics>::new();
+    let mut unsupported = 0;
+
+    for event in events {
+        let metric = event.into_metric();
+        let (resource, scope, attributes) = split_tags(&metric);
+        let Some((name, data)) = metric_data(&metric, attributes, now) else {
+            unsupported += 1;
+            continue;
+        };
+
+        let index = resource_metrics
+            .iter()
+            .position(|metrics| metrics.resource == resource)
+            .unwrap_or_else(|| {
+                resource_metrics.push(ResourceMetrics {
+                    resource,
+                    scope_metrics: Vec::new(),
+                    schema_url: String::new(),
+                });
+                resource_metrics.len() - 1
+            });
+        let scope_metrics = &mut resource_metrics[index].scope_metrics;
+
+        let index = scope_metrics
+            .iter()
+            .position(|metrics| metrics.scope == scope)
+            .unwrap_or_else(|| {
+                scope_metrics.push(ScopeMetrics {
+                    scope,
+                    metrics: Vec::new(),
+                    schema_url: String::new(),
+                });
+                scope_metrics.len() - 1
+            });
+        push_metric(&mut scope_metrics[index].metrics, name, data);
+    }
+
+    if unsupported > 0 {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: unsupported,
+            reason: "Metric can't be represented in OTLP.",
+        });
+    }
+
+    ExportMetricsServiceRequest { resource_metrics }
+}
+
+pub(super) fn encode_traces(events: Vec<Event>) -> ExportTraceServiceRequest {
+    let mut resource_spans = Vec::<ResourceSpans>::new();
+
+    for event in events {
+        let (resource, scope, span) = span(event.into_trace());
+
+        let index = resource_spans
+            .iter()
+            .position(|spans| spans.resource == resource)
+            .unwrap_or_else(|| {
+                resource_spans.push(ResourceSpans {
+                    resource,
+                    scope_spans: Vec::new(),
+                    schema_url: String::new(),
+                });
+                resource_spans.len() - 1
+            });
+        let scope_spans = &mut resource_spans[index].scope_spans;
+
+        let index = scope_spans
+            .iter()
+            .position(|spans| spans.scope == scope)
+            .unwrap_or_else(|| {
+                scope_spans.push(ScopeSpans {
+                    scope,
+                    spans: Vec::new(),
+                    schema_url: String::new(),
+                });
+                scope_spans.len() - 1
+            });
+        scope_spans[index].spans.push(span);
+    }
+
+    ExportTraceServiceRequest { resource_spans }
+}
+
+/// Removes an OTLP log field, which is held in the source metadata with the `Vector` log namespace
+/// and at the root of the event with the `Legacy` log namespace.
+fn remove_log_field(log: &mut LogEvent, namespace: LogNamespace, key: &str) -> Option<Value> {
+    match namespace {
+        LogNamespace::Vector => log.remove(metadata_path!(SOURCE_NAME, key)),
+        LogNamespace::Legacy => log.remove(event_path!(key)),
+    }
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/logs/data-model.md
+fn log_record(
+    mut log: LogEvent,
+    now: DateTime<Utc>,
+) -> (Option<Resource>, Option<InstrumentationScope>, LogRecord) {
+    let namespace = log.namespace();
+
+    let resource = remove_log_field(&mut log, namespace, RESOURCE_KEY).and_then(resource);
+    let scope = remove_log_field(&mut log, namespace, SCOPE_KEY).and_then(scope);
+    let mut attributes = remove_log_field(&mut log, namespace, ATTRIBUTES_KEY)
+        .map(key_values)
+        .unwrap_or_default();
+    let trace_id =
+        remove_log_field(&mut log, namespace, TRACE_ID_KEY).map_or_else(Vec::new, hex_id);
+    let span_id = remove_log_field(&mut log, namespace, SPAN_ID_KEY).map_or_else(Vec::new, hex_id);
+    let severity_text = remove_log_field(&mut log, namespace, SEVERITY_TEXT_KEY)
+        .map(string)
+        .unwrap_or_default();
+    let severity_number =
+        remove_log_field(&mut log, namespace, SEVERITY_NUMBER_KEY).map_or(0, integer) as i32;
+    let flags = remove_log_field(&mut log, namespace, FLAGS_KEY).map_or(0, integer) as u32;
+    let dropped_attributes_count =
+        remove_log_field(&mut log, namespace, DROPPED_ATTRIBUTES_COUNT_KEY).map_or(0, integer)
+            as u32;
+    let observed_time_unix_nano = remove_log_field(&mut log, namespace, OBSERVED_TIMESTAMP_KEY)
+        .map_or_else(|| timestamp_nanos(now), nanos);
+    let time_unix_nano = match namespace {
+        LogNamespace::Vector => log.remove(metadata_path!(SOURCE_NAME, "timestamp")),
+        LogNamespace::Legacy => log.remove_timestamp(),
+    }
+    .map_or(0, nanos);
+
+    let body = match namespace {
+        LogNamespace::Vector => log.into_parts().0,
+        LogNamespace::Legacy => {
+            if let Some(path) = log_schema().source_type_key_target_path() {
+                log.remove(path);
+            }
+            let message = log_schema()
+                .message_key_target_path()
+                .and_then(|path| log.remove(path))
+                .unwrap_or(Value::Null);
+
+            // Any other fields, such as the ones added by non-OTLP sources, are kept as attributes.
+            if let Value::Object(fields) = log.into_parts().0 {
+                for (key, value) in fields {
+                    if !attributes.iter().any(|attribute| attribute.key == key) {
+                        attributes.push(key_value(key, value));
+                    }
+                }
+            }
+            message
+        }
+    };
+
+    let log_record = LogRecord {
+        time_unix_nano,
+        observed_time_unix_nano,
+        severity_number,
+        severity_text,
+        body: (body != Value::Null).then(|| any_value(body)),
+        attributes,
+        dropped_attributes_count,
+        flags,
+        trace_id,
+        span_id,
+    };
+
+    (resource, scope, log_record)
+}
+
+/// Splits the tags of a metric into its resource, instrumentation scope, and data point
+/// attributes, based on the tag prefixes used by the `opentelemetry` source.
+fn split_tags(
+    metric: &Metric,
+) -> (
+    Option<Resource>,
+    Option<InstrumentationScope>,
+    Vec<KeyValue>,
+) {
+    let mut resource = Vec::new();
+    let mut scope = InstrumentationScope::default();
+    let mut attributes = Vec::new();
+
+    for (key, value) in metric
+        .tags()
+        .into_iter()
+        .flat_map(|tags| tags.iter_single())
+    {
+        if let Some(key) = key.strip_prefix(RESOURCE_TAG_PREFIX) {
+            resource.push(key_value(key.to_owned(), value.into()));
+        } else if let Some(key) = key.strip_prefix(SCOPE_TAG_PREFIX) {
+            match key {
+                "name" => scope.name = value.to_owned(),
+                "version" => scope.version = value.to_owned(),
+                _ => scope
+                    .attributes
+                    .push(key_value(key.to_owned(), value.into())),
+            }
+        } else {
+            attributes.push(key_value(key.to_owned(), value.into()));
+        }
+    }
+
+    let resource = (!resource.is_empty()).then(|| Resource {
+        attributes: resource,
+        dropped_attributes_count: 0,
+    });
+    let scope = (scope != InstrumentationScope::default()).then_some(scope);
+
+    (resource, scope, attributes)
+}
+
+const fn aggregation_temporality(kind: MetricKind) -> i32 {
+    match kind {
+        MetricKind::Incremental => AggregationTemporality::Delta as i32,
+        MetricKind::Absolute => AggregationTemporality::Cumulative as i32,
+    }
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/metrics/data-model.md
+fn metric_data(
+    metric: &Metric,
+    attributes: Vec<KeyValue>,
+    now: DateTime<Utc>,
+) -> Option<(String, Data)> {
+    let name = encode_namespace(metric.namespace(), '.', metric.name());
+    let kind = metric.kind();
+    let time_unix_nano = timestamp_nanos(metric.timestamp().unwrap_or(now));
+    // Incremental metrics cover the interval that ended at their timestamp, if it is known.
+    let start_time_unix_nano = match (kind, metric.interval_ms()) {
+        (MetricKind::Incremental, Some(interval_ms)) => {
+            time_unix_nano.saturating_sub(u64::from(interval_ms.get()) * 1_000_000)
+        }
+        _ => 0,
+    };
+    let number_point = |value| NumberDataPoint {
+        attributes: attributes.clone(),
+        start_time_unix_nano,
+        time_unix_nano,
+        value: Some(value),
+        exemplars: Vec::new(),
+        flags: 0,
+    };
+    let summary = |quantiles: Vec<ValueAtQuantile>, count, sum| {
+        Data::Summary(Summary {
+            data_points: vec![SummaryDataPoint {
+                attributes: attributes.clone(),
+                start_time_unix_nano,
+                time_unix_nano,
+                count,
+                sum,
+                quantile_values: quantiles,
+                flags: 0,
+            }],
+        })
+    };
+
+    let data = match metric.value() {
+        MetricValue::Counter { value } => Data::Sum(Sum {
+            data_points: vec![number_point(number_data_point::Value::AsDouble(*value))],
+            aggregation_temporality: aggregation_temporality(kind),
+            is_monotonic: true,
+        }),
+        MetricValue::Gauge { value } => {
+            let point = number_point(number_data_point::Value::AsDouble(*value));
+            match kind {
+                MetricKind::Absolute => Data::Gauge(Gauge {
+                    data_points: vec![point],
+                }),
+                // A gauge delta can only be represented as a non-monotonic sum.
+                MetricKind::Incremental => Data::Sum(Sum {
+                    data_points: vec![point],
+                    aggregation_temporality: aggregation_temporality(kind),
+                    is_monotonic: false,
+                }),
+            }
+        }
+        MetricValue::Set { values } => Data::Gauge(Gauge {
+            data_points: vec![number_point(number_data_point::Value::AsInt(
+                values.len() as i64
+            ))],
+        }),
+        value @ MetricValue::Distribution { .. } => match value.distribution_to_sketch()? {
+            MetricValue::Sketch {
+                sketch: MetricSketch::AgentDDSketch(sketch),
+            } => sketch_summary(&sketch, summary),
+            _ => return None,
+        },
+        MetricValue::AggregatedHistogram {
+            buckets,
+            count,
+            sum,
+        } => Data::Histogram(Histogram {
+            data_points: vec![histogram_point(
+                buckets,
+                *count,
+                *sum,
+                HistogramDataPoint {
+                    attributes: attributes.clone(),
+                    start_time_unix_nano,
+                    time_unix_nano,
+                    ..Default::default()
+                },
+            )],
+            aggregation_temporality: aggregation_temporality(kind),
+        }),
+        MetricValue::AggregatedSummary {
+            quantiles,
+            count,
+            sum,
+        } => summary(
+            quantiles
+                .iter()
+                .map(|Quantile { quantile, value }| ValueAtQuantile {
+                    quantile: *quantile,
+                    value: *value,
+                })
+                .collect(),
+            *count,
+            *sum,
+        ),
+        MetricValue::Sketch {
+            sketch: MetricSketch::AgentDDSketch(sketch),
+        } => sketch_summary(sketch, summary),
+    };
+
+    Some((name, data))
+}
+
+fn histogram_point(
+    buckets: &[Bucket],
+    count: u64,
+    sum: f64,
+    point: HistogramDataPoint,
+) -> HistogramDataPoint {
+    // OTLP histograms have one more bucket than there are bounds, the last one being unbounded.
+    let mut explicit_bounds: Vec<f64> = buckets.iter().map(|bucket| bucket.upper_limit).collect();
+    let mut bucket_counts: Vec<u64> = buckets.iter().map(|bucket| bucket.count).collect();
+    if explicit_bounds.last() == Some(&f64::INFINITY) {
+        explicit_bounds.pop();
+    } else {
+        let bucketed = bucket_counts.iter().sum::<u64>();
+        bucket_counts.push(count.saturating_sub(bucketed));
+    }
+
+    HistogramDataPoint {
+        count,
+        sum: Some(sum),
+        bucket_counts,
+        explicit_bounds,
+        ..point
+    }
+}
+
+fn sketch_summary(
+    sketch: &AgentDDSketch,
+    summary: impl FnOnce(Vec<ValueAtQuantile>, u64, f64) -> Data,
+) -> Data {
+    let quantiles = SUMMARY_QUANTILES
+        .iter()
+        .filter_map(|quantile| {
+            sketch.quantile(*quantile).map(|value| ValueAtQuantile {
+                quantile: *quantile,
+                value,
+            })
+        })
+        .collect();
+
+    summary(
+        quantiles,
+        u64::from(sketch.count()),
+        sketch.sum().unwrap_or_default(),
+    )
+}
+
+/// Adds the metric data to the metric of the same name and shape, so that data points of the same
+/// series share a metric, or adds a new metric.
+fn push_metric(metrics: &mut Vec<PBMetric>, name: String, data: Data) {
+    let existing = metrics.iter_mut().find(|metric| {
+        metric.name == name
+            && metric
+                .data
+                .as_ref()
+                .is_some_and(|existing| same_shape(existing, &data))
+    });
+
+    match existing.and_then(|metric| metric.data.as_mut()) {
+        Some(existing) => merge_data_points(existing, data),
+        None => metrics.push(PBMetric {
+            name,
+            description: String::new(),
+            unit: String::new(),
+            data: Some(data),
+        }),
+    }
+}
+
+fn same_shape(a: &Data, b: &Data) -> bool {
+    match (a, b) {
+        (Data::Gauge(_), Data::Gauge(_)) | (Data::Summary(_), Data::Summary(_)) => true,
+        (Data::Sum(a), Data::Sum(b)) => {
+            a.aggregation_temporality == b.aggregation_temporality
+                && a.is_monotonic == b.is_monotonic
+        }
+        (Data::Histogram(a), Data::Histogram(b)) => {
+            a.aggregation_temporality == b.aggregation_temporality
+        }
+        _ => false,
+    }
+}
+
+fn merge_data_points(existing: &mut Data, data: Data) {
+    match (existing, data) {
+        (Data::Gauge(existing), Data::Gauge(gauge)) => {
+            existing.data_points.extend(gauge.data_points)
+        }
+        (Data::Sum(existing), Data::Sum(sum)) => existing.data_points.extend(sum.data_points),
+        (Data::Histogram(existing), Data::Histogram(histogram)) => {
+            existing.data_points.extend(histogram.data_points)
+        }
+        (Data::Summary(existing), Data::Summary(summary)) => {
+            existing.data_points.extend(summary.data_points)
+        }
+        _ => unreachable!("metrics are only merged with data of the same shape"),
+    }
+}
+
+// https://github.com/open-telemetry/opentelemetry-specification/blob/v1.15.0/specification/trace/api.md#span
+fn span(trace: TraceEvent) -> (Option<Resource>, Option<InstrumentationScope>, Span) {
+    let (mut fields, _) = trace.into_parts();
+
+    let resource = fields.remove(RESOURCE_KEY).and_then(resource);
+    let scope = fields.remove(SCOPE_KEY).and_then(scope);
+    let mut attributes = fields
+        .remove(ATTRIBUTES_KEY)
+        .map(key_values)
+        .unwrap_or_default();
+    let status = fields
+        .remove("status")
+        .map(|value| {
+            let mut status = object(value);
+            Status {
+                message: status.remove("message").map(string).unwrap_or_default(),
+                code: status.remove("code").map_or(0, integer) as i32,
+            }
+        })
+        .filter(|status| *status != Status::default());
+
+    let mut span = Span {
+        trace_id: fields.remove(TRACE_ID_KEY).map_or_else(Vec::new, hex_id),
+        span_id: fields.remove(SPAN_ID_KEY).map_or_else(Vec::new, hex_id),
+        trace_state: fields.remove("trace_state").map(string).unwrap_or_default(),
+        parent_span_id: fields
+            .remove("parent_span_id")
+            .map_or_else(Vec::new, hex_id),
+        name: fields.remove("name").map(string).unwrap_or_default(),
+        kind: fields.remove("kind").map_or(0, integer) as i32,
+        start_time_unix_nano: fields.remove("start_time_unix_nano").map_or(0, nanos),
+        end_time_unix_nano: fields.remove("end_time_unix_nano").map_or(0, nanos),
+        attributes: Vec::new(),
+        dropped_attributes_count: fields
+            .remove(DROPPED_ATTRIBUTES_COUNT_KEY)
+            .map_or(0, integer) as u32,
+        events: fields
+            .remove("events")
+            .map(|events| array(events).into_iter().map(span_event).collect())
+            .unwrap_or_default(),
+        dropped_events_count: fields.remove("dropped_events_count").map_or(0, integer) as u32,
+        links: fields
+            .remove("links")
+            .map(|links| array(links).into_iter().map(link).collect())
+            .unwrap_or_default(),
+        dropped_links_count: fields.remove("dropped_links_count").map_or(0, integer) as u32,
+        status,
+    };
+
+    // The ingest timestamp is added by the `opentelemetry` source, while any other fields are kept
+    // as attributes.
+    fields.remove("ingest_timestamp");
+    for (key, value) in fields {
+        if !attributes.iter().any(|attribute| attribute.key == key) {
+            attributes.push(key_value(key, value));
+        }
+    }
+    span.attributes = attributes;
+
+    (resource, scope, span)
+}
+
+fn span_event(value: Value) -> SpanEvent {
+    let mut event = object(value);
+    SpanEvent {
+        time_unix_nano: event.remove("time_unix_nano").map_or(0, nanos),
+        name: event.remove("name").map(string).unwrap_or_default(),
+        attributes: event
+            .remove(ATTRIBUTES_KEY)
+            .map(key_values)
+            .unwrap_or_default(),
+        dropped_attributes_count: event
+            .remove(DROPPED_ATTRIBUTES_COUNT_KEY)
+            .map_or(0, integer) as u32,
+    }
+}
+
+fn link(value: Value) -> Link {
+    let mut link = object(value);
+    Link {
+        trace_id: link.remove(TRACE_ID_KEY).map_or_else(Vec::new, hex_id),
+        span_id: link.remove(SPAN_ID_KEY).map_or_else(Vec::new, hex_id),
+        trace_state: link.remove("trace_state").map(string).unwrap_or_default(),
+        attributes: link
+            .remove(ATTRIBUTES_KEY)
+            .map(key_values)
+            .unwrap_or_default(),
+        dropped_attributes_count: link.remove(DROPPED_ATTRIBUTES_COUNT_KEY).map_or(0, integer)
+            as u32,
+    }
+}
+
+fn resource(value: Value) -> Option<Resource> {
+    let attributes = key_values(value);
+    (!attributes.is_empty()).then_some(Resource {
+        attributes,
+        dropped_attributes_count: 0,
+    })
+}
+
+fn scope(value: Value) -> Option<InstrumentationScope> {
+    let mut scope = object(value);
+    let scope = InstrumentationScope {
+        name: scope.remove("name").map(string).unwrap_or_default(),
+        version: scope.remove("version").map(string).unwrap_or_default(),
+        attributes: scope
+            .remove(ATTRIBUTES_KEY)
+            .map(key_values)
+            .unwrap_or_default(),
+        dropped_attributes_count: scope
+            .remove(DROPPED_ATTRIBUTES_COUNT_KEY)
+            .map_or(0, integer) as u32,
+    };
+    (scope != InstrumentationScope::default()).then_some(scope)
+}
+
+fn object(value: Value) -> BTreeMap<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        _ => BTreeMap::new(),
+    }
+}
+
+fn array(value: Value) -> Vec<Value> {
+    match value {
+        Value::Array(values) => values,
+        _ => Vec::new(),
+    }
+}
+
+fn string(value: Value) -> String {
+    value.to_string_lossy().into_owned()
+}
+
+fn integer(value: Value) -> i64 {
+    match value {
+        Value::Integer(value) => value,
+        Value::Float(value) => value.into_inner() as i64,
+        _ => 0,
+    }
+}
+
+fn nanos(value: Value) -> u64 {
+    match value {
+        Value::Timestamp(timestamp) => timestamp_nanos(timestamp),
+        Value::Integer(nanos) => nanos.max(0) as u64,
+        _ => 0,
+    }
+}
+
+fn timestamp_nanos(timestamp: DateTime<Utc>) -> u64 {
+    timestamp.timestamp_nanos().max(0) as u64
+}
+
+/// Decodes a hex encoded trace or span ID, as set by the `opentelemetry` source. Invalid IDs are
+/// left empty.
+fn hex_id(value: Value) -> Vec<u8> {
+    match value {
+        Value::Bytes(bytes) => hex::decode(bytes).unwrap_or_default(),
+        _ => Vec::new(),
+    }
+}
+
+fn key_values(value: Value) -> Vec<KeyValue> {
+    object(value)
+        .into_iter()
+        .map(|(key, value)| key_value(key, value))
+        .collect()
+}
+
+fn key_value(key: String, value: Value) -> KeyValue {
+    KeyValue {
+        key,
+        value: Some(any_value(value)),
+    }
+}
+
+fn any_value(value: Value) -> AnyValue {
+    let value = match value {
+        Value::Bytes(bytes) => match String::from_utf8(bytes.to_vec()) {
+            Ok(string) => Some(any_value::Value::StringValue(string)),
+            Err(error) => Some(any_value::Value::BytesValue(error.into_bytes())),
+        },
+        Value::Integer(value) => Some(any_value::Value::IntValue(value)),
+        Value::Float(value) => Some(any_value::Value::DoubleValue(value.into_inner())),
+        Value::Boolean(value) => Some(any_value::Value::BoolValue(value)),
+        value @ (Value::Regex(_) | Value::Timestamp(_)) => {
+            Some(any_value::Value::StringValue(string(value)))
+        }
+        Value::Array(values) => Some(any_value::Value::ArrayValue(ArrayValue {
+            values: values.into_iter().map(any_value).collect(),
+        })),
+        Value::Object(fields) => Some(any_value::Value::KvlistValue(KeyValueList {
+            values: fields
+                .into_iter()
+                .map(|(key, value)| key_value(key, value))
+                .collect(),
+        })),
+        Value::Null => None,
+    };
+
+    AnyValue { value }
+}
@@ -0,0 +1,409 @@
+//! Encoding of OTLP export requests as JSON, for the `http_json` protocol.
+//!
+//! This follows the [OTLP/HTTP JSON encoding][otlp_json], which is the standard protobuf JSON
+//! mapping with the exception that trace and span IDs are hex encoded rather than base64 encoded,
+//! and enum values are always encoded as integers. As with the protobuf encoding, fields holding
+//! their default value are omitted.
+//!
+//! [otlp_json]: https://opentelemetry.io/docs/specs/otlp/#json-protobuf-encoding
+
+use base64::prelude::{Engine as _, BASE64_STANDARD};
+use opentelemetry_proto::proto::{
+    collector::{
+        logs::v1::ExportLogsServiceRequest, metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::ExportTraceServiceRequest,
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+    logs::v1::LogRecord,
+    metrics::v1::{
+        metric::Data, number_data_point, HistogramDataPoint, Metric, NumberDataPoint,
+        SummaryDataPoint,
+    },
+    resource::v1::Resource,
+    trace::v1::{
+        span::{Event as SpanEvent, Link},
+        Span, Status,
+    },
+};
+use serde_json::{Map, Value as JsonValue};
+
+pub(super) fn logs(request: &ExportLogsServiceRequest) -> JsonValue {
+    let resource_logs = request.resource_logs.iter().map(|resource_logs| {
+        let scope_logs = resource_logs.scope_logs.iter().map(|scope_logs| {
+            Object::default()
+                .message("scope", scope_logs.scope.as_ref().map(scope))
+                .array("logRecords", scope_logs.log_records.iter().map(log_record))
+                .string("schemaUrl", &scope_logs.schema_url)
+                .into()
+        });
+
+        Object::default()
+            .message("resource", resource_logs.resource.as_ref().map(resource))
+            .array("scopeLogs", scope_logs)
+            .string("schemaUrl", &resource_logs.schema_url)
+            .into()
+    });
+
+    Object::default()
+        .array("resourceLogs", resource_logs)
+        .into()
+}
+
+pub(super) fn metrics(request: &ExportMetricsServiceRequest) -> JsonValue {
+    let resource_metrics = request.resource_metrics.iter().map(|resource_metrics| {
+        let scope_metrics = resource_metrics.scope_metrics.iter().map(|scope_metrics| {
+            Object::default()
+                .message("scope", scope_metrics.scope.as_ref().map(scope))
+                .array("metrics", scope_metrics.metrics.iter().map(metric))
+                .string("schemaUrl", &scope_metrics.schema_url)
+                .into()
+        });
+
+        Object::default()
+            .message("resource", resource_metrics.resource.as_ref().map(resource))
+            .array("scopeMetrics", scope_metrics)
+            .string("schemaUrl", &resource_metrics.schema_url)
+            .into()
+    });
+
+    Object::default()
+        .array("resourceMetrics", resource_metrics)
+        .into()
+}
+
+pub(super) fn traces(request: &ExportTraceServiceRequest) -> JsonValue {
+    let resource_spans = request.resource_spans.iter().map(|resource_spans| {
+        let scope_spans = resource_spans.scope_spans.iter().map(|scope_spans| {
+            Object::default()
+                .message("scope", scope_spans.scope.as_ref().map(scope))
+                .array("spans", scope_spans.spans.iter().map(span))
+                .string("schemaUrl", &scope_spans.schema_url)
+                .into()
+        });
+
+        Object::default()
+            .message("resource", resource_spans.resource.as_ref().map(resource))
+            .array("scopeSpans", scope_spans)
+            .string("schemaUrl", &resource_spans.schema_url)
+            .into()
+    });
+
+    Object::default()
+        .array("resourceSpans", resource_spans)
+        .into()
+}
+
+/// Builds a JSON object, omitting fields holding their default value.
+#[derive(Default)]
+struct Object(Map<String, JsonValue>);
+
+impl Object {
+    fn insert(mut self, key: &str, value: JsonValue) -> Self {
+        self.0.insert(key.to_owned(), value);
+        self
+    }
+
+    fn string(self, key: &str, value: &str) -> Self {
+        if value.is_empty() {
+            self
+        } else {
+            self.insert(key, value.into())
+        }
+    }
+
+    fn int(self, key: &str, value: impl Into<i64>) -> Self {
+        match value.into() {
+            0 => self,
+            value => self.insert(key, value.into()),
+        }
+    }
+
+    /// 64-bit integers are encoded as strings, as these can't be represented in JSON numbers.
+    fn int64(self, key: &str, value: impl Into<i128>) -> Self {
+        match value.into() {
+            0 => self,
+            value => self.insert(key, value.to_string().into()),
+        }
+    }
+
+    fn double(self, key: &str, value: f64) -> Self {
+        if value == 0.0 {
+            self
+        } else {
+            self.insert(key, double(value))
+        }
+    }
+
+    fn bool(self, key: &str, value: bool) -> Self {
+        if value {
+            self.insert(key, value.into())
+        } else {
+            self
+        }
+    }
+
+    fn id(self, key: &str, id: &[u8]) -> Self {
+        if id.is_empty() {
+            self
+        } else {
+            self.insert(key, hex::encode(id).into())
+        }
+    }
+
+    fn message(self, key: &str, value: Option<JsonValue>) -> Self {
+        match value {
+            Some(value) => self.insert(key, value),
+            None => self,
+        }
+    }
+
+    fn array(self, key: &str, values: impl IntoIterator<Item = JsonValue>) -> Self {
+        let values: Vec<_> = values.into_iter().collect();
+        if values.is_empty() {
+            self
+        } else {
+            self.insert(key, values.into())
+        }
+    }
+}
+
+impl From<Object> for JsonValue {
+    fn from(object: Object) -> Self {
+        JsonValue::Object(object.0)
+    }
+}
+
+/// Non-finite doubles can't be represented in JSON numbers, so these are encoded as strings.
+fn double(value: f64) -> JsonValue {
+    if value.is_nan() {
+        "NaN".into()
+    } else if value == f64::INFINITY {
+        "Infinity".into()
+    } else if value == f64::NEG_INFINITY {
+        "-Infinity".into()
+    } else {
+        value.into()
+    }
+}
+
+fn resource(resource: &Resource) -> JsonValue {
+    Object::default()
+        .array("attributes", resource.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", resource.dropped_attributes_count)
+        .into()
+}
+
+fn scope(scope: &InstrumentationScope) -> JsonValue {
+    Object::default()
+        .string("name", &scope.name)
+        .string("version", &scope.version)
+        .array("attributes", scope.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", scope.dropped_attributes_count)
+        .into()
+}
+
+fn key_value(key_value: &KeyValue) -> JsonValue {
+    Object::default()
+        .insert("key", key_value.key.as_str().into())
+        .message("value", key_value.value.as_ref().map(any_value))
+        .into()
+}
+
+fn any_value(value: &AnyValue) -> JsonValue {
+    let object = Object::default();
+    match &value.value {
+        Some(any_value::Value::StringValue(value)) => {
+            object.insert("stringValue", value.as_str().into())
+        }
+        Some(any_value::Value::BoolValue(value)) => object.insert("boolValue", (*value).into()),
+        Some(any_value::Value::IntValue(value)) => {
+            object.insert("intValue", value.to_string().into())
+        }
+        Some(any_value::Value::DoubleValue(value)) => object.insert("doubleValue", double(*value)),
+        Some(any_value::Value::ArrayValue(array)) => object.insert(
+            "arrayValue",
+            Object::default()
+                .array("values", array.values.iter().map(any_value))
+                .into(),
+        ),
+        Some(any_value::Value::KvlistValue(list)) => object.insert(
+            "kvlistValue",
+            Object::default()
+                .array("values", list.values.iter().map(key_value))
+                .into(),
+        ),
+        Some(any_value::Value::BytesValue(value)) => {
+            object.insert("bytesValue", BASE64_STANDARD.encode(value).into())
+        }
+        None => object,
+    }
+    .into()
+}
+
+fn log_record(record: &LogRecord) -> JsonValue {
+    Object::default()
+        .int64("timeUnixNano", record.time_unix_nano)
+        .int64("observedTimeUnixNano", record.observed_time_unix_nano)
+        .int("severityNumber", record.severity_number)
+        .string("severityText", &record.severity_text)
+        .message("body", record.body.as_ref().map(any_value))
+        .array("attributes", record.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", record.dropped_attributes_count)
+        .int("flags", record.flags)
+        .id("traceId", &record.trace_id)
+        .id("spanId", &record.span_id)
+        .into()
+}
+
+fn metric(metric: &Metric) -> JsonValue {
+    let object = Object::default()
+        .string("name", &metric.name)
+        .string("description", &metric.description)
+        .string("unit", &metric.unit);
+
+    match &metric.data {
+        Some(Data::Gauge(gauge)) => object.insert(
+            "gauge",
+            Object::default()
+                .array("dataPoints", gauge.data_points.iter().map(number_point))
+                .into(),
+        ),
+        Some(Data::Sum(sum)) => object.insert(
+            "sum",
+            Object::default()
+                .array("dataPoints", sum.data_points.iter().map(number_point))
+                .int("aggregationTemporality", sum.aggregation_temporality)
+                .bool("isMonotonic", sum.is_monotonic)
+                .into(),
+        ),
+        Some(Data::Histogram(histogram)) => object.insert(
+            "histogram",
+            Object::default()
+                .array(
+                    "dataPoints",
+                    histogram.data_points.iter().map(histogram_point),
+                )
+                .int("aggregationTemporality", histogram.aggregation_temporality)
+                .into(),
+        ),
+        Some(Data::Summary(summary)) => object.insert(
+            "summary",
+            Object::default()
+                .array("dataPoints", summary.data_points.iter().map(summary_point))
+                .into(),
+        ),
+        // The sink never produces exponential histograms.
+        Some(Data::ExponentialHistogram(_)) | None => object,
+    }
+    .into()
+}
+
+fn number_point(point: &NumberDataPoint) -> JsonValue {
+    let object = Object::default()
+        .array("attributes", point.attributes.iter().map(key_value))
+        .int64("startTimeUnixNano", point.start_time_unix_nano)
+        .int64("timeUnixNano", point.time_unix_nano)
+        .int("flags", point.flags);
+
+    match point.value {
+        Some(number_data_point::Value::AsDouble(value)) => object.insert("asDouble", double(value)),
+        Some(number_data_point::Value::AsInt(value)) => {
+            object.insert("asInt", value.to_string().into())
+        }
+        None => object,
+    }
+    .into()
+}
+
+fn histogram_point(point: &HistogramDataPoint) -> JsonValue {
+    let object = Object::default()
+        .array("attributes", point.attributes.iter().map(key_value))
+        .int64("startTimeUnixNano", point.start_time_unix_nano)
+        .int64("timeUnixNano", point.time_unix_nano)
+        .int64("count", point.count)
+        .array(
+            "bucketCounts",
+            point
+                .bucket_counts
+                .iter()
+                .map(|count| count.to_string().into()),
+        )
+        .array(
+            "explicitBounds",
+            point.explicit_bounds.iter().copied().map(double),
+        )
+        .int("flags", point.flags);
+
+    // Unlike other fields, optional fields are included whenever they are set.
+    [("sum", point.sum), ("min", point.min), ("max", point.max)]
+        .into_iter()
+        .fold(object, |object, (key, value)| {
+            object.message(key, value.map(double))
+        })
+        .into()
+}
+
+fn summary_point(point: &SummaryDataPoint) -> JsonValue {
+    let quantile_values = point.quantile_values.iter().map(|quantile| {
+        Object::default()
+            .double("quantile", quantile.quantile)
+            .double("value", quantile.value)
+            .into()
+    });
+
+    Object::default()
+        .array("attributes", point.attributes.iter().map(key_value))
+        .int64("startTimeUnixNano", point.start_time_unix_nano)
+        .int64("timeUnixNano", point.time_unix_nano)
+        .int64("count", point.count)
+        .double("sum", point.sum)
+        .array("quantileValues", quantile_values)
+        .int("flags", point.flags)
+        .into()
+}
+
+fn span(span: &Span) -> JsonValue {
+    Object::default()
+        .id("traceId", &span.trace_id)
+        .id("spanId", &span.span_id)
+        .string("traceState", &span.trace_state)
+        .id("parentSpanId", &span.parent_span_id)
+        .string("name", &span.name)
+        .int("kind", span.kind)
+        .int64("startTimeUnixNano", span.start_time_unix_nano)
+        .int64("endTimeUnixNano", span.end_time_unix_nano)
+        .array("attributes", span.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", span.dropped_attributes_count)
+        .array("events", span.events.iter().map(span_event))
+        .int("droppedEventsCount", span.dropped_events_count)
+        .array("links", span.links.iter().map(link))
+        .int("droppedLinksCount", span.dropped_links_count)
+        .message("status", span.status.as_ref().map(status))
+        .into()
+}
+
+fn span_event(event: &SpanEvent) -> JsonValue {
+    Object::default()
+        .int64("timeUnixNano", event.time_unix_nano)
+        .string("name", &event.name)
+        .array("attributes", event.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", event.dropped_attributes_count)
+        .into()
+}
+
+fn link(link: &Link) -> JsonValue {
+    Object::default()
+        .id("traceId", &link.trace_id)
+        .id("spanId", &link.span_id)
+        .string("traceState", &link.trace_state)
+        .array("attributes", link.attributes.iter().map(key_value))
+        .int("droppedAttributesCount", link.dropped_attributes_count)
+        .into()
+}
+
+fn status(status: &Status) -> JsonValue {
+    Object::default()
+        .string("message", &status.message)
+        .int("code", status.code)
+        .into()
+}
@@ -0,0 +1,42 @@
+//! `OpenTelemetry` sink
+//! Exports logs, metrics, and traces to an OTLP endpoint over gRPC or HTTP.
+
+use std::time::Duration;
+
+use http::StatusCode;
+use snafu::Snafu;
+
+use crate::http::HttpError;
+
+mod config;
+mod encoder;
+mod json;
+mod service;
+mod sink;
+#[cfg(test)]
+mod tests;
+
+pub use config::OpenTelemetryConfig;
+
+#[derive(Debug, Snafu)]
+#[snafu(visibility(pub(super)))]
+pub(super) enum OpenTelemetrySinkError {
+    #[snafu(display("OTLP gRPC request failed: {}", source))]
+    Grpc { source: tonic::Status },
+
+    #[snafu(display("OTLP HTTP request failed: {}", source))]
+    Http { source: HttpError },
+
+    #[snafu(display("OTLP HTTP request was rejected with status {}: {}", status, body))]
+    Response {
+        status: StatusCode,
+        retry_after: Option<Duration>,
+        body: String,
+    },
+
+    #[snafu(display("Failed to encode OTLP request: {}", source))]
+    Encode { source: std::io::Error },
+
+    #[snafu(display("Failed to build OTLP HTTP request: {}", source))]
+    BuildRequest { source: http::Error },
+}
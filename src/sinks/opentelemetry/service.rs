@@ -0,0 +1,524 @@
+use std::{
+    io::Write,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use http::{
+    header::{CONTENT_ENCODING, CONTENT_TYPE, RETRY_AFTER},
+    HeaderName, HeaderValue, StatusCode, Uri,
+};
+use hyper::{client::HttpConnector, Body};
+use hyper_openssl::HttpsConnector;
+use hyper_proxy::ProxyConnector;
+use indexmap::IndexMap;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+    metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+    trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+};
+use prost::Message;
+use snafu::ResultExt;
+use tonic::{body::BoxBody, codec::CompressionEncoding, IntoRequest};
+use tower::Service;
+use vector_common::request_metadata::{GroupedCountByteSize, MetaDescriptive, RequestMetadata};
+use vector_core::stream::DriverResponse;
+
+use super::{json, BuildRequestSnafu, EncodeSnafu, GrpcSnafu, HttpSnafu, OpenTelemetrySinkError};
+use crate::{
+    event::{EventFinalizers, EventStatus, Finalizable},
+    http::HttpClient,
+    internal_events::EndpointBytesSent,
+    sinks::util::{retries::RetryLogic, uri, Compression, Compressor},
+};
+
+mod rpc {
+    include!(concat!(env!("OUT_DIR"), "/google.rpc.rs"));
+}
+
+/// `google.rpc.RetryInfo`, which servers attach to a throttling status to tell the client how long
+/// to wait before retrying.
+#[derive(Clone, PartialEq, Message)]
+struct RetryInfo {
+    #[prost(message, optional, tag = "1")]
+    retry_delay: Option<prost_types::Duration>,
+}
+
+const RETRY_INFO_TYPE_URL: &str = "type.googleapis.com/google.rpc.RetryInfo";
+
+/// The longest delay requested by the server that is waited out before retrying a request.
+///
+/// The delay counts against the request timeout, so longer delays are capped to let the request
+/// be retried rather than time out.
+const MAX_THROTTLE_DELAY: Duration = Duration::from_secs(30);
+
+/// An OTLP export request for one of the signals.
+#[derive(Clone, Debug)]
+pub(super) enum ExportRequest {
+    Logs(ExportLogsServiceRequest),
+    Metrics(ExportMetricsServiceRequest),
+    Traces(ExportTraceServiceRequest),
+}
+
+impl ExportRequest {
+    pub(super) fn encoded_len(&self) -> usize {
+        match self {
+            Self::Logs(request) => request.encoded_len(),
+            Self::Metrics(request) => request.encoded_len(),
+            Self::Traces(request) => request.encoded_len(),
+        }
+    }
+
+    fn encode_to_vec(&self) -> Vec<u8> {
+        match self {
+            Self::Logs(request) => request.encode_to_vec(),
+            Self::Metrics(request) => request.encode_to_vec(),
+            Self::Traces(request) => request.encode_to_vec(),
+        }
+    }
+
+    fn encode_json(&self) -> serde_json::Result<Vec<u8>> {
+        serde_json::to_vec(&match self {
+            Self::Logs(request) => json::logs(request),
+            Self::Metrics(request) => json::metrics(request),
+            Self::Traces(request) => json::traces(request),
+        })
+    }
+
+    /// The path of the OTLP/HTTP endpoint for the signal.
+    const fn http_path(&self) -> &'static str {
+        match self {
+            Self::Logs(_) => "/v1/logs",
+            Self::Metrics(_) => "/v1/metrics",
+            Self::Traces(_) => "/v1/traces",
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(super) struct OpenTelemetryRequest {
+    pub(super) finalizers: EventFinalizers,
+    pub(super) metadata: RequestMetadata,
+    pub(super) request: ExportRequest,
+}
+
+impl Finalizable for OpenTelemetryRequest {
+    fn take_finalizers(&mut self) -> EventFinalizers {
+        self.finalizers.take_finalizers()
+    }
+}
+
+impl MetaDescriptive for OpenTelemetryRequest {
+    fn get_metadata(&self) -> &RequestMetadata {
+        &self.metadata
+    }
+
+    fn metadata_mut(&mut self) -> &mut RequestMetadata {
+        &mut self.metadata
+    }
+}
+
+pub(super) struct OpenTelemetryResponse {
+    events_byte_size: GroupedCountByteSize,
+}
+
+impl DriverResponse for OpenTelemetryResponse {
+    fn event_status(&self) -> EventStatus {
+        EventStatus::Delivered
+    }
+
+    fn events_sent(&self) -> &GroupedCountByteSize {
+        &self.events_byte_size
+    }
+}
+
+type GrpcClient = hyper::Client<ProxyConnector<HttpsConnector<HttpConnector>>, BoxBody>;
+
+#[derive(Clone, Debug)]
+pub(super) enum Transport {
+    Grpc {
+        logs: LogsServiceClient<HyperSvc>,
+        metrics: MetricsServiceClient<HyperSvc>,
+        traces: TraceServiceClient<HyperSvc>,
+    },
+    Http {
+        client: HttpClient,
+        endpoint: Uri,
+        headers: IndexMap<HeaderName, HeaderValue>,
+        json: bool,
+        compression: bool,
+    },
+}
+
+impl Transport {
+    pub(super) fn grpc(
+        client: GrpcClient,
+        endpoint: Uri,
+        headers: IndexMap<HeaderName, HeaderValue>,
+        compression: bool,
+    ) -> Self {
+        let service = HyperSvc {
+            uri: endpoint,
+            headers,
+            client,
+        };
+        let mut logs = LogsServiceClient::new(service.clone());
+        let mut metrics = MetricsServiceClient::new(service.clone());
+        let mut traces = TraceServiceClient::new(service);
+
+        if compression {
+            logs = logs.send_compressed(CompressionEncoding::Gzip);
+            metrics = metrics.send_compressed(CompressionEncoding::Gzip);
+            traces = traces.send_compressed(CompressionEncoding::Gzip);
+        }
+
+        Self::Grpc {
+            logs,
+            metrics,
+            traces,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct OpenTelemetryService {
+    transport: Transport,
+    protocol: String,
+    endpoint: String,
+}
+
+impl OpenTelemetryService {
+    pub(super) fn new(transport: Transport, endpoint: &Uri) -> Self {
+        let (protocol, endpoint) = uri::protocol_endpoint(endpoint.clone());
+        Self {
+            transport,
+            protocol,
+            endpoint,
+        }
+    }
+}
+
+impl Service<OpenTelemetryRequest> for OpenTelemetryService {
+    type Response = OpenTelemetryResponse;
+    type Error = crate::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut request: OpenTelemetryRequest) -> Self::Future {
+        let service = self.clone();
+        let metadata = std::mem::take(request.metadata_mut());
+        let events_byte_size = metadata.into_events_estimated_json_encoded_byte_size();
+
+        Box::pin(async move {
+            let result = match service.transport {
+                Transport::Grpc {
+                    logs,
+                    metrics,
+                    traces,
+                } => export_grpc(logs, metrics, traces, request.request).await,
+                Transport::Http {
+                    client,
+                    endpoint,
+                    headers,
+                    json,
+                    compression,
+                } => {
+                    export_http(
+                        client,
+                        &endpoint,
+                        &headers,
+                        json,
+                        compression,
+                        request.request,
+                    )
+                    .await
+                }
+            };
+
+            match result {
+                Ok(byte_size) => {
+                    emit!(EndpointBytesSent {
+                        byte_size,
+                        protocol: &service.protocol,
+                        endpoint: &service.endpoint,
+                    });
+
+                    Ok(OpenTelemetryResponse { events_byte_size })
+                }
+                Err(error) => {
+                    // Wait out the delay requested by a throttling server before the request is
+                    // retried, as the retry backoff doesn't take it into account.
+                    if let Some(delay) = throttle_delay(&error) {
+                        tokio::time::sleep(delay.min(MAX_THROTTLE_DELAY)).await;
+                    }
+                    Err(error.into())
+                }
+            }
+        })
+    }
+}
+
+async fn export_grpc(
+    mut logs: LogsServiceClient<HyperSvc>,
+    mut metrics: MetricsServiceClient<HyperSvc>,
+    mut traces: TraceServiceClient<HyperSvc>,
+    request: ExportRequest,
+) -> Result<usize, OpenTelemetrySinkError> {
+    let byte_size = request.encoded_len();
+    match request {
+        ExportRequest::Logs(request) => logs.export(request.into_request()).await.map(drop),
+        ExportRequest::Metrics(request) => metrics.export(request.into_request()).await.map(drop),
+        ExportRequest::Traces(request) => traces.export(request.into_request()).await.map(drop),
+    }
+    .context(GrpcSnafu)?;
+
+    Ok(byte_size)
+}
+
+async fn export_http(
+    client: HttpClient,
+    endpoint: &Uri,
+    headers: &IndexMap<HeaderName, HeaderValue>,
+    json: bool,
+    compression: bool,
+    request: ExportRequest,
+) -> Result<usize, OpenTelemetrySinkError> {
+    let uri = format!(
+        "{}{}",
+        endpoint.to_string().trim_end_matches('/'),
+        request.http_path()
+    );
+    let (content_type, body) = if json {
+        let body = request
+            .encode_json()
+            .map_err(std::io::Error::from)
+            .context(EncodeSnafu)?;
+        ("application/json", body)
+    } else {
+        ("application/x-protobuf", request.encode_to_vec())
+    };
+
+    let mut builder = http::Request::post(uri).header(CONTENT_TYPE, content_type);
+    let body = if compression {
+        builder = builder.header(CONTENT_ENCODING, "gzip");
+        let mut compressor = Compressor::from(Compression::gzip_default());
+        compressor.write_all(&body).context(EncodeSnafu)?;
+        compressor.finish().context(EncodeSnafu)?.freeze()
+    } else {
+        Bytes::from(body)
+    };
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+
+    let byte_size = body.len();
+    let request = builder.body(Body::from(body)).context(BuildRequestSnafu)?;
+    let response = client.send(request).await.context(HttpSnafu)?;
+
+    let status = response.status();
+    if status.is_success() {
+        return Ok(byte_size);
+    }
+
+    let retry_after = response
+        .headers()
+        .get(RETRY_AFTER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .map(Duration::from_secs);
+    let protobuf = response
+        .headers()
+        .get(CONTENT_TYPE)
+        .is_some_and(|value| value.as_bytes() == b"application/x-protobuf");
+    let body = hyper::body::to_bytes(response.into_body())
+        .await
+        .unwrap_or_default();
+    // Failed requests are answered with a `google.rpc.Status` when using protobuf payloads.
+    let body = match rpc::Status::decode(body.as_ref()) {
+        Ok(status) if protobuf => status.message,
+        _ => String::from_utf8_lossy(&body).into_owned(),
+    };
+
+    Err(OpenTelemetrySinkError::Response {
+        status,
+        retry_after,
+        body,
+    })
+}
+
+/// Returns the delay requested by the server before retrying, if any.
+fn throttle_delay(error: &OpenTelemetrySinkError) -> Option<Duration> {
+    match error {
+        OpenTelemetrySinkError::Grpc { source } => retry_info_delay(source),
+        OpenTelemetrySinkError::Response { retry_after, .. } => *retry_after,
+        _ => None,
+    }
+}
+
+fn retry_info_delay(status: &tonic::Status) -> Option<Duration> {
+    let status = rpc::Status::decode(status.details()).ok()?;
+    status
+        .details
+        .iter()
+        .filter(|detail| detail.type_url == RETRY_INFO_TYPE_URL)
+        .find_map(|detail| RetryInfo::decode(detail.value.as_slice()).ok()?.retry_delay)
+        .map(|delay| Duration::new(delay.seconds.max(0) as u64, delay.nanos.max(0) as u32))
+}
+
+/// Retries requests failing with the statuses that the OTLP specification defines as retryable.
+///
+/// See <https://opentelemetry.io/docs/specs/otlp/#failures>.
+#[derive(Clone, Debug)]
+pub(super) struct OpenTelemetryRetryLogic;
+
+impl RetryLogic for OpenTelemetryRetryLogic {
+    type Error = OpenTelemetrySinkError;
+    type Response = OpenTelemetryResponse;
+
+    fn is_retriable_error(&self, error: &Self::Error) -> bool {
+        use tonic::Code::*;
+
+        match error {
+            OpenTelemetrySinkError::Grpc { source } => match source.code() {
+                Cancelled | DeadlineExceeded | Aborted | OutOfRange | Unavailable | DataLoss => {
+                    true
+                }
+                // Throttled requests are only retryable if the server says when to retry them.
+                ResourceExhausted => retry_info_delay(source).is_some(),
+                // Connection failures are reported with an unknown status holding the error.
+                Unknown => std::error::Error::source(source).is_some(),
+                _ => false,
+            },
+            OpenTelemetrySinkError::Http { .. } => true,
+            OpenTelemetrySinkError::Response { status, .. } => matches!(
+                *status,
+                StatusCode::TOO_MANY_REQUESTS
+                    | StatusCode::BAD_GATEWAY
+                    | StatusCode::SERVICE_UNAVAILABLE
+                    | StatusCode::GATEWAY_TIMEOUT
+            ),
+            OpenTelemetrySinkError::Encode { .. } | OpenTelemetrySinkError::BuildRequest { .. } => {
+                false
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(super) struct HyperSvc {
+    uri: Uri,
+    headers: IndexMap<HeaderName, HeaderValue>,
+    client: GrpcClient,
+}
+
+impl Service<hyper::Request<BoxBody>> for HyperSvc {
+    type Response = hyper::Response<hyper::Body>;
+    type Error = hyper::Error;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    // Emission of an internal event in case of errors is handled upstream by the caller.
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    // Emission of internal events for errors and dropped events is handled upstream by the caller.
+    fn call(&mut self, mut req: hyper::Request<BoxBody>) -> Self::Future {
+        let uri = Uri::builder()
+            .scheme(self.uri.scheme().unwrap().clone())
+            .authority(self.uri.authority().unwrap().clone())
+            .path_and_query(req.uri().path_and_query().unwrap().clone())
+            .build()
+            .unwrap();
+
+        *req.uri_mut() = uri;
+        for (name, value) in &self.headers {
+            req.headers_mut().insert(name.clone(), value.clone());
+        }
+
+        Box::pin(self.client.request(req))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn throttled_status(retry_delay: Option<Duration>) -> tonic::Status {
+        let details = retry_delay
+            .map(|delay| prost_types::Any {
+                type_url: RETRY_INFO_TYPE_URL.to_owned(),
+                value: RetryInfo {
+                    retry_delay: Some(prost_types::Duration {
+                        seconds: delay.as_secs() as i64,
+                        nanos: delay.subsec_nanos() as i32,
+                    }),
+                }
+                .encode_to_vec(),
+            })
+            .into_iter()
+            .collect();
+        let status = rpc::Status {
+            code: tonic::Code::ResourceExhausted as i32,
+            message: "throttled".to_owned(),
+            details,
+        };
+
+        tonic::Status::with_details(
+            tonic::Code::ResourceExhausted,
+            "throttled",
+            status.encode_to_vec().into(),
+        )
+    }
+
+    #[test]
+    fn retries_throttled_grpc_requests_with_retry_info() {
+        let logic = OpenTelemetryRetryLogic;
+
+        let error = OpenTelemetrySinkError::Grpc {
+            source: throttled_status(Some(Duration::from_millis(1500))),
+        };
+        assert!(logic.is_retriable_error(&error));
+        assert_eq!(throttle_delay(&error), Some(Duration::from_millis(1500)));
+
+        let error = OpenTelemetrySinkError::Grpc {
+            source: throttled_status(None),
+        };
+        assert!(!logic.is_retriable_error(&error));
+        assert_eq!(throttle_delay(&error), None);
+    }
+
+    #[test]
+    fn retries_grpc_statuses() {
+        let logic = OpenTelemetryRetryLogic;
+        let error = |code| OpenTelemetrySinkError::Grpc {
+            source: tonic::Status::new(code, "error"),
+        };
+
+        assert!(logic.is_retriable_error(&error(tonic::Code::Unavailable)));
+        assert!(logic.is_retriable_error(&error(tonic::Code::DeadlineExceeded)));
+        assert!(!logic.is_retriable_error(&error(tonic::Code::InvalidArgument)));
+        assert!(!logic.is_retriable_error(&error(tonic::Code::Unauthenticated)));
+    }
+
+    #[test]
+    fn retries_http_statuses() {
+        let logic = OpenTelemetryRetryLogic;
+        let error = |status| OpenTelemetrySinkError::Response {
+            status,
+            retry_after: None,
+            body: String::new(),
+        };
+
+        assert!(logic.is_retriable_error(&error(StatusCode::TOO_MANY_REQUESTS)));
+        assert!(logic.is_retriable_error(&error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(!logic.is_retriable_error(&error(StatusCode::BAD_REQUEST)));
+        assert!(!logic.is_retriable_error(&error(StatusCode::INTERNAL_SERVER_ERROR)));
+    }
+}
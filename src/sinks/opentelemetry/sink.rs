@@ -0,0 +1,102 @@
+use std::{fmt, num::NonZeroUsize};
+
+use async_trait::async_trait;
+use futures::{stream::BoxStream, StreamExt};
+use tower::Service;
+use vector_core::{
+    config::telemetry,
+    event::{Event, Finalizable},
+    partition::Partitioner,
+    stream::{BatcherSettings, DriverResponse},
+    ByteSizeOf, EstimatedJsonEncodedSizeOf,
+};
+
+use super::{
+    encoder::{encode_logs, encode_metrics, encode_traces},
+    service::{ExportRequest, OpenTelemetryRequest},
+};
+use crate::sinks::util::{metadata::RequestMetadataBuilder, SinkBuilderExt, StreamSink};
+
+/// The OTLP signal that an event is exported as, each of which has its own export service.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Signal {
+    Logs,
+    Metrics,
+    Traces,
+}
+
+/// Partitions events by signal, as each export request only holds a single signal.
+struct SignalPartitioner;
+
+impl Partitioner for SignalPartitioner {
+    type Item = Event;
+    type Key = Signal;
+
+    fn partition(&self, item: &Self::Item) -> Self::Key {
+        match item {
+            Event::Log(_) => Signal::Logs,
+            Event::Metric(_) => Signal::Metrics,
+            Event::Trace(_) => Signal::Traces,
+        }
+    }
+}
+
+pub(super) struct OpenTelemetrySink<S> {
+    pub(super) service: S,
+    pub(super) batch_settings: BatcherSettings,
+}
+
+impl<S> OpenTelemetrySink<S>
+where
+    S: Service<OpenTelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        input
+            .batched_partitioned(SignalPartitioner, self.batch_settings)
+            .filter_map(|(signal, mut events)| async move {
+                let finalizers = events.take_finalizers();
+                let mut json_byte_size = telemetry().create_request_count_byte_size();
+                for event in &events {
+                    json_byte_size.add_event(event, event.estimated_json_encoded_size_of());
+                }
+                let builder =
+                    RequestMetadataBuilder::new(events.len(), events.size_of(), json_byte_size);
+
+                let request = match signal {
+                    Signal::Logs => ExportRequest::Logs(encode_logs(events)),
+                    Signal::Metrics => ExportRequest::Metrics(encode_metrics(events)),
+                    Signal::Traces => ExportRequest::Traces(encode_traces(events)),
+                };
+
+                // Metrics that can't be represented in OTLP, which the encoder reports as dropped,
+                // may leave nothing to export. Their finalizers are then dropped as well, which
+                // acknowledges the events without delivering them.
+                let request_size = NonZeroUsize::new(request.encoded_len())?;
+
+                Some(OpenTelemetryRequest {
+                    finalizers,
+                    metadata: builder.with_request_size(request_size),
+                    request,
+                })
+            })
+            .into_driver(self.service)
+            .run()
+            .await
+    }
+}
+
+#[async_trait]
+impl<S> StreamSink<Event> for OpenTelemetrySink<S>
+where
+    S: Service<OpenTelemetryRequest> + Send + 'static,
+    S::Future: Send + 'static,
+    S::Response: DriverResponse + Send + 'static,
+    S::Error: fmt::Debug + Into<crate::Error> + Send,
+{
+    async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
+        self.run_inner(input).await
+    }
+}
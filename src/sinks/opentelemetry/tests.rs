@@ -0,0 +1,381 @@
+use bytes::{Buf, Bytes};
+use futures::{channel::mpsc, StreamExt};
+use http::request::Parts;
+use lookup::event_path;
+use opentelemetry_proto::proto::{
+    collector::{
+        logs::v1::ExportLogsServiceRequest,
+        metrics::v1::ExportMetricsServiceRequest,
+        trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
+    metrics::v1::{
+        metric::Data, number_data_point, AggregationTemporality, Gauge, Histogram,
+        HistogramDataPoint, Metric as OtelMetric, NumberDataPoint, ResourceMetrics, ScopeMetrics,
+        Sum,
+    },
+    resource::v1::Resource,
+    trace::v1::{span::SpanKind, status::StatusCode, ResourceSpans, ScopeSpans, Span, Status},
+};
+use prost::Message;
+use vector_core::event::{BatchNotifier, BatchStatus};
+
+use super::{
+    config::OpenTelemetryConfig,
+    encoder::{encode_logs, encode_metrics, encode_traces},
+};
+use crate::{
+    config::{SinkConfig, SinkContext},
+    event::{Event, LogEvent},
+    sinks::util::test::{build_test_server_generic, build_test_server_status},
+    test_util::{
+        components::{run_and_assert_sink_compliance, HTTP_SINK_TAGS},
+        next_addr,
+    },
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<OpenTelemetryConfig>();
+}
+
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+fn resource(value: &str) -> Option<Resource> {
+    Some(Resource {
+        attributes: vec![string_attribute("service.name", value)],
+        dropped_attributes_count: 0,
+    })
+}
+
+fn scope() -> Option<InstrumentationScope> {
+    Some(InstrumentationScope {
+        name: "scope_name".into(),
+        version: "1.0".into(),
+        attributes: vec![],
+        dropped_attributes_count: 0,
+    })
+}
+
+fn number_point(value: f64) -> NumberDataPoint {
+    NumberDataPoint {
+        attributes: vec![string_attribute("point_key", "point_val")],
+        start_time_unix_nano: 0,
+        time_unix_nano: 1,
+        value: Some(number_data_point::Value::AsDouble(value)),
+        exemplars: vec![],
+        flags: 0,
+    }
+}
+
+fn otel_metric(name: &str, data: Data) -> OtelMetric {
+    OtelMetric {
+        name: name.into(),
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    }
+}
+
+fn metrics_request() -> ExportMetricsServiceRequest {
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: resource("checkout"),
+            scope_metrics: vec![ScopeMetrics {
+                scope: scope(),
+                metrics: vec![
+                    otel_metric(
+                        "gauge",
+                        Data::Gauge(Gauge {
+                            data_points: vec![number_point(1.5)],
+                        }),
+                    ),
+                    otel_metric(
+                        "counter",
+                        Data::Sum(Sum {
+                            data_points: vec![number_point(3.0)],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            is_monotonic: true,
+                        }),
+                    ),
+                    otel_metric(
+                        "histogram",
+                        Data::Histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                attributes: vec![string_attribute("point_key", "point_val")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 6,
+                                sum: Some(12.5),
+                                bucket_counts: vec![1, 2, 3],
+                                explicit_bounds: vec![1.0, 5.0],
+                                exemplars: vec![],
+                                flags: 0,
+                                min: None,
+                                max: None,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                        }),
+                    ),
+                ],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+fn trace_request() -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: resource("checkout"),
+            scope_spans: vec![ScopeSpans {
+                scope: scope(),
+                spans: vec![Span {
+                    trace_id: hex::decode("4ac52aadf321c2e531db005df08792f5").unwrap(),
+                    span_id: hex::decode("0b9e4bda2a55530d").unwrap(),
+                    trace_state: "".into(),
+                    parent_span_id: hex::decode("1a2b3c4d5e6f7a8b").unwrap(),
+                    name: "GET /users".into(),
+                    kind: SpanKind::Server as i32,
+                    start_time_unix_nano: 1,
+                    end_time_unix_nano: 2,
+                    attributes: vec![string_attribute("attr_key", "attr_val")],
+                    dropped_attributes_count: 0,
+                    events: vec![],
+                    dropped_events_count: 0,
+                    links: vec![],
+                    dropped_links_count: 0,
+                    status: Some(Status {
+                        message: "failed".into(),
+                        code: StatusCode::Error as i32,
+                    }),
+                }],
+                schema_url: String::new(),
+            }],
+            schema_url: String::new(),
+        }],
+    }
+}
+
+#[test]
+fn metrics_round_trip() {
+    let request = metrics_request();
+    let events = request
+        .resource_metrics
+        .clone()
+        .into_iter()
+        .flat_map(ResourceMetrics::into_event_iter)
+        .collect();
+
+    assert_eq!(encode_metrics(events), request);
+}
+
+#[test]
+fn traces_round_trip() {
+    let request = trace_request();
+    let events = request
+        .resource_spans
+        .clone()
+        .into_iter()
+        .flat_map(ResourceSpans::into_event_iter)
+        .collect();
+
+    assert_eq!(encode_traces(events), request);
+}
+
+#[test]
+fn logs_are_grouped_by_resource() {
+    let log = |service: &str, message: &str| {
+        let mut log = LogEvent::from(message);
+        log.insert(event_path!("resources", "service.name"), service);
+        log.insert("host", "localhost");
+        Event::from(log)
+    };
+
+    let request = encode_logs(vec![
+        log("checkout", "one"),
+        log("payments", "two"),
+        log("checkout", "three"),
+    ]);
+
+    assert_eq!(request.resource_logs.len(), 2);
+    assert_eq!(request.resource_logs[0].resource, resource("checkout"));
+    assert_eq!(request.resource_logs[1].resource, resource("payments"));
+
+    let records = &request.resource_logs[0].scope_logs[0].log_records;
+    assert_eq!(records.len(), 2);
+    assert_eq!(
+        records[1].body,
+        Some(AnyValue {
+            value: Some(any_value::Value::StringValue("three".into())),
+        })
+    );
+    // Fields that aren't part of the OTLP data model are kept as attributes.
+    assert_eq!(
+        records[1].attributes,
+        vec![string_attribute("host", "localhost")]
+    );
+}
+
+fn config(endpoint: &str, protocol: &str) -> OpenTelemetryConfig {
+    toml::from_str(&format!(
+        r#"
+            endpoint = "{}"
+            protocol = "{}"
+            batch.timeout_secs = 0.1
+        "#,
+        endpoint, protocol
+    ))
+    .unwrap()
+}
+
+fn log_events(batch: &BatchNotifier) -> impl futures::Stream<Item = Event> {
+    futures::stream::iter(
+        ["one", "two", "three"]
+            .map(|message| Event::from(LogEvent::from(message).with_batch_notifier(batch))),
+    )
+}
+
+async fn collect_requests(rx: mpsc::Receiver<(Parts, Bytes)>) -> Vec<(Parts, Bytes)> {
+    rx.collect().await
+}
+
+#[tokio::test]
+async fn delivers_http_json() {
+    let addr = next_addr();
+    let config = config(&format!("http://{}", addr), "http_json");
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server_generic(addr, || {
+        hyper::Response::builder()
+            .header("content-type", "application/json")
+            .body(hyper::Body::from("{}"))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    run_and_assert_sink_compliance(sink, log_events(&batch), &HTTP_SINK_TAGS).await;
+    drop(batch);
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = collect_requests(rx).await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/json");
+
+    let body: serde_json::Value = serde_json::from_slice(body).unwrap();
+    let records = &body["resourceLogs"][0]["scopeLogs"][0]["logRecords"];
+    assert_eq!(records.as_array().unwrap().len(), 3);
+    assert_eq!(records[0]["body"]["stringValue"], "one");
+}
+
+#[tokio::test]
+async fn delivers_http_protobuf() {
+    let addr = next_addr();
+    let config = config(&format!("http://{}/otlp", addr), "http_protobuf");
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server_generic(addr, || {
+        hyper::Response::builder()
+            .header("content-type", "application/x-protobuf")
+            .body(hyper::Body::empty())
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    run_and_assert_sink_compliance(sink, log_events(&batch), &HTTP_SINK_TAGS).await;
+    drop(batch);
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+
+    let requests = collect_requests(rx).await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(parts.uri.path(), "/otlp/v1/logs");
+    assert_eq!(parts.headers["content-type"], "application/x-protobuf");
+
+    let request = ExportLogsServiceRequest::decode(body.clone()).unwrap();
+    assert_eq!(request.resource_logs[0].scope_logs[0].log_records.len(), 3);
+}
+
+#[tokio::test]
+async fn rejects_http_bad_request() {
+    let addr = next_addr();
+    let config = config(&format!("http://{}", addr), "http_protobuf");
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (_rx, trigger, server) = build_test_server_status(addr, http::StatusCode::BAD_REQUEST);
+    tokio::spawn(server);
+
+    let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+    sink.run_events(log_events(&batch).collect::<Vec<_>>().await)
+        .await
+        .expect("Running sink failed");
+    drop(batch);
+    drop(trigger);
+
+    assert_eq!(receiver.try_recv(), Ok(BatchStatus::Rejected));
+}
+
+// one byte for the compression flag plus four bytes for the length
+const GRPC_HEADER_SIZE: usize = 5;
+
+fn grpc_frame(message: impl Message) -> Bytes {
+    let message = message.encode_to_vec();
+    let mut frame = vec![0];
+    frame.extend((message.len() as u32).to_be_bytes());
+    frame.extend(message);
+    frame.into()
+}
+
+#[tokio::test]
+async fn delivers_grpc_traces() {
+    let addr = next_addr();
+    let config = config(&format!("http://{}", addr), "grpc");
+    let (sink, _) = config.build(SinkContext::default()).await.unwrap();
+
+    let (rx, trigger, server) = build_test_server_generic(addr, || {
+        hyper::Response::builder()
+            .header("grpc-status", "0") // OK
+            .header("content-type", "application/grpc")
+            .body(hyper::Body::from(grpc_frame(ExportTraceServiceResponse {})))
+            .unwrap()
+    });
+    tokio::spawn(server);
+
+    let request = trace_request();
+    let events = request
+        .resource_spans
+        .clone()
+        .into_iter()
+        .flat_map(ResourceSpans::into_event_iter)
+        .collect::<Vec<_>>();
+    run_and_assert_sink_compliance(sink, futures::stream::iter(events), &HTTP_SINK_TAGS).await;
+    drop(trigger);
+
+    let requests = collect_requests(rx).await;
+    assert_eq!(requests.len(), 1);
+    let (parts, body) = &requests[0];
+    assert_eq!(
+        parts.uri.path(),
+        "/opentelemetry.proto.collector.trace.v1.TraceService/Export"
+    );
+
+    let mut body = body.clone();
+    body.advance(GRPC_HEADER_SIZE);
+    assert_eq!(ExportTraceServiceRequest::decode(body).unwrap(), request);
+}
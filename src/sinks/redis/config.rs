@@ -1,3 +1,7 @@
+use std::num::NonZeroUsize;
+
+use indexmap::IndexMap;
+use lookup::lookup_v2::ConfigValuePath;
 use redis::{aio::ConnectionManager, RedisResult};
 use snafu::prelude::*;
 
@@ -23,6 +27,12 @@ pub enum DataTypeConfig {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream as entries, which consumer groups can read and
+    /// acknowledge independently of each other.
+    Stream,
 }
 
 /// List-specific options.
@@ -34,6 +44,50 @@ pub struct ListOption {
     pub(super) method: Method,
 }
 
+/// Stream-specific options.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The field of each stream entry that holds the encoded event.
+    #[serde(default = "default_stream_field")]
+    #[configurable(metadata(docs::examples = "message"))]
+    pub(super) field: String,
+
+    /// Additional fields to add to each stream entry, mapped from fields of the event.
+    ///
+    /// The value of each event field is added as-is for strings, and in its string representation
+    /// otherwise. Event fields that don't exist are not added to the entry. The mapped fields are
+    /// read before `encoding.except_fields` and `encoding.only_fields` are applied, so they can be
+    /// excluded from the encoded event.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The event field to map to this stream entry field."
+    ))]
+    #[configurable(metadata(docs::examples = "stream_fields_examples()"))]
+    pub(super) fields: IndexMap<String, ConfigValuePath>,
+
+    /// The approximate maximum number of entries to keep in the stream.
+    ///
+    /// When set, the stream is trimmed with `MAXLEN ~` as entries are added. Redis trims the stream
+    /// in whole nodes, so it can hold slightly more entries than this. By default, the stream is not
+    /// trimmed.
+    #[configurable(metadata(docs::examples = 100000))]
+    #[configurable(metadata(docs::type_unit = "entries"))]
+    pub(super) maxlen: Option<NonZeroUsize>,
+}
+
+pub(super) fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
+fn stream_fields_examples() -> IndexMap<String, String> {
+    IndexMap::from_iter([
+        ("host".to_owned(), "host".to_owned()),
+        ("level".to_owned(), "log.level".to_owned()),
+    ])
+}
+
 /// Method for pushing messages into a `list`.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
@@ -79,6 +133,10 @@ pub struct RedisSinkConfig {
     #[serde(alias = "list")]
     pub(super) list_option: Option<ListOption>,
 
+    #[configurable(derived)]
+    #[serde(alias = "stream")]
+    pub(super) stream_option: Option<StreamOption>,
+
     /// The URL of the Redis endpoint to connect to.
     ///
     /// The URL _must_ take the form of `protocol://server:port/db` where the protocol can either be
@@ -1,13 +1,15 @@
 use codecs::JsonSerializerConfig;
 use futures::stream;
+use indexmap::IndexMap;
+use lookup::lookup_v2::ConfigValuePath;
 use rand::Rng;
-use redis::AsyncCommands;
+use redis::{streams::StreamRangeReply, AsyncCommands};
 use vector_core::{
     config::{init_telemetry, Tags, Telemetry},
     event::LogEvent,
 };
 
-use super::config::{DataTypeConfig, ListOption, Method, RedisSinkConfig};
+use super::config::{DataTypeConfig, ListOption, Method, RedisSinkConfig, StreamOption};
 use crate::{
     sinks::prelude::*,
     test_util::{
@@ -42,6 +44,7 @@ async fn redis_sink_list_lpush() {
         list_option: Some(ListOption {
             method: Method::LPush,
         }),
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: Some(u64::MAX),
@@ -106,6 +109,7 @@ async fn redis_sink_list_rpush() {
         list_option: Some(ListOption {
             method: Method::RPush,
         }),
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: Some(u64::MAX),
@@ -184,6 +188,7 @@ async fn redis_sink_channel() {
         encoding: JsonSerializerConfig::default().into(),
         data_type: DataTypeConfig::Channel,
         list_option: None,
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: Some(u64::MAX),
@@ -260,6 +265,7 @@ async fn redis_sink_channel_data_volume_tags() {
         encoding: JsonSerializerConfig::default().into(),
         data_type: DataTypeConfig::Channel,
         list_option: None,
+        stream_option: None,
         batch: BatchConfig::default(),
         request: TowerRequestConfig {
             rate_limit_num: Some(u64::MAX),
@@ -290,3 +296,65 @@ async fn redis_sink_channel_data_volume_tags() {
         }
     }
 }
+
+#[tokio::test]
+async fn redis_sink_stream_xadd() {
+    trace_init();
+
+    let key = Template::try_from(format!("test-{}", random_string(10)))
+        .expect("should not fail to create key template");
+    debug!("Test key name: {}.", key);
+    let num_events = 100;
+
+    let cnf = RedisSinkConfig {
+        endpoint: redis_server(),
+        key: key.clone(),
+        encoding: JsonSerializerConfig::default().into(),
+        data_type: DataTypeConfig::Stream,
+        list_option: None,
+        stream_option: Some(StreamOption {
+            field: "payload".to_owned(),
+            fields: IndexMap::from_iter([(
+                "sequence".to_owned(),
+                ConfigValuePath::try_from("sequence".to_owned()).unwrap(),
+            )]),
+            maxlen: None,
+        }),
+        batch: BatchConfig::default(),
+        request: TowerRequestConfig {
+            rate_limit_num: Some(u64::MAX),
+            ..Default::default()
+        },
+        acknowledgements: Default::default(),
+    };
+
+    let mut events: Vec<Event> = Vec::new();
+    for i in 0..num_events {
+        let mut e = LogEvent::from(format!("message {}", i));
+        e.insert("sequence", i);
+        events.push(e.into());
+    }
+    let input = stream::iter(events.clone().into_iter().map(Into::into));
+
+    let cnf2 = cnf.clone();
+    assert_sink_compliance(&SINK_TAGS, async move {
+        let cx = SinkContext::default();
+        let (sink, _healthcheck) = cnf2.build(cx).await.unwrap();
+        sink.run(input).await
+    })
+    .await
+    .expect("Running sink failed");
+
+    let mut conn = cnf.build_client().await.unwrap();
+
+    let reply: StreamRangeReply = conn.xrange_all(key.to_string()).await.unwrap();
+    assert_eq!(reply.ids.len(), num_events);
+
+    for (i, entry) in reply.ids.iter().enumerate() {
+        let e = events.get(i).unwrap().as_log();
+        let payload: String = entry.get("payload").unwrap();
+        assert_eq!(payload, serde_json::to_string(e).unwrap());
+        let sequence: String = entry.get("sequence").unwrap();
+        assert_eq!(sequence, i.to_string());
+    }
+}
@@ -19,7 +19,7 @@ use snafu::Snafu;
 
 use crate::sinks::prelude::*;
 
-use self::config::Method;
+use self::config::{Method, StreamOption};
 
 use super::util::EncodedLength;
 
@@ -33,7 +33,7 @@ pub(super) enum RedisSinkError {
     SendError { source: RedisError },
 }
 
-#[derive(Clone, Copy, Debug, Derivative)]
+#[derive(Clone, Debug, Derivative)]
 #[derivative(Default)]
 pub enum DataType {
     /// The Redis `list` type.
@@ -46,12 +46,19 @@ pub enum DataType {
     ///
     /// Redis channels function in a pub/sub fashion, allowing many-to-many broadcasting and receiving.
     Channel,
+
+    /// The Redis `stream` type.
+    ///
+    /// Messages are appended to the stream as entries, along with any mapped fields.
+    Stream(StreamOption),
 }
 
-/// Wrapper for an `Event` that also stored the rendered key.
+/// Wrapper for an `Event` that also stored the rendered key, and the stream entry fields mapped from
+/// the event.
 pub(super) struct RedisEvent {
     event: Event,
     key: String,
+    fields: Vec<(String, Bytes)>,
 }
 
 impl Finalizable for RedisEvent {
@@ -105,10 +112,16 @@ impl MetaDescriptive for RedisRequest {
 pub(super) struct RedisKvEntry {
     key: String,
     value: Bytes,
+    fields: Vec<(String, Bytes)>,
 }
 
 impl EncodedLength for RedisKvEntry {
     fn encoded_length(&self) -> usize {
         self.value.len()
+            + self
+                .fields
+                .iter()
+                .map(|(name, value)| name.len() + value.len())
+                .sum::<usize>()
     }
 }
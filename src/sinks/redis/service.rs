@@ -1,6 +1,6 @@
 use std::task::{Context, Poll};
 
-use redis::aio::ConnectionManager;
+use redis::{aio::ConnectionManager, streams::StreamMaxlen};
 
 use crate::sinks::prelude::*;
 
@@ -30,8 +30,8 @@ impl Service<RedisRequest> for RedisService {
         let mut pipe = redis::pipe();
 
         for kv in kvs.request {
-            match self.data_type {
-                super::DataType::List(method) => match method {
+            match &self.data_type {
+                super::DataType::List(method) => match *method {
                     Method::LPush => {
                         if count > 1 {
                             pipe.atomic().lpush(kv.key, kv.value.as_ref());
@@ -54,13 +54,52 @@ impl Service<RedisRequest> for RedisService {
                         pipe.publish(kv.key, kv.value.as_ref());
                     }
                 }
+                super::DataType::Stream(options) => {
+                    let mut items = Vec::with_capacity(kv.fields.len() + 1);
+                    items.push((options.field.as_str(), kv.value.as_ref()));
+                    items.extend(
+                        kv.fields
+                            .iter()
+                            .map(|(name, value)| (name.as_str(), value.as_ref())),
+                    );
+
+                    if count > 1 {
+                        pipe.atomic();
+                    }
+                    match options.maxlen {
+                        Some(maxlen) => {
+                            pipe.xadd_maxlen(
+                                &kv.key,
+                                StreamMaxlen::Approx(maxlen.get()),
+                                "*",
+                                &items,
+                            )
+                            .ignore();
+                        }
+                        None => {
+                            pipe.xadd(&kv.key, "*", &items).ignore();
+                        }
+                    }
+                }
             }
         }
 
+        // `XADD` replies with the ID of the new entry rather than a status, so a stream request has
+        // succeeded as a whole once the pipeline has.
+        let is_stream = matches!(self.data_type, super::DataType::Stream(_));
+
         let byte_size = kvs.metadata.events_byte_size();
 
         Box::pin(async move {
-            match pipe.query_async(&mut conn).await {
+            let result = if is_stream {
+                pipe.query_async::<_, ()>(&mut conn)
+                    .await
+                    .map(|()| vec![true; count])
+            } else {
+                pipe.query_async(&mut conn).await
+            };
+
+            match result {
                 Ok(event_status) => Ok(RedisResponse {
                     event_status,
                     events_byte_size: kvs.metadata.into_events_estimated_json_encoded_byte_size(),
@@ -1,5 +1,7 @@
 use std::future;
 
+use bytes::Bytes;
+use lookup::PathPrefix;
 use redis::{aio::ConnectionManager, RedisError};
 
 use crate::sinks::{
@@ -8,7 +10,7 @@ use crate::sinks::{
 };
 
 use super::{
-    config::{DataTypeConfig, RedisSinkConfig},
+    config::{default_stream_field, DataTypeConfig, RedisSinkConfig, StreamOption},
     request_builder::request_builder,
     service::{RedisResponse, RedisService},
     RedisEvent,
@@ -30,6 +32,15 @@ impl RedisSink {
         let data_type = match config.data_type {
             DataTypeConfig::Channel => super::DataType::Channel,
             DataTypeConfig::List => super::DataType::List(method.unwrap_or_default()),
+            DataTypeConfig::Stream => {
+                super::DataType::Stream(config.stream_option.clone().unwrap_or_else(|| {
+                    StreamOption {
+                        field: default_stream_field(),
+                        fields: Default::default(),
+                        maxlen: None,
+                    }
+                }))
+            }
         };
 
         let batcher_settings = config.batch.validate()?.into_batcher_settings()?;
@@ -51,7 +62,7 @@ impl RedisSink {
     }
 
     /// Transforms an event into a `Redis` event by rendering the template field used to
-    /// determine the key.
+    /// determine the key, and reading the fields mapped to stream entry fields.
     /// Returns `None` if there is an error whilst rendering. An error event is also emitted.
     fn make_redis_event(&self, event: Event) -> Option<RedisEvent> {
         let key = self
@@ -66,7 +77,12 @@ impl RedisSink {
             })
             .ok()?;
 
-        Some(RedisEvent { event, key })
+        let fields = match &self.data_type {
+            super::DataType::Stream(options) => stream_fields(&event, options),
+            _ => Vec::new(),
+        };
+
+        Some(RedisEvent { event, key, fields })
     }
 
     async fn run_inner(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -77,7 +93,7 @@ impl RedisSink {
 
         let service = RedisService {
             conn: self.conn.clone(),
-            data_type: self.data_type,
+            data_type: self.data_type.clone(),
         };
 
         let service = ServiceBuilder::new()
@@ -99,6 +115,22 @@ impl RedisSink {
     }
 }
 
+/// Reads the event fields that are mapped to stream entry fields.
+pub(super) fn stream_fields(event: &Event, options: &StreamOption) -> Vec<(String, Bytes)> {
+    let Event::Log(log) = event else {
+        return Vec::new();
+    };
+
+    options
+        .fields
+        .iter()
+        .filter_map(|(name, path)| {
+            log.get((PathPrefix::Event, path))
+                .map(|value| (name.clone(), value.coerce_to_bytes()))
+        })
+        .collect()
+}
+
 #[async_trait]
 impl StreamSink<Event> for RedisSink {
     async fn run(self: Box<Self>, input: BoxStream<'_, Event>) -> Result<(), ()> {
@@ -1,10 +1,17 @@
 use std::collections::HashMap;
 
+use bytes::Bytes;
 use codecs::{JsonSerializerConfig, TextSerializerConfig};
+use indexmap::IndexMap;
+use lookup::lookup_v2::ConfigValuePath;
 use vector_common::request_metadata::GroupedCountByteSize;
 use vector_core::event::LogEvent;
 
-use super::{config::RedisSinkConfig, request_builder::encode_event};
+use super::{
+    config::{RedisSinkConfig, StreamOption},
+    request_builder::encode_event,
+    sink::stream_fields,
+};
 use crate::{
     codecs::{Encoder, Transformer},
     config::log_schema,
@@ -71,3 +78,37 @@ fn redis_encode_event() {
     let map: HashMap<String, String> = serde_json::from_slice(&result[..]).unwrap();
     assert!(!map.contains_key("key"));
 }
+
+#[test]
+fn redis_stream_fields() {
+    let mut evt = LogEvent::from("hello_world");
+    evt.insert("host", "localhost");
+    evt.insert("status", 200);
+
+    let options = StreamOption {
+        field: "message".to_owned(),
+        fields: IndexMap::from_iter([
+            (
+                "host".to_owned(),
+                ConfigValuePath::try_from("host".to_owned()).unwrap(),
+            ),
+            (
+                "code".to_owned(),
+                ConfigValuePath::try_from("status".to_owned()).unwrap(),
+            ),
+            (
+                "missing".to_owned(),
+                ConfigValuePath::try_from("missing".to_owned()).unwrap(),
+            ),
+        ]),
+        maxlen: None,
+    };
+
+    assert_eq!(
+        stream_fields(&evt.into(), &options),
+        vec![
+            ("host".to_owned(), Bytes::from("localhost")),
+            ("code".to_owned(), Bytes::from("200")),
+        ]
+    );
+}
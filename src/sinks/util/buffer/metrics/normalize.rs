@@ -1,5 +1,10 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    num::NonZeroUsize,
+    time::{Duration, Instant},
+};
 
+use lru::LruCache;
 use vector_core::event::{
     metric::{MetricData, MetricSeries},
     EventMetadata, Metric, MetricKind,
@@ -79,40 +84,134 @@ impl<N> From<N> for MetricNormalizer<N> {
     }
 }
 
-type MetricEntry = (MetricData, EventMetadata);
+/// Bounds on the state held by a [`MetricSet`].
+///
+/// By default, a metric set is unbounded and series are held until they are explicitly removed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MetricSetSettings {
+    /// The maximum number of series to hold.
+    ///
+    /// When a new series would exceed this limit, the least recently updated series is evicted.
+    pub max_series: Option<NonZeroUsize>,
+
+    /// The amount of time a series is held after it was last updated.
+    pub time_to_live: Option<Duration>,
+}
+
+#[derive(Clone)]
+struct MetricEntry {
+    data: MetricData,
+    metadata: EventMetadata,
+    updated: Instant,
+}
+
+impl MetricEntry {
+    fn new(data: MetricData, metadata: EventMetadata) -> Self {
+        Self {
+            data,
+            metadata,
+            updated: Instant::now(),
+        }
+    }
+}
+
+/// The series held by a [`MetricSet`].
+enum Entries {
+    /// Series held until they are explicitly removed.
+    Unbounded(HashMap<MetricSeries, MetricEntry>),
+    /// Series ordered from the least to the most recently updated, so that they can be evicted.
+    Bounded(LruCache<MetricSeries, MetricEntry>),
+}
+
+fn lru_cache(max_series: Option<NonZeroUsize>) -> LruCache<MetricSeries, MetricEntry> {
+    match max_series {
+        Some(max_series) => LruCache::new(max_series),
+        None => LruCache::unbounded(),
+    }
+}
 
 /// Metric storage for use with normalization.
 ///
 /// This is primarily a wrapper around `HashMap` with convenience methods to make it easier to perform
-/// normalization-specific operations.
-#[derive(Clone, Default)]
-pub struct MetricSet(HashMap<MetricSeries, MetricEntry>);
+/// normalization-specific operations. When bounded by [`MetricSetSettings`], series are held in an `LruCache`
+/// instead, ordered from the least to the most recently updated.
+pub struct MetricSet {
+    entries: Entries,
+    settings: MetricSetSettings,
+}
+
+impl Default for MetricSet {
+    fn default() -> Self {
+        Self::with_settings(MetricSetSettings::default())
+    }
+}
+
+impl Clone for MetricSet {
+    fn clone(&self) -> Self {
+        let entries = match &self.entries {
+            Entries::Unbounded(entries) => Entries::Unbounded(entries.clone()),
+            Entries::Bounded(entries) => {
+                let mut cloned = lru_cache(self.settings.max_series);
+                // Insert the series from the least recently updated, to keep their order
+                for (series, entry) in entries.iter().rev() {
+                    cloned.push(series.clone(), entry.clone());
+                }
+                Entries::Bounded(cloned)
+            }
+        };
+        Self {
+            entries,
+            settings: self.settings,
+        }
+    }
+}
 
 impl MetricSet {
-    /// Creates an empty `MetricSet` with the specified capacity.
+    /// Creates an empty, unbounded `MetricSet` with the specified capacity.
     ///
     /// The metric set will be able to hold at least `capacity` elements without reallocating. If `capacity` is 0, the
     /// metric set will not allocate.
     pub fn with_capacity(capacity: usize) -> Self {
-        Self(HashMap::with_capacity(capacity))
+        Self {
+            entries: Entries::Unbounded(HashMap::with_capacity(capacity)),
+            settings: MetricSetSettings::default(),
+        }
+    }
+
+    /// Creates an empty `MetricSet` whose state is bounded by the given settings.
+    pub fn with_settings(settings: MetricSetSettings) -> Self {
+        let entries = match settings {
+            MetricSetSettings {
+                max_series: None,
+                time_to_live: None,
+            } => Entries::Unbounded(HashMap::new()),
+            _ => Entries::Bounded(lru_cache(settings.max_series)),
+        };
+        Self { entries, settings }
     }
 
     /// Returns the number of elements in the set.
     pub fn len(&self) -> usize {
-        self.0.len()
+        match &self.entries {
+            Entries::Unbounded(entries) => entries.len(),
+            Entries::Bounded(entries) => entries.len(),
+        }
     }
 
     /// Returns `true` if the set contains no elements.
     pub fn is_empty(&self) -> bool {
-        self.0.is_empty()
+        self.len() == 0
     }
 
     /// Consumes this `MetricSet` and returns a vector of `Metric`.
     pub fn into_metrics(self) -> Vec<Metric> {
-        self.0
-            .into_iter()
-            .map(|(series, (data, metadata))| Metric::from_parts(series, data, metadata))
-            .collect()
+        let into_metric = |(series, entry): (_, MetricEntry)| {
+            Metric::from_parts(series, entry.data, entry.metadata)
+        };
+        match self.entries {
+            Entries::Unbounded(entries) => entries.into_iter().map(into_metric).collect(),
+            Entries::Bounded(entries) => entries.into_iter().map(into_metric).collect(),
+        }
     }
 
     /// Either pass the metric through as-is if absolute, or convert it
@@ -137,22 +236,23 @@ impl MetricSet {
     /// state buffer to keep track of the value throughout the entire
     /// application uptime.
     fn incremental_to_absolute(&mut self, mut metric: Metric) -> Metric {
-        match self.0.get_mut(metric.series()) {
+        match self.get_mut(metric.series()) {
             Some(existing) => {
-                if existing.0.value.add(metric.value()) {
-                    metric = metric.with_value(existing.0.value.clone());
+                if existing.data.value.add(metric.value()) {
+                    existing.updated = Instant::now();
+                    metric = metric.with_value(existing.data.value.clone());
                 } else {
                     // Metric changed type, store this as the new reference value
-                    self.0.insert(
+                    self.insert_entry(
                         metric.series().clone(),
-                        (metric.data().clone(), EventMetadata::default()),
+                        MetricEntry::new(metric.data().clone(), EventMetadata::default()),
                     );
                 }
             }
             None => {
-                self.0.insert(
+                self.insert_entry(
                     metric.series().clone(),
-                    (metric.data().clone(), EventMetadata::default()),
+                    MetricEntry::new(metric.data().clone(), EventMetadata::default()),
                 );
             }
         }
@@ -181,12 +281,13 @@ impl MetricSet {
         // introducing a small amount of lag before a metric is emitted by having to wait to see it
         // again, but this is a behavior we have to observe for sinks that can only handle
         // incremental updates.
-        match self.0.get_mut(metric.series()) {
+        match self.get_mut(metric.series()) {
             Some(reference) => {
                 let new_value = metric.value().clone();
                 // From the stored reference value, emit an increment
-                if metric.subtract(&reference.0) {
-                    reference.0.value = new_value;
+                if metric.subtract(&reference.data) {
+                    reference.data.value = new_value;
+                    reference.updated = Instant::now();
                     Some(metric.into_incremental())
                 } else {
                     // Metric changed type, store this and emit nothing
@@ -204,7 +305,42 @@ impl MetricSet {
 
     fn insert(&mut self, metric: Metric) {
         let (series, data, metadata) = metric.into_parts();
-        self.0.insert(series, (data, metadata));
+        self.insert_entry(series, MetricEntry::new(data, metadata));
+    }
+
+    /// Gets the entry for a series, unless it has outlived the configured time-to-live.
+    ///
+    /// The series becomes the most recently updated one, so callers must either update the entry or replace it.
+    fn get_mut(&mut self, series: &MetricSeries) -> Option<&mut MetricEntry> {
+        match &mut self.entries {
+            Entries::Unbounded(entries) => entries.get_mut(series),
+            Entries::Bounded(entries) => {
+                let time_to_live = self.settings.time_to_live;
+                let expired = entries.peek(series).map_or(true, |entry| {
+                    time_to_live.map_or(false, |ttl| entry.updated.elapsed() >= ttl)
+                });
+                if expired {
+                    None
+                } else {
+                    entries.get_mut(series)
+                }
+            }
+        }
+    }
+
+    /// Inserts the entry of a series, evicting the least recently updated series if the set is full.
+    fn insert_entry(&mut self, series: MetricSeries, entry: MetricEntry) {
+        match &mut self.entries {
+            Entries::Unbounded(entries) => {
+                entries.insert(series, entry);
+            }
+            Entries::Bounded(entries) => {
+                if let Some(time_to_live) = self.settings.time_to_live {
+                    expire(entries, time_to_live);
+                }
+                entries.push(series, entry);
+            }
+        }
     }
 
     pub fn insert_update(&mut self, metric: Metric) {
@@ -212,11 +348,12 @@ impl MetricSet {
             MetricKind::Absolute => Some(metric),
             MetricKind::Incremental => {
                 // Incremental metrics update existing entries, if present
-                match self.0.get_mut(metric.series()) {
+                match self.get_mut(metric.series()) {
                     Some(existing) => {
                         let (series, data, metadata) = metric.into_parts();
-                        if existing.0.update(&data) {
-                            existing.1.merge(metadata);
+                        if existing.data.update(&data) {
+                            existing.metadata.merge(metadata);
+                            existing.updated = Instant::now();
                             None
                         } else {
                             warn!(message = "Metric changed type, dropping old value.", %series);
@@ -236,6 +373,21 @@ impl MetricSet {
     ///
     /// If the series existed and was removed, returns `true`.  Otherwise, `false`.
     pub fn remove(&mut self, series: &MetricSeries) -> bool {
-        self.0.remove(series).is_some()
+        match &mut self.entries {
+            Entries::Unbounded(entries) => entries.remove(series).is_some(),
+            Entries::Bounded(entries) => entries.pop(series).is_some(),
+        }
+    }
+}
+
+/// Removes all series that have outlived the time-to-live.
+///
+/// Series are ordered by their last update, so this only visits the expired series.
+fn expire(entries: &mut LruCache<MetricSeries, MetricEntry>, time_to_live: Duration) {
+    while let Some((_, entry)) = entries.peek_lru() {
+        if entry.updated.elapsed() < time_to_live {
+            break;
+        }
+        entries.pop_lru();
     }
 }
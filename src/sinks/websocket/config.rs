@@ -8,11 +8,9 @@ use crate::{
     codecs::EncodingConfig,
     config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
     http::Auth,
-    sinks::{
-        websocket::sink::{ConnectSnafu, WebSocketConnector, WebSocketError, WebSocketSink},
-        Healthcheck, VectorSink,
-    },
+    sinks::{websocket::sink::WebSocketSink, Healthcheck, VectorSink},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
+    websocket::{ConnectSnafu, WebSocketConnector, WebSocketError},
 };
 
 /// Configuration for the `websocket` sink.
@@ -0,0 +1,97 @@
+use std::{net::SocketAddr, num::NonZeroUsize};
+
+use codecs::JsonSerializerConfig;
+use vector_config::configurable_component;
+
+use crate::{
+    codecs::EncodingConfig,
+    config::{AcknowledgementsConfig, GenerateConfig, Input, SinkConfig, SinkContext},
+    sinks::{websocket_server::sink::WebSocketListenerSink, Healthcheck, VectorSink},
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+};
+
+/// Configuration for the `websocket_server` sink.
+#[configurable_component(sink(
+    "websocket_server",
+    "Stream observability events to clients connected over WebSocket."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketListenerSinkConfig {
+    /// The socket address to listen for WebSocket connections on.
+    #[configurable(metadata(docs::examples = "0.0.0.0:8080"))]
+    #[configurable(metadata(docs::examples = "localhost:8080"))]
+    pub address: SocketAddr,
+
+    #[configurable(derived)]
+    pub tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    pub encoding: EncodingConfig,
+
+    /// The maximum number of events queued for each client.
+    ///
+    /// When a client doesn't keep up and its queue is full, further events are dropped for that
+    /// client only, instead of slowing down the sink.
+    #[serde(default = "default_client_queue_size")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub client_queue_size: NonZeroUsize,
+
+    #[configurable(derived)]
+    #[serde(
+        default,
+        deserialize_with = "crate::serde::bool_or_struct",
+        skip_serializing_if = "crate::serde::skip_serializing_if_default"
+    )]
+    pub acknowledgements: AcknowledgementsConfig,
+}
+
+fn default_client_queue_size() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+impl GenerateConfig for WebSocketListenerSinkConfig {
+    fn generate_config() -> toml::Value {
+        toml::Value::try_from(Self {
+            address: "0.0.0.0:8080".parse().unwrap(),
+            tls: None,
+            encoding: JsonSerializerConfig::default().into(),
+            client_queue_size: default_client_queue_size(),
+            acknowledgements: Default::default(),
+        })
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket_server")]
+impl SinkConfig for WebSocketListenerSinkConfig {
+    async fn build(&self, _cx: SinkContext) -> crate::Result<(VectorSink, Healthcheck)> {
+        let tls = MaybeTlsSettings::from_config(&self.tls, true)?;
+        let listener = tls.bind(&self.address).await?;
+        let sink = WebSocketListenerSink::new(self, listener)?;
+
+        Ok((
+            VectorSink::from_event_streamsink(sink),
+            Box::pin(async { Ok(()) }),
+        ))
+    }
+
+    fn input(&self) -> Input {
+        Input::new(self.encoding.config().input_type())
+    }
+
+    fn acknowledgements(&self) -> &AcknowledgementsConfig {
+        &self.acknowledgements
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketListenerSinkConfig>();
+    }
+}
@@ -0,0 +1,449 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use bytes::BytesMut;
+use futures::{sink::SinkExt, stream::BoxStream, StreamExt};
+use lookup::{lookup_v2::parse_target_path, OwnedTargetPath};
+use tokio::{net::TcpStream, sync::mpsc};
+use tokio_tungstenite::{
+    accept_hdr_async,
+    tungstenite::{
+        handshake::server::{ErrorResponse, Request, Response},
+        http::StatusCode,
+        protocol::Message,
+    },
+};
+use tokio_util::codec::Encoder as _;
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
+use vector_core::{
+    internal_event::{
+        ByteSize, BytesSent, CountByteSize, EventsSent, InternalEventHandle as _, Output, Protocol,
+    },
+    EstimatedJsonEncodedSizeOf,
+};
+
+use crate::{
+    codecs::{Encoder, Transformer},
+    conditions::{Condition, ConditionalConfig, VrlConfig},
+    emit,
+    event::{Event, EventStatus, Finalizable},
+    internal_events::{
+        ConnectionOpen, OpenGauge, WsListenerConnectionError, WsListenerConnectionEstablished,
+        WsListenerConnectionShutdown, WsListenerSendError,
+    },
+    sinks::util::StreamSink,
+    sinks::websocket_server::config::WebSocketListenerSinkConfig,
+    tls::{MaybeTlsIncomingStream, MaybeTlsListener},
+};
+
+/// The query parameter a client sets to a VRL condition that events must match.
+const CONDITION_PARAM: &str = "condition";
+
+/// The prefix of the query parameters a client sets to the value a field of the events must have.
+const FIELD_PARAM_PREFIX: &str = "field.";
+
+/// The events a client subscribed to, as given by the query parameters of its connection request.
+///
+/// For example, connecting to `ws://localhost:8080/?field.service=api&condition=.status >= 500`
+/// only streams events whose `service` field is `api` and whose `status` field is at least 500.
+#[derive(Default)]
+struct ClientFilter {
+    condition: Option<Condition>,
+    fields: Vec<(OwnedTargetPath, String)>,
+}
+
+impl ClientFilter {
+    fn from_query(query: Option<&str>) -> Result<Self, String> {
+        let mut filter = Self::default();
+
+        for (key, value) in url::form_urlencoded::parse(query.unwrap_or_default().as_bytes()) {
+            if key == CONDITION_PARAM {
+                let config = VrlConfig {
+                    source: value.into_owned(),
+                    runtime: Default::default(),
+                };
+                let condition = config
+                    .build(&Default::default())
+                    .map_err(|error| format!("Invalid condition: {}", error))?;
+                filter.condition = Some(condition);
+            } else if let Some(field) = key.strip_prefix(FIELD_PARAM_PREFIX) {
+                let path = parse_target_path(field)
+                    .map_err(|error| format!("Invalid field {:?}: {}", field, error))?;
+                filter.fields.push((path, value.into_owned()));
+            }
+        }
+
+        Ok(filter)
+    }
+
+    fn matches(&self, event: &Event) -> bool {
+        if !self.fields.is_empty() {
+            let Event::Log(log) = event else {
+                return false;
+            };
+            let fields_match = self.fields.iter().all(|(path, expected)| {
+                log.get(path)
+                    .map_or(false, |value| value.to_string_lossy() == expected.as_str())
+            });
+            if !fields_match {
+                return false;
+            }
+        }
+
+        match &self.condition {
+            Some(condition) => condition.check(event.clone()).0,
+            None => true,
+        }
+    }
+}
+
+struct Client {
+    filter: ClientFilter,
+    queue: mpsc::Sender<Message>,
+}
+
+type Clients = Arc<Mutex<HashMap<SocketAddr, Client>>>;
+
+pub struct WebSocketListenerSink {
+    listener: Option<MaybeTlsListener>,
+    transformer: Transformer,
+    encoder: Encoder<()>,
+    client_queue_size: usize,
+}
+
+impl WebSocketListenerSink {
+    pub fn new(
+        config: &WebSocketListenerSinkConfig,
+        listener: MaybeTlsListener,
+    ) -> crate::Result<Self> {
+        let transformer = config.encoding.transformer();
+        let serializer = config.encoding.build()?;
+        let encoder = Encoder::<()>::new(serializer);
+
+        Ok(Self {
+            listener: Some(listener),
+            transformer,
+            encoder,
+            client_queue_size: config.client_queue_size.get(),
+        })
+    }
+
+    const fn should_encode_as_binary(&self) -> bool {
+        use codecs::encoding::Serializer::{
+            Avro, Csv, Gelf, Json, Logfmt, Native, NativeJson, RawMessage, Text,
+        };
+
+        match self.encoder.serializer() {
+            RawMessage(_) | Avro(_) | Native(_) => true,
+            Csv(_) | Logfmt(_) | Gelf(_) | Json(_) | Text(_) | NativeJson(_) => false,
+        }
+    }
+
+    fn encode(&mut self, mut event: Event) -> Option<Message> {
+        self.transformer.transform(&mut event);
+
+        let mut bytes = BytesMut::new();
+        // Errors are handled by `Encoder`.
+        self.encoder.encode(event, &mut bytes).ok()?;
+
+        Some(if self.should_encode_as_binary() {
+            Message::binary(bytes)
+        } else {
+            Message::text(String::from_utf8_lossy(&bytes))
+        })
+    }
+}
+
+async fn accept_clients(
+    mut listener: MaybeTlsListener,
+    clients: Clients,
+    client_queue_size: usize,
+) {
+    loop {
+        match listener.accept().await {
+            Ok(stream) => {
+                tokio::spawn(handle_client(
+                    stream,
+                    Arc::clone(&clients),
+                    client_queue_size,
+                ));
+            }
+            Err(error) => emit!(WsListenerConnectionError {
+                error: Box::new(error)
+            }),
+        }
+    }
+}
+
+async fn handle_client(
+    stream: MaybeTlsIncomingStream<TcpStream>,
+    clients: Clients,
+    client_queue_size: usize,
+) {
+    let client = stream.peer_addr();
+
+    let mut filter = None;
+    let ws_stream = accept_hdr_async(stream, |request: &Request, response: Response| {
+        match ClientFilter::from_query(request.uri().query()) {
+            Ok(client_filter) => {
+                filter = Some(client_filter);
+                Ok(response)
+            }
+            Err(message) => {
+                let mut response = ErrorResponse::new(Some(message));
+                *response.status_mut() = StatusCode::BAD_REQUEST;
+                Err(response)
+            }
+        }
+    })
+    .await;
+    let (ws_stream, filter) = match (ws_stream, filter) {
+        (Ok(ws_stream), Some(filter)) => (ws_stream, filter),
+        (Err(error), _) => {
+            emit!(WsListenerConnectionError {
+                error: Box::new(error)
+            });
+            return;
+        }
+        // The handshake only succeeds once the filter was accepted.
+        (Ok(_), None) => return,
+    };
+
+    let (queue, mut receiver) = mpsc::channel(client_queue_size);
+    clients
+        .lock()
+        .expect("clients lock poisoned")
+        .insert(client, Client { filter, queue });
+
+    emit!(WsListenerConnectionEstablished { client });
+    let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+    let bytes_sent = register!(BytesSent::from(Protocol("websocket".into())));
+
+    let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+    loop {
+        tokio::select! {
+            message = receiver.recv() => match message {
+                Some(message) => {
+                    let message_len = message.len();
+                    if let Err(error) = ws_sink.send(message).await {
+                        emit!(WsListenerSendError { error });
+                        break;
+                    }
+                    bytes_sent.emit(ByteSize(message_len));
+                }
+                // The sink is shutting down.
+                None => {
+                    _ = ws_sink.close().await;
+                    break;
+                }
+            },
+
+            // Pings are answered automatically by tungstenite while reading from the stream, and
+            // any other message from the client is ignored.
+            message = ws_stream.next() => match message {
+                Some(Ok(_)) => {}
+                Some(Err(_)) | None => break,
+            },
+        }
+    }
+
+    clients
+        .lock()
+        .expect("clients lock poisoned")
+        .remove(&client);
+    emit!(WsListenerConnectionShutdown { client });
+}
+
+#[async_trait]
+impl StreamSink<Event> for WebSocketListenerSink {
+    async fn run(mut self: Box<Self>, mut input: BoxStream<'_, Event>) -> Result<(), ()> {
+        let clients = Clients::default();
+        let listener = self.listener.take().expect("sink is only run once");
+        let acceptor = tokio::spawn(accept_clients(
+            listener,
+            Arc::clone(&clients),
+            self.client_queue_size,
+        ));
+        let events_sent = register!(EventsSent::from(Output(None)));
+
+        while let Some(mut event) = input.next().await {
+            let finalizers = event.take_finalizers();
+
+            let queues = clients
+                .lock()
+                .expect("clients lock poisoned")
+                .values()
+                .filter(|client| client.filter.matches(&event))
+                .map(|client| client.queue.clone())
+                .collect::<Vec<_>>();
+
+            if !queues.is_empty() {
+                let event_byte_size = event.estimated_json_encoded_size_of();
+                match self.encode(event) {
+                    Some(message) => {
+                        let mut sent = false;
+                        for queue in queues {
+                            // A client that doesn't keep up only misses events, the sink and the
+                            // other clients are not held back.
+                            match queue.try_send(message.clone()) {
+                                Ok(()) => sent = true,
+                                Err(mpsc::error::TrySendError::Full(_)) => {
+                                    emit!(ComponentEventsDropped::<INTENTIONAL> {
+                                        count: 1,
+                                        reason: "Client queue is full.",
+                                    });
+                                }
+                                // The client disconnected in the meantime.
+                                Err(mpsc::error::TrySendError::Closed(_)) => {}
+                            }
+                        }
+                        // The event is counted once, however many clients it is broadcast to.
+                        if sent {
+                            events_sent.emit(CountByteSize(1, event_byte_size));
+                        }
+                    }
+                    None => {
+                        finalizers.update_status(EventStatus::Errored);
+                        continue;
+                    }
+                }
+            }
+
+            finalizers.update_status(EventStatus::Delivered);
+        }
+
+        acceptor.abort();
+        // Dropping the queues makes the connected clients close their connections.
+        clients.lock().expect("clients lock poisoned").clear();
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::NonZeroUsize;
+
+    use codecs::JsonSerializerConfig;
+    use futures::{channel::mpsc::UnboundedSender, Stream};
+    use serde_json::Value as JsonValue;
+    use tokio::time::{sleep, timeout, Duration};
+    use tokio_tungstenite::{connect_async, tungstenite::Error as WsError};
+
+    use super::*;
+    use crate::{
+        event::LogEvent,
+        test_util::{next_addr, trace_init},
+        tls::MaybeTlsSettings,
+    };
+
+    async fn start_sink(addr: SocketAddr) -> UnboundedSender<Event> {
+        let config = WebSocketListenerSinkConfig {
+            address: addr,
+            tls: None,
+            encoding: JsonSerializerConfig::default().into(),
+            client_queue_size: NonZeroUsize::new(10).unwrap(),
+            acknowledgements: Default::default(),
+        };
+        let listener = MaybeTlsSettings::Raw(()).bind(&addr).await.unwrap();
+        let sink = Box::new(WebSocketListenerSink::new(&config, listener).unwrap());
+
+        let (tx, rx) = futures::channel::mpsc::unbounded();
+        tokio::spawn(async move { sink.run(rx.boxed()).await });
+        tx
+    }
+
+    fn log_event(service: &str, message: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("service", service);
+        log.into()
+    }
+
+    async fn next_message<S>(stream: &mut S) -> Option<Message>
+    where
+        S: Stream<Item = Result<Message, WsError>> + Unpin,
+    {
+        timeout(Duration::from_secs(5), stream.next())
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+    }
+
+    async fn next_log_message<S>(stream: &mut S) -> JsonValue
+    where
+        S: Stream<Item = Result<Message, WsError>> + Unpin,
+    {
+        let text = next_message(stream).await.unwrap().into_text().unwrap();
+        serde_json::from_str::<JsonValue>(&text).unwrap()["message"].clone()
+    }
+
+    #[tokio::test]
+    async fn streams_filtered_events_to_clients() {
+        trace_init();
+
+        let addr = next_addr();
+        let tx = start_sink(addr).await;
+
+        let (mut all, _) = connect_async(format!("ws://{}/", addr)).await.unwrap();
+        let (mut api, _) = connect_async(format!("ws://{}/?field.service=api", addr))
+            .await
+            .unwrap();
+        let condition: String =
+            url::form_urlencoded::byte_serialize(br#"contains(string!(.message), "error")"#)
+                .collect();
+        let (mut errors, _) = connect_async(format!("ws://{}/?condition={}", addr, condition))
+            .await
+            .unwrap();
+
+        // Give the sink time to register the clients before sending events.
+        sleep(Duration::from_millis(100)).await;
+
+        tx.unbounded_send(log_event("api", "request handled"))
+            .unwrap();
+        tx.unbounded_send(log_event("db", "query error")).unwrap();
+
+        assert_eq!(next_log_message(&mut all).await, "request handled");
+        assert_eq!(next_log_message(&mut all).await, "query error");
+        assert_eq!(next_log_message(&mut api).await, "request handled");
+        assert_eq!(next_log_message(&mut errors).await, "query error");
+
+        // Neither of the filtered clients receives the other event before the sink shuts down.
+        drop(tx);
+        assert!(matches!(
+            next_message(&mut api).await,
+            Some(Message::Close(_)) | None
+        ));
+        assert!(matches!(
+            next_message(&mut errors).await,
+            Some(Message::Close(_)) | None
+        ));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_condition() {
+        trace_init();
+
+        let addr = next_addr();
+        let _tx = start_sink(addr).await;
+
+        let result = connect_async(format!("ws://{}/?condition=.message%20%2B%2B", addr)).await;
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn filter_matches_fields() {
+        let filter = ClientFilter::from_query(Some("field.service=api&field.level=error")).unwrap();
+
+        let mut event = log_event("api", "message");
+        assert!(!filter.matches(&event));
+        event.as_mut_log().insert("level", "error");
+        assert!(filter.matches(&event));
+        assert!(!filter.matches(&log_event("db", "message")));
+    }
+}
@@ -17,7 +17,7 @@ use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path, OwnedValuePat
 use rdkafka::{
     consumer::{CommitMode, Consumer, ConsumerContext, Rebalance, StreamConsumer},
     message::{BorrowedMessage, Headers as _, Message},
-    ClientConfig, ClientContext, Statistics,
+    ClientConfig, ClientContext, Offset, Statistics, TopicPartitionList,
 };
 use serde_with::serde_as;
 use snafu::{ResultExt, Snafu};
@@ -40,11 +40,15 @@ use crate::{
     event::{BatchNotifier, BatchStatus, Event, Value},
     internal_events::{
         KafkaBytesReceived, KafkaEventsReceived, KafkaOffsetUpdateError, KafkaReadError,
-        StreamClosedError,
+        KafkaSchemaRegistryError, StreamClosedError,
+    },
+    kafka::{
+        self,
+        schema_registry::{DecodedPayload, SchemaRegistryClient, SchemaRegistryConfig},
     },
-    kafka,
     serde::{bool_or_struct, default_decoding, default_framing_message_based},
     shutdown::ShutdownSignal,
+    sinks::util::retries::ExponentialBackoff,
     SourceSender,
 };
 
@@ -54,6 +58,21 @@ enum BuildError {
     KafkaCreateError { source: rdkafka::error::KafkaError },
     #[snafu(display("Could not subscribe to Kafka topics: {}", source))]
     KafkaSubscribeError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Exactly one of `topics` and `partitions` must be set."))]
+    InvalidTopics,
+    #[snafu(display("A `group_id` must be set to subscribe to `topics`."))]
+    MissingGroupId,
+    #[snafu(display(
+        "Topic {:?} is a regular expression, which can't be read from or up to a timestamp.",
+        topic
+    ))]
+    TopicPattern { topic: String },
+    #[snafu(display("Could not fetch Kafka metadata: {}", source))]
+    KafkaMetadataError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not resolve Kafka offsets: {}", source))]
+    KafkaOffsetsError { source: rdkafka::error::KafkaError },
+    #[snafu(display("Could not assign Kafka partitions: {}", source))]
+    KafkaAssignError { source: rdkafka::error::KafkaError },
 }
 
 /// Metrics configuration.
@@ -64,6 +83,33 @@ struct Metrics {
     pub topic_lag_metric: bool,
 }
 
+/// A partition to read, along with the offsets to read it from and up to.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct KafkaPartitionConfig {
+    /// The name of the topic the partition belongs to.
+    #[configurable(metadata(docs::examples = "topic-1"))]
+    topic: String,
+
+    /// The number of the partition.
+    #[configurable(metadata(docs::examples = 0))]
+    partition: i32,
+
+    /// The offset of the first message to read.
+    ///
+    /// If not set, reading starts at `start_timestamp` if that is set, and at the position given by
+    /// `auto_offset_reset` otherwise.
+    #[configurable(metadata(docs::examples = 1000))]
+    start_offset: Option<i64>,
+
+    /// The offset to stop reading at. The message at this offset is not read.
+    ///
+    /// If not set, reading stops at `end_timestamp` if that is set, and never stops otherwise.
+    #[configurable(metadata(docs::examples = 2000))]
+    end_offset: Option<i64>,
+}
+
 /// Configuration for the `kafka` source.
 #[serde_as]
 #[configurable_component(source("kafka", "Collect logs from Apache Kafka."))]
@@ -83,6 +129,9 @@ pub struct KafkaSourceConfig {
     /// The Kafka topics names to read events from.
     ///
     /// Regular expression syntax is supported if the topic begins with `^`.
+    ///
+    /// Either `topics` or `partitions` must be set.
+    #[serde(default)]
     #[configurable(metadata(
         docs::examples = "^(prefix1|prefix2)-.+",
         docs::examples = "topic-1",
@@ -90,9 +139,39 @@ pub struct KafkaSourceConfig {
     ))]
     topics: Vec<String>,
 
+    /// The partitions to read events from, assigned explicitly instead of through the consumer
+    /// group.
+    ///
+    /// Either `topics` or `partitions` must be set.
+    #[serde(default)]
+    partitions: Vec<KafkaPartitionConfig>,
+
     /// The consumer group name to be used to consume events from Kafka.
+    ///
+    /// This must be set to subscribe to `topics`. When partitions are assigned explicitly, through
+    /// `partitions`, `start_timestamp`, or `end_timestamp`, the offsets are only committed to the
+    /// group if it is set, and are never used to decide where to start reading.
     #[configurable(metadata(docs::examples = "consumer-group-name"))]
-    group_id: String,
+    group_id: Option<String>,
+
+    /// Starts reading each partition at the first message whose timestamp is at or after this time.
+    ///
+    /// The offsets of the messages are resolved when the source starts, and all partitions of
+    /// `topics` are assigned explicitly instead of through the consumer group. Offsets given in
+    /// `partitions` take precedence.
+    #[configurable(metadata(docs::examples = "2023-10-01T00:00:00Z"))]
+    start_timestamp: Option<DateTime<Utc>>,
+
+    /// Stops reading each partition at the first message whose timestamp is at or after this time.
+    ///
+    /// The offsets of the messages are resolved when the source starts, and all partitions of
+    /// `topics` are assigned explicitly instead of through the consumer group. Offsets given in
+    /// `partitions` take precedence.
+    ///
+    /// Once every partition reached its end, and all events were acknowledged, the source
+    /// finishes.
+    #[configurable(metadata(docs::examples = "2023-10-02T00:00:00Z"))]
+    end_timestamp: Option<DateTime<Utc>>,
 
     /// If offsets for consumer group do not exist, set them using this strategy.
     ///
@@ -201,6 +280,18 @@ pub struct KafkaSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    /// The Confluent Schema Registry used to decode messages in its wire format.
+    ///
+    /// Messages starting with the magic byte and schema ID of the wire format are decoded with the
+    /// schema resolved from the registry. Avro and JSON Schema payloads are decoded directly, while
+    /// Protobuf payloads are decoded with the configured `decoding` once their message indexes are
+    /// skipped. Other messages are decoded with `decoding` as usual.
+    ///
+    /// While the registry is unavailable, decoding is retried with a backoff and no further messages
+    /// are read. Messages that fail to decode with their schema are dropped.
+    #[configurable(metadata(docs::advanced))]
+    schema_registry: Option<SchemaRegistryConfig>,
+
     #[configurable(derived)]
     #[serde(default, deserialize_with = "bool_or_struct")]
     acknowledgements: SourceAcknowledgementsConfig,
@@ -219,6 +310,13 @@ impl KafkaSourceConfig {
     fn keys(&self) -> Keys {
         Keys::from(log_schema(), self)
     }
+
+    /// Whether partitions are assigned explicitly by the source, rather than by the consumer group.
+    fn assigns_partitions(&self) -> bool {
+        !self.partitions.is_empty()
+            || self.start_timestamp.is_some()
+            || self.end_timestamp.is_some()
+    }
 }
 
 const fn default_session_timeout_ms() -> Duration {
@@ -290,19 +388,40 @@ impl SourceConfig for KafkaSourceConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let consumer = create_consumer(self)?;
-        let decoder =
-            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
-                .build()?;
+        let (consumer, ends) = if self.assigns_partitions() {
+            // Resolving the offsets to read blocks on requests to the brokers.
+            let config = self.clone();
+            tokio::task::spawn_blocking(move || {
+                assign_partitions(&consumer, &config).map(|ends| (consumer, ends))
+            })
+            .await??
+        } else {
+            (consumer, PartitionEnds::default())
+        };
+        let decoder = MessageDecoder {
+            decoder: DecodingConfig::new(
+                self.framing.clone(),
+                self.decoding.clone(),
+                log_namespace,
+            )
+            .build()?,
+            schema_registry: self
+                .schema_registry
+                .as_ref()
+                .map(|registry| registry.build(&cx.proxy))
+                .transpose()?,
+            log_namespace,
+        };
         let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
 
         Ok(Box::pin(kafka_source(
             self.clone(),
             consumer,
+            ends,
             decoder,
             cx.shutdown,
             cx.out,
             acknowledgements,
-            log_namespace,
         )))
     }
 
@@ -371,11 +490,11 @@ impl SourceConfig for KafkaSourceConfig {
 async fn kafka_source(
     config: KafkaSourceConfig,
     consumer: StreamConsumer<CustomContext>,
-    decoder: Decoder,
+    mut ends: PartitionEnds,
+    decoder: MessageDecoder,
     mut shutdown: ShutdownSignal,
     mut out: SourceSender,
     acknowledgements: bool,
-    log_namespace: LogNamespace,
 ) -> Result<(), ()> {
     let consumer = Arc::new(consumer);
     let (finalizer, mut ack_stream) =
@@ -390,12 +509,20 @@ async fn kafka_source(
     }
 
     let mut stream = consumer.stream();
+    let mut pending_acks = 0_usize;
 
     loop {
+        // Once every partition is read up to its end, the source finishes as soon as the events
+        // read are acknowledged.
+        if ends.is_finished() && pending_acks == 0 {
+            break;
+        }
+
         tokio::select! {
             biased;
             _ = &mut shutdown => break,
             entry = ack_stream.next() => if let Some((status, entry)) = entry {
+                pending_acks -= 1;
                 if status == BatchStatus::Delivered {
                     if let Err(error) =
                         consumer.store_offset(&entry.topic, entry.partition, entry.offset)
@@ -404,9 +531,10 @@ async fn kafka_source(
                     }
                 }
             },
-            message = stream.next() => match message {
+            message = stream.next(), if !ends.is_finished() => match message {
                 None => break,  // WHY?
                 Some(Err(error)) => emit!(KafkaReadError { error }),
+                Some(Ok(msg)) if !ends.read_message(&consumer, &msg) => {}
                 Some(Ok(msg)) => {
                     emit!(KafkaBytesReceived {
                         byte_size: msg.payload_len(),
@@ -415,7 +543,9 @@ async fn kafka_source(
                         partition: msg.partition(),
                     });
 
-                    parse_message(msg, decoder.clone(), config.keys(), &finalizer, &mut out, &consumer, log_namespace).await;
+                    if parse_message(msg, &decoder, config.keys(), &finalizer, &mut out, &consumer, &shutdown).await {
+                        pending_acks += 1;
+                    }
                 }
             },
         }
@@ -431,16 +561,71 @@ async fn kafka_source(
     Ok(())
 }
 
+/// Decodes the payloads of messages into events.
+#[derive(Clone)]
+struct MessageDecoder {
+    decoder: Decoder,
+    schema_registry: Option<SchemaRegistryClient>,
+    log_namespace: LogNamespace,
+}
+
+impl MessageDecoder {
+    /// Resolves the schema of the payload from the registry, if the source uses one.
+    ///
+    /// While the registry is unavailable, requests are retried until they succeed or the source
+    /// shuts down, so that the messages of the partition are not skipped. Payloads that can't be
+    /// decoded are dropped.
+    async fn resolve(&self, payload: &[u8], shutdown: &ShutdownSignal) -> Option<DecodedPayload> {
+        let Some(registry) = &self.schema_registry else {
+            return Some(DecodedPayload::Raw(Bytes::copy_from_slice(payload)));
+        };
+
+        let mut backoff = ExponentialBackoff::from_millis(2)
+            .factor(250)
+            .max_delay(Duration::from_secs(30));
+        loop {
+            match registry.decode(payload).await {
+                Ok(payload) => return Some(payload),
+                Err(error) if error.is_retriable() => {
+                    emit!(KafkaSchemaRegistryError {
+                        error,
+                        drop_event: false,
+                    });
+                    tokio::select! {
+                        _ = shutdown.clone() => return None,
+                        _ = tokio::time::sleep(backoff.next().unwrap()) => {}
+                    }
+                }
+                Err(error) => {
+                    emit!(KafkaSchemaRegistryError {
+                        error,
+                        drop_event: true,
+                    });
+                    return None;
+                }
+            }
+        }
+    }
+}
+
+/// Sends the events decoded from `msg`, returning whether they are awaiting acknowledgement.
 async fn parse_message(
     msg: BorrowedMessage<'_>,
-    decoder: Decoder,
+    decoder: &MessageDecoder,
     keys: Keys<'_>,
     finalizer: &Option<Arc<OrderedFinalizer<FinalizerEntry>>>,
     out: &mut SourceSender,
     consumer: &Arc<StreamConsumer<CustomContext>>,
-    log_namespace: LogNamespace,
-) {
-    if let Some((count, mut stream)) = parse_stream(&msg, decoder, keys, log_namespace) {
+    shutdown: &ShutdownSignal,
+) -> bool {
+    let Some(payload) = msg.payload() else {
+        return false; // skip messages with empty payload
+    };
+    let Some(payload) = decoder.resolve(payload, shutdown).await else {
+        return false;
+    };
+
+    if let Some((count, mut stream)) = parse_stream(&msg, payload, decoder, keys) {
         match finalizer {
             Some(finalizer) => {
                 let (batch, receiver) = BatchNotifier::new_with_receiver();
@@ -454,6 +639,7 @@ async fn parse_message(
                         // here, when `stream` is dropped and runs the destructor [...]".
                         drop(stream);
                         finalizer.add(msg.into(), receiver);
+                        return true;
                     }
                 }
             }
@@ -471,22 +657,36 @@ async fn parse_message(
             },
         }
     }
+    false
 }
 
 // Turn the received message into a stream of parsed events.
 fn parse_stream<'a>(
     msg: &BorrowedMessage<'a>,
-    decoder: Decoder,
+    payload: DecodedPayload,
+    decoder: &MessageDecoder,
     keys: Keys<'a>,
-    log_namespace: LogNamespace,
 ) -> Option<(usize, impl Stream<Item = Event> + 'a)> {
-    let payload = msg.payload()?; // skip messages with empty payload
-
     let rmsg = ReceivedMessage::from(msg);
+    let log_namespace = decoder.log_namespace;
+
+    let payload = match payload {
+        DecodedPayload::Raw(payload) => payload,
+        DecodedPayload::Value(value) => {
+            let mut event = Event::from(log_namespace.new_log_from_data(value));
+            emit!(KafkaEventsReceived {
+                count: 1,
+                byte_size: event.estimated_json_encoded_size_of(),
+                topic: &rmsg.topic,
+                partition: rmsg.partition,
+            });
+            rmsg.apply(&keys, &mut event, log_namespace);
+            return Some((1, futures::stream::once(async { event }).boxed()));
+        }
+    };
+    let payload = Cursor::new(payload);
 
-    let payload = Cursor::new(Bytes::copy_from_slice(payload));
-
-    let mut stream = FramedRead::new(payload, decoder);
+    let mut stream = FramedRead::new(payload, decoder.decoder.clone());
     let (count, _) = stream.size_hint();
     let stream = stream! {
         while let Some(result) = stream.next().await {
@@ -674,9 +874,18 @@ impl<'a> From<BorrowedMessage<'a>> for FinalizerEntry {
 }
 
 fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<CustomContext>> {
+    if config.topics.is_empty() == config.partitions.is_empty() {
+        return Err(BuildError::InvalidTopics.into());
+    }
+    if config.group_id.is_none() && !config.assigns_partitions() {
+        return Err(BuildError::MissingGroupId.into());
+    }
+
     let mut client_config = ClientConfig::new();
+    if let Some(group_id) = &config.group_id {
+        client_config.set("group.id", group_id);
+    }
     client_config
-        .set("group.id", &config.group_id)
         .set("bootstrap.servers", &config.bootstrap_servers)
         .set("auto.offset.reset", &config.auto_offset_reset)
         .set(
@@ -692,7 +901,7 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
             &config.fetch_wait_max_ms.as_millis().to_string(),
         )
         .set("enable.partition.eof", "false")
-        .set("enable.auto.commit", "true")
+        .set("enable.auto.commit", config.group_id.is_some().to_string())
         .set(
             "auto.commit.interval.ms",
             &config.commit_interval_ms.as_millis().to_string(),
@@ -714,12 +923,244 @@ fn create_consumer(config: &KafkaSourceConfig) -> crate::Result<StreamConsumer<C
             config.metrics.topic_lag_metric,
         ))
         .context(KafkaCreateSnafu)?;
-    let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
-    consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    if !config.assigns_partitions() {
+        let topics: Vec<&str> = config.topics.iter().map(|s| s.as_str()).collect();
+        consumer.subscribe(&topics).context(KafkaSubscribeSnafu)?;
+    }
 
     Ok(consumer)
 }
 
+/// Where reading a partition ends.
+#[derive(Clone, Copy, Debug)]
+enum PartitionEnd {
+    /// Reading ends at the message with this offset, which is not read.
+    Offset(i64),
+
+    /// Reading ends at the first message with a timestamp, in milliseconds, at or after this one.
+    ///
+    /// This is used for `end_timestamp` when no such message existed when the source started.
+    Timestamp(i64),
+}
+
+/// Tracks the partitions that are still to be read up to their end.
+#[derive(Debug, Default)]
+struct PartitionEnds {
+    /// The ends of the partitions that were not read up to their end yet, by topic and partition.
+    ///
+    /// This is only set when every assigned partition has an end, as the source can't finish
+    /// otherwise.
+    remaining: Option<HashMap<String, HashMap<i32, PartitionEnd>>>,
+}
+
+impl PartitionEnds {
+    fn is_finished(&self) -> bool {
+        self.remaining.as_ref().is_some_and(HashMap::is_empty)
+    }
+
+    /// Returns whether `msg` is to be read, pausing its partition once it is read up to its end.
+    fn read_message(
+        &mut self,
+        consumer: &StreamConsumer<CustomContext>,
+        msg: &BorrowedMessage<'_>,
+    ) -> bool {
+        let Some(remaining) = &mut self.remaining else {
+            return true;
+        };
+        let Some(partitions) = remaining.get_mut(msg.topic()) else {
+            return false;
+        };
+        let Some(end) = partitions.get(&msg.partition()) else {
+            return false;
+        };
+
+        let (read, finished) = match *end {
+            PartitionEnd::Offset(end) => (msg.offset() < end, msg.offset() + 1 >= end),
+            PartitionEnd::Timestamp(end) => {
+                let read = msg
+                    .timestamp()
+                    .to_millis()
+                    .map_or(true, |timestamp| timestamp < end);
+                (read, !read)
+            }
+        };
+
+        if finished {
+            let mut tpl = TopicPartitionList::new();
+            tpl.add_partition(msg.topic(), msg.partition());
+            if let Err(error) = consumer.pause(&tpl) {
+                emit!(KafkaReadError { error });
+            }
+
+            partitions.remove(&msg.partition());
+            if partitions.is_empty() {
+                remaining.remove(msg.topic());
+            }
+        }
+
+        read
+    }
+}
+
+/// Assigns the partitions to read to the consumer, starting each of them at its configured offset
+/// or at `start_timestamp`, and returns where reading them ends.
+fn assign_partitions(
+    consumer: &StreamConsumer<CustomContext>,
+    config: &KafkaSourceConfig,
+) -> crate::Result<PartitionEnds> {
+    let timeout = config.socket_timeout_ms;
+
+    let partitions = if config.partitions.is_empty() {
+        let mut partitions = Vec::new();
+        for topic in &config.topics {
+            if topic.starts_with('^') {
+                return Err(BuildError::TopicPattern {
+                    topic: topic.clone(),
+                }
+                .into());
+            }
+
+            let metadata = consumer
+                .fetch_metadata(Some(topic), timeout)
+                .context(KafkaMetadataSnafu)?;
+            for partition in metadata
+                .topics()
+                .iter()
+                .flat_map(|topic| topic.partitions())
+            {
+                partitions.push(KafkaPartitionConfig {
+                    topic: topic.clone(),
+                    partition: partition.id(),
+                    start_offset: None,
+                    end_offset: None,
+                });
+            }
+        }
+        partitions
+    } else {
+        config.partitions.clone()
+    };
+
+    let start_offsets = config
+        .start_timestamp
+        .map(|timestamp| offsets_for_time(consumer, &partitions, timestamp, timeout))
+        .transpose()?;
+    let end_offsets = config
+        .end_timestamp
+        .map(|timestamp| offsets_for_time(consumer, &partitions, timestamp, timeout))
+        .transpose()?;
+
+    let mut assignment = TopicPartitionList::new();
+    let mut remaining = HashMap::<String, HashMap<i32, PartitionEnd>>::new();
+    let mut every_partition_ends = true;
+    for (i, partition) in partitions.iter().enumerate() {
+        let start = match (partition.start_offset, &start_offsets) {
+            (Some(offset), _) => Offset::Offset(offset),
+            (None, Some(offsets)) => offsets[i],
+            // Without offsets committed for the partition, this starts at the position given by
+            // `auto_offset_reset`.
+            (None, None) => Offset::Invalid,
+        };
+
+        let end = match (partition.end_offset, &end_offsets, config.end_timestamp) {
+            (Some(offset), _, _) => Some(PartitionEnd::Offset(offset)),
+            (None, Some(offsets), Some(timestamp)) => Some(match offsets[i] {
+                Offset::Offset(offset) => PartitionEnd::Offset(offset),
+                _ => PartitionEnd::Timestamp(timestamp.timestamp_millis()),
+            }),
+            _ => None,
+        };
+
+        match end {
+            // A partition without messages to read is not assigned at all, as no message would
+            // ever mark it as read up to its end.
+            Some(PartitionEnd::Offset(end))
+                if start_position(consumer, config, partition, start, timeout)? >= end => {}
+            Some(end) => {
+                assignment
+                    .add_partition_offset(&partition.topic, partition.partition, start)
+                    .context(KafkaAssignSnafu)?;
+                remaining
+                    .entry(partition.topic.clone())
+                    .or_default()
+                    .insert(partition.partition, end);
+            }
+            None => {
+                every_partition_ends = false;
+                assignment
+                    .add_partition_offset(&partition.topic, partition.partition, start)
+                    .context(KafkaAssignSnafu)?;
+            }
+        }
+    }
+
+    consumer.assign(&assignment).context(KafkaAssignSnafu)?;
+
+    Ok(PartitionEnds {
+        remaining: every_partition_ends.then_some(remaining),
+    })
+}
+
+/// Resolves the offsets of the first messages of `partitions` at or after `timestamp`.
+///
+/// Partitions without such a message resolve to `Offset::End`.
+fn offsets_for_time(
+    consumer: &StreamConsumer<CustomContext>,
+    partitions: &[KafkaPartitionConfig],
+    timestamp: DateTime<Utc>,
+    timeout: Duration,
+) -> crate::Result<Vec<Offset>> {
+    let mut tpl = TopicPartitionList::new();
+    for partition in partitions {
+        tpl.add_partition_offset(
+            &partition.topic,
+            partition.partition,
+            Offset::Offset(timestamp.timestamp_millis()),
+        )
+        .context(KafkaOffsetsSnafu)?;
+    }
+
+    let offsets = consumer
+        .offsets_for_times(tpl, timeout)
+        .context(KafkaOffsetsSnafu)?;
+
+    Ok(partitions
+        .iter()
+        .map(|partition| {
+            offsets
+                .find_partition(&partition.topic, partition.partition)
+                .map_or(Offset::End, |elem| elem.offset())
+        })
+        .collect())
+}
+
+/// Resolves the offset of the first message read from `partition` when starting at `start`.
+fn start_position(
+    consumer: &StreamConsumer<CustomContext>,
+    config: &KafkaSourceConfig,
+    partition: &KafkaPartitionConfig,
+    start: Offset,
+    timeout: Duration,
+) -> crate::Result<i64> {
+    let (low, high) = consumer
+        .fetch_watermarks(&partition.topic, partition.partition, timeout)
+        .context(KafkaOffsetsSnafu)?;
+
+    Ok(match start {
+        Offset::Offset(offset) => offset,
+        Offset::Beginning => low,
+        Offset::Invalid
+            if matches!(
+                config.auto_offset_reset.as_str(),
+                "smallest" | "earliest" | "beginning"
+            ) =>
+        {
+            low
+        }
+        _ => high,
+    })
+}
+
 #[derive(Default)]
 struct CustomContext {
     stats: kafka::KafkaStatisticsContext,
@@ -779,7 +1220,7 @@ mod test {
         KafkaSourceConfig {
             bootstrap_servers: kafka_address(9091),
             topics: vec![topic.into()],
-            group_id: group.into(),
+            group_id: Some(group.into()),
             auto_offset_reset: "beginning".into(),
             session_timeout_ms: Duration::from_millis(6000),
             commit_interval_ms: Duration::from_millis(1),
@@ -893,6 +1334,37 @@ mod test {
         };
         assert!(create_consumer(&config).is_err());
     }
+
+    #[tokio::test]
+    async fn consumer_create_requires_group_to_subscribe() {
+        let config = KafkaSourceConfig {
+            group_id: None,
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        assert!(create_consumer(&config).is_err());
+    }
+
+    #[tokio::test]
+    async fn consumer_create_assigns_partitions_without_group() {
+        let config = KafkaSourceConfig {
+            topics: vec![],
+            partitions: vec![KafkaPartitionConfig {
+                topic: "topic".into(),
+                partition: 0,
+                start_offset: Some(10),
+                end_offset: Some(20),
+            }],
+            group_id: None,
+            ..make_config("topic", "group", LogNamespace::Legacy)
+        };
+        assert!(create_consumer(&config).is_ok());
+
+        let config = KafkaSourceConfig {
+            topics: vec!["topic".into()],
+            ..config
+        };
+        assert!(create_consumer(&config).is_err());
+    }
 }
 
 #[cfg(feature = "kafka-integration-tests")]
@@ -901,7 +1373,7 @@ mod integration_test {
     use std::time::Duration;
 
     use chrono::{DateTime, SubsecRound, Utc};
-    use futures::Stream;
+    use futures::{future, Stream};
     use rdkafka::{
         admin::{AdminClient, AdminOptions, NewTopic, TopicReplication},
         client::DefaultClientContext,
@@ -1108,6 +1580,71 @@ mod integration_test {
         }
     }
 
+    /// Runs the source until it finishes, returning the events it read.
+    async fn read_until_finished(config: KafkaSourceConfig) -> Vec<Event> {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        let (result, events) = tokio::time::timeout(
+            Duration::from_secs(30),
+            future::join(source, rx.collect::<Vec<_>>()),
+        )
+        .await
+        .expect("source did not finish");
+        assert_eq!(result, Ok(()));
+        events
+    }
+
+    fn offsets(events: &[Event]) -> Vec<i64> {
+        events
+            .iter()
+            .map(|event| event.as_log()["offset"].as_integer().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn reads_partition_offsets_and_finishes() {
+        let topic = format!("test-topic-{}", random_string(10));
+        send_events(topic.clone(), 10).await;
+
+        let config = KafkaSourceConfig {
+            topics: vec![],
+            partitions: vec![KafkaPartitionConfig {
+                topic: topic.clone(),
+                partition: 0,
+                start_offset: Some(2),
+                end_offset: Some(5),
+            }],
+            group_id: None,
+            ..make_config(&topic, "unused", LogNamespace::Legacy)
+        };
+
+        let events = read_until_finished(config).await;
+        assert_eq!(offsets(&events), vec![2, 3, 4]);
+    }
+
+    #[tokio::test]
+    async fn reads_between_timestamps_and_finishes() {
+        let topic = format!("test-topic-{}", random_string(10));
+        send_events(topic.clone(), 3).await;
+        sleep(Duration::from_millis(100)).await;
+        let start = send_events(topic.clone(), 4).await;
+        sleep(Duration::from_millis(100)).await;
+        let end = send_events(topic.clone(), 3).await;
+
+        let config = KafkaSourceConfig {
+            start_timestamp: Some(start),
+            end_timestamp: Some(end),
+            group_id: None,
+            ..make_config(&topic, "unused", LogNamespace::Legacy)
+        };
+
+        let events = read_until_finished(config).await;
+        assert_eq!(offsets(&events), vec![3, 4, 5, 6]);
+    }
+
     fn make_rand_config() -> (String, String, KafkaSourceConfig) {
         let topic = format!("test-topic-{}", random_string(10));
         let group_id = format!("test-group-{}", random_string(10));
@@ -1146,22 +1683,26 @@ mod integration_test {
         let (trigger_shutdown, shutdown, shutdown_done) = ShutdownSignal::new_wired();
         let consumer = create_consumer(&config).unwrap();
 
-        let decoder = DecodingConfig::new(
-            config.framing.clone(),
-            config.decoding.clone(),
+        let decoder = MessageDecoder {
+            decoder: DecodingConfig::new(
+                config.framing.clone(),
+                config.decoding.clone(),
+                log_namespace,
+            )
+            .build()
+            .unwrap(),
+            schema_registry: None,
             log_namespace,
-        )
-        .build()
-        .unwrap();
+        };
 
         tokio::spawn(kafka_source(
             config,
             consumer,
+            PartitionEnds::default(),
             decoder,
             shutdown,
             tx,
             acknowledgements,
-            log_namespace,
         ));
         (trigger_shutdown, shutdown_done)
     }
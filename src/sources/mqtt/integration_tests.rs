@@ -0,0 +1,109 @@
+#![allow(clippy::print_stdout)] //tests
+
+use std::time::Duration;
+
+use rumqttc::{AsyncClient, MqttOptions, QoS};
+use vector_core::config::log_schema;
+
+use super::*;
+use crate::{
+    config::SourceContext,
+    test_util::{
+        collect_n,
+        components::{assert_source_compliance, SOURCE_TAGS},
+        random_string, trace_init,
+    },
+};
+
+fn mqtt_host() -> String {
+    std::env::var("MQTT_HOST").unwrap_or_else(|_| "localhost".into())
+}
+
+fn make_config(topic: &str) -> MqttSourceConfig {
+    MqttSourceConfig {
+        connection: MqttConnectionConfig {
+            host: mqtt_host(),
+            ..Default::default()
+        },
+        topics: vec![topic.to_owned()],
+        ..Default::default()
+    }
+}
+
+async fn publish(topic: &str, messages: &[&str]) {
+    let options = MqttOptions::new(
+        format!("publisher-{}", random_string(10)),
+        mqtt_host(),
+        1883,
+    );
+    let (client, mut eventloop) = AsyncClient::new(options, 16);
+
+    for message in messages {
+        client
+            .publish(topic, QoS::AtLeastOnce, false, message.as_bytes().to_vec())
+            .await
+            .unwrap();
+    }
+    client.disconnect().await.unwrap();
+
+    // Drive the connection until the disconnect has been sent.
+    while eventloop.poll().await.is_ok() {}
+}
+
+async fn source_receives(config: MqttSourceConfig, publish_to: &str) {
+    let messages = ["first message", "second message", "third message"];
+
+    let events = assert_source_compliance(&SOURCE_TAGS, async move {
+        let (tx, rx) = SourceSender::new_test();
+        let source = config
+            .build(SourceContext::new_test(tx, None))
+            .await
+            .unwrap();
+        tokio::spawn(source);
+
+        // Give the source time to connect and subscribe before publishing.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+        publish(publish_to, &messages).await;
+
+        collect_n(rx, messages.len()).await
+    })
+    .await;
+
+    for (event, message) in events.iter().zip(messages) {
+        let log = event.as_log();
+        assert_eq!(
+            log[log_schema().message_key().unwrap().to_string()],
+            message.into()
+        );
+        assert_eq!(log["topic"], publish_to.into());
+    }
+}
+
+#[tokio::test]
+async fn mqtt_source_receives_messages() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    source_receives(make_config(&topic), &topic).await;
+}
+
+#[tokio::test]
+async fn mqtt_source_wildcard_filter() {
+    trace_init();
+
+    let prefix = format!("test-{}", random_string(10));
+    let config = make_config(&format!("{}/+/temperature", prefix));
+    source_receives(config, &format!("{}/sensor-1/temperature", prefix)).await;
+}
+
+#[tokio::test]
+async fn mqtt_source_exactly_once() {
+    trace_init();
+
+    let topic = format!("test-{}", random_string(10));
+    let config = MqttSourceConfig {
+        quality_of_service: MqttQoS::ExactlyOnce,
+        ..make_config(&topic)
+    };
+    source_receives(config, &topic).await;
+}
@@ -0,0 +1,410 @@
+//! `MQTT` source.
+//! Subscribes to topic filters on an MQTT 3.1.1 broker.
+use std::time::Duration;
+
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use rumqttc::{AsyncClient, Event as MqttEvent, EventLoop, Packet, Publish, SubscribeFilter};
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        error_stage, ByteSize, BytesReceived, CountByteSize, EventsReceived,
+        InternalEventHandle as _, Protocol,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace, SourceAcknowledgementsConfig},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::Kind;
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::{BatchNotifier, BatchStatus, Event},
+    internal_events::{MqttAckError, MqttConnectionError, MqttSubscribeError, StreamClosedError},
+    mqtt::{MqttConfigError, MqttConnectionConfig, MqttQoS},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceSender,
+};
+
+#[cfg(all(test, feature = "mqtt-integration-tests"))]
+mod integration_tests;
+
+/// The capacity of the channel between the client and its event loop.
+const REQUEST_CHANNEL_CAPACITY: usize = 1024;
+
+/// How long to wait before polling the event loop again after a connection error.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("{}", source))]
+    Config { source: MqttConfigError },
+    #[snafu(display("MQTT Config Error: at least one topic filter must be configured"))]
+    NoTopics,
+}
+
+/// Configuration for the `mqtt` source.
+#[configurable_component(source("mqtt", "Collect events from topics on an MQTT broker."))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct MqttSourceConfig {
+    #[serde(flatten)]
+    pub(crate) connection: MqttConnectionConfig,
+
+    /// The MQTT [topic filters][mqtt_topics] to subscribe to.
+    ///
+    /// Filters may contain the `+` single-level and `#` multi-level wildcards.
+    ///
+    /// [mqtt_topics]: https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/os/mqtt-v3.1.1-os.html#_Toc398718106
+    #[configurable(metadata(docs::examples = "sensors/+/temperature"))]
+    #[configurable(metadata(docs::examples = "devices/#"))]
+    pub(crate) topics: Vec<String>,
+
+    /// The maximum Quality of Service level requested for the subscriptions.
+    #[serde(default)]
+    pub(crate) quality_of_service: MqttQoS,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the topic the message was published to.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    #[configurable(metadata(docs::examples = "topic"))]
+    pub(crate) topic_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    pub(crate) framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    pub(crate) decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    pub(crate) acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    pub log_namespace: Option<bool>,
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+impl GenerateConfig for MqttSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["vector/#"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "mqtt")]
+impl SourceConfig for MqttSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        if self.topics.is_empty() {
+            return Err(BuildError::NoTopics.into());
+        }
+
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        let mut options = self.connection.build_options().context(ConfigSnafu)?;
+        // Messages are only acknowledged to the broker once they are delivered downstream.
+        options.set_manual_acks(acknowledgements);
+        let (client, eventloop) = AsyncClient::new(options, REQUEST_CHANNEL_CAPACITY);
+
+        Ok(Box::pin(mqtt_source(
+            self.clone(),
+            client,
+            eventloop,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                MqttSourceConfig::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn mqtt_source(
+    config: MqttSourceConfig,
+    client: AsyncClient,
+    mut eventloop: EventLoop,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        UnorderedFinalizer::<Publish>::maybe_new(acknowledgements, Some(shutdown.clone()));
+
+    // Acknowledgements are sent from a separate task so that waiting on the request channel of the
+    // client never blocks the loop that drives the connection.
+    let ack_client = client.clone();
+    tokio::spawn(async move {
+        while let Some((status, publish)) = ack_stream.next().await {
+            handle_ack(&ack_client, status, &publish).await;
+        }
+    });
+
+    let filters = config
+        .topics
+        .iter()
+        .map(|topic| SubscribeFilter::new(topic.clone(), config.quality_of_service.into()))
+        .collect::<Vec<_>>();
+
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::from("mqtt")));
+
+    loop {
+        let event = tokio::select! {
+            _ = &mut shutdown => break,
+            event = eventloop.poll() => event,
+        };
+
+        match event {
+            Ok(MqttEvent::Incoming(Packet::ConnAck(connack))) => {
+                // Subscriptions are part of the session state, so they only need to be renewed when
+                // the broker did not resume a previous session.
+                if !connack.session_present {
+                    if let Err(error) = client.try_subscribe_many(filters.clone()) {
+                        emit!(MqttSubscribeError { error });
+                        return Err(());
+                    }
+                }
+            }
+            Ok(MqttEvent::Incoming(Packet::Publish(publish))) => {
+                bytes_received.emit(ByteSize(publish.payload.len()));
+
+                let mut events = Vec::new();
+                let mut stream = FramedRead::new(publish.payload.as_ref(), decoder.clone());
+                while let Some(next) = stream.next().await {
+                    match next {
+                        Ok((decoded, _byte_size)) => events.extend(decoded),
+                        Err(error) => {
+                            // Error is logged by `crate::codecs`, no further
+                            // handling is needed here.
+                            if !error.can_continue() {
+                                break;
+                            }
+                        }
+                    }
+                }
+
+                let count = events.len();
+                events_received.emit(CountByteSize(count, events.estimated_json_encoded_size_of()));
+
+                let now = Utc::now();
+                for event in &mut events {
+                    if let Event::Log(log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            MqttSourceConfig::NAME,
+                            now,
+                        );
+                        log_namespace.insert_source_metadata(
+                            MqttSourceConfig::NAME,
+                            log,
+                            config.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                            path!("topic"),
+                            publish.topic.as_str(),
+                        );
+                    }
+                }
+
+                match &finalizer {
+                    Some(finalizer) => {
+                        let (batch, receiver) = BatchNotifier::new_with_receiver();
+                        let events = events
+                            .into_iter()
+                            .map(|event| event.with_batch_notifier(&batch));
+
+                        if out.send_batch(events).await.is_err() {
+                            emit!(StreamClosedError { count });
+                            return Err(());
+                        }
+                        finalizer.add(publish, receiver);
+                    }
+                    None => {
+                        if out.send_batch(events).await.is_err() {
+                            emit!(StreamClosedError { count });
+                            return Err(());
+                        }
+                    }
+                }
+            }
+            Ok(_) => {}
+            Err(error) => {
+                emit!(MqttConnectionError {
+                    error,
+                    stage: error_stage::RECEIVING,
+                });
+                // The event loop reconnects on the next poll.
+                tokio::select! {
+                    _ = &mut shutdown => break,
+                    _ = tokio::time::sleep(RECONNECT_DELAY) => {},
+                }
+            }
+        }
+    }
+
+    _ = client.try_disconnect();
+    Ok(())
+}
+
+async fn handle_ack(client: &AsyncClient, status: BatchStatus, publish: &Publish) {
+    match status {
+        // Rejected events can never be delivered, so they are acknowledged as well to keep the
+        // broker from redelivering them.
+        BatchStatus::Delivered | BatchStatus::Rejected => {
+            if let Err(error) = client.ack(publish).await {
+                emit!(MqttAckError { error });
+            }
+        }
+        // MQTT has no negative acknowledgement. Leaving the message unacknowledged makes the
+        // broker redeliver it when the session is resumed.
+        BatchStatus::Errored => {
+            warn!(
+                message = "Message was not acknowledged as its events failed to be delivered.",
+                topic = %publish.topic,
+                internal_log_rate_limit = true,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::OwnedTargetPath;
+    use vector_core::schema::Definition;
+    use vrl::value::kind::Collection;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MqttSourceConfig>();
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = MqttSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definition = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("mqtt", "topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, Some(expected_definition));
+    }
+
+    #[test]
+    fn output_schema_definition_legacy_namespace() {
+        let config = MqttSourceConfig::default();
+
+        let definition = config
+            .outputs(LogNamespace::Legacy)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition = Definition::new_with_default_metadata(
+            Kind::object(Collection::empty()),
+            [LogNamespace::Legacy],
+        )
+        .with_event_field(
+            &owned_value_path!("message"),
+            Kind::bytes(),
+            Some("message"),
+        )
+        .with_event_field(&owned_value_path!("timestamp"), Kind::timestamp(), None)
+        .with_event_field(&owned_value_path!("source_type"), Kind::bytes(), None)
+        .with_event_field(&owned_value_path!("topic"), Kind::bytes(), None);
+
+        assert_eq!(definition, Some(expected_definition));
+    }
+
+    #[test]
+    fn persistent_session_requires_client_id() {
+        let config: MqttSourceConfig = toml::from_str(
+            r#"
+            host = "127.0.0.1"
+            topics = ["devices/#"]
+            clean_session = false
+            "#,
+        )
+        .unwrap();
+
+        assert!(matches!(
+            config.connection.build_options(),
+            Err(MqttConfigError::MissingClientId)
+        ));
+    }
+}
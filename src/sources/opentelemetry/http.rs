@@ -3,8 +3,10 @@ use std::net::SocketAddr;
 use bytes::Bytes;
 use futures_util::FutureExt;
 use http::StatusCode;
-use opentelemetry_proto::proto::collector::logs::v1::{
-    ExportLogsServiceRequest, ExportLogsServiceResponse,
+use opentelemetry_proto::proto::collector::{
+    logs::v1::{ExportLogsServiceRequest, ExportLogsServiceResponse},
+    metrics::v1::{ExportMetricsServiceRequest, ExportMetricsServiceResponse},
+    trace::v1::{ExportTraceServiceRequest, ExportTraceServiceResponse},
 };
 use prost::Message;
 use snafu::Snafu;
@@ -66,8 +68,57 @@ pub(crate) fn build_warp_filter(
     bytes_received: Registered<BytesReceived>,
     events_received: Registered<EventsReceived>,
 ) -> BoxedFilter<(Response,)> {
+    let log_filters = build_ingest_filter::<ExportLogsServiceResponse, _>(
+        super::LOGS,
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        events_received.clone(),
+        move |body| decode_log_body(body, log_namespace),
+    );
+    let metrics_filters = build_ingest_filter::<ExportMetricsServiceResponse, _>(
+        super::METRICS,
+        acknowledgements,
+        out.clone(),
+        bytes_received.clone(),
+        events_received.clone(),
+        decode_metrics_body,
+    );
+    let trace_filters = build_ingest_filter::<ExportTraceServiceResponse, _>(
+        super::TRACES,
+        acknowledgements,
+        out,
+        bytes_received,
+        events_received,
+        decode_trace_body,
+    );
+
+    log_filters
+        .or(metrics_filters)
+        .unify()
+        .or(trace_filters)
+        .unify()
+        .boxed()
+}
+
+/// Builds the filter for the `/v1/<telemetry_type>` endpoint, sending the decoded events to the
+/// output of the same name.
+fn build_ingest_filter<Resp, F>(
+    telemetry_type: &'static str,
+    acknowledgements: bool,
+    out: SourceSender,
+    bytes_received: Registered<BytesReceived>,
+    events_received: Registered<EventsReceived>,
+    make_events: F,
+) -> BoxedFilter<(Response,)>
+where
+    Resp: Message + Default + Send + 'static,
+    F: Fn(Bytes) -> Result<Vec<Event>, ErrorMessage> + Clone + Send + Sync + 'static,
+{
     warp::post()
-        .and(warp::path!("v1" / "logs"))
+        .and(warp::path("v1"))
+        .and(warp::path(telemetry_type))
+        .and(warp::path::end())
         .and(warp::header::exact_ignore_case(
             "content-type",
             "application/x-protobuf",
@@ -75,40 +126,64 @@ pub(crate) fn build_warp_filter(
         .and(warp::header::optional::<String>("content-encoding"))
         .and(warp::body::bytes())
         .and_then(move |encoding_header: Option<String>, body: Bytes| {
-            let events = decode(&encoding_header, body).and_then(|body| {
-                bytes_received.emit(ByteSize(body.len()));
-                decode_body(body, log_namespace, &events_received)
-            });
-
-            handle_request(events, acknowledgements, out.clone(), super::LOGS)
+            let events = decode(&encoding_header, body)
+                .and_then(|body| {
+                    bytes_received.emit(ByteSize(body.len()));
+                    make_events(body)
+                })
+                .map(|events| {
+                    events_received.emit(CountByteSize(
+                        events.len(),
+                        events.estimated_json_encoded_size_of(),
+                    ));
+                    events
+                });
+
+            handle_request(
+                events,
+                acknowledgements,
+                out.clone(),
+                telemetry_type,
+                Resp::default(),
+            )
         })
         .boxed()
 }
 
-fn decode_body(
-    body: Bytes,
-    log_namespace: LogNamespace,
-    events_received: &Registered<EventsReceived>,
-) -> Result<Vec<Event>, ErrorMessage> {
-    let request = ExportLogsServiceRequest::decode(body).map_err(|error| {
+fn decode_request<T: Message + Default>(body: Bytes) -> Result<T, ErrorMessage> {
+    T::decode(body).map_err(|error| {
         ErrorMessage::new(
             StatusCode::BAD_REQUEST,
             format!("Could not decode request: {}", error),
         )
-    })?;
+    })
+}
 
-    let events: Vec<Event> = request
+fn decode_log_body(body: Bytes, log_namespace: LogNamespace) -> Result<Vec<Event>, ErrorMessage> {
+    let request: ExportLogsServiceRequest = decode_request(body)?;
+    Ok(request
         .resource_logs
         .into_iter()
         .flat_map(|v| v.into_event_iter(log_namespace))
-        .collect();
+        .collect())
+}
 
-    events_received.emit(CountByteSize(
-        events.len(),
-        events.estimated_json_encoded_size_of(),
-    ));
+fn decode_metrics_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
+    let request: ExportMetricsServiceRequest = decode_request(body)?;
+    Ok(request
+        .resource_metrics
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect())
+}
 
-    Ok(events)
+fn decode_trace_body(body: Bytes) -> Result<Vec<Event>, ErrorMessage> {
+    let request: ExportTraceServiceRequest = decode_request(body)?;
+    Ok(request
+        .resource_spans
+        .into_iter()
+        .flat_map(|v| v.into_event_iter())
+        .collect())
 }
 
 async fn handle_request(
@@ -116,6 +191,7 @@ async fn handle_request(
     acknowledgements: bool,
     mut out: SourceSender,
     output: &str,
+    response: impl Message,
 ) -> Result<Response, Rejection> {
     match events {
         Ok(mut events) => {
@@ -128,11 +204,9 @@ async fn handle_request(
             })?;
 
             match receiver {
-                None => Ok(protobuf(ExportLogsServiceResponse {}).into_response()),
+                None => Ok(protobuf(response).into_response()),
                 Some(receiver) => match receiver.await {
-                    BatchStatus::Delivered => {
-                        Ok(protobuf(ExportLogsServiceResponse {}).into_response())
-                    }
+                    BatchStatus::Delivered => Ok(protobuf(response).into_response()),
                     BatchStatus::Errored => Err(warp::reject::custom(Status {
                         code: 2, // UNKNOWN - OTLP doesn't require use of status.code, but we can't encode a None here
                         message: "Error delivering contents to sink".into(),
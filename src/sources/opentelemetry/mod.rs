@@ -17,7 +17,12 @@ use opentelemetry_proto::convert::{
     SEVERITY_NUMBER_KEY, SEVERITY_TEXT_KEY, SPAN_ID_KEY, TRACE_ID_KEY,
 };
 
-use opentelemetry_proto::proto::collector::logs::v1::logs_service_server::LogsServiceServer;
+use opentelemetry_proto::proto::collector::{
+    logs::v1::logs_service_server::LogsServiceServer,
+    metrics::v1::metrics_service_server::MetricsServiceServer,
+    trace::v1::trace_service_server::TraceServiceServer,
+};
+use tonic::{codec::CompressionEncoding, transport::server::Routes};
 use vector_common::internal_event::{BytesReceived, EventsReceived, Protocol};
 use vector_config::configurable_component;
 use vector_core::{
@@ -36,11 +41,13 @@ use crate::{
         SourceContext, SourceOutput,
     },
     serde::bool_or_struct,
-    sources::{util::grpc::run_grpc_server, Source},
+    sources::{util::grpc::run_grpc_server_with_routes, Source},
     tls::{MaybeTlsSettings, TlsEnableableConfig},
 };
 
 pub const LOGS: &str = "logs";
+pub const METRICS: &str = "metrics";
+pub const TRACES: &str = "traces";
 
 /// Configuration for the `opentelemetry` source.
 #[configurable_component(source("opentelemetry", "Receive OTLP data through gRPC or HTTP."))]
@@ -132,20 +139,33 @@ impl SourceConfig for OpentelemetryConfig {
         let log_namespace = cx.log_namespace(self.log_namespace);
 
         let grpc_tls_settings = MaybeTlsSettings::from_config(&self.grpc.tls, true)?;
-        let grpc_service = LogsServiceServer::new(Service {
+        let grpc_service = Service {
             pipeline: cx.out.clone(),
             acknowledgements,
             log_namespace,
             events_received: events_received.clone(),
-        })
-        .accept_compressed(tonic::codec::CompressionEncoding::Gzip)
+        };
         // Tonic added a default of 4MB in 0.9. This replaces the old behavior.
-        .max_decoding_message_size(usize::MAX);
+        let grpc_routes = Routes::new(
+            LogsServiceServer::new(grpc_service.clone())
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            MetricsServiceServer::new(grpc_service.clone())
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        )
+        .add_service(
+            TraceServiceServer::new(grpc_service)
+                .accept_compressed(CompressionEncoding::Gzip)
+                .max_decoding_message_size(usize::MAX),
+        );
 
-        let grpc_source = run_grpc_server(
+        let grpc_source = run_grpc_server_with_routes(
             self.grpc.address,
             grpc_tls_settings,
-            grpc_service,
+            grpc_routes,
             cx.shutdown.clone(),
         )
         .map_err(|error| {
@@ -258,7 +278,11 @@ impl SourceConfig for OpentelemetryConfig {
             }
         };
 
-        vec![SourceOutput::new_logs(DataType::Log, schema_definition).with_port(LOGS)]
+        vec![
+            SourceOutput::new_logs(DataType::Log, schema_definition).with_port(LOGS),
+            SourceOutput::new_metrics().with_port(METRICS),
+            SourceOutput::new_traces().with_port(TRACES),
+        ]
     }
 
     fn resources(&self) -> Vec<Resource> {
@@ -3,11 +3,23 @@ use futures::Stream;
 use futures_util::StreamExt;
 use lookup::path;
 use opentelemetry_proto::proto::{
-    collector::logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
-    common::v1::{any_value, AnyValue, KeyValue},
+    collector::{
+        logs::v1::{logs_service_client::LogsServiceClient, ExportLogsServiceRequest},
+        metrics::v1::{metrics_service_client::MetricsServiceClient, ExportMetricsServiceRequest},
+        trace::v1::{trace_service_client::TraceServiceClient, ExportTraceServiceRequest},
+    },
+    common::v1::{any_value, AnyValue, InstrumentationScope, KeyValue},
     logs::v1::{LogRecord, ResourceLogs, ScopeLogs},
+    metrics::v1::{
+        exponential_histogram_data_point::Buckets, metric::Data, number_data_point,
+        summary_data_point::ValueAtQuantile, AggregationTemporality, ExponentialHistogram,
+        ExponentialHistogramDataPoint, Gauge, Histogram, HistogramDataPoint, Metric as OtelMetric,
+        NumberDataPoint, ResourceMetrics, ScopeMetrics, Sum, Summary, SummaryDataPoint,
+    },
     resource::v1::Resource as OtelResource,
+    trace::v1::{span::SpanKind, status::StatusCode, ResourceSpans, ScopeSpans, Span, Status},
 };
+use prost::Message;
 use similar_asserts::assert_eq;
 use std::collections::BTreeMap;
 use std::sync::Arc;
@@ -18,8 +30,12 @@ use vrl::value;
 use crate::config::OutputId;
 use crate::{
     config::{SourceConfig, SourceContext},
-    event::{into_event_stream, Event, EventStatus, LogEvent, Value},
-    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS},
+    event::{
+        into_event_stream,
+        metric::{Bucket, Quantile},
+        Event, EventStatus, LogEvent, Metric, MetricKind, MetricTags, MetricValue, Value,
+    },
+    sources::opentelemetry::{GrpcConfig, HttpConfig, OpentelemetryConfig, LOGS, METRICS, TRACES},
     test_util::{
         self,
         components::{assert_source_compliance, SOURCE_TAGS},
@@ -281,6 +297,460 @@ async fn receive_grpc_logs_legacy_namespace() {
     .await;
 }
 
+fn string_attribute(key: &str, value: &str) -> KeyValue {
+    KeyValue {
+        key: key.into(),
+        value: Some(AnyValue {
+            value: Some(any_value::Value::StringValue(value.into())),
+        }),
+    }
+}
+
+fn test_source() -> OpentelemetryConfig {
+    OpentelemetryConfig {
+        grpc: GrpcConfig {
+            address: next_addr(),
+            tls: Default::default(),
+        },
+        http: HttpConfig {
+            address: next_addr(),
+            tls: Default::default(),
+        },
+        acknowledgements: Default::default(),
+        log_namespace: Default::default(),
+    }
+}
+
+fn number_point(value: number_data_point::Value) -> NumberDataPoint {
+    NumberDataPoint {
+        attributes: vec![string_attribute("point_key", "point_val")],
+        start_time_unix_nano: 0,
+        time_unix_nano: 1,
+        value: Some(value),
+        exemplars: vec![],
+        flags: 0,
+    }
+}
+
+fn otel_metric(name: &str, data: Data) -> OtelMetric {
+    OtelMetric {
+        name: name.into(),
+        description: String::new(),
+        unit: String::new(),
+        data: Some(data),
+    }
+}
+
+fn metrics_request() -> ExportMetricsServiceRequest {
+    ExportMetricsServiceRequest {
+        resource_metrics: vec![ResourceMetrics {
+            resource: Some(OtelResource {
+                attributes: vec![string_attribute("res_key", "res_val")],
+                dropped_attributes_count: 0,
+            }),
+            scope_metrics: vec![ScopeMetrics {
+                scope: Some(InstrumentationScope {
+                    name: "scope_name".into(),
+                    version: "1.0".into(),
+                    attributes: vec![],
+                    dropped_attributes_count: 0,
+                }),
+                metrics: vec![
+                    otel_metric(
+                        "gauge",
+                        Data::Gauge(Gauge {
+                            data_points: vec![number_point(number_data_point::Value::AsDouble(
+                                1.5,
+                            ))],
+                        }),
+                    ),
+                    otel_metric(
+                        "counter",
+                        Data::Sum(Sum {
+                            data_points: vec![number_point(number_data_point::Value::AsInt(3))],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                            is_monotonic: true,
+                        }),
+                    ),
+                    otel_metric(
+                        "up_down_counter",
+                        Data::Sum(Sum {
+                            data_points: vec![number_point(number_data_point::Value::AsInt(-2))],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                            is_monotonic: false,
+                        }),
+                    ),
+                    otel_metric(
+                        "histogram",
+                        Data::Histogram(Histogram {
+                            data_points: vec![HistogramDataPoint {
+                                attributes: vec![string_attribute("point_key", "point_val")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 6,
+                                sum: Some(12.0),
+                                bucket_counts: vec![1, 2, 3],
+                                explicit_bounds: vec![1.0, 5.0],
+                                exemplars: vec![],
+                                flags: 0,
+                                min: None,
+                                max: None,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Cumulative as i32,
+                        }),
+                    ),
+                    otel_metric(
+                        "exponential_histogram",
+                        Data::ExponentialHistogram(ExponentialHistogram {
+                            data_points: vec![ExponentialHistogramDataPoint {
+                                attributes: vec![string_attribute("point_key", "point_val")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 6,
+                                sum: Some(5.0),
+                                scale: 0,
+                                zero_count: 1,
+                                positive: Some(Buckets {
+                                    offset: 0,
+                                    bucket_counts: vec![2, 1],
+                                }),
+                                negative: Some(Buckets {
+                                    offset: 1,
+                                    bucket_counts: vec![2],
+                                }),
+                                flags: 0,
+                                exemplars: vec![],
+                                min: None,
+                                max: None,
+                            }],
+                            aggregation_temporality: AggregationTemporality::Delta as i32,
+                        }),
+                    ),
+                    otel_metric(
+                        "summary",
+                        Data::Summary(Summary {
+                            data_points: vec![SummaryDataPoint {
+                                attributes: vec![string_attribute("point_key", "point_val")],
+                                start_time_unix_nano: 0,
+                                time_unix_nano: 1,
+                                count: 4,
+                                sum: 10.0,
+                                quantile_values: vec![ValueAtQuantile {
+                                    quantile: 0.5,
+                                    value: 2.0,
+                                }],
+                                flags: 0,
+                            }],
+                        }),
+                    ),
+                ],
+                schema_url: "v1".into(),
+            }],
+            schema_url: "v1".into(),
+        }],
+    }
+}
+
+fn expected_metrics() -> Vec<Metric> {
+    let tags = MetricTags::from_iter([
+        ("point_key".to_owned(), "point_val".to_owned()),
+        ("resource.res_key".to_owned(), "res_val".to_owned()),
+        ("scope.name".to_owned(), "scope_name".to_owned()),
+        ("scope.version".to_owned(), "1.0".to_owned()),
+    ]);
+    let metric = |name, kind, value| {
+        Metric::new(name, kind, value)
+            .with_tags(Some(tags.clone()))
+            .with_timestamp(Some(Utc.timestamp_nanos(1)))
+    };
+
+    vec![
+        metric(
+            "gauge",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: 1.5 },
+        ),
+        metric(
+            "counter",
+            MetricKind::Incremental,
+            MetricValue::Counter { value: 3.0 },
+        ),
+        metric(
+            "up_down_counter",
+            MetricKind::Absolute,
+            MetricValue::Gauge { value: -2.0 },
+        ),
+        metric(
+            "histogram",
+            MetricKind::Absolute,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 5.0,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: f64::INFINITY,
+                        count: 3,
+                    },
+                ],
+                count: 6,
+                sum: 12.0,
+            },
+        ),
+        metric(
+            "exponential_histogram",
+            MetricKind::Incremental,
+            MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: -2.0,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 0.0,
+                        count: 1,
+                    },
+                    Bucket {
+                        upper_limit: 2.0,
+                        count: 2,
+                    },
+                    Bucket {
+                        upper_limit: 4.0,
+                        count: 1,
+                    },
+                ],
+                count: 6,
+                sum: 5.0,
+            },
+        ),
+        metric(
+            "summary",
+            MetricKind::Absolute,
+            MetricValue::AggregatedSummary {
+                quantiles: vec![Quantile {
+                    quantile: 0.5,
+                    value: 2.0,
+                }],
+                count: 4,
+                sum: 10.0,
+            },
+        ),
+    ]
+}
+
+fn trace_request() -> ExportTraceServiceRequest {
+    ExportTraceServiceRequest {
+        resource_spans: vec![ResourceSpans {
+            resource: Some(OtelResource {
+                attributes: vec![string_attribute("res_key", "res_val")],
+                dropped_attributes_count: 0,
+            }),
+            scope_spans: vec![ScopeSpans {
+                scope: Some(InstrumentationScope {
+                    name: "scope_name".into(),
+                    version: "1.0".into(),
+                    attributes: vec![],
+                    dropped_attributes_count: 0,
+                }),
+                spans: vec![Span {
+                    trace_id: str_into_hex_bytes("4ac52aadf321c2e531db005df08792f5"),
+                    span_id: str_into_hex_bytes("0b9e4bda2a55530d"),
+                    trace_state: "".into(),
+                    parent_span_id: str_into_hex_bytes("1a2b3c4d5e6f7a8b"),
+                    name: "GET /users".into(),
+                    kind: SpanKind::Server as i32,
+                    start_time_unix_nano: 1,
+                    end_time_unix_nano: 2,
+                    attributes: vec![string_attribute("attr_key", "attr_val")],
+                    dropped_attributes_count: 0,
+                    events: vec![],
+                    dropped_events_count: 0,
+                    links: vec![],
+                    dropped_links_count: 0,
+                    status: Some(Status {
+                        message: "failed".into(),
+                        code: StatusCode::Error as i32,
+                    }),
+                }],
+                schema_url: "v1".into(),
+            }],
+            schema_url: "v1".into(),
+        }],
+    }
+}
+
+fn assert_trace(event: &Event) {
+    let trace = event.as_trace();
+    assert_eq!(
+        trace.get("trace_id").unwrap(),
+        &value!("4ac52aadf321c2e531db005df08792f5")
+    );
+    assert_eq!(trace.get("span_id").unwrap(), &value!("0b9e4bda2a55530d"));
+    assert_eq!(
+        trace.get("parent_span_id").unwrap(),
+        &value!("1a2b3c4d5e6f7a8b")
+    );
+    assert_eq!(trace.get("name").unwrap(), &value!("GET /users"));
+    assert_eq!(trace.get("kind").unwrap(), &value!(2));
+    assert_eq!(
+        trace.get("start_time_unix_nano").unwrap(),
+        &value!(Utc.timestamp_nanos(1))
+    );
+    assert_eq!(
+        trace.get("end_time_unix_nano").unwrap(),
+        &value!(Utc.timestamp_nanos(2))
+    );
+    assert_eq!(
+        trace.get("attributes").unwrap(),
+        &value!({attr_key: "attr_val"})
+    );
+    assert_eq!(
+        trace.get("status").unwrap(),
+        &value!({message: "failed", code: 2})
+    );
+    assert_eq!(
+        trace.get("resources").unwrap(),
+        &value!({res_key: "res_val"})
+    );
+    assert_eq!(
+        trace.get("scope").unwrap(),
+        &value!({name: "scope_name", version: "1.0", dropped_attributes_count: 0})
+    );
+    assert!(trace.get("ingest_timestamp").unwrap().is_timestamp());
+}
+
+#[tokio::test]
+async fn receive_grpc_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let source = test_source();
+        let grpc_addr = source.grpc.address;
+
+        let (sender, metrics_output) = new_source_with_output(EventStatus::Delivered, METRICS);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = MetricsServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        client
+            .export(Request::new(metrics_request()))
+            .await
+            .unwrap();
+
+        let output = test_util::collect_ready(metrics_output).await;
+        let metrics = output
+            .into_iter()
+            .map(|event| {
+                let (series, data, _) = event.into_metric().into_parts();
+                (series, data)
+            })
+            .collect::<Vec<_>>();
+        let expected = expected_metrics()
+            .into_iter()
+            .map(|metric| {
+                let (series, data, _) = metric.into_parts();
+                (series, data)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(metrics, expected);
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_grpc_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let source = test_source();
+        let grpc_addr = source.grpc.address;
+
+        let (sender, traces_output) = new_source_with_output(EventStatus::Delivered, TRACES);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(grpc_addr).await;
+
+        let mut client = TraceServiceClient::connect(format!("http://{}", grpc_addr))
+            .await
+            .unwrap();
+        client.export(Request::new(trace_request())).await.unwrap();
+
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        assert_trace(&output.pop().unwrap());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_traces() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let source = test_source();
+        let http_addr = source.http.address;
+
+        let (sender, traces_output) = new_source_with_output(EventStatus::Delivered, TRACES);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(http_addr).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/traces", http_addr))
+            .header("Content-Type", "application/x-protobuf")
+            .body(trace_request().encode_to_vec())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let mut output = test_util::collect_ready(traces_output).await;
+        assert_eq!(output.len(), 1);
+        assert_trace(&output.pop().unwrap());
+    })
+    .await;
+}
+
+#[tokio::test]
+async fn receive_http_metrics() {
+    assert_source_compliance(&SOURCE_TAGS, async {
+        let source = test_source();
+        let http_addr = source.http.address;
+
+        let (sender, metrics_output) = new_source_with_output(EventStatus::Delivered, METRICS);
+        let server = source
+            .build(SourceContext::new_test(sender, None))
+            .await
+            .unwrap();
+        tokio::spawn(server);
+        test_util::wait_for_tcp(http_addr).await;
+
+        let response = reqwest::Client::new()
+            .post(format!("http://{}/v1/metrics", http_addr))
+            .header("Content-Type", "application/x-protobuf")
+            .body(metrics_request().encode_to_vec())
+            .send()
+            .await
+            .unwrap();
+        assert!(response.status().is_success());
+
+        let output = test_util::collect_ready(metrics_output).await;
+        assert_eq!(output.len(), expected_metrics().len());
+    })
+    .await;
+}
+
 pub(super) fn new_source(
     status: EventStatus,
 ) -> (
@@ -295,6 +765,17 @@ pub(super) fn new_source(
     (sender, logs_output, recv)
 }
 
+fn new_source_with_output(
+    status: EventStatus,
+    output: &str,
+) -> (SourceSender, impl Stream<Item = Event>) {
+    let (mut sender, _) = SourceSender::new_test_finalize(status);
+    let output = sender
+        .add_outputs(status, output.to_string())
+        .flat_map(into_event_stream);
+    (sender, output)
+}
+
 fn str_into_hex_bytes(s: &str) -> Vec<u8> {
     // unwrap is okay in test
     hex::decode(s).unwrap()
@@ -0,0 +1,644 @@
+use std::collections::BTreeMap;
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::StreamExt;
+use lookup::{lookup_v2::OptionalValuePath, owned_value_path, path};
+use pulsar::{consumer::Message, message::proto::MessageIdData, Consumer, SubType, TokioExecutor};
+use regex::Regex;
+use snafu::{ResultExt, Snafu};
+use tokio_util::codec::FramedRead;
+use vector_common::{
+    finalizer::UnorderedFinalizer,
+    internal_event::{
+        ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+    },
+};
+use vector_config::configurable_component;
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    event::{BatchNotifier, BatchStatus},
+    EstimatedJsonEncodedSizeOf,
+};
+use vrl::value::{kind::Collection, Kind, Value};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    internal_events::{PulsarAckError, PulsarReceiveError, StreamClosedError},
+    pulsar::{create_pulsar_client, PulsarAuthConfig},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    SourceAcknowledgementsConfig, SourceSender,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Exactly one of `topics` and `topics_pattern` must be set."))]
+    InvalidTopics,
+    #[snafu(display("Invalid topics pattern: {}", source))]
+    TopicsPattern { source: regex::Error },
+    #[snafu(display("Pulsar Connect Error: {}", source))]
+    Connect { source: pulsar::Error },
+    #[snafu(display("Pulsar Subscribe Error: {}", source))]
+    Subscribe { source: pulsar::Error },
+}
+
+/// The type of a Pulsar subscription, which determines how messages are distributed among the
+/// consumers sharing it.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum PulsarSubscriptionType {
+    /// Only a single consumer can be attached to the subscription.
+    #[default]
+    Exclusive,
+
+    /// Messages are distributed among all consumers attached to the subscription.
+    Shared,
+
+    /// Messages are delivered to a single active consumer, and fail over to another one when the
+    /// active consumer disconnects.
+    Failover,
+
+    /// Messages are distributed among all consumers attached to the subscription, while messages
+    /// with the same key are always delivered to the same consumer.
+    KeyShared,
+}
+
+impl From<PulsarSubscriptionType> for SubType {
+    fn from(subscription_type: PulsarSubscriptionType) -> Self {
+        match subscription_type {
+            PulsarSubscriptionType::Exclusive => SubType::Exclusive,
+            PulsarSubscriptionType::Shared => SubType::Shared,
+            PulsarSubscriptionType::Failover => SubType::Failover,
+            PulsarSubscriptionType::KeyShared => SubType::KeyShared,
+        }
+    }
+}
+
+/// Configuration for the `pulsar` source.
+#[configurable_component(source(
+    "pulsar",
+    "Collect observability events from Apache Pulsar topics."
+))]
+#[derive(Clone, Debug, Derivative)]
+#[derivative(Default)]
+#[serde(deny_unknown_fields)]
+pub struct PulsarSourceConfig {
+    /// The endpoint to which the Pulsar client should connect to.
+    ///
+    /// The endpoint should specify the pulsar protocol and port.
+    #[configurable(metadata(docs::examples = "pulsar://127.0.0.1:6650"))]
+    endpoint: String,
+
+    /// The Pulsar topics to read messages from.
+    ///
+    /// Either `topics` or `topics_pattern` must be set.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "persistent://public/default/my-topic"))]
+    topics: Vec<String>,
+
+    /// A regular expression that selects the topics to read messages from.
+    ///
+    /// The pattern is matched against the fully qualified names of the topics in `namespace`, such
+    /// as `persistent://public/default/my-topic`. Topics created after the source started are
+    /// picked up as well.
+    ///
+    /// Either `topics` or `topics_pattern` must be set.
+    #[configurable(metadata(docs::examples = "persistent://public/default/logs-.*"))]
+    topics_pattern: Option<String>,
+
+    /// The namespace in which topics are looked up when using `topics_pattern`.
+    #[serde(default = "default_namespace")]
+    #[derivative(Default(value = "default_namespace()"))]
+    #[configurable(metadata(docs::examples = "public/default"))]
+    namespace: String,
+
+    /// The name of the subscription to consume messages with.
+    ///
+    /// Acknowledged messages are tracked per subscription, so consumers sharing a subscription
+    /// don't read the same messages twice.
+    #[serde(default = "default_subscription_name")]
+    #[derivative(Default(value = "default_subscription_name()"))]
+    #[configurable(metadata(docs::examples = "vector"))]
+    subscription_name: String,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    subscription_type: PulsarSubscriptionType,
+
+    /// The name of the consumer. If not specified, the default name assigned by Pulsar is used.
+    #[configurable(metadata(docs::examples = "consumer-name"))]
+    consumer_name: Option<String>,
+
+    /// The maximum number of messages that the broker sends to the consumer ahead of processing.
+    ///
+    /// If not specified, the default of the Pulsar client is used.
+    #[configurable(metadata(docs::type_unit = "messages"))]
+    #[configurable(metadata(docs::examples = 1000))]
+    batch_size: Option<u32>,
+
+    /// Overrides the name of the log field used to add the message key to each event.
+    ///
+    /// The value is the partition key of the Pulsar message.
+    ///
+    /// By default, `"message_key"` is used.
+    #[serde(default = "default_key_field")]
+    #[derivative(Default(value = "default_key_field()"))]
+    #[configurable(metadata(docs::examples = "message_key"))]
+    key_field: OptionalValuePath,
+
+    /// Overrides the name of the log field used to add the topic to each event.
+    ///
+    /// The value is the fully qualified name of the topic the message was read from.
+    ///
+    /// By default, `"topic"` is used.
+    #[serde(default = "default_topic_key")]
+    #[derivative(Default(value = "default_topic_key()"))]
+    #[configurable(metadata(docs::examples = "topic"))]
+    topic_key: OptionalValuePath,
+
+    /// Overrides the name of the log field used to add the properties to each event.
+    ///
+    /// The value is the properties of the Pulsar message.
+    ///
+    /// By default, `"properties"` is used.
+    #[serde(default = "default_properties_key")]
+    #[derivative(Default(value = "default_properties_key()"))]
+    #[configurable(metadata(docs::examples = "properties"))]
+    properties_key: OptionalValuePath,
+
+    #[configurable(derived)]
+    auth: Option<PulsarAuthConfig>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    #[derivative(Default(value = "default_framing_message_based()"))]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    #[derivative(Default(value = "default_decoding()"))]
+    decoding: DeserializerConfig,
+
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+fn default_namespace() -> String {
+    "public/default".to_owned()
+}
+
+fn default_subscription_name() -> String {
+    "vector".to_owned()
+}
+
+fn default_key_field() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("message_key"))
+}
+
+fn default_topic_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("topic"))
+}
+
+fn default_properties_key() -> OptionalValuePath {
+    OptionalValuePath::from(owned_value_path!("properties"))
+}
+
+impl GenerateConfig for PulsarSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"
+            endpoint = "pulsar://127.0.0.1:6650"
+            topics = ["persistent://public/default/vector"]
+            subscription_name = "vector""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "pulsar")]
+impl SourceConfig for PulsarSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let acknowledgements = cx.do_acknowledgements(self.acknowledgements);
+        let consumer = self.create_consumer().await?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        Ok(Box::pin(pulsar_source(
+            self.clone(),
+            consumer,
+            decoder,
+            log_namespace,
+            acknowledgements,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata()
+            .with_source_metadata(
+                Self::NAME,
+                self.key_field.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("message_key"),
+                Kind::bytes().or_undefined(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                self.topic_key.path.clone().map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("topic"),
+                Kind::bytes(),
+                None,
+            )
+            .with_source_metadata(
+                Self::NAME,
+                self.properties_key
+                    .path
+                    .clone()
+                    .map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("properties"),
+                Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                None,
+            );
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        true
+    }
+}
+
+impl PulsarSourceConfig {
+    async fn create_consumer(&self) -> Result<Consumer<Vec<u8>, TokioExecutor>, BuildError> {
+        let topics_pattern = match (self.topics.is_empty(), &self.topics_pattern) {
+            (false, None) => None,
+            (true, Some(pattern)) => Some(Regex::new(pattern).context(TopicsPatternSnafu)?),
+            _ => return Err(BuildError::InvalidTopics),
+        };
+
+        let client = create_pulsar_client(&self.endpoint, self.auth.as_ref())
+            .await
+            .context(ConnectSnafu)?;
+
+        let mut builder = client
+            .consumer()
+            .with_subscription(&self.subscription_name)
+            .with_subscription_type(self.subscription_type.into());
+
+        builder = match topics_pattern {
+            None => builder.with_topics(&self.topics),
+            Some(pattern) => builder
+                .with_topic_regex(pattern)
+                .with_lookup_namespace(&self.namespace),
+        };
+        if let Some(consumer_name) = &self.consumer_name {
+            builder = builder.with_consumer_name(consumer_name);
+        }
+        if let Some(batch_size) = self.batch_size {
+            builder = builder.with_batch_size(batch_size);
+        }
+
+        builder.build().await.context(SubscribeSnafu)
+    }
+}
+
+#[derive(Debug)]
+struct FinalizerEntry {
+    topic: String,
+    message_id: MessageIdData,
+}
+
+impl From<&Message<Vec<u8>>> for FinalizerEntry {
+    fn from(msg: &Message<Vec<u8>>) -> Self {
+        Self {
+            topic: msg.topic.clone(),
+            message_id: msg.message_id().clone(),
+        }
+    }
+}
+
+async fn pulsar_source(
+    config: PulsarSourceConfig,
+    mut consumer: Consumer<Vec<u8>, TokioExecutor>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    acknowledgements: bool,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    let (finalizer, mut ack_stream) =
+        UnorderedFinalizer::<FinalizerEntry>::maybe_new(acknowledgements, Some(shutdown.clone()));
+
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol::TCP));
+
+    loop {
+        tokio::select! {
+            biased;
+            _ = &mut shutdown => break,
+            entry = ack_stream.next() => if let Some((status, entry)) = entry {
+                handle_ack(&mut consumer, status, entry).await;
+            },
+            message = consumer.next() => match message {
+                None => break,
+                Some(Err(error)) => emit!(PulsarReceiveError { error: error.into() }),
+                Some(Ok(msg)) => {
+                    bytes_received.emit(ByteSize(msg.payload.data.len()));
+
+                    let events = parse_message(&config, &msg, decoder.clone(), log_namespace).await;
+                    let count = events.len();
+                    events_received.emit(CountByteSize(count, events.estimated_json_encoded_size_of()));
+
+                    match &finalizer {
+                        Some(finalizer) => {
+                            let (batch, receiver) = BatchNotifier::new_with_receiver();
+                            let events = events
+                                .into_iter()
+                                .map(|event| event.with_batch_notifier(&batch));
+
+                            if out.send_batch(events).await.is_err() {
+                                emit!(StreamClosedError { count });
+                                return Err(());
+                            }
+                            finalizer.add(FinalizerEntry::from(&msg), receiver);
+                        }
+                        None => {
+                            if out.send_batch(events).await.is_err() {
+                                emit!(StreamClosedError { count });
+                                return Err(());
+                            }
+                            handle_ack(&mut consumer, BatchStatus::Delivered, FinalizerEntry::from(&msg)).await;
+                        }
+                    }
+                }
+            },
+        }
+    }
+
+    Ok(())
+}
+
+async fn parse_message(
+    config: &PulsarSourceConfig,
+    msg: &Message<Vec<u8>>,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+) -> Vec<Event> {
+    let metadata = &msg.payload.metadata;
+    let key = metadata
+        .partition_key
+        .as_ref()
+        .map(|key| Value::from(Bytes::from(key.clone())));
+    let properties = metadata
+        .properties
+        .iter()
+        .map(|property| {
+            (
+                property.key.clone(),
+                Value::from(Bytes::from(property.value.clone())),
+            )
+        })
+        .collect::<BTreeMap<_, _>>();
+
+    let now = Utc::now();
+    let mut events = Vec::new();
+    let mut stream = FramedRead::new(msg.payload.data.as_ref(), decoder);
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((decoded, _byte_size)) => {
+                events.extend(decoded.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            PulsarSourceConfig::NAME,
+                            now,
+                        );
+
+                        if let Some(key) = &key {
+                            log_namespace.insert_source_metadata(
+                                PulsarSourceConfig::NAME,
+                                log,
+                                config.key_field.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                                path!("message_key"),
+                                key.clone(),
+                            );
+                        }
+
+                        log_namespace.insert_source_metadata(
+                            PulsarSourceConfig::NAME,
+                            log,
+                            config.topic_key.path.as_ref().map(LegacyKey::InsertIfEmpty),
+                            path!("topic"),
+                            msg.topic.as_str(),
+                        );
+
+                        log_namespace.insert_source_metadata(
+                            PulsarSourceConfig::NAME,
+                            log,
+                            config
+                                .properties_key
+                                .path
+                                .as_ref()
+                                .map(LegacyKey::InsertIfEmpty),
+                            path!("properties"),
+                            properties.clone(),
+                        );
+                    }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+    events
+}
+
+async fn handle_ack(
+    consumer: &mut Consumer<Vec<u8>, TokioExecutor>,
+    status: BatchStatus,
+    entry: FinalizerEntry,
+) {
+    let result = match status {
+        BatchStatus::Delivered => consumer.ack_with_id(&entry.topic, entry.message_id).await,
+        // Negatively acknowledged messages are redelivered by the broker after a delay.
+        BatchStatus::Errored | BatchStatus::Rejected => {
+            consumer.nack_with_id(&entry.topic, entry.message_id).await
+        }
+    };
+
+    if let Err(error) = result {
+        emit!(PulsarAckError {
+            error: error.into()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use lookup::OwnedTargetPath;
+    use vector_core::schema::Definition;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<PulsarSourceConfig>();
+    }
+
+    #[test]
+    fn output_schema_definition_vector_namespace() {
+        let config = PulsarSourceConfig {
+            log_namespace: Some(true),
+            ..Default::default()
+        };
+
+        let definitions = config
+            .outputs(LogNamespace::Vector)
+            .remove(0)
+            .schema_definition(true);
+
+        let expected_definition =
+            Definition::new_with_default_metadata(Kind::bytes(), [LogNamespace::Vector])
+                .with_meaning(OwnedTargetPath::event_root(), "message")
+                .with_metadata_field(
+                    &owned_value_path!("vector", "source_type"),
+                    Kind::bytes(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("vector", "ingest_timestamp"),
+                    Kind::timestamp(),
+                    None,
+                )
+                .with_metadata_field(
+                    &owned_value_path!("pulsar", "message_key"),
+                    Kind::bytes().or_undefined(),
+                    None,
+                )
+                .with_metadata_field(&owned_value_path!("pulsar", "topic"), Kind::bytes(), None)
+                .with_metadata_field(
+                    &owned_value_path!("pulsar", "properties"),
+                    Kind::object(Collection::empty().with_unknown(Kind::bytes())),
+                    None,
+                );
+
+        assert_eq!(definitions, Some(expected_definition));
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_topics() {
+        let config = PulsarSourceConfig {
+            topics: vec!["vector".to_owned()],
+            topics_pattern: Some("vector-.*".to_owned()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            config.create_consumer().await,
+            Err(BuildError::InvalidTopics)
+        ));
+    }
+}
+
+#[cfg(all(test, feature = "pulsar-integration-tests"))]
+mod integration_tests {
+    use pulsar::producer::Message as ProducerMessage;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            random_string, trace_init,
+        },
+    };
+
+    fn pulsar_address() -> String {
+        std::env::var("PULSAR_ADDRESS").unwrap_or_else(|_| "pulsar://127.0.0.1:6650".into())
+    }
+
+    #[tokio::test]
+    async fn consumes_messages_with_metadata() {
+        trace_init();
+
+        let topic = format!("persistent://public/default/test-{}", random_string(10));
+        let config = PulsarSourceConfig {
+            endpoint: pulsar_address(),
+            topics: vec![topic.clone()],
+            subscription_type: PulsarSubscriptionType::Shared,
+            ..Default::default()
+        };
+
+        let producer_topic = topic.clone();
+        let events = assert_source_compliance(&SOURCE_TAGS, async move {
+            let (tx, rx) = SourceSender::new_test();
+            let cx = SourceContext::new_test(tx, None);
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(source);
+
+            let client = create_pulsar_client(&pulsar_address(), None).await.unwrap();
+            let mut producer = client
+                .producer()
+                .with_topic(&producer_topic)
+                .build()
+                .await
+                .unwrap();
+            for i in 0..10 {
+                producer
+                    .send(ProducerMessage {
+                        payload: format!("message {}", i).into_bytes(),
+                        partition_key: Some("key".to_owned()),
+                        properties: [("property".to_owned(), i.to_string())].into(),
+                        ..Default::default()
+                    })
+                    .await
+                    .unwrap()
+                    .await
+                    .unwrap();
+            }
+
+            collect_n(rx, 10).await
+        })
+        .await;
+
+        for (i, event) in events.iter().enumerate() {
+            let log = event.as_log();
+            assert_eq!(
+                log[log_schema().message_key().unwrap().to_string()],
+                format!("message {}", i).into()
+            );
+            assert_eq!(log["message_key"], "key".into());
+            assert_eq!(log["topic"], topic.clone().into());
+            assert_eq!(log["properties.property"], i.to_string().into());
+        }
+    }
+}
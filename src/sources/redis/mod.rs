@@ -23,11 +23,13 @@ use crate::{
     config::{log_schema, GenerateConfig, SourceConfig, SourceContext, SourceOutput},
     event::Event,
     internal_events::{EventsReceived, StreamClosedError},
-    serde::{default_decoding, default_framing_message_based},
+    serde::{bool_or_struct, default_decoding, default_framing_message_based},
+    SourceAcknowledgementsConfig,
 };
 
 mod channel;
 mod list;
+mod stream;
 
 #[derive(Debug, Snafu)]
 enum BuildError {
@@ -49,6 +51,12 @@ pub enum DataTypeConfig {
     ///
     /// This is based on Redis' Pub/Sub capabilities.
     Channel,
+
+    /// The `stream` data type.
+    ///
+    /// Entries are read as part of a consumer group, and acknowledged once their events have been
+    /// processed.
+    Stream,
 }
 
 /// Options for the Redis `list` data type.
@@ -60,6 +68,65 @@ pub struct ListOption {
     method: Method,
 }
 
+/// Options for the Redis `stream` data type.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StreamOption {
+    /// The consumer group to read the stream as.
+    ///
+    /// The group, and the stream itself, are created if they don't exist yet. A newly created group
+    /// only receives entries added to the stream after its creation.
+    #[configurable(metadata(docs::examples = "vector"))]
+    group: String,
+
+    /// The name of this consumer within the consumer group.
+    ///
+    /// Each instance reading from the same group must use a distinct name. By default, the hostname
+    /// is used.
+    #[configurable(metadata(docs::examples = "vector-01"))]
+    consumer: Option<String>,
+
+    /// The field of each stream entry that holds the message to decode.
+    #[serde(default = "default_stream_field")]
+    #[configurable(metadata(docs::examples = "message"))]
+    field: String,
+
+    /// The maximum number of entries to read from the stream at once.
+    #[serde(default = "default_stream_batch_size")]
+    #[configurable(metadata(docs::type_unit = "entries"))]
+    batch_size: usize,
+
+    /// The minimum amount of time, in seconds, that an entry must have been pending before it is
+    /// claimed by this consumer.
+    ///
+    /// Entries are pending when they were read by a consumer of the group but not acknowledged, for
+    /// example because the consumer stopped or their events failed to be delivered. Claiming them
+    /// makes sure they are eventually processed. Set to `0` to never claim pending entries.
+    #[serde(default = "default_claim_min_idle_secs")]
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    #[configurable(metadata(docs::human_name = "Claim Minimum Idle Time"))]
+    claim_min_idle_secs: u64,
+
+    /// Sets the name of the log field to use to add the entry ID to each event.
+    ///
+    /// By default, this is not set and the field is not automatically added.
+    #[configurable(metadata(docs::examples = "redis_id"))]
+    id_key: Option<OptionalValuePath>,
+}
+
+const fn default_stream_batch_size() -> usize {
+    100
+}
+
+const fn default_claim_min_idle_secs() -> u64 {
+    60
+}
+
+fn default_stream_field() -> String {
+    "message".to_owned()
+}
+
 /// Method for getting events from the `list` data type.
 #[configurable_component]
 #[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
@@ -98,13 +165,16 @@ impl From<&redis::ConnectionInfo> for ConnectionInfo {
 #[derive(Clone, Debug, Derivative)]
 #[serde(deny_unknown_fields)]
 pub struct RedisSourceConfig {
-    /// The Redis data type (`list` or `channel`) to use.
+    /// The Redis data type (`list`, `channel`, or `stream`) to use.
     #[serde(default)]
     data_type: DataTypeConfig,
 
     #[configurable(derived)]
     list: Option<ListOption>,
 
+    #[configurable(derived)]
+    stream: Option<StreamOption>,
+
     /// The Redis URL to connect to.
     ///
     /// The URL must take the form of `protocol://server:port/db` where the `protocol` can either be `redis` or `rediss` for connections secured using TLS.
@@ -133,6 +203,10 @@ pub struct RedisSourceConfig {
     #[derivative(Default(value = "default_decoding()"))]
     decoding: DeserializerConfig,
 
+    #[configurable(derived)]
+    #[serde(default, deserialize_with = "bool_or_struct")]
+    acknowledgements: SourceAcknowledgementsConfig,
+
     /// The namespace to use for logs. This overrides the global setting.
     #[configurable(metadata(docs::hidden))]
     #[serde(default)]
@@ -193,6 +267,14 @@ impl SourceConfig for RedisSourceConfig {
                 handler.watch(method).await
             }
             DataTypeConfig::Channel => handler.subscribe(connection_info).await,
+            DataTypeConfig::Stream => {
+                let options = self
+                    .stream
+                    .clone()
+                    .ok_or("`stream.group` must be set when `data_type` is `stream`.")?;
+                let acknowledgements = handler.cx.do_acknowledgements(self.acknowledgements);
+                handler.read_stream(options, acknowledgements).await
+            }
         }
     }
 
@@ -205,7 +287,7 @@ impl SourceConfig for RedisSourceConfig {
             .and_then(|k| k.path)
             .map(LegacyKey::InsertIfEmpty);
 
-        let schema_definition = self
+        let mut schema_definition = self
             .decoding
             .schema_definition(log_namespace)
             .with_source_metadata(
@@ -217,6 +299,20 @@ impl SourceConfig for RedisSourceConfig {
             )
             .with_standard_vector_source_metadata();
 
+        if let (DataTypeConfig::Stream, Some(stream)) = (self.data_type, &self.stream) {
+            schema_definition = schema_definition.with_source_metadata(
+                Self::NAME,
+                stream
+                    .id_key
+                    .clone()
+                    .and_then(|k| k.path)
+                    .map(LegacyKey::InsertIfEmpty),
+                &owned_value_path!("id"),
+                Kind::bytes(),
+                None,
+            );
+        }
+
         vec![SourceOutput::new_logs(
             self.decoding.output_type(),
             schema_definition,
@@ -224,7 +320,7 @@ impl SourceConfig for RedisSourceConfig {
     }
 
     fn can_acknowledge(&self) -> bool {
-        false
+        matches!(self.data_type, DataTypeConfig::Stream)
     }
 }
 
@@ -241,19 +337,26 @@ struct InputHandler {
 
 impl InputHandler {
     async fn handle_line(&mut self, line: String) -> Result<(), ()> {
+        let events = self.decode(line.as_bytes()).await;
+        self.send(events).await
+    }
+
+    /// Decodes a message read from Redis into events, annotated with the source metadata.
+    async fn decode(&mut self, message: &[u8]) -> Vec<Event> {
         let now = Utc::now();
 
-        self.bytes_received.emit(ByteSize(line.len()));
+        self.bytes_received.emit(ByteSize(message.len()));
 
-        let mut stream = FramedRead::new(line.as_ref(), self.decoder.clone());
+        let mut events = Vec::new();
+        let mut stream = FramedRead::new(message, self.decoder.clone());
         while let Some(next) = stream.next().await {
             match next {
-                Ok((events, _byte_size)) => {
-                    let count = events.len();
-                    let byte_size = events.estimated_json_encoded_size_of();
+                Ok((decoded, _byte_size)) => {
+                    let count = decoded.len();
+                    let byte_size = decoded.estimated_json_encoded_size_of();
                     self.events_received.emit(CountByteSize(count, byte_size));
 
-                    let events = events.into_iter().map(|mut event| {
+                    events.extend(decoded.into_iter().map(|mut event| {
                         if let Event::Log(ref mut log) = event {
                             self.log_namespace.insert_vector_metadata(
                                 log,
@@ -278,12 +381,7 @@ impl InputHandler {
                         };
 
                         event
-                    });
-
-                    if (self.cx.out.send_batch(events).await).is_err() {
-                        emit!(StreamClosedError { count });
-                        return Err(());
-                    }
+                    }));
                 }
                 Err(error) => {
                     // Error is logged by `crate::codecs::Decoder`, no further
@@ -294,6 +392,15 @@ impl InputHandler {
                 }
             }
         }
+        events
+    }
+
+    async fn send(&mut self, events: Vec<Event>) -> Result<(), ()> {
+        let count = events.len();
+        if (self.cx.out.send_batch(events).await).is_err() {
+            emit!(StreamClosedError { count });
+            return Err(());
+        }
         Ok(())
     }
 }
@@ -345,11 +452,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -386,11 +495,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Rpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: Some(OptionalValuePath::from(owned_value_path!("remapped_key"))),
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(true),
         };
 
@@ -427,11 +538,13 @@ mod integration_test {
             list: Some(ListOption {
                 method: Method::Lpop,
             }),
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
@@ -451,6 +564,53 @@ mod integration_test {
         );
     }
 
+    #[tokio::test]
+    async fn redis_source_stream_consumer_group() {
+        let client = redis::Client::open(REDIS_SERVER).unwrap();
+        let mut conn = client.get_tokio_connection_manager().await.unwrap();
+
+        let key = format!("test-stream-{}", random_string(10));
+        debug!("Test key name: {}.", key);
+
+        // Create the group ahead of the source so that it reads the entries added below.
+        let _: () = conn.xgroup_create_mkstream(&key, "vector", "0").await.unwrap();
+        let mut ids = Vec::new();
+        for message in ["1", "2", "3"] {
+            let id: String = conn.xadd(&key, "*", &[("message", message)]).await.unwrap();
+            ids.push(id);
+        }
+
+        let config = RedisSourceConfig {
+            data_type: DataTypeConfig::Stream,
+            list: None,
+            stream: Some(StreamOption {
+                group: "vector".to_owned(),
+                consumer: Some("consumer".to_owned()),
+                field: default_stream_field(),
+                batch_size: default_stream_batch_size(),
+                claim_min_idle_secs: default_claim_min_idle_secs(),
+                id_key: Some(OptionalValuePath::from(owned_value_path!("redis_id"))),
+            }),
+            url: REDIS_SERVER.to_owned(),
+            key: key.clone(),
+            redis_key: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            acknowledgements: true.into(),
+            log_namespace: Some(false),
+        };
+
+        let events = run_and_assert_source_compliance_n(config, 3, &SOURCE_TAGS).await;
+
+        for (event, (message, id)) in events.iter().zip(["1", "2", "3"].iter().zip(&ids)) {
+            assert_eq!(
+                event.as_log()[log_schema().message_key().unwrap().to_string()],
+                (*message).into()
+            );
+            assert_eq!(event.as_log()["redis_id"], id.as_str().into());
+        }
+    }
+
     #[tokio::test]
     async fn redis_source_channel_consume_event() {
         let key = format!("test-channel-{}", random_string(10));
@@ -460,11 +620,13 @@ mod integration_test {
         let config = RedisSourceConfig {
             data_type: DataTypeConfig::Channel,
             list: None,
+            stream: None,
             url: REDIS_SERVER.to_owned(),
             key: key.clone(),
             redis_key: None,
             framing: default_framing_message_based(),
             decoding: default_decoding(),
+            acknowledgements: Default::default(),
             log_namespace: Some(false),
         };
 
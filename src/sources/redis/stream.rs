@@ -0,0 +1,335 @@
+use std::time::Duration;
+
+use futures::StreamExt;
+use lookup::path;
+use redis::{
+    aio::ConnectionManager,
+    streams::{StreamId, StreamRangeReply, StreamReadOptions, StreamReadReply},
+    AsyncCommands, ErrorKind, RedisError, RedisResult, Value,
+};
+use snafu::{ResultExt, Snafu};
+use tokio::time::Instant;
+use vector_common::finalizer::UnorderedFinalizer;
+use vector_core::{
+    config::LegacyKey,
+    event::{BatchNotifier, BatchStatus},
+};
+
+use super::{list::backoff_exponential, InputHandler, RedisSourceConfig, StreamOption};
+use crate::{
+    event::Event,
+    internal_events::{RedisReceiveEventError, RedisStreamAckError},
+    sources::Source,
+};
+
+#[derive(Debug, Snafu)]
+enum BuildError {
+    #[snafu(display("Failed to create connection: {}", source))]
+    Connection { source: RedisError },
+    #[snafu(display("Failed to create consumer group: {}", source))]
+    CreateGroup { source: RedisError },
+}
+
+/// How long a read waits for new entries, which bounds how late pending entries are claimed.
+const READ_BLOCK: Duration = Duration::from_secs(1);
+
+/// The `XAUTOCLAIM` cursor that starts, and marks the end of, a scan of the pending entries.
+const CLAIM_CURSOR_START: &str = "0-0";
+
+/// The `XREADGROUP` ID that reads entries never delivered to any consumer of the group.
+const NEW_ENTRIES: &str = ">";
+
+impl InputHandler {
+    pub(super) async fn read_stream(
+        mut self,
+        options: StreamOption,
+        acknowledgements: bool,
+    ) -> crate::Result<Source> {
+        let mut conn = self
+            .client
+            .get_tokio_connection_manager()
+            .await
+            .context(ConnectionSnafu {})?;
+
+        create_group(&mut conn, &self.key, &options.group)
+            .await
+            .context(CreateGroupSnafu {})?;
+
+        let consumer = match &options.consumer {
+            Some(consumer) => consumer.clone(),
+            None => crate::get_hostname()?,
+        };
+
+        Ok(Box::pin(async move {
+            let mut shutdown = self.cx.shutdown.clone();
+            let (finalizer, mut ack_stream) =
+                UnorderedFinalizer::<String>::maybe_new(acknowledgements, Some(shutdown.clone()));
+
+            let mut ack_conn = conn.clone();
+            let key = self.key.clone();
+            let group = options.group.clone();
+            tokio::spawn(async move {
+                while let Some((status, id)) = ack_stream.next().await {
+                    handle_ack(&mut ack_conn, &key, &group, status, id).await;
+                }
+            });
+
+            let claim_min_idle = Duration::from_secs(options.claim_min_idle_secs);
+            let mut claim_cursor = CLAIM_CURSOR_START.to_owned();
+            let mut next_claim = Instant::now();
+
+            // Entries that were delivered to this consumer but never acknowledged, for example
+            // because Vector was stopped before their events were processed, are read again before
+            // any new entries.
+            let mut read_id = "0".to_owned();
+            let mut retry: u32 = 0;
+
+            loop {
+                if !claim_min_idle.is_zero() && Instant::now() >= next_claim {
+                    let res = tokio::select! {
+                        res = autoclaim(
+                            &mut conn,
+                            &self.key,
+                            &options,
+                            &consumer,
+                            claim_min_idle,
+                            &claim_cursor,
+                        ) => res,
+                        _ = &mut shutdown => break,
+                    };
+
+                    match res {
+                        Ok((cursor, entries)) => {
+                            // Keep claiming until the scan of the pending entries is complete.
+                            if cursor == CLAIM_CURSOR_START {
+                                next_claim = Instant::now() + claim_min_idle;
+                            }
+                            claim_cursor = cursor;
+
+                            if let Err(()) = self
+                                .handle_entries(&mut conn, &options, entries, finalizer.as_ref())
+                                .await
+                            {
+                                break;
+                            }
+                        }
+                        Err(error) => {
+                            emit!(RedisReceiveEventError::from(error));
+                            next_claim = Instant::now() + claim_min_idle;
+                        }
+                    }
+                }
+
+                let res = tokio::select! {
+                    res = read_group(&mut conn, &self.key, &options, &consumer, &read_id) => res,
+                    _ = &mut shutdown => break,
+                };
+
+                match res {
+                    Err(error) => {
+                        let kind = error.kind();
+
+                        emit!(RedisReceiveEventError::from(error));
+
+                        if kind == ErrorKind::IoError {
+                            retry += 1;
+                            backoff_exponential(retry).await
+                        }
+                    }
+                    Ok(entries) => {
+                        if retry > 0 {
+                            retry = 0
+                        }
+                        if read_id != NEW_ENTRIES {
+                            read_id = match entries.last() {
+                                Some(entry) => entry.id.clone(),
+                                None => NEW_ENTRIES.to_owned(),
+                            };
+                        }
+                        if let Err(()) = self
+                            .handle_entries(&mut conn, &options, entries, finalizer.as_ref())
+                            .await
+                        {
+                            break;
+                        }
+                    }
+                }
+            }
+            Ok(())
+        }))
+    }
+
+    async fn handle_entries(
+        &mut self,
+        conn: &mut ConnectionManager,
+        options: &StreamOption,
+        entries: Vec<StreamId>,
+        finalizer: Option<&UnorderedFinalizer<String>>,
+    ) -> Result<(), ()> {
+        let mut processed = Vec::new();
+
+        for entry in entries {
+            let mut events = match entry.map.get(&options.field) {
+                Some(value) => match redis::from_redis_value::<Vec<u8>>(value) {
+                    Ok(message) => self.decode(&message).await,
+                    Err(error) => {
+                        emit!(RedisReceiveEventError::from(error));
+                        Vec::new()
+                    }
+                },
+                None => {
+                    emit!(RedisReceiveEventError::from(RedisError::from((
+                        ErrorKind::TypeError,
+                        "Stream entry is missing the message field",
+                        format!("entry {} has no field {:?}", entry.id, options.field),
+                    ))));
+                    Vec::new()
+                }
+            };
+
+            for event in &mut events {
+                if let Event::Log(log) = event {
+                    self.log_namespace.insert_source_metadata(
+                        RedisSourceConfig::NAME,
+                        log,
+                        options
+                            .id_key
+                            .as_ref()
+                            .and_then(|k| k.path.as_ref())
+                            .map(LegacyKey::InsertIfEmpty),
+                        path!("id"),
+                        entry.id.as_str(),
+                    );
+                }
+            }
+
+            // Entries that can't be decoded are acknowledged as well, as reading them again would
+            // not produce any events either.
+            match finalizer {
+                Some(finalizer) => {
+                    let (batch, receiver) = BatchNotifier::new_with_receiver();
+                    let events = events
+                        .into_iter()
+                        .map(|event| event.with_batch_notifier(&batch))
+                        .collect();
+                    drop(batch);
+
+                    self.send(events).await?;
+                    finalizer.add(entry.id, receiver);
+                }
+                None => {
+                    self.send(events).await?;
+                    processed.push(entry.id);
+                }
+            }
+        }
+
+        if !processed.is_empty() {
+            if let Err(error) = conn
+                .xack::<_, _, _, i64>(&self.key, &options.group, &processed)
+                .await
+            {
+                emit!(RedisStreamAckError { error });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+async fn create_group(conn: &mut ConnectionManager, key: &str, group: &str) -> RedisResult<()> {
+    match conn
+        .xgroup_create_mkstream::<_, _, _, ()>(key, group, "$")
+        .await
+    {
+        // The group was already created by a previous run, or by another instance.
+        Err(error) if error.code() == Some("BUSYGROUP") => Ok(()),
+        res => res,
+    }
+}
+
+async fn read_group(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    consumer: &str,
+    id: &str,
+) -> RedisResult<Vec<StreamId>> {
+    let read_options = StreamReadOptions::default()
+        .group(&options.group, consumer)
+        .count(options.batch_size)
+        .block(READ_BLOCK.as_millis() as usize);
+
+    conn.xread_options::<_, _, Option<StreamReadReply>>(&[key], &[id], &read_options)
+        .await
+        .map(|reply| {
+            reply
+                .into_iter()
+                .flat_map(|reply| reply.keys)
+                .flat_map(|key| key.ids)
+                .collect()
+        })
+}
+
+/// Claims entries that have been pending for longer than `min_idle`, returning the cursor to
+/// continue the scan of the pending entries from along with the claimed entries.
+async fn autoclaim(
+    conn: &mut ConnectionManager,
+    key: &str,
+    options: &StreamOption,
+    consumer: &str,
+    min_idle: Duration,
+    cursor: &str,
+) -> RedisResult<(String, Vec<StreamId>)> {
+    let reply: Vec<Value> = redis::cmd("XAUTOCLAIM")
+        .arg(key)
+        .arg(&options.group)
+        .arg(consumer)
+        .arg(min_idle.as_millis() as u64)
+        .arg(cursor)
+        .arg("COUNT")
+        .arg(options.batch_size)
+        .query_async(conn)
+        .await?;
+
+    // Since Redis 7, the reply has a third element listing the IDs of claimed entries that no
+    // longer exist in the stream. Those are removed from the pending entries by Redis already.
+    let mut reply = reply.iter();
+    let cursor = match reply.next() {
+        Some(value) => redis::from_redis_value(value)?,
+        None => CLAIM_CURSOR_START.to_owned(),
+    };
+    let entries = match reply.next() {
+        Some(value) => redis::from_redis_value::<StreamRangeReply>(value)?.ids,
+        None => Vec::new(),
+    };
+
+    Ok((cursor, entries))
+}
+
+async fn handle_ack(
+    conn: &mut ConnectionManager,
+    key: &str,
+    group: &str,
+    status: BatchStatus,
+    id: String,
+) {
+    match status {
+        // Rejected events can never be delivered, so their entries are acknowledged as well to
+        // keep them from being claimed again.
+        BatchStatus::Delivered | BatchStatus::Rejected => {
+            if let Err(error) = conn.xack::<_, _, _, i64>(key, group, &[id]).await {
+                emit!(RedisStreamAckError { error });
+            }
+        }
+        // Leaving the entry pending makes it get claimed again once `claim_min_idle_secs` has
+        // passed.
+        BatchStatus::Errored => {
+            warn!(
+                message = "Stream entry was not acknowledged as its events failed to be delivered.",
+                id = %id,
+                internal_log_rate_limit = true,
+            );
+        }
+    }
+}
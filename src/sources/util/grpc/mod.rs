@@ -8,7 +8,7 @@ use hyper::Body;
 use std::{convert::Infallible, net::SocketAddr};
 use tonic::{
     body::BoxBody,
-    transport::server::{NamedService, Server},
+    transport::server::{NamedService, Routes, Server},
 };
 use tower::Service;
 use tracing::{Instrument, Span};
@@ -30,6 +30,16 @@ where
         + 'static,
     S::Future: Send + 'static,
 {
+    run_grpc_server_with_routes(address, tls_settings, Routes::new(service), shutdown).await
+}
+
+/// Runs a gRPC server serving all of the given services on the same address.
+pub async fn run_grpc_server_with_routes(
+    address: SocketAddr,
+    tls_settings: MaybeTlsSettings,
+    routes: Routes,
+    shutdown: ShutdownSignal,
+) -> crate::Result<()> {
     let span = Span::current();
     let (tx, rx) = tokio::sync::oneshot::channel::<ShutdownSignalToken>();
     let listener = tls_settings.bind(&address).await?;
@@ -49,7 +59,7 @@ where
         // modified or wrapped.. so instead of a cleaner design, we're opting here to bake it all together until the
         // crates are sufficiently flexible for us to craft a better design.
         .layer(DecompressionAndMetricsLayer)
-        .add_service(service)
+        .add_routes(routes)
         .serve_with_incoming_shutdown(stream, shutdown.map(|token| tx.send(token).unwrap()))
         .in_current_span()
         .await?;
@@ -0,0 +1,420 @@
+use std::{
+    io,
+    num::NonZeroU64,
+    time::{Duration, Instant},
+};
+
+use bytes::Bytes;
+use chrono::Utc;
+use codecs::decoding::{DeserializerConfig, FramingConfig, StreamDecodingError};
+use futures::{SinkExt, StreamExt};
+use tokio::time;
+use tokio_tungstenite::tungstenite::{error::Error as WsError, protocol::Message};
+use tokio_util::codec::FramedRead;
+use vector_common::internal_event::{
+    ByteSize, BytesReceived, CountByteSize, EventsReceived, InternalEventHandle as _, Protocol,
+};
+use vector_config::configurable_component;
+use vector_core::{config::LogNamespace, EstimatedJsonEncodedSizeOf};
+
+use crate::{
+    codecs::{Decoder, DecodingConfig},
+    config::{GenerateConfig, SourceConfig, SourceContext, SourceOutput},
+    event::Event,
+    http::Auth,
+    internal_events::{
+        ConnectionOpen, OpenGauge, StreamClosedError, WsConnectionError, WsConnectionShutdown,
+        WsReceiveError,
+    },
+    serde::{default_decoding, default_framing_message_based},
+    shutdown::ShutdownSignal,
+    tls::{MaybeTlsSettings, TlsEnableableConfig},
+    websocket::{is_closed, PingInterval, WebSocketConnector},
+    SourceSender,
+};
+
+/// Configuration for the `websocket` source.
+#[configurable_component(source(
+    "websocket",
+    "Collect observability events from a WebSocket server."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WebSocketSourceConfig {
+    /// The WebSocket URI to connect to.
+    ///
+    /// This should include the protocol and host, but can also include the port, path, and any other valid part of a URI.
+    #[configurable(metadata(docs::examples = "ws://127.0.0.1:9000/events"))]
+    #[configurable(metadata(docs::examples = "wss://stream.example.com/ws"))]
+    uri: String,
+
+    #[configurable(derived)]
+    tls: Option<TlsEnableableConfig>,
+
+    #[configurable(derived)]
+    auth: Option<Auth>,
+
+    /// A text message to send to the server after connecting.
+    ///
+    /// This is typically used to subscribe to a channel or topic on servers that require it. The
+    /// message is sent again after every reconnect.
+    #[configurable(metadata(docs::examples = r#"{"action": "subscribe", "channel": "logs"}"#))]
+    initial_message: Option<String>,
+
+    /// The interval, in seconds, between sending [Ping][ping]s to the remote peer.
+    ///
+    /// If this option is not configured, pings are not sent on an interval.
+    ///
+    /// If the `ping_timeout` is not set, pings are still sent but there is no expectation of pong
+    /// response times.
+    ///
+    /// [ping]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.2
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_interval: Option<NonZeroU64>,
+
+    /// The number of seconds to wait for a [Pong][pong] response from the remote peer.
+    ///
+    /// If a response is not received within this time, the connection is re-established.
+    ///
+    /// [pong]: https://www.rfc-editor.org/rfc/rfc6455#section-5.5.3
+    // NOTE: this option is not relevant if the `ping_interval` is not configured.
+    #[configurable(metadata(docs::type_unit = "seconds"))]
+    ping_timeout: Option<NonZeroU64>,
+
+    #[configurable(derived)]
+    #[serde(default = "default_framing_message_based")]
+    framing: FramingConfig,
+
+    #[configurable(derived)]
+    #[serde(default = "default_decoding")]
+    decoding: DeserializerConfig,
+
+    /// The namespace to use for logs. This overrides the global setting.
+    #[configurable(metadata(docs::hidden))]
+    #[serde(default)]
+    log_namespace: Option<bool>,
+}
+
+impl GenerateConfig for WebSocketSourceConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(r#"uri = "ws://127.0.0.1:9000/events""#).unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "websocket")]
+impl SourceConfig for WebSocketSourceConfig {
+    async fn build(&self, cx: SourceContext) -> crate::Result<super::Source> {
+        let log_namespace = cx.log_namespace(self.log_namespace);
+        let tls = MaybeTlsSettings::from_config(&self.tls, false)?;
+        let connector = WebSocketConnector::new(self.uri.clone(), tls, self.auth.clone())?;
+        let decoder =
+            DecodingConfig::new(self.framing.clone(), self.decoding.clone(), log_namespace)
+                .build()?;
+
+        Ok(Box::pin(websocket_source(
+            self.clone(),
+            connector,
+            decoder,
+            log_namespace,
+            cx.shutdown,
+            cx.out,
+        )))
+    }
+
+    fn outputs(&self, global_log_namespace: LogNamespace) -> Vec<SourceOutput> {
+        let log_namespace = global_log_namespace.merge(self.log_namespace);
+        let schema_definition = self
+            .decoding
+            .schema_definition(log_namespace)
+            .with_standard_vector_source_metadata();
+
+        vec![SourceOutput::new_logs(
+            self.decoding.output_type(),
+            schema_definition,
+        )]
+    }
+
+    fn can_acknowledge(&self) -> bool {
+        false
+    }
+}
+
+impl WebSocketSourceConfig {
+    fn check_received_pong_time(&self, last_pong: Instant) -> Result<(), WsError> {
+        if let Some(ping_timeout) = self.ping_timeout {
+            if last_pong.elapsed() > Duration::from_secs(ping_timeout.into()) {
+                return Err(WsError::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    "Pong not received in time",
+                )));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// How long a connection must stay up for the reconnection backoff to be reset, even if it didn't
+/// deliver any data.
+const STABLE_CONNECTION_DURATION: Duration = Duration::from_secs(60);
+
+async fn websocket_source(
+    config: WebSocketSourceConfig,
+    connector: WebSocketConnector,
+    decoder: Decoder,
+    log_namespace: LogNamespace,
+    mut shutdown: ShutdownSignal,
+    mut out: SourceSender,
+) -> Result<(), ()> {
+    const PING: &[u8] = b"PING";
+
+    let events_received = register!(EventsReceived);
+    let bytes_received = register!(BytesReceived::from(Protocol("websocket".into())));
+
+    // The backoff is shared by all connections, and only reset once a connection is stable
+    let mut backoff = WebSocketConnector::fresh_backoff();
+    let mut stable = true;
+
+    loop {
+        if !stable {
+            // The previous connection was closed before it delivered any data, so wait before
+            // reconnecting to avoid a tight reconnection loop
+            tokio::select! {
+                _ = time::sleep(backoff.next().unwrap()) => {},
+                _ = &mut shutdown => break,
+            }
+        }
+        let ws_stream = tokio::select! {
+            ws_stream = connector.connect_with_backoff(&mut backoff) => ws_stream,
+            _ = &mut shutdown => break,
+        };
+        let connected_at = Instant::now();
+        stable = false;
+        let (mut ws_sink, mut ws_stream) = ws_stream.split();
+
+        let _open_token = OpenGauge::new().open(|count| emit!(ConnectionOpen { count }));
+
+        if let Some(message) = &config.initial_message {
+            if let Err(error) = ws_sink.send(Message::text(message.clone())).await {
+                emit!(WsConnectionError { error });
+                continue;
+            }
+        }
+
+        // tokio::time::Interval panics if the period arg is zero. Since the config members are
+        // using NonZeroU64 that is not something we need to account for.
+        let mut ping_interval = PingInterval::new(config.ping_interval.map(u64::from));
+        let mut last_pong = Instant::now();
+
+        let result = loop {
+            let data = tokio::select! {
+                _ = &mut shutdown => {
+                    _ = ws_sink.close().await;
+                    return Ok(());
+                },
+
+                _ = ping_interval.tick() => {
+                    let result = match config.check_received_pong_time(last_pong) {
+                        Ok(()) => ws_sink.send(Message::Ping(PING.to_vec())).await,
+                        Err(error) => Err(error),
+                    };
+                    if let Err(error) = result {
+                        break Err(error);
+                    }
+                    continue;
+                },
+
+                // Pongs are sent automatically by tungstenite during reading from the stream.
+                message = ws_stream.next() => match message {
+                    Some(Ok(Message::Text(text))) => Bytes::from(text),
+                    Some(Ok(Message::Binary(data))) => Bytes::from(data),
+                    Some(Ok(Message::Pong(_))) => {
+                        last_pong = Instant::now();
+                        continue;
+                    },
+                    Some(Ok(_)) => continue,
+                    Some(Err(error)) => break Err(error),
+                    None => break Ok(()),
+                },
+            };
+
+            bytes_received.emit(ByteSize(data.len()));
+            if !stable {
+                stable = true;
+                backoff.reset();
+            }
+
+            let events = decode_message(data, decoder.clone(), log_namespace).await;
+            let count = events.len();
+            if count == 0 {
+                continue;
+            }
+            events_received.emit(CountByteSize(
+                count,
+                events.estimated_json_encoded_size_of(),
+            ));
+
+            if out.send_batch(events).await.is_err() {
+                emit!(StreamClosedError { count });
+                return Err(());
+            }
+        };
+
+        match result {
+            Err(error) if !is_closed(&error) => emit!(WsReceiveError { error }),
+            _ => emit!(WsConnectionShutdown),
+        }
+
+        if connected_at.elapsed() >= STABLE_CONNECTION_DURATION {
+            stable = true;
+            backoff.reset();
+        }
+    }
+
+    Ok(())
+}
+
+async fn decode_message(data: Bytes, decoder: Decoder, log_namespace: LogNamespace) -> Vec<Event> {
+    let now = Utc::now();
+    let mut events = Vec::new();
+    let mut stream = FramedRead::new(data.as_ref(), decoder);
+    while let Some(next) = stream.next().await {
+        match next {
+            Ok((decoded, _byte_size)) => {
+                events.extend(decoded.into_iter().map(|mut event| {
+                    if let Event::Log(ref mut log) = event {
+                        log_namespace.insert_standard_vector_source_metadata(
+                            log,
+                            WebSocketSourceConfig::NAME,
+                            now,
+                        );
+                    }
+                    event
+                }));
+            }
+            Err(error) => {
+                // Error is logged by `crate::codecs`, no further
+                // handling is needed here.
+                if !error.can_continue() {
+                    break;
+                }
+            }
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpListener;
+    use tokio_tungstenite::accept_async;
+
+    use super::*;
+    use crate::{
+        config::log_schema,
+        test_util::{
+            collect_n,
+            components::{assert_source_compliance, SOURCE_TAGS},
+            next_addr, trace_init,
+        },
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WebSocketSourceConfig>();
+    }
+
+    fn make_config(uri: String, initial_message: Option<String>) -> WebSocketSourceConfig {
+        WebSocketSourceConfig {
+            uri,
+            tls: None,
+            auth: None,
+            initial_message,
+            ping_interval: None,
+            ping_timeout: None,
+            framing: default_framing_message_based(),
+            decoding: default_decoding(),
+            log_namespace: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn receives_text_and_binary_messages() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        let server = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let mut ws = accept_async(stream).await.unwrap();
+
+            let subscribe = ws.next().await.unwrap().unwrap();
+            assert_eq!(subscribe, Message::text("subscribe"));
+
+            ws.send(Message::text("text message")).await.unwrap();
+            ws.send(Message::binary(b"binary message".to_vec()))
+                .await
+                .unwrap();
+            ws
+        });
+
+        let config = make_config(format!("ws://{}", addr), Some("subscribe".to_owned()));
+
+        let events = assert_source_compliance(&SOURCE_TAGS, async {
+            let (tx, rx) = SourceSender::new_test();
+            let cx = SourceContext::new_test(tx, None);
+            let source = config.build(cx).await.unwrap();
+            tokio::spawn(source);
+
+            collect_n(rx, 2).await
+        })
+        .await;
+
+        let messages = events
+            .iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()].to_string_lossy()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["text message", "binary message"]);
+        assert_eq!(
+            events[0].as_log()[log_schema().source_type_key().unwrap().to_string()],
+            "websocket".into()
+        );
+
+        drop(server.await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn reconnects_after_server_closes() {
+        trace_init();
+
+        let addr = next_addr();
+        let listener = TcpListener::bind(addr).await.unwrap();
+        tokio::spawn(async move {
+            for message in ["first", "second"] {
+                let (stream, _) = listener.accept().await.unwrap();
+                let mut ws = accept_async(stream).await.unwrap();
+                ws.send(Message::text(message)).await.unwrap();
+                ws.close(None).await.unwrap();
+            }
+        });
+
+        let config = make_config(format!("ws://{}", addr), None);
+
+        let (tx, rx) = SourceSender::new_test();
+        let cx = SourceContext::new_test(tx, None);
+        let source = config.build(cx).await.unwrap();
+        tokio::spawn(source);
+
+        let events = collect_n(rx, 2).await;
+        let messages = events
+            .iter()
+            .map(|event| {
+                event.as_log()[log_schema().message_key().unwrap().to_string()].to_string_lossy()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["first", "second"]);
+    }
+}
@@ -6,6 +6,7 @@ use std::{
 
 use async_stream::stream;
 use futures::{Stream, StreamExt};
+use vector_common::internal_event::{ComponentEventsDropped, INTENTIONAL};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 
@@ -28,6 +29,68 @@ pub struct AggregateConfig {
     #[serde(default = "default_interval_ms")]
     #[configurable(metadata(docs::human_name = "Flush Interval"))]
     pub interval_ms: u64,
+
+    /// The aggregation mode to apply to metrics within each interval.
+    #[configurable(derived)]
+    #[serde(default)]
+    pub mode: AggregationMode,
+}
+
+/// The aggregation mode.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Derivative, Eq, PartialEq)]
+#[derivative(Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AggregationMode {
+    /// Sums incremental metrics and keeps the latest value of absolute metrics.
+    #[derivative(Default)]
+    Auto,
+
+    /// Sums incremental metrics.
+    ///
+    /// Absolute metrics are dropped.
+    Sum,
+
+    /// Keeps the latest value of absolute metrics.
+    ///
+    /// Incremental metrics are dropped.
+    Latest,
+
+    /// Counts the metrics received for each series, emitted as an incremental counter.
+    Count,
+
+    /// Emits the difference between the latest value of an absolute metric and its value in
+    /// the previous interval.
+    ///
+    /// Counters are emitted as incremental metrics. Nothing is emitted for a series until it has
+    /// been seen in two consecutive intervals. Incremental metrics are dropped.
+    Diff,
+
+    /// Emits the maximum value of an absolute gauge.
+    ///
+    /// Other metrics are dropped.
+    Max,
+
+    /// Emits the minimum value of an absolute gauge.
+    ///
+    /// Other metrics are dropped.
+    Min,
+
+    /// Emits the mean value of an absolute gauge.
+    ///
+    /// Other metrics are dropped.
+    Mean,
+
+    /// Emits the population standard deviation of an absolute gauge.
+    ///
+    /// Other metrics are dropped.
+    Stdev,
+
+    /// Converts the values of an absolute gauge into an incremental distribution.
+    ///
+    /// This allows downsampling high-frequency gauges while preserving their spread. Other
+    /// metrics are dropped.
+    Distribution,
 }
 
 const fn default_interval_ms() -> u64 {
@@ -59,57 +122,201 @@ impl TransformConfig for AggregateConfig {
 
 type MetricEntry = (metric::MetricData, EventMetadata);
 
+/// The gauge values seen for a series within an interval, along with the time of the latest one.
+type SamplesEntry = (metric::MetricTime, Vec<f64>, EventMetadata);
+
 #[derive(Debug)]
 pub struct Aggregate {
     interval: Duration,
+    mode: AggregationMode,
     map: HashMap<metric::MetricSeries, MetricEntry>,
+    prev_map: HashMap<metric::MetricSeries, MetricEntry>,
+    samples_map: HashMap<metric::MetricSeries, SamplesEntry>,
 }
 
 impl Aggregate {
     pub fn new(config: &AggregateConfig) -> crate::Result<Self> {
         Ok(Self {
             interval: Duration::from_millis(config.interval_ms),
+            mode: config.mode,
             map: Default::default(),
+            prev_map: Default::default(),
+            samples_map: Default::default(),
         })
     }
 
     fn record(&mut self, event: Event) {
         let (series, data, metadata) = event.into_metric().into_parts();
 
-        match data.kind {
-            metric::MetricKind::Incremental => match self.map.entry(series) {
-                Entry::Occupied(mut entry) => {
-                    let existing = entry.get_mut();
-                    // In order to update (add) the new and old kind's must match
-                    if existing.0.kind == data.kind && existing.0.update(&data) {
-                        existing.1.merge(metadata);
-                    } else {
-                        emit!(AggregateUpdateFailed);
-                        *existing = (data, metadata);
-                    }
-                }
-                Entry::Vacant(entry) => {
-                    entry.insert((data, metadata));
-                }
-            },
-            metric::MetricKind::Absolute => {
+        match (self.mode, data.kind) {
+            (AggregationMode::Auto | AggregationMode::Sum, metric::MetricKind::Incremental) => {
+                self.record_sum(series, data, metadata)
+            }
+            (
+                AggregationMode::Auto | AggregationMode::Latest | AggregationMode::Diff,
+                metric::MetricKind::Absolute,
+            ) => {
                 // Always replace/store
                 self.map.insert(series, (data, metadata));
             }
+            (AggregationMode::Count, _) => {
+                let data = metric::MetricData::from_parts(
+                    data.time,
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Counter { value: 1.0 },
+                );
+                self.record_sum(series, data, metadata)
+            }
+            (
+                AggregationMode::Max
+                | AggregationMode::Min
+                | AggregationMode::Mean
+                | AggregationMode::Stdev
+                | AggregationMode::Distribution,
+                metric::MetricKind::Absolute,
+            ) => match data.value {
+                metric::MetricValue::Gauge { value } => {
+                    self.record_sample(series, data.time, value, metadata)
+                }
+                _ => return Self::drop_unsupported(),
+            },
+            _ => return Self::drop_unsupported(),
         };
 
         emit!(AggregateEventRecorded);
     }
 
+    fn record_sum(
+        &mut self,
+        series: metric::MetricSeries,
+        data: metric::MetricData,
+        metadata: EventMetadata,
+    ) {
+        match self.map.entry(series) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                // In order to update (add) the new and old kind's must match
+                if existing.0.kind == data.kind && existing.0.update(&data) {
+                    existing.1.merge(metadata);
+                } else {
+                    emit!(AggregateUpdateFailed);
+                    *existing = (data, metadata);
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((data, metadata));
+            }
+        }
+    }
+
+    fn record_sample(
+        &mut self,
+        series: metric::MetricSeries,
+        time: metric::MetricTime,
+        value: f64,
+        metadata: EventMetadata,
+    ) {
+        match self.samples_map.entry(series) {
+            Entry::Occupied(mut entry) => {
+                let existing = entry.get_mut();
+                existing.0 = time;
+                existing.1.push(value);
+                existing.2.merge(metadata);
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((time, vec![value], metadata));
+            }
+        }
+    }
+
+    fn drop_unsupported() {
+        emit!(ComponentEventsDropped::<INTENTIONAL> {
+            count: 1,
+            reason: "Metric not supported by the aggregation mode."
+        });
+    }
+
     fn flush_into(&mut self, output: &mut Vec<Event>) {
         let map = std::mem::take(&mut self.map);
+        // Only the series seen during this interval are remembered for the next one
+        let mut prev_map = std::mem::take(&mut self.prev_map);
         for (series, entry) in map.into_iter() {
-            let metric = metric::Metric::from_parts(series, entry.0, entry.1);
+            let (data, metadata) = if self.mode == AggregationMode::Diff {
+                let diffed = Self::diff(prev_map.remove(&series), &entry);
+                self.prev_map.insert(series.clone(), entry);
+                match diffed {
+                    Some(diffed) => diffed,
+                    None => continue,
+                }
+            } else {
+                entry
+            };
+            let metric = metric::Metric::from_parts(series, data, metadata);
+            output.push(Event::Metric(metric));
+        }
+
+        let samples_map = std::mem::take(&mut self.samples_map);
+        for (series, (time, values, metadata)) in samples_map.into_iter() {
+            let data = self.summarize(time, &values);
+            let metric = metric::Metric::from_parts(series, data, metadata);
             output.push(Event::Metric(metric));
         }
 
         emit!(AggregateFlushed);
     }
+
+    /// Subtracts the value of a series in the previous interval from its current value.
+    ///
+    /// Returns `None` if the series wasn't seen in the previous interval, or if its type changed,
+    /// as there is nothing to compare against.
+    fn diff(prev: Option<MetricEntry>, entry: &MetricEntry) -> Option<MetricEntry> {
+        let prev = prev?;
+        let mut diffed = entry.clone();
+        if !diffed.0.value.subtract(&prev.0.value) {
+            emit!(AggregateUpdateFailed);
+            return None;
+        }
+        // The difference between two values of a counter is an increment
+        if matches!(diffed.0.value, metric::MetricValue::Counter { .. }) {
+            diffed.0.kind = metric::MetricKind::Incremental;
+        }
+        Some(diffed)
+    }
+
+    fn summarize(&self, time: metric::MetricTime, values: &[f64]) -> metric::MetricData {
+        let count = values.len() as f64;
+        let mean = values.iter().sum::<f64>() / count;
+
+        let value = match self.mode {
+            AggregationMode::Max => values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            AggregationMode::Min => values.iter().copied().fold(f64::INFINITY, f64::min),
+            AggregationMode::Mean => mean,
+            AggregationMode::Stdev => {
+                let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / count;
+                variance.sqrt()
+            }
+            _ => {
+                let samples = values
+                    .iter()
+                    .map(|&value| metric::Sample { value, rate: 1 })
+                    .collect();
+                return metric::MetricData::from_parts(
+                    time,
+                    metric::MetricKind::Incremental,
+                    metric::MetricValue::Distribution {
+                        samples,
+                        statistic: metric::StatisticKind::Histogram,
+                    },
+                );
+            }
+        };
+
+        metric::MetricData::from_parts(
+            time,
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value },
+        )
+    }
 }
 
 impl TaskTransform<Event> for Aggregate {
@@ -192,6 +399,7 @@ mod tests {
     fn incremental() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            mode: AggregationMode::Auto,
         })
         .unwrap();
 
@@ -262,6 +470,7 @@ mod tests {
     fn absolute() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            mode: AggregationMode::Auto,
         })
         .unwrap();
 
@@ -327,6 +536,7 @@ mod tests {
     fn conflicting_value_type() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            mode: AggregationMode::Auto,
         })
         .unwrap();
 
@@ -384,6 +594,7 @@ mod tests {
     fn conflicting_kinds() {
         let mut agg = Aggregate::new(&AggregateConfig {
             interval_ms: 1000_u64,
+            mode: AggregationMode::Auto,
         })
         .unwrap();
 
@@ -434,6 +645,193 @@ mod tests {
         assert_eq!(&summed, &out[0]);
     }
 
+    fn gauge(value: f64) -> Event {
+        make_metric(
+            "gauge_a",
+            metric::MetricKind::Absolute,
+            metric::MetricValue::Gauge { value },
+        )
+    }
+
+    fn counter(value: f64) -> Event {
+        make_metric(
+            "counter_a",
+            metric::MetricKind::Incremental,
+            metric::MetricValue::Counter { value },
+        )
+    }
+
+    fn aggregate_with_mode(mode: AggregationMode, events: Vec<Event>) -> Vec<Event> {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            mode,
+        })
+        .unwrap();
+        for event in events {
+            agg.record(event);
+        }
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        out
+    }
+
+    #[test]
+    fn mode_sum_drops_absolute() {
+        let out = aggregate_with_mode(
+            AggregationMode::Sum,
+            vec![counter(1.0), counter(2.0), gauge(3.0)],
+        );
+        assert_eq!(vec![counter(3.0)], out);
+    }
+
+    #[test]
+    fn mode_latest_drops_incremental() {
+        let out = aggregate_with_mode(
+            AggregationMode::Latest,
+            vec![counter(1.0), gauge(2.0), gauge(3.0)],
+        );
+        assert_eq!(vec![gauge(3.0)], out);
+    }
+
+    #[test]
+    fn mode_count() {
+        let out = aggregate_with_mode(
+            AggregationMode::Count,
+            vec![gauge(5.0), gauge(6.0), gauge(7.0)],
+        );
+        assert_eq!(
+            vec![make_metric(
+                "gauge_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Counter { value: 3.0 },
+            )],
+            out
+        );
+    }
+
+    #[test]
+    fn mode_diff() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Diff,
+        })
+        .unwrap();
+
+        // The first interval has nothing to compare against, so nothing is flushed
+        agg.record(gauge(10.0));
+        agg.record(gauge(12.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert!(out.is_empty());
+
+        agg.record(gauge(15.0));
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(vec![gauge(3.0)], out);
+
+        agg.record(gauge(11.0));
+        out.clear();
+        agg.flush_into(&mut out);
+        assert_eq!(vec![gauge(-4.0)], out);
+    }
+
+    #[test]
+    fn mode_diff_counters_are_incremental() {
+        let absolute_counter = |value| {
+            make_metric(
+                "counter_a",
+                metric::MetricKind::Absolute,
+                metric::MetricValue::Counter { value },
+            )
+        };
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Diff,
+        })
+        .unwrap();
+
+        agg.record(absolute_counter(10.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+        assert!(out.is_empty());
+
+        agg.record(absolute_counter(15.0));
+        agg.flush_into(&mut out);
+        assert_eq!(vec![counter(5.0)], out);
+    }
+
+    #[test]
+    fn mode_diff_forgets_missing_series() {
+        let mut agg = Aggregate::new(&AggregateConfig {
+            interval_ms: 1000_u64,
+            mode: AggregationMode::Diff,
+        })
+        .unwrap();
+
+        agg.record(gauge(10.0));
+        let mut out = vec![];
+        agg.flush_into(&mut out);
+
+        // The series is not seen during this interval, so its previous value is evicted
+        agg.flush_into(&mut out);
+        assert!(agg.prev_map.is_empty());
+
+        agg.record(gauge(15.0));
+        agg.flush_into(&mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn mode_gauge_statistics() {
+        let values = || {
+            vec![
+                gauge(2.0),
+                gauge(4.0),
+                gauge(4.0),
+                gauge(4.0),
+                gauge(5.0),
+                gauge(5.0),
+                gauge(7.0),
+                gauge(9.0),
+            ]
+        };
+
+        for (mode, expected) in [
+            (AggregationMode::Max, 9.0),
+            (AggregationMode::Min, 2.0),
+            (AggregationMode::Mean, 5.0),
+            (AggregationMode::Stdev, 2.0),
+        ] {
+            let out = aggregate_with_mode(mode, values());
+            assert_eq!(vec![gauge(expected)], out, "{:?}", mode);
+        }
+    }
+
+    #[test]
+    fn mode_gauge_statistics_drop_others() {
+        let out = aggregate_with_mode(AggregationMode::Mean, vec![counter(1.0), gauge(2.0)]);
+        assert_eq!(vec![gauge(2.0)], out);
+    }
+
+    #[test]
+    fn mode_distribution() {
+        let out = aggregate_with_mode(
+            AggregationMode::Distribution,
+            vec![gauge(1.0), gauge(2.0), gauge(3.0)],
+        );
+        assert_eq!(
+            vec![make_metric(
+                "gauge_a",
+                metric::MetricKind::Incremental,
+                metric::MetricValue::Distribution {
+                    samples: vector_core::samples![1.0 => 1, 2.0 => 1, 3.0 => 1],
+                    statistic: metric::StatisticKind::Histogram,
+                },
+            )],
+            out
+        );
+    }
+
     #[tokio::test]
     async fn transform_shutdown() {
         let agg = toml::from_str::<AggregateConfig>(
@@ -1,19 +1,26 @@
-use std::{future::ready, num::NonZeroUsize, pin::Pin};
+use std::{
+    num::NonZeroUsize,
+    pin::Pin,
+    time::{Duration, Instant},
+};
 
+use async_stream::stream;
 use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use futures::{Stream, StreamExt};
-use lookup::lookup_v2::ConfigTargetPath;
+use lookup::{lookup_v2::ConfigTargetPath, owned_value_path};
 use lru::LruCache;
+use serde_with::serde_as;
 use vector_config::configurable_component;
 use vector_core::config::{clone_input_definitions, LogNamespace};
-use vrl::path::OwnedTargetPath;
+use vrl::{path::OwnedTargetPath, value::Kind};
 
 use crate::{
     config::{
         log_schema, DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
         TransformOutput,
     },
-    event::{Event, Value},
+    event::{Event, LogEvent, Value},
     internal_events::DedupeEventsDropped,
     schema,
     transforms::{TaskTransform, Transform},
@@ -62,12 +69,24 @@ pub enum FieldMatchConfig {
 }
 
 /// Caching configuration for deduplication.
+#[serde_as]
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(deny_unknown_fields)]
 pub struct CacheConfig {
     /// Number of events to cache and use for comparing incoming events to previously seen events.
     pub num_events: NonZeroUsize,
+
+    /// The maximum time an event is cached for, in seconds.
+    ///
+    /// Once an event has been cached for this long, the next matching event is no longer
+    /// considered a duplicate, and is cached in its place. By default, events are cached until
+    /// they are evicted to make room for new ones.
+    #[serde(default)]
+    #[serde_as(as = "Option<serde_with::DurationSeconds<u64>>")]
+    #[configurable(metadata(docs::examples = 60))]
+    #[configurable(metadata(docs::human_name = "Time To Live"))]
+    pub ttl_secs: Option<Duration>,
 }
 
 /// Configuration for the `dedupe` transform.
@@ -82,11 +101,21 @@ pub struct DedupeConfig {
     #[configurable(derived)]
     #[serde(default = "default_cache_config")]
     pub cache: CacheConfig,
+
+    /// Whether to emit a summary event when a cached event with suppressed duplicates expires.
+    ///
+    /// The summary is a copy of the cached event, with its message replaced by
+    /// `message repeated N times`, its timestamp set to the time of the last duplicate, and a
+    /// `repeat_count` field holding the number of duplicates. Summaries are emitted when the cached
+    /// event reaches `cache.ttl_secs`, is evicted from the cache, or when Vector shuts down.
+    #[serde(default)]
+    pub emit_summaries: bool,
 }
 
 fn default_cache_config() -> CacheConfig {
     CacheConfig {
         num_events: NonZeroUsize::new(5000).expect("static non-zero number"),
+        ttl_secs: None,
     }
 }
 
@@ -132,9 +161,23 @@ impl DedupeConfig {
     }
 }
 
+/// How often cached events are checked for expiry, at most.
+const EXPIRY_CHECK_PERIOD: Duration = Duration::from_secs(1);
+
 pub struct Dedupe {
     fields: FieldMatchConfig,
-    cache: LruCache<CacheEntry, bool>,
+    cache: LruCache<CacheEntry, CachedEvent>,
+    ttl: Option<Duration>,
+    emit_summaries: bool,
+}
+
+/// The state kept for each cached event.
+struct CachedEvent {
+    first_seen: Instant,
+    /// A copy of the cached event without its finalizers, only kept when summaries are emitted.
+    event: Option<LogEvent>,
+    suppressed: usize,
+    last_suppressed: DateTime<Utc>,
 }
 
 impl GenerateConfig for DedupeConfig {
@@ -142,6 +185,7 @@ impl GenerateConfig for DedupeConfig {
         toml::Value::try_from(Self {
             fields: None,
             cache: default_cache_config(),
+            emit_summaries: false,
         })
         .unwrap()
     }
@@ -151,6 +195,9 @@ impl GenerateConfig for DedupeConfig {
 #[typetag::serde(name = "dedupe")]
 impl TransformConfig for DedupeConfig {
     async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        if self.cache.ttl_secs == Some(Duration::ZERO) {
+            return Err("`cache.ttl_secs` must be greater than zero".into());
+        }
         Ok(Transform::event_task(Dedupe::new(self.clone())))
     }
 
@@ -164,10 +211,18 @@ impl TransformConfig for DedupeConfig {
         input_definitions: &[(OutputId, schema::Definition)],
         _: LogNamespace,
     ) -> Vec<TransformOutput> {
-        vec![TransformOutput::new(
-            DataType::Log,
-            clone_input_definitions(input_definitions),
-        )]
+        let mut definitions = clone_input_definitions(input_definitions);
+        if self.emit_summaries {
+            for definition in definitions.values_mut() {
+                *definition = definition.clone().with_event_field(
+                    &owned_value_path!("repeat_count"),
+                    Kind::integer().or_undefined(),
+                    None,
+                );
+            }
+        }
+
+        vec![TransformOutput::new(DataType::Log, definitions)]
     }
 }
 
@@ -196,7 +251,7 @@ type TypeId = u8;
 /// iterating over the fields of the incoming Events, we know that the
 /// CacheEntries for 2 equivalent events will always contain the fields in the
 /// same order.
-#[derive(PartialEq, Eq, Hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
 enum CacheEntry {
     Match(Vec<Option<(TypeId, Bytes)>>),
     Ignore(Vec<(OwnedTargetPath, TypeId, Bytes)>),
@@ -224,18 +279,94 @@ impl Dedupe {
         Self {
             fields,
             cache: LruCache::new(num_entries),
+            ttl: config.cache.ttl_secs,
+            emit_summaries: config.emit_summaries,
         }
     }
 
-    fn transform_one(&mut self, event: Event) -> Option<Event> {
+    fn is_expired(&self, cached: &CachedEvent, now: Instant) -> bool {
+        self.ttl
+            .map_or(false, |ttl| now.duration_since(cached.first_seen) >= ttl)
+    }
+
+    fn transform_one(&mut self, event: Event, now: Instant, output: &mut Vec<Event>) {
         let cache_entry = build_cache_entry(&event, &self.fields);
-        if self.cache.put(cache_entry, true).is_some() {
-            emit!(DedupeEventsDropped { count: 1 });
-            None
-        } else {
-            Some(event)
+
+        if let Some(cached) = self.cache.peek(&cache_entry) {
+            if self.is_expired(cached, now) {
+                let cached = self.cache.pop(&cache_entry).expect("entry is cached");
+                self.summarize(cached, output);
+            } else {
+                let cached = self.cache.get_mut(&cache_entry).expect("entry is cached");
+                cached.suppressed += 1;
+                cached.last_suppressed = Utc::now();
+                emit!(DedupeEventsDropped { count: 1 });
+                return;
+            }
+        }
+
+        let cached = CachedEvent {
+            first_seen: now,
+            event: self.emit_summaries.then(|| summary_template(&event)),
+            suppressed: 0,
+            last_suppressed: Utc::now(),
+        };
+        if let Some((_, evicted)) = self.cache.push(cache_entry, cached) {
+            self.summarize(evicted, output);
+        }
+        output.push(event);
+    }
+
+    /// Removes the cached events that have expired.
+    fn flush_expired(&mut self, now: Instant, output: &mut Vec<Event>) {
+        let expired = self
+            .cache
+            .iter()
+            .filter(|(_, cached)| self.is_expired(cached, now))
+            .map(|(entry, _)| entry.clone())
+            .collect::<Vec<_>>();
+        for entry in expired {
+            if let Some(cached) = self.cache.pop(&entry) {
+                self.summarize(cached, output);
+            }
         }
     }
+
+    fn flush_all(&mut self, output: &mut Vec<Event>) {
+        while let Some((_, cached)) = self.cache.pop_lru() {
+            self.summarize(cached, output);
+        }
+    }
+
+    /// Emits a summary of the duplicates of a cached event, if there were any.
+    fn summarize(&self, cached: CachedEvent, output: &mut Vec<Event>) {
+        let mut log = match cached.event {
+            Some(log) if cached.suppressed > 0 => log,
+            _ => return,
+        };
+
+        if let Some(message_path) = log.message_path().cloned() {
+            log.insert(
+                &message_path,
+                format!("message repeated {} times", cached.suppressed),
+            );
+        }
+        if let Some(timestamp_path) = log.timestamp_path().cloned() {
+            log.insert(&timestamp_path, cached.last_suppressed);
+        }
+        log.insert("repeat_count", cached.suppressed as i64);
+        output.push(Event::Log(log));
+    }
+}
+
+/// Copies an event to later summarize its repeats with.
+///
+/// The copy doesn't share the finalizers of the event, so that caching it doesn't delay the
+/// acknowledgement of the event.
+fn summary_template(event: &Event) -> LogEvent {
+    let (value, mut metadata) = event.as_log().clone().into_parts();
+    drop(metadata.take_finalizers());
+    LogEvent::from_parts(value, metadata)
 }
 
 /// Takes in an Event and returns a CacheEntry to place into the LRU cache
@@ -280,21 +411,52 @@ fn build_cache_entry(event: &Event, fields: &FieldMatchConfig) -> CacheEntry {
 
 impl TaskTransform<Event> for Dedupe {
     fn transform(
-        self: Box<Self>,
-        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
     ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
     where
         Self: 'static,
     {
-        let mut inner = self;
-        Box::pin(task.filter_map(move |v| ready(inner.transform_one(v))))
+        let mut expiry_check = tokio::time::interval(
+            self.ttl
+                .map_or(EXPIRY_CHECK_PERIOD, |ttl| ttl.min(EXPIRY_CHECK_PERIOD)),
+        );
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = expiry_check.tick(), if self.ttl.is_some() => {
+                        self.flush_expired(Instant::now(), &mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_all(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.transform_one(event, Instant::now(), &mut output),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::{collections::BTreeMap, sync::Arc};
+    use std::{
+        collections::BTreeMap,
+        sync::Arc,
+        time::{Duration, Instant},
+    };
 
+    use futures::{stream, StreamExt};
     use lookup::lookup_v2::ConfigTargetPath;
     use tokio::sync::mpsc;
     use tokio_stream::wrappers::ReceiverStream;
@@ -303,11 +465,12 @@ mod tests {
 
     use crate::config::schema::Definition;
     use crate::{
-        event::{Event, LogEvent, Value},
+        event::{BatchNotifier, BatchStatus, Event, LogEvent, Value},
         test_util::components::assert_transform_compliance,
         transforms::{
-            dedupe::{CacheConfig, DedupeConfig, FieldMatchConfig},
+            dedupe::{CacheConfig, Dedupe, DedupeConfig, FieldMatchConfig},
             test::create_topology,
+            TaskTransform,
         },
     };
 
@@ -323,8 +486,10 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
             },
             fields: Some(FieldMatchConfig::MatchFields(fields)),
+            emit_summaries: false,
         }
     }
 
@@ -339,8 +504,10 @@ mod tests {
         DedupeConfig {
             cache: CacheConfig {
                 num_events: std::num::NonZeroUsize::new(num_events).expect("non-zero num_events"),
+                ttl_secs: None,
             },
             fields: Some(FieldMatchConfig::IgnoreFields(fields)),
+            emit_summaries: false,
         }
     }
 
@@ -774,4 +941,149 @@ mod tests {
         })
         .await;
     }
+
+    fn dedupe(config: &str) -> Dedupe {
+        Dedupe::new(toml::from_str::<DedupeConfig>(config).unwrap())
+    }
+
+    fn event(message: &str, value: &str) -> Event {
+        let mut event = Event::Log(LogEvent::from(message));
+        event.as_mut_log().insert("matched", value);
+        event
+    }
+
+    fn messages(events: &[Event]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| event.as_log()["message"].to_string_lossy().into_owned())
+            .collect()
+    }
+
+    const SUMMARY_CONFIG: &str = r#"
+        fields.match = ["matched"]
+        cache.ttl_secs = 10
+        emit_summaries = true
+    "#;
+
+    #[test]
+    fn dedupe_ttl_expires_events() {
+        let mut dedupe = dedupe(
+            r#"
+            fields.match = ["matched"]
+            cache.ttl_secs = 10
+            "#,
+        );
+        let mut output = Vec::new();
+        let now = Instant::now();
+
+        dedupe.transform_one(event("first", "a"), now, &mut output);
+        dedupe.transform_one(
+            event("second", "a"),
+            now + Duration::from_secs(5),
+            &mut output,
+        );
+        dedupe.transform_one(
+            event("third", "a"),
+            now + Duration::from_secs(10),
+            &mut output,
+        );
+        dedupe.transform_one(
+            event("fourth", "a"),
+            now + Duration::from_secs(15),
+            &mut output,
+        );
+
+        assert_eq!(messages(&output), vec!["first", "third"]);
+    }
+
+    #[test]
+    fn dedupe_summarizes_expired_events() {
+        let mut dedupe = dedupe(SUMMARY_CONFIG);
+        let mut output = Vec::new();
+        let now = Instant::now();
+
+        dedupe.transform_one(event("first", "a"), now, &mut output);
+        dedupe.transform_one(event("second", "a"), now, &mut output);
+        dedupe.transform_one(event("third", "a"), now, &mut output);
+        dedupe.transform_one(event("other", "b"), now, &mut output);
+        assert_eq!(output.len(), 2);
+
+        output.clear();
+        dedupe.flush_expired(now + Duration::from_secs(9), &mut output);
+        assert!(output.is_empty());
+
+        // Events without duplicates expire without a summary
+        dedupe.flush_expired(now + Duration::from_secs(10), &mut output);
+        assert_eq!(output.len(), 1);
+        let summary = output[0].as_log();
+        assert_eq!(
+            summary.get("message"),
+            Some(&Value::from("message repeated 2 times"))
+        );
+        assert_eq!(summary.get("matched"), Some(&Value::from("a")));
+        assert_eq!(summary.get("repeat_count"), Some(&Value::from(2)));
+        assert!(summary.get("timestamp").is_some());
+
+        output.clear();
+        dedupe.flush_all(&mut output);
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn dedupe_summarizes_evicted_events() {
+        let mut dedupe = dedupe(
+            r#"
+            fields.match = ["matched"]
+            cache.num_events = 1
+            emit_summaries = true
+            "#,
+        );
+        let mut output = Vec::new();
+        let now = Instant::now();
+
+        dedupe.transform_one(event("first", "a"), now, &mut output);
+        dedupe.transform_one(event("second", "a"), now, &mut output);
+        dedupe.transform_one(event("other", "b"), now, &mut output);
+
+        assert_eq!(
+            messages(&output),
+            vec!["first", "message repeated 1 times", "other"]
+        );
+    }
+
+    #[tokio::test]
+    async fn dedupe_summarizes_events_when_input_ends() {
+        let dedupe = Box::new(dedupe(SUMMARY_CONFIG));
+        let output = dedupe
+            .transform(Box::pin(stream::iter(vec![
+                event("first", "a"),
+                event("second", "a"),
+            ])))
+            .collect::<Vec<_>>()
+            .await;
+
+        assert_eq!(output.len(), 2);
+        assert_eq!(
+            output[1].as_log().get("repeat_count"),
+            Some(&Value::from(1))
+        );
+    }
+
+    #[test]
+    fn dedupe_summaries_do_not_delay_acknowledgements() {
+        let mut dedupe = dedupe(SUMMARY_CONFIG);
+        let mut output = Vec::new();
+        let (batch, mut receiver) = BatchNotifier::new_with_receiver();
+
+        dedupe.transform_one(
+            event("first", "a").with_batch_notifier(&batch),
+            Instant::now(),
+            &mut output,
+        );
+        drop(batch);
+        drop(output);
+
+        // The cached copy of the event doesn't hold its finalizers
+        assert_eq!(receiver.try_recv(), Ok(BatchStatus::Delivered));
+    }
 }
@@ -0,0 +1,287 @@
+use std::collections::HashSet;
+
+use vector_config::configurable_component;
+use vector_core::config::{clone_input_definitions, LogNamespace};
+use vector_core::transform::SyncTransform;
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::Event,
+    schema,
+    transforms::{route::UNMATCHED_ROUTE, Transform},
+};
+
+/// A named route.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct Route {
+    /// The name of the route.
+    ///
+    /// The route can be referenced as an input by other components with the name
+    /// `<transform_name>.<name>`.
+    #[configurable(metadata(docs::examples = "errors", docs::examples = "audit"))]
+    pub name: String,
+
+    /// The condition an event must match to be sent to this route.
+    pub condition: AnyCondition,
+}
+
+/// Configuration for the `exclusive_route` transform.
+#[configurable_component(transform(
+    "exclusive_route",
+    "Route each event to the first of an ordered list of conditions it matches."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ExclusiveRouteConfig {
+    /// An ordered list of routes.
+    ///
+    /// Conditions are checked in order, and each event is sent to the first route it matches only.
+    /// If an event doesn't match any route, it is sent to the `<transform_name>._unmatched` output.
+    ///
+    /// Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
+    /// as a route name.
+    pub routes: Vec<Route>,
+}
+
+impl GenerateConfig for ExclusiveRouteConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[[routes]]
+name = "errors"
+condition = '.level == "error"'
+
+[[routes]]
+name = "warnings"
+condition = '.level == "warn"'"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "exclusive_route")]
+impl TransformConfig for ExclusiveRouteConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let route = ExclusiveRoute::new(self, context)?;
+        Ok(Transform::synchronous(route))
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn validate(&self, _: &schema::Definition) -> Result<(), Vec<String>> {
+        let mut errors = Vec::new();
+        let mut names = HashSet::new();
+        for route in &self.routes {
+            if route.name == UNMATCHED_ROUTE || route.name == "_default" {
+                errors.push(format!(
+                    "cannot have a named output with reserved name: `{}`",
+                    route.name
+                ));
+            } else if !names.insert(route.name.as_str()) {
+                errors.push(format!("duplicate route name: `{}`", route.name));
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        self.routes
+            .iter()
+            .map(|route| route.name.as_str())
+            .chain(std::iter::once(UNMATCHED_ROUTE))
+            .map(|output_name| {
+                TransformOutput::new(DataType::all(), clone_input_definitions(input_definitions))
+                    .with_port(output_name)
+            })
+            .collect()
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Clone)]
+pub struct ExclusiveRoute {
+    conditions: Vec<(String, Condition)>,
+}
+
+impl ExclusiveRoute {
+    pub fn new(config: &ExclusiveRouteConfig, context: &TransformContext) -> crate::Result<Self> {
+        let conditions = config
+            .routes
+            .iter()
+            .map(|route| {
+                let condition = route.condition.build(&context.enrichment_tables)?;
+                Ok((route.name.clone(), condition))
+            })
+            .collect::<crate::Result<_>>()?;
+        Ok(Self { conditions })
+    }
+}
+
+impl SyncTransform for ExclusiveRoute {
+    fn transform(
+        &mut self,
+        mut event: Event,
+        output: &mut vector_core::transform::TransformOutputsBuf,
+    ) {
+        for (output_name, condition) in &self.conditions {
+            let (result, checked) = condition.check(event);
+            if result {
+                output.push(Some(output_name), checked);
+                return;
+            }
+            event = checked;
+        }
+        output.push(Some(UNMATCHED_ROUTE), event);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use vector_core::transform::TransformOutputsBuf;
+
+    use super::*;
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<ExclusiveRouteConfig>();
+    }
+
+    fn config() -> ExclusiveRouteConfig {
+        toml::from_str(
+            r#"
+            [[routes]]
+            name = "first"
+            condition = '.message == "hello world"'
+
+            [[routes]]
+            name = "second"
+            condition = '.second == "second"'
+        "#,
+        )
+        .unwrap()
+    }
+
+    fn route(message: serde_json::Value) -> HashMap<&'static str, Vec<Event>> {
+        let output_names = ["first", "second", UNMATCHED_ROUTE];
+        let event = Event::from_json_value(message, LogNamespace::Legacy).unwrap();
+
+        let mut transform = ExclusiveRoute::new(&config(), &Default::default()).unwrap();
+        let mut outputs = TransformOutputsBuf::new_with_capacity(
+            output_names
+                .iter()
+                .map(|output_name| {
+                    TransformOutput::new(DataType::all(), HashMap::new())
+                        .with_port(output_name.to_owned())
+                })
+                .collect(),
+            1,
+        );
+        transform.transform(event, &mut outputs);
+
+        output_names
+            .into_iter()
+            .map(|output_name| (output_name, outputs.drain_named(output_name).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn routes_to_first_match_only() {
+        let outputs = route(serde_json::json!({"message": "hello world", "second": "second"}));
+        assert_eq!(outputs["first"].len(), 1);
+        assert!(outputs["second"].is_empty());
+        assert!(outputs[UNMATCHED_ROUTE].is_empty());
+    }
+
+    #[test]
+    fn routes_to_later_match() {
+        let outputs = route(serde_json::json!({"message": "NOPE", "second": "second"}));
+        assert!(outputs["first"].is_empty());
+        assert_eq!(outputs["second"].len(), 1);
+        assert!(outputs[UNMATCHED_ROUTE].is_empty());
+    }
+
+    #[test]
+    fn routes_unmatched() {
+        let outputs = route(serde_json::json!({"message": "NOPE"}));
+        assert!(outputs["first"].is_empty());
+        assert!(outputs["second"].is_empty());
+        assert_eq!(outputs[UNMATCHED_ROUTE].len(), 1);
+    }
+
+    #[test]
+    fn outputs_include_unmatched() {
+        let outputs = config().outputs(
+            enrichment::TableRegistry::default(),
+            &[(
+                OutputId::from("in"),
+                schema::Definition::default_legacy_namespace(),
+            )],
+            LogNamespace::Legacy,
+        );
+        let ports: Vec<_> = outputs
+            .iter()
+            .map(|output| output.port.as_deref())
+            .collect();
+        assert_eq!(
+            ports,
+            vec![Some("first"), Some("second"), Some(UNMATCHED_ROUTE)]
+        );
+        assert!(outputs.iter().all(|output| output
+            .schema_definitions(true)
+            .contains_key(&OutputId::from("in"))));
+    }
+
+    #[test]
+    fn validates_route_names() {
+        let config = toml::from_str::<ExclusiveRouteConfig>(
+            r#"
+            [[routes]]
+            name = "_unmatched"
+            condition = "true"
+
+            [[routes]]
+            name = "first"
+            condition = "true"
+
+            [[routes]]
+            name = "first"
+            condition = "false"
+        "#,
+        )
+        .unwrap();
+
+        let errors = config
+            .validate(&schema::Definition::default_legacy_namespace())
+            .unwrap_err();
+        assert_eq!(
+            errors,
+            vec![
+                "cannot have a named output with reserved name: `_unmatched`".to_owned(),
+                "duplicate route name: `first`".to_owned(),
+            ]
+        );
+    }
+}
@@ -0,0 +1,218 @@
+use std::{collections::HashMap, num::NonZeroUsize, time::Duration};
+
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    event::Event,
+    schema,
+    sinks::util::buffer::metrics::{MetricSet, MetricSetSettings},
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `incremental_to_absolute` transform.
+#[configurable_component(transform(
+    "incremental_to_absolute",
+    "Convert incremental metrics to absolute metrics."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct IncrementalToAbsoluteConfig {
+    /// The amount of time, in seconds, a series is tracked after it was last seen.
+    ///
+    /// When an incremental metric arrives for a series that has expired, its running total starts over
+    /// from the value of that metric.
+    #[serde(default = "default_time_to_live_secs")]
+    #[configurable(metadata(docs::human_name = "Time To Live"))]
+    pub time_to_live_secs: u64,
+
+    /// The maximum number of series to track.
+    ///
+    /// When a new series would exceed this limit, the least recently seen series is evicted, and its
+    /// running total starts over if it's seen again. By default, the number of series is unbounded.
+    #[configurable(metadata(docs::type_unit = "series"))]
+    pub max_series: Option<NonZeroUsize>,
+}
+
+const fn default_time_to_live_secs() -> u64 {
+    300
+}
+
+impl Default for IncrementalToAbsoluteConfig {
+    fn default() -> Self {
+        Self {
+            time_to_live_secs: default_time_to_live_secs(),
+            max_series: None,
+        }
+    }
+}
+
+impl_generate_config_from_default!(IncrementalToAbsoluteConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "incremental_to_absolute")]
+impl TransformConfig for IncrementalToAbsoluteConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(IncrementalToAbsolute::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::metric()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        _: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+    }
+}
+
+/// Maintains a running total for each incremental series and emits it as an absolute metric.
+///
+/// Absolute metrics are passed through unchanged.
+#[derive(Clone)]
+pub struct IncrementalToAbsolute {
+    state: MetricSet,
+}
+
+impl IncrementalToAbsolute {
+    pub fn new(config: &IncrementalToAbsoluteConfig) -> Self {
+        Self {
+            state: MetricSet::with_settings(MetricSetSettings {
+                max_series: config.max_series,
+                time_to_live: Some(Duration::from_secs(config.time_to_live_secs)),
+            }),
+        }
+    }
+}
+
+impl FunctionTransform for IncrementalToAbsolute {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        if let Some(metric) = self.state.make_absolute(event.into_metric()) {
+            output.push(Event::Metric(metric));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vector_common::config::ComponentKey;
+    use vrl::value::Kind;
+
+    use super::*;
+    use crate::{
+        event::{
+            metric::{MetricKind, MetricValue},
+            Metric,
+        },
+        schema::Definition,
+        test_util::components::assert_transform_compliance,
+        transforms::test::{create_topology, transform_one},
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<IncrementalToAbsoluteConfig>();
+    }
+
+    fn counter(name: &'static str, kind: MetricKind, value: f64) -> Metric {
+        Metric::new(name, kind, MetricValue::Counter { value })
+    }
+
+    fn process(transform: &mut IncrementalToAbsolute, metric: Metric) -> Metric {
+        transform_one(transform, Event::Metric(metric))
+            .unwrap()
+            .into_metric()
+    }
+
+    #[test]
+    fn accumulates_incremental_metrics() {
+        let mut transform = IncrementalToAbsolute::new(&IncrementalToAbsoluteConfig::default());
+
+        for (value, total) in [(1.0, 1.0), (2.0, 3.0), (5.0, 8.0)] {
+            let output = process(&mut transform, counter("a", MetricKind::Incremental, value));
+            assert_eq!(output, counter("a", MetricKind::Absolute, total));
+        }
+
+        let output = process(&mut transform, counter("b", MetricKind::Incremental, 2.0));
+        assert_eq!(output, counter("b", MetricKind::Absolute, 2.0));
+    }
+
+    #[test]
+    fn passes_through_absolute_metrics() {
+        let mut transform = IncrementalToAbsolute::new(&IncrementalToAbsoluteConfig::default());
+
+        let output = process(&mut transform, counter("a", MetricKind::Absolute, 10.0));
+        assert_eq!(output, counter("a", MetricKind::Absolute, 10.0));
+    }
+
+    #[test]
+    fn evicts_least_recently_seen_series() {
+        let mut transform = IncrementalToAbsolute::new(&IncrementalToAbsoluteConfig {
+            max_series: NonZeroUsize::new(2),
+            ..Default::default()
+        });
+
+        process(&mut transform, counter("a", MetricKind::Incremental, 1.0));
+        process(&mut transform, counter("b", MetricKind::Incremental, 1.0));
+        process(&mut transform, counter("a", MetricKind::Incremental, 1.0));
+        // Tracking `c` evicts `b`, which was seen least recently
+        process(&mut transform, counter("c", MetricKind::Incremental, 1.0));
+
+        let output = process(&mut transform, counter("a", MetricKind::Incremental, 1.0));
+        assert_eq!(output, counter("a", MetricKind::Absolute, 3.0));
+        let output = process(&mut transform, counter("b", MetricKind::Incremental, 1.0));
+        assert_eq!(output, counter("b", MetricKind::Absolute, 1.0));
+    }
+
+    #[test]
+    fn expires_series_after_time_to_live() {
+        let mut transform = IncrementalToAbsolute::new(&IncrementalToAbsoluteConfig {
+            time_to_live_secs: 0,
+            ..Default::default()
+        });
+
+        process(&mut transform, counter("a", MetricKind::Incremental, 1.0));
+        let output = process(&mut transform, counter("a", MetricKind::Incremental, 1.0));
+        assert_eq!(output, counter("a", MetricKind::Absolute, 1.0));
+    }
+
+    #[tokio::test]
+    async fn emits_metrics_immediately() {
+        assert_transform_compliance(async {
+            let config = IncrementalToAbsoluteConfig::default();
+            let (tx, rx) = mpsc::channel(10);
+            let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+            let mut event = Event::Metric(counter("a", MetricKind::Incremental, 2.0))
+                .with_source_id(Arc::new(ComponentKey::from("in")))
+                .with_upstream_id(Arc::new(OutputId::from("transform")));
+            event.metadata_mut().set_schema_definition(&Arc::new(
+                Definition::new_with_default_metadata(Kind::any_object(), [LogNamespace::Legacy]),
+            ));
+            event.metadata_mut().set_source_type("unit_test_stream");
+
+            tx.send(event.clone()).await.unwrap();
+            let output = out.recv().await.unwrap().into_metric();
+            assert_eq!(output.kind(), MetricKind::Absolute);
+            assert_eq!(output.value(), &MetricValue::Counter { value: 2.0 });
+
+            tx.send(event).await.unwrap();
+            let output = out.recv().await.unwrap().into_metric();
+            assert_eq!(output.value(), &MetricValue::Counter { value: 4.0 });
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
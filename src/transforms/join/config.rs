@@ -0,0 +1,191 @@
+use std::{
+    collections::{HashMap, HashSet},
+    time::Duration,
+};
+
+use indexmap::IndexMap;
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema::{self, Definition},
+    transforms::{
+        join::{Join, UNMATCHED},
+        reduce::MergeStrategy,
+        Transform,
+    },
+};
+
+/// Configuration for the `join` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "join",
+    "Correlate log events from two inputs that share the same values for a set of fields."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct JoinConfig {
+    /// The input whose events are the left side of the join.
+    ///
+    /// Must be the ID of one of the transform's inputs, such as `my_source` or `my_route.errors`.
+    #[configurable(metadata(docs::examples = "http_requests"))]
+    pub left: String,
+
+    /// The input whose events are the right side of the join.
+    ///
+    /// Must be the ID of one of the transform's inputs, such as `my_source` or `my_route.errors`.
+    #[configurable(metadata(docs::examples = "http_responses"))]
+    pub right: String,
+
+    /// An ordered list of fields by which to join events.
+    ///
+    /// Events from the left and right inputs are joined when they have the same values for all of
+    /// these fields. Events missing any of these fields are never joined, and are flushed right
+    /// away as unmatched events.
+    #[configurable(metadata(docs::examples = "request_id", docs::examples = "client_ip"))]
+    pub on: Vec<String>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub join_type: JoinType,
+
+    /// The maximum period of time an event waits for a matching event from the other input, in
+    /// milliseconds.
+    ///
+    /// Right events are kept for this period of time, and are joined with every matching left event
+    /// received in the meantime.
+    #[serde(default = "default_window_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Window"))]
+    pub window_ms: Duration,
+
+    /// The interval to check for and flush any expired events, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_ms: Duration,
+
+    /// A map of field names to custom merge strategies.
+    ///
+    /// For each field specified, the given strategy is used for combining the values of the left
+    /// and right events. Otherwise, the value of the left event is kept when both events have the
+    /// field.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "An individual merge strategy."
+    ))]
+    pub merge_strategies: IndexMap<String, MergeStrategy>,
+}
+
+/// The type of join.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JoinType {
+    /// Only joined events are sent to the default output.
+    ///
+    /// Left events for which no matching right event was received are sent to the `unmatched`
+    /// output.
+    #[default]
+    Inner,
+
+    /// Joined events, as well as left events for which no matching right event was received, are
+    /// sent to the default output.
+    Left,
+}
+
+const fn default_window_ms() -> Duration {
+    Duration::from_millis(30000)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+impl JoinConfig {
+    /// Ensures that `left` and `right` are inputs of the transform.
+    fn validate_inputs(&self, context: &TransformContext) -> crate::Result<()> {
+        // The definitions of the default output are keyed by the inputs of the transform
+        let inputs = context
+            .schema_definitions
+            .get(&None)
+            .map(|definitions| {
+                definitions
+                    .keys()
+                    .map(ToString::to_string)
+                    .collect::<HashSet<_>>()
+            })
+            .unwrap_or_default();
+
+        for (option, input) in [("left", &self.left), ("right", &self.right)] {
+            if !inputs.contains(input) {
+                return Err(format!(
+                    "`{}` must be one of the inputs of the transform, got {:?}",
+                    option, input
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl GenerateConfig for JoinConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"left = "http_requests"
+right = "http_responses"
+on = ["request_id"]"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "join")]
+impl TransformConfig for JoinConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        // Outside of a topology, such as in tests, the inputs of the transform are unknown
+        if context.key.is_some() {
+            self.validate_inputs(context)?;
+        }
+        Join::new(self).map(Transform::multi_output_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Joined events combine the fields of events from both inputs, so all of the definitions
+        // are merged for the default output.
+        let merged_definition = input_definitions
+            .iter()
+            .map(|(_output, definition)| definition.clone())
+            .reduce(Definition::merge)
+            .unwrap_or_else(Definition::any);
+
+        let mut default_definitions = HashMap::new();
+        let mut unmatched_definitions = HashMap::new();
+        for (output, definition) in input_definitions {
+            default_definitions.insert(output.clone(), merged_definition.clone());
+            // Unmatched events are not modified.
+            unmatched_definitions.insert(output.clone(), definition.clone());
+        }
+
+        vec![
+            TransformOutput::new(DataType::Log, default_definitions),
+            TransformOutput::new(DataType::Log, unmatched_definitions).with_port(UNMATCHED),
+        ]
+    }
+}
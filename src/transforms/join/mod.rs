@@ -0,0 +1,293 @@
+use std::{
+    collections::{hash_map, BTreeMap, HashMap, VecDeque},
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use async_stream::stream;
+use futures::{Stream, StreamExt};
+use indexmap::IndexMap;
+
+use crate::{
+    config::TransformOutput,
+    event::{discriminant::Discriminant, Event, EventArray, EventContainer, LogEvent, Value},
+    internal_events::JoinUnknownInput,
+    transforms::{
+        reduce::{get_value_merger, MergeStrategy, ReduceValueMerger},
+        MultiOutputTaskTransform, TransformOutputsBuf,
+    },
+};
+
+mod config;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{JoinConfig, JoinType};
+
+pub(crate) const UNMATCHED: &str = "unmatched";
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+enum Side {
+    Left,
+    Right,
+}
+
+/// A right event, kept until it expires to be joined with matching left events.
+#[derive(Debug)]
+struct RightEvent {
+    event: LogEvent,
+    expires_at: Instant,
+    matched: bool,
+}
+
+pub struct Join {
+    left: String,
+    right: String,
+    on: Vec<String>,
+    join_type: JoinType,
+    window: Duration,
+    flush_period: Duration,
+    merge_strategies: IndexMap<String, MergeStrategy>,
+    /// Left events waiting for a matching right event, in the order they expire, by join key.
+    left_events: HashMap<Discriminant, VecDeque<(LogEvent, Instant)>>,
+    right_events: HashMap<Discriminant, RightEvent>,
+}
+
+impl Join {
+    pub fn new(config: &JoinConfig) -> crate::Result<Self> {
+        if config.left == config.right {
+            return Err("`left` and `right` must be different inputs".into());
+        }
+
+        Ok(Self {
+            left: config.left.clone(),
+            right: config.right.clone(),
+            on: config.on.clone(),
+            join_type: config.join_type,
+            window: config.window_ms,
+            flush_period: config.flush_period_ms,
+            merge_strategies: config.merge_strategies.clone(),
+            left_events: HashMap::new(),
+            right_events: HashMap::new(),
+        })
+    }
+
+    fn side(&self, event: &LogEvent) -> Option<Side> {
+        let input = event.metadata().upstream_id()?.to_string();
+        if input == self.left {
+            Some(Side::Left)
+        } else if input == self.right {
+            Some(Side::Right)
+        } else {
+            None
+        }
+    }
+
+    fn has_join_fields(&self, event: &LogEvent) -> bool {
+        self.on.iter().all(|field| {
+            event
+                .parse_path_and_get_value(field.as_str())
+                .ok()
+                .flatten()
+                .is_some()
+        })
+    }
+
+    fn transform_one(&mut self, event: Event, now: Instant, output: &mut TransformOutputsBuf) {
+        let event = event.into_log();
+        let Some(side) = self.side(&event) else {
+            emit!(JoinUnknownInput {
+                input: event.metadata().upstream_id()
+            });
+            output.push(Some(UNMATCHED), event.into());
+            return;
+        };
+        // Events missing any of the fields to join on are never matched.
+        if !self.has_join_fields(&event) {
+            match side {
+                Side::Left => self.flush_left(event, output),
+                Side::Right => output.push(Some(UNMATCHED), event.into()),
+            }
+            return;
+        }
+        let key = Discriminant::from_log_event(&event, &self.on);
+
+        match side {
+            Side::Left => match self.right_events.get_mut(&key) {
+                Some(right) => {
+                    right.matched = true;
+                    let joined = merge(event, &right.event, &self.merge_strategies);
+                    output.push(None, joined.into());
+                }
+                None => self
+                    .left_events
+                    .entry(key)
+                    .or_default()
+                    .push_back((event, now + self.window)),
+            },
+            Side::Right => {
+                let waiting = self.left_events.remove(&key).unwrap_or_default();
+                let matched = !waiting.is_empty();
+                for (left, _) in waiting {
+                    let joined = merge(left, &event, &self.merge_strategies);
+                    output.push(None, joined.into());
+                }
+
+                let right = RightEvent {
+                    event,
+                    expires_at: now + self.window,
+                    matched,
+                };
+                // Only the latest right event is kept for each key.
+                if let Some(previous) = self.right_events.insert(key, right) {
+                    self.flush_right(previous, output);
+                }
+            }
+        }
+    }
+
+    fn flush_left(&self, event: LogEvent, output: &mut TransformOutputsBuf) {
+        match self.join_type {
+            JoinType::Inner => output.push(Some(UNMATCHED), event.into()),
+            JoinType::Left => output.push(None, event.into()),
+        }
+    }
+
+    fn flush_right(&self, right: RightEvent, output: &mut TransformOutputsBuf) {
+        if !right.matched {
+            output.push(Some(UNMATCHED), right.event.into());
+        }
+    }
+
+    fn flush_expired(&mut self, now: Instant, output: &mut TransformOutputsBuf) {
+        let mut expired_left = Vec::new();
+        self.left_events.retain(|_, waiting| {
+            while waiting
+                .front()
+                .map_or(false, |(_, expires_at)| *expires_at <= now)
+            {
+                expired_left.extend(waiting.pop_front().map(|(event, _)| event));
+            }
+            !waiting.is_empty()
+        });
+        for event in expired_left {
+            self.flush_left(event, output);
+        }
+
+        let expired_right = self
+            .right_events
+            .iter()
+            .filter(|(_, right)| right.expires_at <= now)
+            .map(|(key, _)| key.clone())
+            .collect::<Vec<_>>();
+        for key in expired_right {
+            if let Some(right) = self.right_events.remove(&key) {
+                self.flush_right(right, output);
+            }
+        }
+    }
+
+    fn flush_all(&mut self, output: &mut TransformOutputsBuf) {
+        for (_, waiting) in std::mem::take(&mut self.left_events) {
+            for (event, _) in waiting {
+                self.flush_left(event, output);
+            }
+        }
+        for (_, right) in std::mem::take(&mut self.right_events) {
+            self.flush_right(right, output);
+        }
+    }
+}
+
+/// Merges the fields of a right event into a left event.
+fn merge(
+    left: LogEvent,
+    right: &LogEvent,
+    strategies: &IndexMap<String, MergeStrategy>,
+) -> LogEvent {
+    let (left_value, mut metadata) = left.into_parts();
+    metadata.merge(right.metadata().clone());
+
+    let mut fields: HashMap<String, Box<dyn ReduceValueMerger>> = HashMap::new();
+    for (k, v) in into_fields(left_value)
+        .into_iter()
+        .chain(into_fields(right.value().clone()))
+    {
+        match fields.entry(k) {
+            hash_map::Entry::Vacant(entry) => {
+                // Without a custom strategy, the first value, from the left event, is kept.
+                let strategy = strategies
+                    .get(entry.key())
+                    .unwrap_or(&MergeStrategy::Discard);
+                match get_value_merger(v, strategy) {
+                    Ok(merger) => {
+                        entry.insert(merger);
+                    }
+                    Err(error) => {
+                        warn!(message = "Failed to merge value.", %error);
+                    }
+                }
+            }
+            hash_map::Entry::Occupied(mut entry) => {
+                if let Err(error) = entry.get_mut().add(v) {
+                    warn!(message = "Failed to merge value.", %error);
+                }
+            }
+        }
+    }
+
+    let mut event = LogEvent::new_with_metadata(metadata);
+    for (k, v) in fields {
+        if let Err(error) = v.insert_into(k, &mut event) {
+            warn!(message = "Failed to merge values for field.", %error);
+        }
+    }
+    event
+}
+
+fn into_fields(value: Value) -> BTreeMap<String, Value> {
+    match value {
+        Value::Object(fields) => fields,
+        _ => BTreeMap::new(),
+    }
+}
+
+impl MultiOutputTaskTransform for Join {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = EventArray> + Send>>,
+        outputs: Vec<TransformOutput>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut flush_stream = tokio::time::interval(self.flush_period);
+        // Empty buffers are cheap to clone, unlike the schema definitions of the outputs
+        let empty_output = TransformOutputsBuf::new_with_capacity(outputs, 1);
+
+        Box::pin(stream! {
+            let mut done = false;
+            while !done {
+                let mut output = empty_output.clone();
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_expired(Instant::now(), &mut output);
+                    },
+                    maybe_events = input_rx.next() => {
+                        match maybe_events {
+                            None => {
+                                self.flush_all(&mut output);
+                                done = true;
+                            }
+                            Some(events) => {
+                                let now = Instant::now();
+                                for event in events.into_events() {
+                                    self.transform_one(event, now, &mut output);
+                                }
+                            }
+                        }
+                    }
+                };
+                yield output;
+            }
+        })
+    }
+}
@@ -0,0 +1,314 @@
+use std::sync::Arc;
+
+use futures::stream;
+use serde_json::json;
+use vector_core::config::LogNamespace;
+
+use super::*;
+use crate::{
+    config::{ComponentKey, DataType, OutputId, TransformConfig, TransformContext},
+    schema::Definition,
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<JoinConfig>();
+}
+
+fn join(config: &str) -> Join {
+    Join::new(&toml::from_str::<JoinConfig>(config).unwrap()).unwrap()
+}
+
+fn outputs() -> Vec<TransformOutput> {
+    vec![
+        TransformOutput::new(DataType::all(), HashMap::new()),
+        TransformOutput::new(DataType::all(), HashMap::new()).with_port(UNMATCHED),
+    ]
+}
+
+fn event(input: &str, value: serde_json::Value) -> Event {
+    let mut event = Event::from_json_value(value, LogNamespace::Legacy).unwrap();
+    event
+        .metadata_mut()
+        .set_upstream_id(Arc::new(OutputId::from(input)));
+    event
+}
+
+/// Drains the events of both outputs, as JSON values.
+fn drain(output: &mut TransformOutputsBuf) -> (Vec<serde_json::Value>, Vec<serde_json::Value>) {
+    let values = |events: Vec<Event>| {
+        events
+            .into_iter()
+            .map(|event| serde_json::to_value(event.into_log().value()).unwrap())
+            .collect()
+    };
+    (
+        values(output.drain().collect()),
+        values(output.drain_named(UNMATCHED).collect()),
+    )
+}
+
+const CONFIG: &str = r#"
+    left = "responses"
+    right = "requests"
+    on = ["id"]
+    window_ms = 1000
+"#;
+
+#[test]
+fn joins_left_events_with_earlier_right_events() {
+    let mut join = join(CONFIG);
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(
+        event(
+            "requests",
+            json!({"id": 1, "path": "/", "message": "start"}),
+        ),
+        now,
+        &mut output,
+    );
+    join.transform_one(
+        event(
+            "responses",
+            json!({"id": 1, "status": 200, "message": "end"}),
+        ),
+        now,
+        &mut output,
+    );
+    join.transform_one(
+        event(
+            "responses",
+            json!({"id": 1, "status": 500, "message": "end"}),
+        ),
+        now,
+        &mut output,
+    );
+
+    let (joined, unmatched) = drain(&mut output);
+    assert_eq!(
+        joined,
+        vec![
+            json!({"id": 1, "path": "/", "status": 200, "message": "end"}),
+            json!({"id": 1, "path": "/", "status": 500, "message": "end"}),
+        ]
+    );
+    assert!(unmatched.is_empty());
+
+    // Matched right events are not sent to the unmatched output when they expire.
+    join.flush_expired(now + Duration::from_secs(1), &mut output);
+    assert_eq!(drain(&mut output), (vec![], vec![]));
+}
+
+#[test]
+fn joins_waiting_left_events_with_later_right_events() {
+    let mut join = join(CONFIG);
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(
+        event("responses", json!({"id": 1, "status": 200})),
+        now,
+        &mut output,
+    );
+    join.transform_one(
+        event("responses", json!({"id": 2, "status": 404})),
+        now,
+        &mut output,
+    );
+    assert_eq!(drain(&mut output), (vec![], vec![]));
+
+    join.transform_one(
+        event("requests", json!({"id": 1, "path": "/"})),
+        now + Duration::from_millis(500),
+        &mut output,
+    );
+    assert_eq!(
+        drain(&mut output),
+        (vec![json!({"id": 1, "path": "/", "status": 200})], vec![])
+    );
+}
+
+#[test]
+fn sends_expired_events_to_unmatched_output() {
+    let mut join = join(CONFIG);
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(
+        event("responses", json!({"id": 1, "status": 200})),
+        now,
+        &mut output,
+    );
+    join.transform_one(
+        event("requests", json!({"id": 2, "path": "/"})),
+        now,
+        &mut output,
+    );
+
+    join.flush_expired(now + Duration::from_millis(999), &mut output);
+    assert_eq!(drain(&mut output), (vec![], vec![]));
+
+    join.flush_expired(now + Duration::from_secs(1), &mut output);
+    let (joined, mut unmatched) = drain(&mut output);
+    unmatched.sort_by_key(|value| value["id"].as_i64());
+    assert!(joined.is_empty());
+    assert_eq!(
+        unmatched,
+        vec![
+            json!({"id": 1, "status": 200}),
+            json!({"id": 2, "path": "/"}),
+        ]
+    );
+}
+
+#[test]
+fn left_join_sends_unmatched_left_events_to_default_output() {
+    let mut join = join(&format!("{CONFIG}\njoin_type = \"left\""));
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(
+        event("responses", json!({"id": 1, "status": 200})),
+        now,
+        &mut output,
+    );
+    join.flush_expired(now + Duration::from_secs(1), &mut output);
+    assert_eq!(
+        drain(&mut output),
+        (vec![json!({"id": 1, "status": 200})], vec![])
+    );
+}
+
+#[test]
+fn flushes_events_missing_join_fields() {
+    let mut join = join(CONFIG);
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(event("requests", json!({"path": "/"})), now, &mut output);
+    join.transform_one(event("responses", json!({"status": 200})), now, &mut output);
+    assert_eq!(
+        drain(&mut output),
+        (vec![], vec![json!({"path": "/"}), json!({"status": 200})])
+    );
+    assert!(join.left_events.is_empty());
+    assert!(join.right_events.is_empty());
+
+    let mut left_join = self::join(&format!("{CONFIG}\njoin_type = \"left\""));
+    left_join.transform_one(event("requests", json!({"path": "/"})), now, &mut output);
+    left_join.transform_one(event("responses", json!({"status": 200})), now, &mut output);
+    assert_eq!(
+        drain(&mut output),
+        (vec![json!({"status": 200})], vec![json!({"path": "/"})])
+    );
+}
+
+#[test]
+fn merges_fields_with_strategies() {
+    let mut join = join(&format!(
+        r#"{CONFIG}
+        [merge_strategies]
+        bytes = "sum"
+        tags = "flat_unique"
+        "#
+    ));
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+    let now = Instant::now();
+
+    join.transform_one(
+        event("requests", json!({"id": 1, "bytes": 10, "tags": ["a"]})),
+        now,
+        &mut output,
+    );
+    join.transform_one(
+        event("responses", json!({"id": 1, "bytes": 32, "tags": ["b"]})),
+        now,
+        &mut output,
+    );
+
+    let (mut joined, _) = drain(&mut output);
+    joined[0]["tags"]
+        .as_array_mut()
+        .unwrap()
+        .sort_by_key(|tag| tag.to_string());
+    assert_eq!(
+        joined,
+        vec![json!({"id": 1, "bytes": 42, "tags": ["a", "b"]})]
+    );
+}
+
+#[test]
+fn sends_events_from_unknown_inputs_to_unmatched_output() {
+    let mut join = join(CONFIG);
+    let mut output = TransformOutputsBuf::new_with_capacity(outputs(), 1);
+
+    join.transform_one(
+        event("other", json!({"id": 1})),
+        Instant::now(),
+        &mut output,
+    );
+    assert_eq!(drain(&mut output), (vec![], vec![json!({"id": 1})]));
+}
+
+#[test]
+fn rejects_same_left_and_right_inputs() {
+    let config = toml::from_str::<JoinConfig>(
+        r#"
+        left = "requests"
+        right = "requests"
+        on = ["id"]
+        "#,
+    )
+    .unwrap();
+    assert!(Join::new(&config).is_err());
+}
+
+#[tokio::test]
+async fn rejects_left_and_right_outside_of_inputs() {
+    let config = toml::from_str::<JoinConfig>(CONFIG).unwrap();
+    let context = |inputs: &[&str]| TransformContext {
+        key: Some(ComponentKey::from("join")),
+        schema_definitions: HashMap::from([(
+            None,
+            inputs
+                .iter()
+                .map(|input| (OutputId::from(*input), Definition::any()))
+                .collect(),
+        )]),
+        ..Default::default()
+    };
+
+    assert!(config
+        .build(&context(&["requests", "responses"]))
+        .await
+        .is_ok());
+    assert!(config.build(&context(&["requests"])).await.is_err());
+}
+
+#[tokio::test]
+async fn flushes_waiting_events_when_input_ends() {
+    let join = Box::new(join(CONFIG));
+    let events = vec![
+        event("requests", json!({"id": 1, "path": "/"})),
+        event("responses", json!({"id": 2, "status": 200})),
+    ];
+
+    let mut unmatched = join
+        .transform(
+            Box::pin(stream::iter(events).map(EventArray::from)),
+            outputs(),
+        )
+        .flat_map(|mut output| stream::iter(drain(&mut output).1))
+        .collect::<Vec<_>>()
+        .await;
+    unmatched.sort_by_key(|value| value["id"].as_i64());
+    assert_eq!(
+        unmatched,
+        vec![
+            json!({"id": 1, "path": "/"}),
+            json!({"id": 2, "status": 200}),
+        ]
+    );
+}
@@ -0,0 +1,188 @@
+use std::{num::NonZeroUsize, path::PathBuf, time::Duration};
+
+use lookup::{lookup_v2::ConfigTargetPath, owned_value_path, OwnedTargetPath};
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use vrl::value::Kind;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::{log_patterns::LogPatterns, Transform},
+};
+
+/// Configuration for the `log_patterns` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "log_patterns",
+    "Cluster log messages into patterns, and annotate each event with its pattern."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct LogPatternsConfig {
+    /// The field containing the message to cluster.
+    ///
+    /// By default, the message of the event is used.
+    #[configurable(metadata(docs::examples = "message", docs::examples = "parent.child"))]
+    pub message_field: Option<ConfigTargetPath>,
+
+    /// The field to store the ID of the pattern of the event in.
+    #[serde(default = "default_pattern_id_field")]
+    pub pattern_id_field: ConfigTargetPath,
+
+    /// The field to store the pattern of the event in.
+    ///
+    /// The variable parts of the pattern are replaced with `<*>`.
+    #[serde(default = "default_pattern_field")]
+    pub pattern_field: ConfigTargetPath,
+
+    /// The depth of the tree used to match messages to patterns.
+    ///
+    /// Messages are matched by their number of tokens, then by their first `depth - 2` tokens, before
+    /// being compared to the patterns they were matched to. Must be at least `3`.
+    #[serde(default = "default_depth")]
+    pub depth: usize,
+
+    /// The fraction of tokens a message must share with a pattern to be part of it.
+    #[serde(default = "default_similarity_threshold")]
+    #[configurable(validation(range(min = 0.0, max = 1.0)))]
+    pub similarity_threshold: f64,
+
+    /// The maximum number of children of each node of the tree used to match messages to patterns.
+    ///
+    /// Once a node is full, messages with new tokens are matched together.
+    #[serde(default = "default_max_children")]
+    pub max_children: NonZeroUsize,
+
+    /// The maximum number of patterns.
+    ///
+    /// Once this limit is reached, the least recently matched pattern is forgotten to make room
+    /// for new ones.
+    #[serde(default = "default_max_clusters")]
+    #[configurable(metadata(docs::type_unit = "patterns"))]
+    pub max_clusters: NonZeroUsize,
+
+    /// Whether to periodically emit the number of events of each pattern as metrics.
+    ///
+    /// An incremental `log_pattern_events_total` counter is emitted every `flush_period_secs` for
+    /// each pattern that matched events, tagged with `pattern_id` and `pattern`.
+    #[serde(default)]
+    pub emit_metrics: bool,
+
+    /// The interval at which metrics are emitted and patterns are persisted, in seconds.
+    #[serde(default = "default_flush_period_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_secs: Duration,
+
+    /// Whether to persist patterns, so that their IDs are stable across restarts.
+    #[serde(default)]
+    pub persist: bool,
+
+    /// The directory used to persist patterns.
+    ///
+    /// By default, the global `data_dir` option is used. Make sure the running user has write
+    /// permissions to this directory.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "/var/lib/vector"))]
+    #[configurable(metadata(docs::human_name = "Data Directory"))]
+    pub data_dir: Option<PathBuf>,
+}
+
+fn default_pattern_id_field() -> ConfigTargetPath {
+    ConfigTargetPath(OwnedTargetPath::event(owned_value_path!("pattern_id")))
+}
+
+fn default_pattern_field() -> ConfigTargetPath {
+    ConfigTargetPath(OwnedTargetPath::event(owned_value_path!("pattern")))
+}
+
+const fn default_depth() -> usize {
+    4
+}
+
+const fn default_similarity_threshold() -> f64 {
+    0.4
+}
+
+fn default_max_children() -> NonZeroUsize {
+    NonZeroUsize::new(100).unwrap()
+}
+
+fn default_max_clusters() -> NonZeroUsize {
+    NonZeroUsize::new(1000).unwrap()
+}
+
+const fn default_flush_period_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+impl GenerateConfig for LogPatternsConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"depth = 4
+similarity_threshold = 0.4
+max_clusters = 1000"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "log_patterns")]
+impl TransformConfig for LogPatternsConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        if self.depth < 3 {
+            return Err("`depth` must be at least 3".into());
+        }
+
+        let state_path = if self.persist {
+            let subdir = context.key.as_ref().map_or("log_patterns", |key| key.id());
+            let data_dir = context
+                .globals
+                .resolve_and_make_data_subdir(self.data_dir.as_ref(), subdir)?;
+            Some(data_dir.join("patterns.json"))
+        } else {
+            None
+        };
+
+        Ok(Transform::event_task(LogPatterns::new(self, state_path)))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let schema_definitions = input_definitions
+            .iter()
+            .map(|(output, definition)| {
+                let definition = definition
+                    .clone()
+                    .with_field(
+                        &self.pattern_id_field.0,
+                        Kind::integer().or_undefined(),
+                        None,
+                    )
+                    .with_field(&self.pattern_field.0, Kind::bytes().or_undefined(), None);
+                (output.clone(), definition)
+            })
+            .collect();
+
+        let data_type = if self.emit_metrics {
+            DataType::Log | DataType::Metric
+        } else {
+            DataType::Log
+        };
+        vec![TransformOutput::new(data_type, schema_definitions)]
+    }
+}
@@ -0,0 +1,322 @@
+//! An implementation of the Drain log template mining algorithm.
+//!
+//! Messages are split into whitespace separated tokens and routed through a fixed depth tree,
+//! first by their number of tokens, then by their leading tokens. Each leaf holds a set of
+//! clusters, and a message joins the most similar cluster of its leaf, or starts a new one.
+//! Tokens that differ between the messages of a cluster are replaced by a wildcard in its template.
+//!
+//! See <https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf>.
+
+use std::{collections::HashMap, num::NonZeroUsize};
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+
+/// The token replacing the variable parts of a template.
+pub const WILDCARD: &str = "<*>";
+
+/// A group of similar messages.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Cluster {
+    pub id: u64,
+    pub tokens: Vec<String>,
+    /// The keys of the nodes leading to the leaf of the cluster.
+    ///
+    /// These are based on the tokens of the first message of the cluster, which may since have
+    /// been replaced by wildcards in its template.
+    pub path: Vec<String>,
+}
+
+impl Cluster {
+    pub fn template(&self) -> String {
+        self.tokens.join(" ")
+    }
+}
+
+/// The state of a [`Drain`] that can be persisted and restored.
+#[derive(Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct DrainState {
+    pub next_id: u64,
+    /// The clusters, from the least to the most recently matched.
+    pub clusters: Vec<Cluster>,
+}
+
+#[derive(Debug, Default)]
+struct Node {
+    children: HashMap<String, Node>,
+    clusters: Vec<u64>,
+}
+
+pub struct Drain {
+    depth: usize,
+    similarity_threshold: f64,
+    max_children: usize,
+    /// The root nodes, by number of tokens.
+    root: HashMap<usize, Node>,
+    clusters: LruCache<u64, Cluster>,
+    next_id: u64,
+}
+
+impl Drain {
+    pub fn new(
+        depth: usize,
+        similarity_threshold: f64,
+        max_children: NonZeroUsize,
+        max_clusters: NonZeroUsize,
+    ) -> Self {
+        Self {
+            depth,
+            similarity_threshold,
+            max_children: max_children.get(),
+            root: HashMap::new(),
+            clusters: LruCache::new(max_clusters),
+            next_id: 0,
+        }
+    }
+
+    pub fn restore(&mut self, state: DrainState) {
+        self.next_id = state.next_id;
+        for cluster in state.clusters {
+            self.next_id = self.next_id.max(cluster.id + 1);
+            self.insert(cluster);
+        }
+    }
+
+    pub fn state(&self) -> DrainState {
+        DrainState {
+            next_id: self.next_id,
+            clusters: self
+                .clusters
+                .iter()
+                .rev()
+                .map(|(_, cluster)| cluster.clone())
+                .collect(),
+        }
+    }
+
+    /// Adds a message to its cluster, and returns that cluster along with whether it was created or
+    /// its template changed.
+    pub fn add(&mut self, message: &str) -> (&Cluster, bool) {
+        let tokens = message.split_whitespace().collect::<Vec<_>>();
+
+        let path = self.route(&tokens);
+        let candidates = self.node_mut(tokens.len(), &path).clusters.clone();
+        let mut best = None;
+        let mut best_similarity = 0.0;
+        let mut best_wildcards = 0;
+        for id in candidates {
+            let Some(cluster) = self.clusters.peek(&id) else {
+                continue;
+            };
+            let (similarity, wildcards) = similarity(&cluster.tokens, &tokens);
+            if best.is_none()
+                || similarity > best_similarity
+                || (similarity == best_similarity && wildcards > best_wildcards)
+            {
+                best = Some(id);
+                best_similarity = similarity;
+                best_wildcards = wildcards;
+            }
+        }
+
+        match best {
+            Some(id) if best_similarity >= self.similarity_threshold => {
+                let cluster = self.clusters.get_mut(&id).expect("cluster exists");
+                let mut changed = false;
+                for (template, token) in cluster.tokens.iter_mut().zip(&tokens) {
+                    if template.as_str() != *token && template.as_str() != WILDCARD {
+                        *template = WILDCARD.to_string();
+                        changed = true;
+                    }
+                }
+                (&*cluster, changed)
+            }
+            _ => {
+                let id = self.next_id;
+                self.next_id += 1;
+                self.insert(Cluster {
+                    id,
+                    tokens: tokens.into_iter().map(str::to_string).collect(),
+                    path,
+                });
+                (self.clusters.peek(&id).expect("cluster exists"), true)
+            }
+        }
+    }
+
+    fn insert(&mut self, cluster: Cluster) {
+        self.node_mut(cluster.tokens.len(), &cluster.path)
+            .clusters
+            .push(cluster.id);
+
+        if let Some((_, evicted)) = self.clusters.push(cluster.id, cluster) {
+            self.node_mut(evicted.tokens.len(), &evicted.path)
+                .clusters
+                .retain(|id| *id != evicted.id);
+        }
+    }
+
+    /// Gets the keys of the nodes leading to the leaf a message is routed to, creating these nodes
+    /// as needed.
+    fn route(&mut self, tokens: &[&str]) -> Vec<String> {
+        let mut path = Vec::new();
+        let mut node = self.root.entry(tokens.len()).or_default();
+        for token in tokens.iter().take(self.depth.saturating_sub(2)) {
+            // Tokens with digits are likely to be variables, so they are all routed together
+            let key = if token.chars().any(|c| c.is_ascii_digit()) {
+                WILDCARD
+            } else {
+                token
+            };
+
+            let key = if node.children.contains_key(key) || node.children.len() < self.max_children
+            {
+                key
+            } else {
+                WILDCARD
+            };
+            node = node.children.entry(key.to_string()).or_default();
+            path.push(key.to_string());
+        }
+        path
+    }
+
+    /// Gets the node at the end of a path, creating the nodes leading to it as needed.
+    fn node_mut(&mut self, len: usize, path: &[String]) -> &mut Node {
+        let mut node = self.root.entry(len).or_default();
+        for key in path {
+            node = node.children.entry(key.clone()).or_default();
+        }
+        node
+    }
+}
+
+/// Gets the fraction of tokens of a message that are equal to the tokens of a template, and the
+/// number of wildcards of the template.
+fn similarity(template: &[String], tokens: &[&str]) -> (f64, usize) {
+    if tokens.is_empty() {
+        return (1.0, 0);
+    }
+
+    let mut equal = 0;
+    let mut wildcards = 0;
+    for (template, token) in template.iter().zip(tokens) {
+        if template == WILDCARD {
+            wildcards += 1;
+        } else if template == token {
+            equal += 1;
+        }
+    }
+    (equal as f64 / tokens.len() as f64, wildcards)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn drain_with(max_clusters: usize) -> Drain {
+        Drain::new(
+            4,
+            0.4,
+            NonZeroUsize::new(100).unwrap(),
+            NonZeroUsize::new(max_clusters).unwrap(),
+        )
+    }
+
+    #[test]
+    fn merges_similar_messages() {
+        let mut drain = drain_with(10);
+
+        let first = drain.add("connected to 10.0.0.1 in 5ms").0.id;
+        let cluster = drain.add("connected to 10.0.0.2 in 7ms").0.clone();
+        assert_eq!(cluster.id, first);
+        assert_eq!(cluster.template(), "connected to <*> in <*>");
+
+        let other = drain.add("disk is full").0.id;
+        assert_ne!(other, first);
+    }
+
+    #[test]
+    fn separates_messages_by_length() {
+        let mut drain = drain_with(10);
+
+        let first = drain.add("user logged in").0.id;
+        let second = drain.add("user logged in twice").0.id;
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn evicts_least_recently_used_cluster() {
+        let mut drain = drain_with(2);
+
+        let first = drain.add("first message").0.id;
+        drain.add("second message here");
+        drain.add("first message");
+        drain.add("third message is longer");
+
+        let state = drain.state();
+        let ids = state
+            .clusters
+            .iter()
+            .map(|cluster| cluster.id)
+            .collect::<Vec<_>>();
+        assert_eq!(ids, vec![first, 2]);
+    }
+
+    #[test]
+    fn restores_state() {
+        let mut drain = drain_with(10);
+        let id = drain.add("request 1 failed").0.id;
+        drain.add("request 2 failed");
+
+        let mut restored = drain_with(10);
+        restored.restore(drain.state());
+        assert_eq!(restored.state(), drain.state());
+
+        let (cluster, _) = restored.add("request 3 failed");
+        assert_eq!(cluster.id, id);
+        assert_eq!(cluster.template(), "request <*> failed");
+        assert_eq!(restored.add("something else entirely").0.id, 1);
+    }
+
+    fn leaf(drain: &mut Drain, len: usize, path: &[&str]) -> Vec<u64> {
+        let path = path.iter().map(|key| key.to_string()).collect::<Vec<_>>();
+        drain.node_mut(len, &path).clusters.clone()
+    }
+
+    #[test]
+    fn keeps_clusters_on_their_route() {
+        let mut drain = Drain::new(
+            4,
+            0.4,
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+        );
+
+        // Once the root is full, messages with other first tokens share the wildcard child, and
+        // that token becomes a variable of their template
+        let alice = drain.add("alice logged in").0.id;
+        let others = drain.add("bob logged in").0.id;
+        assert_eq!(drain.add("carol logged in").0.template(), "<*> logged in");
+        drain.add("alice logged in");
+
+        // Restoring the most recently used cluster last keeps it on its original route, even
+        // though the root is full by then
+        let mut restored = Drain::new(
+            4,
+            0.4,
+            NonZeroUsize::new(1).unwrap(),
+            NonZeroUsize::new(2).unwrap(),
+        );
+        restored.restore(drain.state());
+        assert_eq!(leaf(&mut restored, 3, &["alice", "logged"]), vec![alice]);
+        assert_eq!(leaf(&mut restored, 3, &[WILDCARD, "logged"]), vec![others]);
+        assert_eq!(restored.add("alice logged in").0.id, alice);
+
+        // Evicted clusters are removed from their leaf
+        restored.add("disk is full");
+        restored.add("queue is empty");
+        assert!(leaf(&mut restored, 3, &["alice", "logged"]).is_empty());
+        assert!(leaf(&mut restored, 3, &[WILDCARD, "logged"]).is_empty());
+    }
+}
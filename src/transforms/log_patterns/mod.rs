@@ -0,0 +1,198 @@
+use std::{
+    collections::HashMap,
+    fs, io,
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::Duration,
+};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use lookup::lookup_v2::ConfigTargetPath;
+use vector_core::metric_tags;
+
+use crate::{
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event,
+    },
+    internal_events::{LogPatternsLoadError, LogPatternsPersistError},
+    transforms::TaskTransform,
+};
+
+mod config;
+mod drain;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::LogPatternsConfig;
+use drain::{Drain, DrainState};
+
+pub struct LogPatterns {
+    message_field: Option<ConfigTargetPath>,
+    pattern_id_field: ConfigTargetPath,
+    pattern_field: ConfigTargetPath,
+    drain: Drain,
+    emit_metrics: bool,
+    flush_period: Duration,
+    /// The latest pattern and the number of events since the last flush, by pattern ID.
+    counts: HashMap<u64, (String, u64)>,
+    state_path: Option<PathBuf>,
+    /// Whether the patterns changed since they were last persisted.
+    changed: bool,
+}
+
+impl LogPatterns {
+    pub fn new(config: &LogPatternsConfig, state_path: Option<PathBuf>) -> Self {
+        let mut drain = Drain::new(
+            config.depth,
+            config.similarity_threshold,
+            config.max_children,
+            config.max_clusters,
+        );
+        if let Some(state) = state_path.as_deref().and_then(load) {
+            drain.restore(state);
+        }
+
+        Self {
+            message_field: config.message_field.clone(),
+            pattern_id_field: config.pattern_id_field.clone(),
+            pattern_field: config.pattern_field.clone(),
+            drain,
+            emit_metrics: config.emit_metrics,
+            flush_period: config.flush_period_secs,
+            counts: HashMap::new(),
+            state_path,
+            changed: false,
+        }
+    }
+
+    fn record(&mut self, mut event: Event) -> Event {
+        let log = event.as_mut_log();
+        let message = match &self.message_field {
+            Some(field) => log.get(field),
+            None => log.get_message(),
+        };
+        let Some(message) = message.map(|message| message.to_string_lossy().into_owned()) else {
+            return event;
+        };
+
+        let (cluster, changed) = self.drain.add(&message);
+        let id = cluster.id;
+        let pattern = cluster.template();
+        self.changed |= changed;
+
+        log.insert(&self.pattern_id_field, id as i64);
+        log.insert(&self.pattern_field, pattern.clone());
+
+        if self.emit_metrics {
+            let (latest, count) = self.counts.entry(id).or_default();
+            *latest = pattern;
+            *count += 1;
+        }
+
+        event
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        let timestamp = Utc::now();
+        output.extend(self.counts.drain().map(|(id, (pattern, count))| {
+            Event::Metric(
+                Metric::new(
+                    "log_pattern_events_total",
+                    MetricKind::Incremental,
+                    MetricValue::Counter {
+                        value: count as f64,
+                    },
+                )
+                .with_tags(Some(metric_tags!(
+                    "pattern_id" => id.to_string(),
+                    "pattern" => pattern,
+                )))
+                .with_timestamp(Some(timestamp)),
+            )
+        }));
+    }
+
+    async fn persist(&mut self) {
+        let Some(path) = &self.state_path else {
+            return;
+        };
+        if !self.changed {
+            return;
+        }
+
+        let state = self.drain.state();
+        let save_path = path.clone();
+        let result = tokio::task::spawn_blocking(move || save(&save_path, &state))
+            .await
+            .expect("persisting log patterns panicked");
+        match result {
+            Ok(()) => self.changed = false,
+            Err(error) => emit!(LogPatternsPersistError { path, error }),
+        }
+    }
+}
+
+fn load(path: &Path) -> Option<DrainState> {
+    let result = fs::read(path).and_then(|bytes| Ok(serde_json::from_slice::<DrainState>(&bytes)?));
+    match result {
+        Ok(state) => Some(state),
+        Err(error) if error.kind() == io::ErrorKind::NotFound => None,
+        Err(error) => {
+            emit!(LogPatternsLoadError { path, error });
+            None
+        }
+    }
+}
+
+fn save(path: &Path, state: &DrainState) -> io::Result<()> {
+    // Write to a temporary file first, so that the persisted patterns are replaced atomically
+    // and a valid file remains if Vector stops while writing.
+    let tmp_path = path.with_extension("json.tmp");
+    let mut file = io::BufWriter::new(fs::File::create(&tmp_path)?);
+    serde_json::to_writer(&mut file, state)?;
+    file.into_inner()?.sync_all()?;
+    fs::rename(&tmp_path, path)
+}
+
+impl TaskTransform<Event> for LogPatterns {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.flush_period);
+
+        Box::pin(stream! {
+            let mut done = false;
+            while !done {
+                let mut output = Vec::new();
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        // Emit metrics and persist patterns periodically
+                        self.flush_into(&mut output);
+                        self.persist().await;
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                self.persist().await;
+                                done = true;
+                            }
+                            Some(event) => output.push(self.record(event)),
+                        }
+                    }
+                };
+                for event in output {
+                    yield event;
+                }
+            }
+        })
+    }
+}
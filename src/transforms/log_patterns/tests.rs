@@ -0,0 +1,165 @@
+use futures::{stream, StreamExt};
+use vector_core::config::GlobalOptions;
+
+use super::*;
+use crate::{
+    config::{TransformConfig, TransformContext},
+    event::{LogEvent, Value},
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<LogPatternsConfig>();
+}
+
+fn config(config: &str) -> LogPatternsConfig {
+    toml::from_str(config).unwrap()
+}
+
+fn log(message: &str) -> Event {
+    Event::Log(LogEvent::from(message))
+}
+
+fn pattern(event: &Event) -> (i64, String) {
+    let log = event.as_log();
+    let id = match log.get("pattern_id") {
+        Some(Value::Integer(id)) => *id,
+        value => panic!("unexpected pattern_id: {:?}", value),
+    };
+    let pattern = log.get("pattern").unwrap().to_string_lossy().into_owned();
+    (id, pattern)
+}
+
+#[test]
+fn annotates_events_with_pattern() {
+    let mut transform = LogPatterns::new(&config(""), None);
+
+    let first = transform.record(log("user 42 logged in from 10.0.0.1"));
+    let second = transform.record(log("user 7 logged in from 10.0.0.2"));
+    let other = transform.record(log("disk full"));
+
+    assert_eq!(
+        pattern(&first),
+        (0, "user 42 logged in from 10.0.0.1".into())
+    );
+    assert_eq!(pattern(&second), (0, "user <*> logged in from <*>".into()));
+    assert_eq!(pattern(&other), (1, "disk full".into()));
+}
+
+#[test]
+fn uses_configured_fields() {
+    let mut transform = LogPatterns::new(
+        &config(
+            r#"
+            message_field = "msg"
+            pattern_id_field = "cluster.id"
+            pattern_field = "cluster.template"
+            "#,
+        ),
+        None,
+    );
+
+    let mut event = LogEvent::default();
+    event.insert("msg", "disk full");
+    let event = transform.record(Event::Log(event));
+    assert_eq!(event.as_log().get("cluster.id"), Some(&Value::from(0_i64)));
+    assert_eq!(
+        event.as_log().get("cluster.template"),
+        Some(&Value::from("disk full"))
+    );
+
+    // Events without a message are passed through untouched
+    let event = transform.record(Event::Log(LogEvent::default()));
+    assert_eq!(event.as_log().get("cluster"), None);
+}
+
+#[test]
+fn emits_pattern_counts() {
+    let mut transform = LogPatterns::new(&config("emit_metrics = true"), None);
+
+    transform.record(log("request 1 failed"));
+    transform.record(log("request 2 failed"));
+
+    let mut output = Vec::new();
+    transform.flush_into(&mut output);
+    assert_eq!(output.len(), 1);
+    let metric = output[0].as_metric();
+    assert_eq!(metric.name(), "log_pattern_events_total");
+    assert_eq!(metric.value(), &MetricValue::Counter { value: 2.0 });
+    assert_eq!(metric.tag_value("pattern_id"), Some("0".into()));
+    assert_eq!(
+        metric.tag_value("pattern"),
+        Some("request <*> failed".into())
+    );
+
+    // Counts are reset after each flush
+    output.clear();
+    transform.flush_into(&mut output);
+    assert!(output.is_empty());
+}
+
+#[tokio::test]
+async fn pattern_ids_are_stable_across_restarts() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("patterns.json");
+
+    let mut transform = LogPatterns::new(&config(""), Some(path.clone()));
+    transform.record(log("disk full"));
+    transform.record(log("request 1 failed"));
+    transform.persist().await;
+
+    let mut restarted = LogPatterns::new(&config(""), Some(path));
+    assert_eq!(
+        pattern(&restarted.record(log("request 2 failed"))),
+        (1, "request <*> failed".into())
+    );
+    assert_eq!(pattern(&restarted.record(log("cpu hot"))).0, 2);
+}
+
+#[tokio::test]
+async fn persists_patterns_only_when_they_change() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("patterns.json");
+
+    let mut transform = LogPatterns::new(&config(""), Some(path.clone()));
+    transform.record(log("request 1 failed"));
+    transform.record(log("request 2 failed"));
+    assert!(transform.changed);
+    transform.persist().await;
+    assert!(!transform.changed);
+
+    // Matching an existing pattern without changing its template doesn't need persisting
+    transform.record(log("request 3 failed"));
+    assert!(!transform.changed);
+    transform.record(log("disk full"));
+    assert!(transform.changed);
+}
+
+#[tokio::test]
+async fn persists_patterns_in_data_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let context = TransformContext::new_with_globals(GlobalOptions {
+        data_dir: Some(dir.path().to_path_buf()),
+        ..Default::default()
+    });
+
+    let transform = config("persist = true").build(&context).await.unwrap();
+    let output = transform
+        .into_task()
+        .transform_events(Box::pin(stream::iter(vec![log("disk full")])))
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(output.len(), 1);
+
+    let state = load(&dir.path().join("log_patterns").join("patterns.json")).unwrap();
+    assert_eq!(state.clusters.len(), 1);
+    assert_eq!(state.clusters[0].template(), "disk full");
+}
+
+#[tokio::test]
+async fn rejects_shallow_depth() {
+    assert!(config("depth = 2")
+        .build(&TransformContext::default())
+        .await
+        .is_err());
+}
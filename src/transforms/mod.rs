@@ -10,14 +10,24 @@ pub mod aggregate;
 pub mod aws_ec2_metadata;
 #[cfg(feature = "transforms-dedupe")]
 pub mod dedupe;
+#[cfg(feature = "transforms-exclusive_route")]
+pub mod exclusive_route;
 #[cfg(feature = "transforms-filter")]
 pub mod filter;
+#[cfg(feature = "transforms-incremental_to_absolute")]
+pub mod incremental_to_absolute;
+#[cfg(feature = "transforms-join")]
+pub mod join;
+#[cfg(feature = "transforms-log_patterns")]
+pub mod log_patterns;
 #[cfg(feature = "transforms-log_to_metric")]
 pub mod log_to_metric;
 #[cfg(feature = "transforms-lua")]
 pub mod lua;
 #[cfg(feature = "transforms-metric_to_log")]
 pub mod metric_to_log;
+#[cfg(feature = "transforms-redact")]
+pub mod redact;
 #[cfg(feature = "transforms-reduce")]
 pub mod reduce;
 #[cfg(feature = "transforms-remap")]
@@ -30,10 +40,20 @@ pub mod sample;
 pub mod tag_cardinality_limit;
 #[cfg(feature = "transforms-throttle")]
 pub mod throttle;
+#[cfg(feature = "transforms-top_k")]
+pub mod top_k;
+#[cfg(feature = "transforms-trace_sampler")]
+pub mod trace_sampler;
+#[cfg(feature = "transforms-trace_to_metric")]
+pub mod trace_to_metric;
+#[cfg(feature = "transforms-validate")]
+pub mod validate;
+#[cfg(feature = "transforms-window")]
+pub mod window;
 
 pub use vector_core::transform::{
-    FunctionTransform, OutputBuffer, SyncTransform, TaskTransform, Transform, TransformOutputs,
-    TransformOutputsBuf,
+    FunctionTransform, MultiOutputTaskTransform, OutputBuffer, SyncTransform, TaskTransform,
+    Transform, TransformOutputs, TransformOutputsBuf,
 };
 
 #[derive(Debug, Snafu)]
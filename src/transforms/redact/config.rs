@@ -0,0 +1,163 @@
+use lookup::lookup_v2::ConfigTargetPath;
+use vector_common::sensitive_string::SensitiveString;
+use vector_config::configurable_component;
+use vector_core::config::{clone_input_definitions, LogNamespace};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::{redact::Redact, Transform},
+};
+
+/// Configuration for the `redact` transform.
+#[configurable_component(transform(
+    "redact",
+    "Redact sensitive values, such as email addresses or credit card numbers, from log events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct RedactConfig {
+    /// The detectors used to find sensitive values.
+    pub detectors: Vec<DetectorConfig>,
+
+    /// The fields to redact.
+    ///
+    /// When no fields are specified, all string values of the event are redacted, including the
+    /// ones nested in objects and arrays.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "message", docs::examples = "user.email"))]
+    pub fields: Vec<ConfigTargetPath>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub strategy: Strategy,
+}
+
+/// A detector of sensitive values.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of sensitive values to detect."))]
+pub enum DetectorConfig {
+    /// Email addresses.
+    Email,
+
+    /// IPv4 addresses.
+    Ipv4,
+
+    /// IPv6 addresses.
+    Ipv6,
+
+    /// Credit card numbers, validated with the Luhn algorithm.
+    CreditCard,
+
+    /// International bank account numbers, validated with their check digits.
+    Iban,
+
+    /// JSON Web Tokens.
+    Jwt,
+
+    /// AWS access key IDs.
+    AwsAccessKey,
+
+    /// Values matching a regular expression.
+    Regex {
+        /// The regular expression matching sensitive values.
+        #[configurable(metadata(docs::examples = "user-\\d+"))]
+        pattern: String,
+    },
+}
+
+/// The strategy used to redact sensitive values.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(
+    docs::enum_tag_description = "The strategy used to redact sensitive values."
+))]
+pub enum Strategy {
+    /// Replaces each character of sensitive values with `*`.
+    Mask,
+
+    /// Replaces sensitive values with the hex-encoded SHA-256 hash of the salt followed by the
+    /// value.
+    ///
+    /// Hashing keeps equal values correlated across events without revealing them.
+    Hash {
+        /// The salt prepended to sensitive values before hashing them.
+        #[serde(default)]
+        salt: Option<SensitiveString>,
+    },
+
+    /// Removes the fields containing sensitive values.
+    Drop,
+
+    /// Replaces sensitive values with a placeholder.
+    Replace {
+        /// The placeholder replacing sensitive values.
+        #[serde(default = "default_placeholder")]
+        placeholder: String,
+    },
+}
+
+impl Default for Strategy {
+    fn default() -> Self {
+        Self::Replace {
+            placeholder: default_placeholder(),
+        }
+    }
+}
+
+fn default_placeholder() -> String {
+    "[REDACTED]".to_string()
+}
+
+impl GenerateConfig for RedactConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[[detectors]]
+type = "email"
+
+[[detectors]]
+type = "credit_card"
+
+[strategy]
+type = "replace"
+placeholder = "[REDACTED]""#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "redact")]
+impl TransformConfig for RedactConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::function(Redact::new(self)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Redacted values remain strings, so the definition is passed through as-is. Fields removed
+        // by the `drop` strategy are not reflected in it.
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
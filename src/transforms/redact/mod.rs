@@ -0,0 +1,156 @@
+use std::cmp::Reverse;
+
+use lookup::lookup_v2::ConfigTargetPath;
+use sha2::{Digest, Sha256};
+
+use crate::{
+    event::{Event, Value},
+    internal_events::RedactionsPerformed,
+    transforms::{FunctionTransform, OutputBuffer},
+};
+
+mod config;
+mod detector;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{DetectorConfig, RedactConfig, Strategy};
+use detector::Detector;
+
+/// The result of redacting a string.
+enum Redacted {
+    /// The field containing the string must be removed.
+    Drop,
+    Replace(String),
+}
+
+#[derive(Clone)]
+pub struct Redact {
+    detectors: Vec<Detector>,
+    fields: Vec<ConfigTargetPath>,
+    strategy: Strategy,
+}
+
+impl Redact {
+    pub fn new(config: &RedactConfig) -> crate::Result<Self> {
+        let detectors = config
+            .detectors
+            .iter()
+            .map(Detector::build)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            detectors,
+            fields: config.fields.clone(),
+            strategy: config.strategy.clone(),
+        })
+    }
+
+    /// Redacts the sensitive values of a string, counting them per detector. Returns `None` if
+    /// no sensitive values were found.
+    fn redact_str(&self, value: &str, counts: &mut [usize]) -> Option<Redacted> {
+        let mut found = self
+            .detectors
+            .iter()
+            .enumerate()
+            .flat_map(|(index, detector)| detector.find(value).map(move |range| (range, index)))
+            .collect::<Vec<_>>();
+        if found.is_empty() {
+            return None;
+        }
+
+        // When values overlap, the one starting first, then the longest one, is redacted
+        found.sort_by_key(|(range, _)| (range.start, Reverse(range.end)));
+
+        let mut redacted = String::with_capacity(value.len());
+        let mut end = 0;
+        for (range, index) in found {
+            if range.start < end {
+                continue;
+            }
+            counts[index] += 1;
+
+            let sensitive = &value[range.clone()];
+            redacted.push_str(&value[end..range.start]);
+            match &self.strategy {
+                Strategy::Mask => redacted.extend(sensitive.chars().map(|_| '*')),
+                Strategy::Hash { salt } => {
+                    let hash = Sha256::new()
+                        .chain_update(salt.as_ref().map_or("", |salt| salt.inner()))
+                        .chain_update(sensitive)
+                        .finalize();
+                    redacted.push_str(&hex::encode(hash));
+                }
+                Strategy::Drop => {}
+                Strategy::Replace { placeholder } => redacted.push_str(placeholder),
+            }
+            end = range.end;
+        }
+        redacted.push_str(&value[end..]);
+
+        Some(match self.strategy {
+            Strategy::Drop => Redacted::Drop,
+            _ => Redacted::Replace(redacted),
+        })
+    }
+
+    /// Redacts a value and the values nested in it. Returns `true` if the value must be removed.
+    fn redact_value(&self, value: &mut Value, counts: &mut [usize]) -> bool {
+        match value {
+            Value::Bytes(bytes) => {
+                let redacted = self.redact_str(&String::from_utf8_lossy(bytes), counts);
+                match redacted {
+                    None => false,
+                    Some(Redacted::Drop) => true,
+                    Some(Redacted::Replace(redacted)) => {
+                        *value = Value::from(redacted);
+                        false
+                    }
+                }
+            }
+            Value::Object(map) => {
+                map.retain(|_, value| !self.redact_value(value, counts));
+                false
+            }
+            Value::Array(values) => {
+                values.retain_mut(|value| !self.redact_value(value, counts));
+                false
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FunctionTransform for Redact {
+    fn transform(&mut self, output: &mut OutputBuffer, mut event: Event) {
+        let mut counts = vec![0; self.detectors.len()];
+        let log = event.as_mut_log();
+
+        if self.fields.is_empty() {
+            if self.redact_value(log.value_mut(), &mut counts) {
+                *log.value_mut() = Value::Null;
+            }
+        } else {
+            for field in &self.fields {
+                let remove = log
+                    .get_mut(field)
+                    .map_or(false, |value| self.redact_value(value, &mut counts));
+                if remove {
+                    log.remove(field);
+                }
+            }
+        }
+
+        for (detector, count) in self.detectors.iter().zip(counts) {
+            if count > 0 {
+                emit!(RedactionsPerformed {
+                    detector: detector.name,
+                    count,
+                });
+            }
+        }
+
+        output.push(event);
+    }
+}
@@ -0,0 +1,253 @@
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use serde_with::serde_as;
+use vector_config::configurable_component;
+
+/// Configuration of the adaptive sampling mode.
+///
+/// Instead of a fixed `rate`, each unique value of `key_field` gets its own sample rate, adjusted
+/// periodically so that the events forwarded across all keys stay within a budget. Keys with few
+/// events are forwarded in full, while the most frequent keys are sampled harder.
+#[serde_as]
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct AdaptiveConfig {
+    /// The number of events, or of bytes, per second to forward across all keys.
+    #[configurable(metadata(docs::examples = 1000.0))]
+    pub target_per_sec: f64,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub measure: Measure,
+
+    /// The interval at which sample rates are adjusted, in seconds.
+    ///
+    /// Sample rates are based on the volume of each key during the previous interval.
+    #[serde(default = "default_adjustment_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Adjustment Interval"))]
+    pub adjustment_interval_secs: Duration,
+
+    /// The maximum number of keys with their own sample rate.
+    ///
+    /// Once this limit is reached, events with new keys share a single sample rate until the next
+    /// adjustment.
+    #[serde(default = "default_max_keys")]
+    pub max_keys: usize,
+}
+
+/// The volume targeted by `target_per_sec`.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Measure {
+    /// The number of events.
+    #[default]
+    Count,
+
+    /// The estimated JSON-encoded size of events, in bytes.
+    Bytes,
+}
+
+const fn default_adjustment_interval_secs() -> Duration {
+    Duration::from_secs(10)
+}
+
+const fn default_max_keys() -> usize {
+    10_000
+}
+
+#[derive(Clone, Debug)]
+struct KeyState {
+    /// The sample rate of the key, or `None` if all of its events are dropped.
+    rate: Option<u64>,
+    /// The volume of the key since the last adjustment.
+    volume: u64,
+    /// The number of events of the key since the last adjustment.
+    count: u64,
+}
+
+impl Default for KeyState {
+    fn default() -> Self {
+        Self {
+            rate: Some(1),
+            volume: 0,
+            count: 0,
+        }
+    }
+}
+
+/// Samples events with a rate per key, targeting a total volume per second.
+#[derive(Clone, Debug)]
+pub struct AdaptiveSampler {
+    target_per_sec: f64,
+    interval: Duration,
+    max_keys: usize,
+    interval_start: Option<Instant>,
+    keys: HashMap<Option<String>, KeyState>,
+}
+
+impl AdaptiveSampler {
+    pub fn new(config: &AdaptiveConfig) -> Self {
+        Self {
+            target_per_sec: config.target_per_sec,
+            interval: config.adjustment_interval_secs,
+            max_keys: config.max_keys,
+            interval_start: None,
+            keys: HashMap::new(),
+        }
+    }
+
+    /// Records an event of the given key and volume, and returns its sample rate if it's kept.
+    ///
+    /// Keys that weren't seen during the previous interval are kept in full.
+    pub fn sample(&mut self, key: Option<String>, volume: u64, now: Instant) -> Option<u64> {
+        let interval_start = *self.interval_start.get_or_insert(now);
+        let elapsed = now.duration_since(interval_start);
+        if elapsed >= self.interval {
+            self.adjust(elapsed);
+            self.interval_start = Some(now);
+        }
+
+        let key = if self.keys.len() < self.max_keys || self.keys.contains_key(&key) {
+            key
+        } else {
+            None
+        };
+        let state = self.keys.entry(key).or_default();
+        state.volume += volume;
+        let rate = state.rate.filter(|rate| state.count % rate == 0);
+        state.count += 1;
+
+        rate
+    }
+
+    /// Sets the sample rate of each key so that the volume of the previous interval, once
+    /// sampled, fits in the budget of the interval.
+    fn adjust(&mut self, elapsed: Duration) {
+        self.keys.retain(|_, state| state.volume > 0);
+
+        let mut volumes = self
+            .keys
+            .values()
+            .map(|state| state.volume)
+            .collect::<Vec<_>>();
+        let limit = volume_limit(&mut volumes, self.target_per_sec * elapsed.as_secs_f64());
+
+        for state in self.keys.values_mut() {
+            state.rate = match limit {
+                None => Some(1),
+                Some(limit) if limit > 0.0 => {
+                    Some(((state.volume as f64 / limit).ceil() as u64).max(1))
+                }
+                // No budget is left for the key, so all of its events are dropped
+                Some(_) => None,
+            };
+            state.volume = 0;
+            state.count = 0;
+        }
+    }
+}
+
+/// Returns the volume each key can be limited to, so that the total volume fits in the budget, or
+/// `None` if it already does.
+///
+/// Keys below the limit keep their whole volume, and the rest of the budget is evenly split
+/// between the keys above it.
+fn volume_limit(volumes: &mut [u64], budget: f64) -> Option<f64> {
+    volumes.sort_unstable();
+
+    let mut remaining = budget;
+    for (i, volume) in volumes.iter().enumerate() {
+        let keys_left = (volumes.len() - i) as f64;
+        if *volume as f64 * keys_left > remaining {
+            return Some(remaining / keys_left);
+        }
+        remaining -= *volume as f64;
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sampler(target_per_sec: f64, max_keys: usize) -> AdaptiveSampler {
+        AdaptiveSampler::new(&AdaptiveConfig {
+            target_per_sec,
+            measure: Measure::Count,
+            adjustment_interval_secs: Duration::from_secs(10),
+            max_keys,
+        })
+    }
+
+    /// Samples `count` events of the key, returning the number of kept events.
+    fn kept(sampler: &mut AdaptiveSampler, key: &str, count: usize, now: Instant) -> usize {
+        (0..count)
+            .filter(|_| sampler.sample(Some(key.into()), 1, now).is_some())
+            .count()
+    }
+
+    #[test]
+    fn splits_budget_between_frequent_keys() {
+        let mut volumes = vec![900, 10, 90];
+        assert_eq!(volume_limit(&mut volumes, 200.0), Some(100.0));
+        assert_eq!(volume_limit(&mut volumes, 1000.0), None);
+    }
+
+    #[test]
+    fn keeps_rare_keys_and_samples_frequent_keys() {
+        let mut sampler = sampler(10.0, 100);
+        let start = Instant::now();
+
+        // New keys are kept in full
+        assert_eq!(kept(&mut sampler, "frequent", 900, start), 900);
+        assert_eq!(kept(&mut sampler, "rare", 10, start), 10);
+
+        // A budget of 100 events over 10 seconds leaves 90 events for the frequent key
+        let next = start + Duration::from_secs(10);
+        assert_eq!(sampler.sample(Some("frequent".into()), 1, next), Some(10));
+        assert_eq!(kept(&mut sampler, "frequent", 899, next), 89);
+        assert_eq!(kept(&mut sampler, "rare", 10, next), 10);
+    }
+
+    #[test]
+    fn keeps_everything_within_budget() {
+        let mut sampler = sampler(100.0, 100);
+        let start = Instant::now();
+
+        kept(&mut sampler, "a", 500, start);
+        kept(&mut sampler, "b", 500, start);
+        let next = start + Duration::from_secs(10);
+        assert_eq!(kept(&mut sampler, "a", 500, next), 500);
+    }
+
+    #[test]
+    fn drops_everything_without_budget() {
+        let mut sampler = sampler(0.0, 100);
+        let start = Instant::now();
+
+        assert_eq!(kept(&mut sampler, "a", 10, start), 10);
+        let next = start + Duration::from_secs(10);
+        assert_eq!(kept(&mut sampler, "a", 10, next), 0);
+    }
+
+    #[test]
+    fn shares_rate_once_keys_are_exhausted() {
+        let mut sampler = sampler(10.0, 1);
+        let start = Instant::now();
+
+        kept(&mut sampler, "a", 10, start);
+        kept(&mut sampler, "b", 200, start);
+        kept(&mut sampler, "c", 200, start);
+
+        // "b" and "c" shared the same rate
+        let next = start + Duration::from_secs(10);
+        assert_eq!(sampler.sample(Some("a".into()), 1, next), Some(1));
+        assert_eq!(sampler.sample(Some("b".into()), 1, next), Some(5));
+    }
+}
@@ -1,5 +1,10 @@
+use std::time::Instant;
+
 use vector_config::configurable_component;
-use vector_core::config::{LegacyKey, LogNamespace};
+use vector_core::{
+    config::{LegacyKey, LogNamespace},
+    EstimatedJsonEncodedSizeOf,
+};
 use vrl::value::Kind;
 use vrl::{event_path, owned_value_path};
 
@@ -15,6 +20,11 @@ use crate::{
     transforms::{FunctionTransform, OutputBuffer, Transform},
 };
 
+mod adaptive;
+
+use adaptive::AdaptiveSampler;
+pub use adaptive::{AdaptiveConfig, Measure};
+
 /// Configuration for the `sample` transform.
 #[configurable_component(transform(
     "sample",
@@ -27,7 +37,12 @@ pub struct SampleConfig {
     ///
     /// For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
     /// dropped.
-    pub rate: u64,
+    ///
+    /// Required unless `adaptive` is set.
+    pub rate: Option<u64>,
+
+    #[configurable(derived)]
+    pub adaptive: Option<AdaptiveConfig>,
 
     /// The name of the field whose value is hashed to determine if the event should be
     /// sampled.
@@ -40,6 +55,8 @@ pub struct SampleConfig {
     ///
     /// This can be useful to, for example, ensure that all logs for a given transaction are
     /// sampled together, but that overall `1/N` transactions are sampled.
+    ///
+    /// When `adaptive` is set, each unique value for the key gets its own sample rate instead.
     #[configurable(metadata(docs::examples = "message",))]
     pub key_field: Option<String>,
 
@@ -50,7 +67,8 @@ pub struct SampleConfig {
 impl GenerateConfig for SampleConfig {
     fn generate_config() -> toml::Value {
         toml::Value::try_from(Self {
-            rate: 10,
+            rate: Some(10),
+            adaptive: None,
             key_field: None,
             exclude: None::<AnyCondition>,
         })
@@ -62,14 +80,25 @@ impl GenerateConfig for SampleConfig {
 #[typetag::serde(name = "sample")]
 impl TransformConfig for SampleConfig {
     async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
-        Ok(Transform::function(Sample::new(
-            self.rate,
-            self.key_field.clone(),
-            self.exclude
-                .as_ref()
-                .map(|condition| condition.build(&context.enrichment_tables))
-                .transpose()?,
-        )))
+        let exclude = self
+            .exclude
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        let sample = match (self.rate, &self.adaptive) {
+            (Some(0), None) => return Err("`rate` must be greater than zero".into()),
+            (Some(rate), None) => Sample::new(rate, self.key_field.clone(), exclude),
+            (None, Some(adaptive)) => {
+                if adaptive.target_per_sec.is_nan() || adaptive.target_per_sec <= 0.0 {
+                    return Err("`adaptive.target_per_sec` must be greater than zero".into());
+                }
+                Sample::adaptive(adaptive, self.key_field.clone(), exclude)
+            }
+            (Some(_), Some(_)) => return Err("`rate` and `adaptive` are mutually exclusive".into()),
+            (None, None) => return Err("one of `rate` or `adaptive` must be set".into()),
+        };
+        Ok(Transform::function(sample))
     }
 
     fn input(&self) -> Input {
@@ -109,6 +138,7 @@ pub struct Sample {
     key_field: Option<String>,
     exclude: Option<Condition>,
     count: u64,
+    adaptive: Option<(AdaptiveSampler, Measure)>,
 }
 
 impl Sample {
@@ -118,8 +148,41 @@ impl Sample {
             key_field,
             exclude,
             count: 0,
+            adaptive: None,
         }
     }
+
+    pub fn adaptive(
+        config: &AdaptiveConfig,
+        key_field: Option<String>,
+        exclude: Option<Condition>,
+    ) -> Self {
+        Self {
+            adaptive: Some((AdaptiveSampler::new(config), config.measure)),
+            ..Self::new(1, key_field, exclude)
+        }
+    }
+
+    /// Returns the sample rate of the event if it's kept.
+    fn sample(&mut self, event: &Event, key: Option<String>, now: Instant) -> Option<u64> {
+        if let Some((sampler, measure)) = self.adaptive.as_mut() {
+            let volume = match measure {
+                Measure::Count => 1,
+                Measure::Bytes => event.estimated_json_encoded_size_of().get() as u64,
+            };
+            return sampler.sample(key, volume, now);
+        }
+
+        let num = if let Some(value) = key {
+            seahash::hash(value.as_bytes())
+        } else {
+            self.count
+        };
+
+        self.count = (self.count + 1) % self.rate;
+
+        (num % self.rate == 0).then_some(self.rate)
+    }
 }
 
 impl FunctionTransform for Sample {
@@ -152,17 +215,9 @@ impl FunctionTransform for Sample {
                     .flatten(),
                 Event::Metric(_) => panic!("component can never receive metric events"),
             })
-            .map(|v| v.to_string_lossy());
+            .map(|v| v.to_string_lossy().into_owned());
 
-        let num = if let Some(value) = value {
-            seahash::hash(value.as_bytes())
-        } else {
-            self.count
-        };
-
-        self.count = (self.count + 1) % self.rate;
-
-        if num % self.rate == 0 {
+        if let Some(rate) = self.sample(&event, value, Instant::now()) {
             match event {
                 Event::Log(ref mut event) => {
                     event.namespace().insert_source_metadata(
@@ -170,11 +225,11 @@ impl FunctionTransform for Sample {
                         event,
                         Some(LegacyKey::Overwrite(vrl::path!("sample_rate"))),
                         vrl::path!("sample_rate"),
-                        self.rate.to_string(),
+                        rate.to_string(),
                     );
                 }
                 Event::Trace(ref mut event) => {
-                    event.insert(event_path!("sample_rate"), self.rate.to_string());
+                    event.insert(event_path!("sample_rate"), rate.to_string());
                 }
                 Event::Metric(_) => panic!("component can never receive metric events"),
             };
@@ -187,6 +242,8 @@ impl FunctionTransform for Sample {
 
 #[cfg(test)]
 mod tests {
+    use std::time::Duration;
+
     use approx::assert_relative_eq;
 
     use super::*;
@@ -383,6 +440,40 @@ mod tests {
         }
     }
 
+    #[test]
+    fn adaptive_sampler_adds_effective_rate_to_event() {
+        let config = toml::from_str::<AdaptiveConfig>("target_per_sec = 1.0").unwrap();
+        let mut sampler = Sample::adaptive(&config, Some("service".into()), None);
+        let start = Instant::now();
+
+        let mut event = Event::Log(LogEvent::from("hello"));
+        event.as_mut_log().insert("service", "api");
+        for _ in 0..100 {
+            assert_eq!(sampler.sample(&event, Some("api".into()), start), Some(1));
+        }
+        // A budget of 10 events over 10 seconds
+        let next = start + Duration::from_secs(10);
+        assert_eq!(sampler.sample(&event, Some("api".into()), next), Some(10));
+        assert_eq!(sampler.sample(&event, Some("web".into()), next), Some(1));
+
+        event.as_mut_log().insert("service", "web");
+        let passing = transform_one(&mut sampler, event).unwrap();
+        assert_eq!(passing.as_log()["sample_rate"], "1".into());
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_rates() {
+        for config in [
+            "",
+            "rate = 0",
+            "rate = 10\nadaptive.target_per_sec = 100.0",
+            "adaptive.target_per_sec = 0.0",
+        ] {
+            let config = toml::from_str::<SampleConfig>(config).unwrap();
+            assert!(config.build(&TransformContext::default()).await.is_err());
+        }
+    }
+
     #[test]
     fn handles_trace_event() {
         let event: TraceEvent = LogEvent::from("trace").into();
@@ -399,7 +490,8 @@ mod tests {
     async fn emits_internal_events() {
         assert_transform_compliance(async move {
             let config = SampleConfig {
-                rate: 1,
+                rate: Some(1),
+                adaptive: None,
                 key_field: None,
                 exclude: None,
             };
@@ -29,7 +29,7 @@ pub struct TagCardinalityLimit {
 }
 
 impl TagCardinalityLimit {
-    fn new(config: TagCardinalityLimitConfig) -> Self {
+    pub(crate) fn new(config: TagCardinalityLimitConfig) -> Self {
         Self {
             config,
             accepted_tags: HashMap::new(),
@@ -89,7 +89,7 @@ impl TagCardinalityLimit {
             .insert(value.clone());
     }
 
-    fn transform_one(&mut self, mut event: Event) -> Option<Event> {
+    pub(crate) fn transform_one(&mut self, mut event: Event) -> Option<Event> {
         let metric = event.as_mut_metric();
         let metric_name = metric.name().to_string();
         if let Some(tags_map) = metric.tags_mut() {
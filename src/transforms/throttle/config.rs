@@ -0,0 +1,179 @@
+use std::{collections::HashMap, time::Duration};
+
+use governor::clock;
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::{clone_input_definitions, LogNamespace};
+
+use crate::{
+    conditions::AnyCondition,
+    config::{DataType, Input, OutputId, TransformConfig, TransformContext, TransformOutput},
+    schema,
+    template::Template,
+    transforms::{
+        throttle::{Throttle, DROPPED},
+        Transform,
+    },
+};
+
+/// Configuration for the `throttle` transform.
+#[serde_as]
+#[configurable_component(transform("throttle", "Rate limit logs passing through a topology."))]
+#[derive(Clone, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ThrottleConfig {
+    #[configurable(derived)]
+    pub threshold: ThresholdConfig,
+
+    /// The time window in which the configured `threshold` is applied, in seconds.
+    #[serde_as(as = "serde_with::DurationSecondsWithFrac<f64>")]
+    #[configurable(metadata(docs::human_name = "Time Window"))]
+    pub window_secs: Duration,
+
+    /// The value to group events into separate buckets to be rate limited independently.
+    ///
+    /// If left unspecified, or if the event doesn't have `key_field`, then the event is not rate
+    /// limited separately.
+    #[configurable(metadata(docs::examples = "{{ message }}", docs::examples = "{{ hostname }}",))]
+    pub key_field: Option<Template>,
+
+    /// A logical condition used to exclude events from sampling.
+    pub exclude: Option<AnyCondition>,
+
+    /// Thresholds for specific buckets, keyed by the value of `key_field`.
+    ///
+    /// These take precedence over `threshold` and `overrides_table`.
+    #[serde(default)]
+    #[configurable(metadata(
+        docs::additional_props_description = "The threshold of the bucket with this key."
+    ))]
+    pub overrides: HashMap<String, ThresholdConfig>,
+
+    #[configurable(derived)]
+    pub overrides_table: Option<OverridesTableConfig>,
+
+    /// Whether to send throttled events to the `dropped` output instead of discarding them.
+    #[serde(default)]
+    pub reroute_dropped: bool,
+}
+
+/// The number of events, or of bytes, allowed for a given bucket per configured `window_secs`.
+///
+/// Each unique key has its own `threshold`. When both events and bytes are limited, events are
+/// throttled as soon as either limit is exceeded.
+#[configurable_component]
+#[derive(Clone, Copy, Debug)]
+#[serde(untagged)]
+pub enum ThresholdConfig {
+    /// The number of events allowed.
+    Events(u32),
+
+    /// Limits on the number of events, and on their size.
+    Limits(ThresholdLimits),
+}
+
+impl Default for ThresholdConfig {
+    fn default() -> Self {
+        Self::Events(0)
+    }
+}
+
+/// Limits on the number of events, and on their size.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default)]
+#[serde(deny_unknown_fields)]
+pub struct ThresholdLimits {
+    /// The number of events allowed.
+    pub events: Option<u32>,
+
+    /// The number of bytes allowed, measured by the estimated JSON-encoded size of events.
+    pub json_bytes: Option<u32>,
+}
+
+impl ThresholdConfig {
+    pub(super) const fn limits(self) -> ThresholdLimits {
+        match self {
+            Self::Events(events) => ThresholdLimits {
+                events: Some(events),
+                json_bytes: None,
+            },
+            Self::Limits(limits) => limits,
+        }
+    }
+}
+
+/// An enrichment table from which the thresholds of specific buckets are read.
+///
+/// The row whose `key_column` is equal to the value of `key_field` holds the threshold of the
+/// bucket. Buckets without a row, or whose row has no valid limit, use `threshold`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct OverridesTableConfig {
+    /// The name of the enrichment table.
+    #[configurable(metadata(docs::examples = "throttle_overrides"))]
+    pub table: String,
+
+    /// The column matched against the value of `key_field`.
+    #[serde(default = "default_key_column")]
+    pub key_column: String,
+
+    /// The column holding the number of events allowed.
+    #[serde(default = "default_events_column")]
+    pub events_column: String,
+
+    /// The column holding the number of bytes allowed.
+    #[serde(default = "default_json_bytes_column")]
+    pub json_bytes_column: String,
+}
+
+fn default_key_column() -> String {
+    "key".to_string()
+}
+
+fn default_events_column() -> String {
+    "events".to_string()
+}
+
+fn default_json_bytes_column() -> String {
+    "json_bytes".to_string()
+}
+
+impl_generate_config_from_default!(ThrottleConfig);
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "throttle")]
+impl TransformConfig for ThrottleConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        let throttle = Throttle::new(self, context, clock::MonotonicClock)?;
+        Ok(if self.reroute_dropped {
+            Transform::multi_output_task(throttle)
+        } else {
+            Transform::event_task(throttle)
+        })
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The event is not modified, so the definition is passed through as-is
+        let mut outputs = vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )];
+        if self.reroute_dropped {
+            outputs.push(
+                TransformOutput::new(DataType::Log, clone_input_definitions(input_definitions))
+                    .with_port(DROPPED),
+            );
+        }
+        outputs
+    }
+}
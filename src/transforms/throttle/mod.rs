@@ -0,0 +1,439 @@
+use std::{collections::HashMap, num::NonZeroU32, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use enrichment::{Case, Condition as TableCondition, IndexHandle, TableSearch};
+use futures::{Stream, StreamExt};
+use governor::clock;
+use snafu::Snafu;
+use vector_core::EstimatedJsonEncodedSizeOf;
+use vrl::value::Value;
+
+use crate::{
+    conditions::Condition,
+    config::{TransformContext, TransformOutput},
+    event::{Event, EventArray},
+    internal_events::{TemplateRenderingError, ThrottleEventDiscarded},
+    template::Template,
+    transforms::{MultiOutputTaskTransform, TaskTransform, TransformOutputsBuf},
+};
+
+mod config;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{OverridesTableConfig, ThresholdConfig, ThresholdLimits, ThrottleConfig};
+
+/// The name of the output throttled events are sent to when `reroute_dropped` is enabled.
+pub(crate) const DROPPED: &str = "dropped";
+
+/// The limits of a bucket.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+struct Threshold {
+    events: Option<NonZeroU32>,
+    json_bytes: Option<NonZeroU32>,
+}
+
+impl Threshold {
+    /// Returns `None` unless at least one limit is set, and all set limits are valid.
+    fn new(limits: ThresholdLimits, window: Duration) -> Option<Self> {
+        let limit = |limit: Option<u32>| match limit {
+            None => Some(None),
+            Some(limit) => NonZeroU32::new(limit)
+                .filter(|limit| Gcra::new(window, *limit).is_some())
+                .map(Some),
+        };
+        let threshold = Self {
+            events: limit(limits.events)?,
+            json_bytes: limit(limits.json_bytes)?,
+        };
+        (threshold.events.is_some() || threshold.json_bytes.is_some()).then_some(threshold)
+    }
+}
+
+/// A limit enforced with the generic cell rate algorithm, allowing bursts of up to the whole limit.
+#[derive(Clone, Copy, Debug)]
+struct Gcra {
+    /// The time it takes for a single unit of the limit to be replenished.
+    emission_interval: Duration,
+    /// The time it takes for the whole limit to be replenished.
+    window: Duration,
+}
+
+impl Gcra {
+    /// Returns `None` if the limit is too high to be enforced within the window.
+    fn new(window: Duration, limit: NonZeroU32) -> Option<Self> {
+        let emission_interval = window / limit.get();
+        (!emission_interval.is_zero()).then_some(Self {
+            emission_interval,
+            window: emission_interval * limit.get(),
+        })
+    }
+
+    /// Returns the next theoretical arrival time of the limit if `units` fit within it, without
+    /// consuming them.
+    fn check(&self, tat: Duration, now: Duration, units: u32) -> Option<Duration> {
+        let tat = tat.max(now) + self.emission_interval.checked_mul(units)?;
+        (tat - now <= self.window).then_some(tat)
+    }
+}
+
+/// The theoretical arrival times of the limits of a bucket, relative to the creation of its
+/// limiters.
+#[derive(Clone, Copy, Debug, Default)]
+struct BucketState {
+    events: Duration,
+    json_bytes: Duration,
+}
+
+/// The rate limiters of all buckets sharing the same threshold.
+///
+/// Both limits of a bucket are checked before either of them is consumed, so that events throttled
+/// by one limit don't use up the other.
+struct Limiters<C: clock::Clock> {
+    events: Option<Gcra>,
+    json_bytes: Option<Gcra>,
+    buckets: HashMap<Option<String>, BucketState>,
+    clock: C,
+    start: C::Instant,
+}
+
+impl<C: clock::Clock> Limiters<C> {
+    fn new(threshold: Threshold, window: Duration, clock: &C) -> Self {
+        let limiter =
+            |limit: NonZeroU32| Gcra::new(window, limit).expect("thresholds are validated");
+        Self {
+            events: threshold.events.map(limiter),
+            json_bytes: threshold.json_bytes.map(limiter),
+            buckets: HashMap::new(),
+            clock: clock.clone(),
+            start: clock.now(),
+        }
+    }
+
+    fn now(&self) -> Duration {
+        self.clock.now().duration_since(self.start).into()
+    }
+
+    /// Returns whether the event fits within the limits of its bucket, consuming them if so.
+    fn check(&mut self, key: &Option<String>, event: &Event) -> bool {
+        let now = self.now();
+        let state = self.buckets.get(key).copied().unwrap_or_default();
+
+        let events = match &self.events {
+            Some(events) => match events.check(state.events, now, 1) {
+                Some(tat) => tat,
+                None => return false,
+            },
+            None => state.events,
+        };
+        let json_bytes = match &self.json_bytes {
+            Some(json_bytes) => {
+                let size = event
+                    .estimated_json_encoded_size_of()
+                    .get()
+                    .clamp(1, u32::MAX as usize) as u32;
+                match json_bytes.check(state.json_bytes, now, size) {
+                    Some(tat) => tat,
+                    None => return false,
+                }
+            }
+            None => state.json_bytes,
+        };
+
+        self.buckets
+            .insert(key.clone(), BucketState { events, json_bytes });
+        true
+    }
+
+    /// Forgets the buckets whose limits are fully replenished.
+    fn retain_recent(&mut self) {
+        let now = self.now();
+        self.buckets
+            .retain(|_, state| state.events > now || state.json_bytes > now);
+    }
+}
+
+/// An enrichment table holding the thresholds of specific buckets.
+struct OverridesTable {
+    config: OverridesTableConfig,
+    select: Vec<String>,
+    index: IndexHandle,
+    search: TableSearch,
+}
+
+impl OverridesTable {
+    fn new(config: &OverridesTableConfig, context: &TransformContext) -> crate::Result<Self> {
+        let mut registry = context.enrichment_tables.clone();
+        let index = registry
+            .add_index(&config.table, Case::Sensitive, &[&config.key_column])
+            .map_err(|error| ConfigError::OverridesTable {
+                table: config.table.clone(),
+                error,
+            })?;
+
+        Ok(Self {
+            config: config.clone(),
+            select: vec![
+                config.events_column.clone(),
+                config.json_bytes_column.clone(),
+            ],
+            index,
+            search: registry.as_readonly(),
+        })
+    }
+
+    fn find(&self, key: &str, window: Duration) -> Option<Threshold> {
+        let condition = [TableCondition::Equals {
+            field: &self.config.key_column,
+            value: key.into(),
+        }];
+        let row = self
+            .search
+            .find_table_row(
+                &self.config.table,
+                Case::Sensitive,
+                &condition,
+                Some(&self.select),
+                Some(self.index),
+            )
+            .ok()?;
+
+        let limit = |column: &str| match row.get(column) {
+            Some(Value::Integer(limit)) => u32::try_from(*limit).ok(),
+            _ => None,
+        };
+        Threshold::new(
+            ThresholdLimits {
+                events: limit(&self.config.events_column),
+                json_bytes: limit(&self.config.json_bytes_column),
+            },
+            window,
+        )
+    }
+}
+
+enum Outcome {
+    Allowed(Event),
+    Throttled(Event, Option<String>),
+}
+
+pub struct Throttle<C: clock::Clock<Instant = I>, I: clock::Reference> {
+    threshold: Threshold,
+    window: Duration,
+    key_field: Option<Template>,
+    exclude: Option<Condition>,
+    overrides: HashMap<String, Threshold>,
+    overrides_table: Option<OverridesTable>,
+    limiters: HashMap<Threshold, Limiters<C>>,
+    clock: C,
+}
+
+impl<C, I> Throttle<C, I>
+where
+    C: clock::Clock<Instant = I>,
+    I: clock::Reference,
+{
+    pub fn new(
+        config: &ThrottleConfig,
+        context: &TransformContext,
+        clock: C,
+    ) -> crate::Result<Self> {
+        let window = config.window_secs;
+
+        let threshold =
+            Threshold::new(config.threshold.limits(), window).ok_or(ConfigError::NonZero)?;
+        let overrides = config
+            .overrides
+            .iter()
+            .map(|(key, threshold)| {
+                Threshold::new(threshold.limits(), window)
+                    .map(|threshold| (key.clone(), threshold))
+                    .ok_or_else(|| ConfigError::OverrideNonZero { key: key.clone() })
+            })
+            .collect::<Result<_, _>>()?;
+        let overrides_table = config
+            .overrides_table
+            .as_ref()
+            .map(|table| OverridesTable::new(table, context))
+            .transpose()?;
+        let exclude = config
+            .exclude
+            .as_ref()
+            .map(|condition| condition.build(&context.enrichment_tables))
+            .transpose()?;
+
+        Ok(Self {
+            threshold,
+            window,
+            key_field: config.key_field.clone(),
+            exclude,
+            overrides,
+            overrides_table,
+            limiters: HashMap::new(),
+            clock,
+        })
+    }
+
+    fn threshold_for(&self, key: Option<&str>) -> Threshold {
+        let key = match key {
+            Some(key) => key,
+            None => return self.threshold,
+        };
+        if let Some(threshold) = self.overrides.get(key) {
+            return *threshold;
+        }
+        self.overrides_table
+            .as_ref()
+            .and_then(|table| table.find(key, self.window))
+            .unwrap_or(self.threshold)
+    }
+
+    fn check(&mut self, event: Event) -> Outcome {
+        let (throttle, event) = match self.exclude.as_ref() {
+            Some(condition) => {
+                let (result, event) = condition.check(event);
+                (!result, event)
+            }
+            _ => (true, event),
+        };
+        if !throttle {
+            return Outcome::Allowed(event);
+        }
+
+        let key = self.key_field.as_ref().and_then(|t| {
+            t.render_string(&event)
+                .map_err(|error| {
+                    emit!(TemplateRenderingError {
+                        error,
+                        field: Some("key_field"),
+                        drop_event: false,
+                    })
+                })
+                .ok()
+        });
+
+        let threshold = self.threshold_for(key.as_deref());
+        let (window, clock) = (self.window, &self.clock);
+        let limiters = self
+            .limiters
+            .entry(threshold)
+            .or_insert_with(|| Limiters::new(threshold, window, clock));
+
+        if limiters.check(&key, &event) {
+            Outcome::Allowed(event)
+        } else {
+            Outcome::Throttled(event, key)
+        }
+    }
+
+    fn retain_recent(&mut self) {
+        for limiters in self.limiters.values_mut() {
+            limiters.retain_recent();
+        }
+    }
+}
+
+impl<C, I> TaskTransform<Event> for Throttle<C, I>
+where
+    C: clock::Clock<Instant = I> + Send + 'static,
+    I: clock::Reference + Send + 'static,
+{
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_keys = tokio::time::interval(self.window * 2);
+
+        Box::pin(stream! {
+          loop {
+            let done = tokio::select! {
+                biased;
+
+                maybe_event = input_rx.next() => {
+                    match maybe_event {
+                        None => true,
+                        Some(event) => {
+                            match self.check(event) {
+                                Outcome::Allowed(event) => {
+                                    yield event;
+                                }
+                                Outcome::Throttled(_, key) => emit!(ThrottleEventDiscarded {
+                                    key: key.unwrap_or_else(|| "None".to_string()),
+                                }),
+                            }
+                            false
+                        }
+                    }
+                }
+                _ = flush_keys.tick() => {
+                    self.retain_recent();
+                    false
+                }
+            };
+            if done { break }
+          }
+        })
+    }
+}
+
+impl<C, I> MultiOutputTaskTransform for Throttle<C, I>
+where
+    C: clock::Clock<Instant = I> + Send + 'static,
+    I: clock::Reference + Send + 'static,
+{
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = EventArray> + Send>>,
+        outputs: Vec<TransformOutput>,
+    ) -> Pin<Box<dyn Stream<Item = TransformOutputsBuf> + Send>> {
+        let mut flush_keys = tokio::time::interval(self.window * 2);
+        // Empty buffers are cheap to clone, unlike the schema definitions of the outputs
+        let empty_output = TransformOutputsBuf::new_with_capacity(outputs, 1);
+
+        Box::pin(stream! {
+          loop {
+            let done = tokio::select! {
+                biased;
+
+                maybe_events = input_rx.next() => {
+                    match maybe_events {
+                        None => true,
+                        Some(events) => {
+                            let mut output = empty_output.clone();
+                            for event in events.into_events() {
+                                match self.check(event) {
+                                    Outcome::Allowed(event) => output.push(None, event),
+                                    Outcome::Throttled(event, _) => output.push(Some(DROPPED), event),
+                                }
+                            }
+                            yield output;
+                            false
+                        }
+                    }
+                }
+                _ = flush_keys.tick() => {
+                    self.retain_recent();
+                    false
+                }
+            };
+            if done { break }
+          }
+        })
+    }
+}
+
+#[derive(Debug, Snafu)]
+pub enum ConfigError {
+    #[snafu(display("`threshold`, and `window_secs` must be non-zero"))]
+    NonZero,
+
+    #[snafu(display("The threshold of key {:?} in `overrides` must be non-zero", key))]
+    OverrideNonZero { key: String },
+
+    #[snafu(display("Unable to use enrichment table {:?}: {}", table, error))]
+    OverridesTable { table: String, error: String },
+}
@@ -0,0 +1,501 @@
+use std::{collections::BTreeMap, task::Poll};
+
+use enrichment::{Table, TableRegistry};
+use futures::{stream, SinkExt};
+use vector_core::config::DataType;
+
+use super::*;
+use crate::{
+    event::LogEvent,
+    test_util::components::assert_transform_compliance,
+    transforms::{test::create_topology, Transform},
+};
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<ThrottleConfig>();
+}
+
+#[tokio::test]
+async fn throttle_events() {
+    let clock = clock::FakeRelativeClock::default();
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 2
+window_secs = 5
+"#,
+    )
+    .unwrap();
+
+    let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+        .map(Transform::event_task)
+        .unwrap();
+
+    let throttle = throttle.into_task();
+
+    let (mut tx, rx) = futures::channel::mpsc::channel(10);
+    let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+    // tokio interval is always immediately ready, so we poll once to make sure
+    // we trip it/set the interval in the future
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    let mut count = 0_u8;
+    while count < 2 {
+        if let Some(_event) = out_stream.next().await {
+            count += 1;
+        } else {
+            panic!("Unexpectedly received None in output stream");
+        }
+    }
+    assert_eq!(2, count);
+
+    clock.advance(Duration::from_secs(2));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    // We should be back to pending, having the second event dropped
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    clock.advance(Duration::from_secs(3));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    // The rate limiter should now be refreshed and allow an additional event through
+    if let Some(_event) = out_stream.next().await {
+    } else {
+        panic!("Unexpectedly received None in output stream");
+    }
+
+    // We should be back to pending, having nothing waiting for us
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    tx.disconnect();
+
+    // And still nothing there
+    assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+}
+
+#[tokio::test]
+async fn throttle_exclude() {
+    let clock = clock::FakeRelativeClock::default();
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 2
+window_secs = 5
+exclude = """
+exists(.special)
+"""
+"#,
+    )
+    .unwrap();
+
+    let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+        .map(Transform::event_task)
+        .unwrap();
+
+    let throttle = throttle.into_task();
+
+    let (mut tx, rx) = futures::channel::mpsc::channel(10);
+    let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+    // tokio interval is always immediately ready, so we poll once to make sure
+    // we trip it/set the interval in the future
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    let mut count = 0_u8;
+    while count < 2 {
+        if let Some(_event) = out_stream.next().await {
+            count += 1;
+        } else {
+            panic!("Unexpectedly received None in output stream");
+        }
+    }
+    assert_eq!(2, count);
+
+    clock.advance(Duration::from_secs(2));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    // We should be back to pending, having the second event dropped
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    let mut special_log = LogEvent::default();
+    special_log.insert("special", "true");
+    tx.send(special_log.into()).await.unwrap();
+    // The rate limiter should allow this log through regardless of current limit
+    if let Some(_event) = out_stream.next().await {
+    } else {
+        panic!("Unexpectedly received None in output stream");
+    }
+
+    clock.advance(Duration::from_secs(3));
+
+    tx.send(LogEvent::default().into()).await.unwrap();
+
+    // The rate limiter should now be refreshed and allow an additional event through
+    if let Some(_event) = out_stream.next().await {
+    } else {
+        panic!("Unexpectedly received None in output stream");
+    }
+
+    // We should be back to pending, having nothing waiting for us
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    tx.disconnect();
+
+    // And still nothing there
+    assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+}
+
+#[tokio::test]
+async fn throttle_buckets() {
+    let clock = clock::FakeRelativeClock::default();
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+"#,
+    )
+    .unwrap();
+
+    let throttle = Throttle::new(&config, &TransformContext::default(), clock.clone())
+        .map(Transform::event_task)
+        .unwrap();
+
+    let throttle = throttle.into_task();
+
+    let (mut tx, rx) = futures::channel::mpsc::channel(10);
+    let mut out_stream = throttle.transform_events(Box::pin(rx));
+
+    // tokio interval is always immediately ready, so we poll once to make sure
+    // we trip it/set the interval in the future
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    let mut log_a = LogEvent::default();
+    log_a.insert("bucket", "a");
+    let mut log_b = LogEvent::default();
+    log_b.insert("bucket", "b");
+    tx.send(log_a.into()).await.unwrap();
+    tx.send(log_b.into()).await.unwrap();
+
+    let mut count = 0_u8;
+    while count < 2 {
+        if let Some(_event) = out_stream.next().await {
+            count += 1;
+        } else {
+            panic!("Unexpectedly received None in output stream");
+        }
+    }
+    assert_eq!(2, count);
+
+    // We should be back to pending, having nothing waiting for us
+    assert_eq!(Poll::Pending, futures::poll!(out_stream.next()));
+
+    tx.disconnect();
+
+    // And still nothing there
+    assert_eq!(Poll::Ready(None), futures::poll!(out_stream.next()));
+}
+
+#[tokio::test]
+async fn emits_internal_events() {
+    assert_transform_compliance(async move {
+        let config = ThrottleConfig {
+            threshold: ThresholdConfig::Events(1),
+            window_secs: Duration::from_secs_f64(1.0),
+            ..Default::default()
+        };
+        let (tx, rx) = mpsc::channel(1);
+        let (topology, mut out) = create_topology(ReceiverStream::new(rx), config).await;
+
+        let log = LogEvent::from("hello world");
+        tx.send(log.into()).await.unwrap();
+
+        _ = out.recv().await;
+
+        drop(tx);
+        topology.stop().await;
+        assert_eq!(out.recv().await, None);
+    })
+    .await
+}
+
+fn allowed<C, I>(throttle: &mut Throttle<C, I>, bucket: &str, message: &str) -> bool
+where
+    C: clock::Clock<Instant = I>,
+    I: clock::Reference,
+{
+    let mut log = LogEvent::from(message);
+    log.insert("bucket", bucket);
+    matches!(throttle.check(log.into()), Outcome::Allowed(_))
+}
+
+#[test]
+fn throttle_overrides() {
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+
+[overrides]
+a = 2
+b = { json_bytes = 1000 }
+"#,
+    )
+    .unwrap();
+    let mut throttle = Throttle::new(
+        &config,
+        &TransformContext::default(),
+        clock::FakeRelativeClock::default(),
+    )
+    .unwrap();
+
+    assert!(allowed(&mut throttle, "a", "hello"));
+    assert!(allowed(&mut throttle, "a", "hello"));
+    assert!(!allowed(&mut throttle, "a", "hello"));
+
+    for _ in 0..5 {
+        assert!(allowed(&mut throttle, "b", "hello"));
+    }
+
+    assert!(allowed(&mut throttle, "c", "hello"));
+    assert!(!allowed(&mut throttle, "c", "hello"));
+}
+
+#[test]
+fn throttle_json_bytes() {
+    let clock = clock::FakeRelativeClock::default();
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = { events = 10, json_bytes = 100 }
+window_secs = 5
+"#,
+    )
+    .unwrap();
+    let mut throttle = Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
+
+    // With its timestamp and bucket, the event is estimated at 72 to 82 bytes
+    let message = "x".repeat(10);
+    assert!(allowed(&mut throttle, "a", &message));
+    assert!(!allowed(&mut throttle, "a", &message));
+    // Events larger than the threshold are always throttled
+    clock.advance(Duration::from_secs(5));
+    assert!(!allowed(&mut throttle, "a", &"x".repeat(200)));
+    assert!(allowed(&mut throttle, "a", "hello"));
+}
+
+#[test]
+fn throttle_json_bytes_keeps_events_limit() {
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = { events = 2, json_bytes = 200 }
+window_secs = 5
+"#,
+    )
+    .unwrap();
+    let mut throttle = Throttle::new(
+        &config,
+        &TransformContext::default(),
+        clock::FakeRelativeClock::default(),
+    )
+    .unwrap();
+
+    // Events throttled for their size don't count towards the events limit
+    let message = "x".repeat(50);
+    assert!(allowed(&mut throttle, "a", &message));
+    assert!(!allowed(&mut throttle, "a", &message));
+    assert!(allowed(&mut throttle, "a", "hi"));
+    assert!(!allowed(&mut throttle, "a", "hi"));
+}
+
+#[test]
+fn throttle_events_keeps_json_bytes_limit() {
+    let clock = clock::FakeRelativeClock::default();
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = { events = 2, json_bytes = 1000 }
+window_secs = 10
+"#,
+    )
+    .unwrap();
+    let mut throttle = Throttle::new(&config, &TransformContext::default(), clock.clone()).unwrap();
+
+    // Events throttled for their number don't count towards the json_bytes limit
+    let message = "x".repeat(700);
+    assert!(allowed(&mut throttle, "a", "hi"));
+    assert!(allowed(&mut throttle, "a", "hi"));
+    assert!(!allowed(&mut throttle, "a", &message));
+    clock.advance(Duration::from_secs(5));
+    assert!(allowed(&mut throttle, "a", &message));
+}
+
+#[derive(Clone)]
+struct DummyTable(Vec<BTreeMap<String, Value>>);
+
+impl Table for DummyTable {
+    fn find_table_row<'a>(
+        &self,
+        case: Case,
+        condition: &'a [TableCondition<'a>],
+        select: Option<&[String]>,
+        index: Option<IndexHandle>,
+    ) -> Result<BTreeMap<String, Value>, String> {
+        let mut rows = self.find_table_rows(case, condition, select, index)?;
+        match rows.len() {
+            1 => Ok(rows.pop().unwrap()),
+            _ => Err("expected a single row".to_string()),
+        }
+    }
+
+    fn find_table_rows<'a>(
+        &self,
+        _: Case,
+        condition: &'a [TableCondition<'a>],
+        _: Option<&[String]>,
+        _: Option<IndexHandle>,
+    ) -> Result<Vec<BTreeMap<String, Value>>, String> {
+        Ok(self
+            .0
+            .iter()
+            .filter(|row| {
+                condition.iter().all(|condition| match condition {
+                    TableCondition::Equals { field, value } => row.get(*field) == Some(value),
+                    _ => false,
+                })
+            })
+            .cloned()
+            .collect())
+    }
+
+    fn add_index(&mut self, _: Case, _: &[&str]) -> Result<IndexHandle, String> {
+        Ok(IndexHandle(0))
+    }
+
+    fn index_fields(&self) -> Vec<(Case, Vec<String>)> {
+        Vec::new()
+    }
+
+    fn needs_reload(&self) -> bool {
+        false
+    }
+}
+
+#[test]
+fn throttle_overrides_table() {
+    let table = DummyTable(vec![
+        BTreeMap::from([
+            ("key".to_string(), Value::from("a")),
+            ("events".to_string(), Value::from(3_i64)),
+        ]),
+        BTreeMap::from([
+            ("key".to_string(), Value::from("b")),
+            ("events".to_string(), Value::Null),
+        ]),
+    ]);
+    let registry = TableRegistry::default();
+    registry.load(HashMap::from([(
+        "overrides".to_string(),
+        Box::new(table) as Box<dyn Table + Send + Sync>,
+    )]));
+
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 1
+window_secs = 5
+key_field = "{{ bucket }}"
+overrides_table.table = "overrides"
+"#,
+    )
+    .unwrap();
+    let context = TransformContext {
+        enrichment_tables: registry.clone(),
+        ..Default::default()
+    };
+    let mut throttle =
+        Throttle::new(&config, &context, clock::FakeRelativeClock::default()).unwrap();
+    registry.finish_load();
+
+    for _ in 0..3 {
+        assert!(allowed(&mut throttle, "a", "hello"));
+    }
+    assert!(!allowed(&mut throttle, "a", "hello"));
+
+    // Rows without a valid limit use the default threshold
+    assert!(allowed(&mut throttle, "b", "hello"));
+    assert!(!allowed(&mut throttle, "b", "hello"));
+}
+
+#[test]
+fn throttle_rejects_invalid_thresholds() {
+    let build = |config: &str| {
+        Throttle::new(
+            &toml::from_str::<ThrottleConfig>(config).unwrap(),
+            &TransformContext::default(),
+            clock::FakeRelativeClock::default(),
+        )
+        .map(|_| ())
+    };
+
+    assert!(build("threshold = {}\nwindow_secs = 5").is_err());
+    assert!(build("threshold = 1\nwindow_secs = 5\noverrides.a = 0").is_err());
+    assert!(build("threshold = 1\nwindow_secs = 5\noverrides_table.table = \"missing\"").is_err());
+}
+
+#[tokio::test]
+async fn throttle_reroute_dropped() {
+    let config = toml::from_str::<ThrottleConfig>(
+        r#"
+threshold = 1
+window_secs = 5
+reroute_dropped = true
+"#,
+    )
+    .unwrap();
+    let throttle = Throttle::new(
+        &config,
+        &TransformContext::default(),
+        clock::FakeRelativeClock::default(),
+    )
+    .unwrap();
+    let outputs = vec![
+        TransformOutput::new(DataType::Log, HashMap::new()),
+        TransformOutput::new(DataType::Log, HashMap::new()).with_port(DROPPED),
+    ];
+
+    let events = (0..3).map(|i| EventArray::from(Event::from(LogEvent::from(i.to_string()))));
+    let mut output = MultiOutputTaskTransform::transform(
+        Box::new(throttle),
+        Box::pin(stream::iter(events)),
+        outputs,
+    )
+    .collect::<Vec<_>>()
+    .await;
+
+    let allowed = output
+        .iter_mut()
+        .flat_map(|output| output.drain().collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    let dropped = output
+        .iter_mut()
+        .flat_map(|output| output.drain_named(DROPPED).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(allowed, vec![Event::from(LogEvent::from("0"))]);
+    assert_eq!(
+        dropped,
+        vec![
+            Event::from(LogEvent::from("1")),
+            Event::from(LogEvent::from("2")),
+        ]
+    );
+}
@@ -0,0 +1,175 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    time::Duration,
+};
+
+use lookup::owned_value_path;
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::{log_schema, LogNamespace};
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema::{self, Definition},
+    template::Template,
+    transforms::{top_k::TopK, Transform},
+};
+
+/// Configuration for the `top_k` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "top_k",
+    "Track the keys with the most events or bytes, and periodically emit them."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TopKConfig {
+    /// The key to track for each event.
+    #[configurable(metadata(docs::examples = "{{ tenant_id }}", docs::examples = "{{ host }}"))]
+    pub key: Template,
+
+    /// The number of keys to emit.
+    #[serde(default = "default_k")]
+    pub k: usize,
+
+    /// The maximum number of keys tracked at once.
+    ///
+    /// This bounds the memory used by the transform, regardless of the number of distinct keys.
+    /// The more keys are tracked, the more accurate the estimated values are. Defaults to ten times
+    /// `k`.
+    pub capacity: Option<usize>,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub measure: Measure,
+
+    /// The interval at which the top keys are emitted, in seconds.
+    ///
+    /// The values are reset after each emission, so that the top keys of each interval are
+    /// emitted.
+    #[serde(default = "default_interval_secs")]
+    #[serde_as(as = "serde_with::DurationSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Interval"))]
+    pub interval_secs: Duration,
+
+    #[configurable(derived)]
+    #[serde(default)]
+    pub output_type: OutputType,
+
+    /// The name of the gauges emitted when `output_type` is `metric`.
+    #[serde(default = "default_metric_name")]
+    pub metric_name: String,
+}
+
+/// The value tracked for each key.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum Measure {
+    /// The number of events.
+    #[default]
+    Count,
+
+    /// The estimated JSON-encoded size of events, in bytes.
+    Bytes,
+}
+
+impl Measure {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Count => "count",
+            Self::Bytes => "bytes",
+        }
+    }
+}
+
+/// The type of events emitted.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum OutputType {
+    /// A log event listing the top keys.
+    #[default]
+    Log,
+
+    /// A gauge for each top key, tagged with the key.
+    Metric,
+}
+
+const fn default_k() -> usize {
+    10
+}
+
+const fn default_interval_secs() -> Duration {
+    Duration::from_secs(60)
+}
+
+fn default_metric_name() -> String {
+    "top_k".to_string()
+}
+
+impl GenerateConfig for TopKConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"key = "{{ tenant_id }}"
+k = 10"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "top_k")]
+impl TransformConfig for TopKConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        TopK::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::all()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        match self.output_type {
+            OutputType::Log => {
+                let mut definition =
+                    Definition::default_for_namespace(&BTreeSet::from([LogNamespace::Legacy]))
+                        .with_event_field(&owned_value_path!("measure"), Kind::bytes(), None)
+                        .with_event_field(
+                            &owned_value_path!("top"),
+                            Kind::array(
+                                Collection::empty().with_unknown(Kind::object(
+                                    Collection::empty()
+                                        .with_known("key", Kind::bytes())
+                                        .with_known("value", Kind::integer())
+                                        .with_known("error", Kind::integer()),
+                                )),
+                            ),
+                            None,
+                        );
+                if let Some(timestamp_key) = log_schema().timestamp_key() {
+                    definition =
+                        definition.with_event_field(timestamp_key, Kind::timestamp(), None);
+                }
+
+                vec![TransformOutput::new(
+                    DataType::Log,
+                    input_definitions
+                        .iter()
+                        .map(|(output, _)| (output.clone(), definition.clone()))
+                        .collect(),
+                )]
+            }
+            OutputType::Metric => vec![TransformOutput::new(DataType::Metric, HashMap::new())],
+        }
+    }
+}
@@ -0,0 +1,162 @@
+use std::{collections::BTreeMap, pin::Pin, time::Duration};
+
+use async_stream::stream;
+use chrono::Utc;
+use futures::{Stream, StreamExt};
+use vector_core::{config::log_schema, metric_tags, EstimatedJsonEncodedSizeOf};
+
+use crate::{
+    event::{
+        metric::{Metric, MetricKind, MetricValue},
+        Event, LogEvent, Value,
+    },
+    internal_events::TemplateRenderingError,
+    template::Template,
+    transforms::TaskTransform,
+};
+
+mod config;
+mod space_saving;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{Measure, OutputType, TopKConfig};
+use space_saving::SpaceSaving;
+
+pub struct TopK {
+    key: Template,
+    k: usize,
+    measure: Measure,
+    interval: Duration,
+    output_type: OutputType,
+    metric_name: String,
+    sketch: SpaceSaving,
+}
+
+impl TopK {
+    pub fn new(config: &TopKConfig) -> crate::Result<Self> {
+        let capacity = config.capacity.unwrap_or(config.k * 10);
+        if config.k == 0 {
+            return Err("`k` must be greater than zero".into());
+        }
+        if capacity < config.k {
+            return Err("`capacity` must be greater than or equal to `k`".into());
+        }
+
+        Ok(Self {
+            key: config.key.clone(),
+            k: config.k,
+            measure: config.measure,
+            interval: config.interval_secs,
+            output_type: config.output_type,
+            metric_name: config.metric_name.clone(),
+            sketch: SpaceSaving::new(capacity),
+        })
+    }
+
+    fn record(&mut self, event: Event) {
+        let key = match self.key.render_string(&event) {
+            Ok(key) => key,
+            Err(error) => {
+                emit!(TemplateRenderingError {
+                    field: Some("key"),
+                    drop_event: false,
+                    error,
+                });
+                return;
+            }
+        };
+        let weight = match self.measure {
+            Measure::Count => 1,
+            Measure::Bytes => event.estimated_json_encoded_size_of().get() as u64,
+        };
+
+        self.sketch.add(&key, weight);
+    }
+
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        if self.sketch.is_empty() {
+            return;
+        }
+
+        let top = self.sketch.top(self.k);
+        self.sketch.clear();
+        let timestamp = Utc::now();
+
+        match self.output_type {
+            OutputType::Log => {
+                let top = top
+                    .into_iter()
+                    .map(|estimate| {
+                        Value::Object(BTreeMap::from([
+                            ("key".to_string(), Value::from(estimate.key)),
+                            ("value".to_string(), Value::from(estimate.count as i64)),
+                            ("error".to_string(), Value::from(estimate.error as i64)),
+                        ]))
+                    })
+                    .collect::<Vec<_>>();
+
+                let mut log = LogEvent::default();
+                log.insert("measure", self.measure.as_str());
+                log.insert("top", top);
+                log.maybe_insert(log_schema().timestamp_key_target_path(), timestamp);
+                output.push(Event::Log(log));
+            }
+            OutputType::Metric => {
+                output.extend(top.into_iter().map(|estimate| {
+                    Event::Metric(
+                        Metric::new(
+                            self.metric_name.clone(),
+                            MetricKind::Absolute,
+                            MetricValue::Gauge {
+                                value: estimate.count as f64,
+                            },
+                        )
+                        .with_tags(Some(metric_tags!(
+                            "key" => estimate.key,
+                            "measure" => self.measure.as_str(),
+                        )))
+                        .with_timestamp(Some(timestamp)),
+                    )
+                }));
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for TopK {
+    fn transform(
+        mut self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut flush_stream = tokio::time::interval(self.interval);
+
+        Box::pin(stream! {
+            let mut output = Vec::new();
+            let mut done = false;
+            while !done {
+                tokio::select! {
+                    _ = flush_stream.tick() => {
+                        self.flush_into(&mut output);
+                    },
+                    maybe_event = input_rx.next() => {
+                        match maybe_event {
+                            None => {
+                                self.flush_into(&mut output);
+                                done = true;
+                            }
+                            Some(event) => self.record(event),
+                        }
+                    }
+                };
+                for event in output.drain(..) {
+                    yield event;
+                }
+            }
+        })
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::{BTreeSet, HashMap};
+
+/// An estimated count of a key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Estimate {
+    pub key: String,
+    /// The estimated count, which is never lower than the actual count.
+    pub count: u64,
+    /// The maximum overestimation of the count.
+    pub error: u64,
+}
+
+/// Tracks the most frequent keys using the Space-Saving algorithm.
+///
+/// At most `capacity` keys are tracked. When a key that isn't tracked is added while all counters
+/// are in use, it replaces the key with the lowest count, inheriting that count as its error. Any
+/// key whose actual count is higher than the total count divided by `capacity` is guaranteed to be
+/// tracked.
+#[derive(Debug)]
+pub struct SpaceSaving {
+    capacity: usize,
+    counters: HashMap<String, (u64, u64)>,
+    /// The tracked keys, ordered by count.
+    by_count: BTreeSet<(u64, String)>,
+}
+
+impl SpaceSaving {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            counters: HashMap::with_capacity(capacity),
+            by_count: BTreeSet::new(),
+        }
+    }
+
+    pub fn add(&mut self, key: &str, weight: u64) {
+        if let Some((count, _)) = self.counters.get_mut(key) {
+            self.by_count.remove(&(*count, key.to_owned()));
+            *count += weight;
+            self.by_count.insert((*count, key.to_owned()));
+            return;
+        }
+
+        let error = if self.counters.len() < self.capacity {
+            0
+        } else {
+            let (min, evicted) = self.by_count.pop_first().expect("capacity is not zero");
+            self.counters.remove(&evicted);
+            min
+        };
+        self.counters
+            .insert(key.to_owned(), (error + weight, error));
+        self.by_count.insert((error + weight, key.to_owned()));
+    }
+
+    /// Returns the `k` keys with the highest estimated counts, in descending order.
+    pub fn top(&self, k: usize) -> Vec<Estimate> {
+        self.by_count
+            .iter()
+            .rev()
+            .take(k)
+            .map(|(count, key)| Estimate {
+                key: key.clone(),
+                count: *count,
+                error: self.counters[key].1,
+            })
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.counters.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.counters.clear();
+        self.by_count.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(key: &str, count: u64, error: u64) -> Estimate {
+        Estimate {
+            key: key.into(),
+            count,
+            error,
+        }
+    }
+
+    #[test]
+    fn counts_exactly_within_capacity() {
+        let mut sketch = SpaceSaving::new(3);
+        for key in ["a", "b", "a", "c", "a", "b"] {
+            sketch.add(key, 1);
+        }
+
+        assert_eq!(
+            sketch.top(2),
+            vec![estimate("a", 3, 0), estimate("b", 2, 0)]
+        );
+    }
+
+    #[test]
+    fn replaces_lowest_count_when_full() {
+        let mut sketch = SpaceSaving::new(2);
+        sketch.add("a", 5);
+        sketch.add("b", 2);
+        sketch.add("c", 1);
+
+        assert_eq!(
+            sketch.top(3),
+            vec![estimate("a", 5, 0), estimate("c", 3, 2)]
+        );
+    }
+
+    #[test]
+    fn keeps_heavy_hitters_among_many_keys() {
+        let mut sketch = SpaceSaving::new(10);
+        for i in 0..10_000 {
+            sketch.add(&format!("rare-{i}"), 1);
+            if i % 4 == 0 {
+                sketch.add("heavy", 1);
+            }
+        }
+
+        let top = sketch.top(1);
+        assert_eq!(top[0].key, "heavy");
+        assert!(top[0].count >= 2_500);
+        assert!(top[0].count - top[0].error <= 2_500);
+        assert_eq!(sketch.top(100).len(), 10);
+    }
+}
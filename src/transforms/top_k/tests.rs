@@ -0,0 +1,123 @@
+use futures::stream;
+use serde_json::json;
+use vector_core::config::LogNamespace;
+
+use super::*;
+use crate::event::metric::MetricValue;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<TopKConfig>();
+}
+
+fn top_k(config: &str) -> TopK {
+    TopK::new(&toml::from_str::<TopKConfig>(config).unwrap()).unwrap()
+}
+
+fn log(tenant: &str, message: &str) -> Event {
+    Event::from_json_value(
+        json!({"tenant": tenant, "message": message}),
+        LogNamespace::Legacy,
+    )
+    .unwrap()
+}
+
+fn top(event: &Event) -> serde_json::Value {
+    serde_json::to_value(event.as_log().get("top").unwrap()).unwrap()
+}
+
+#[test]
+fn emits_top_keys_by_count() {
+    let mut transform = top_k(
+        r#"
+        key = "{{ tenant }}"
+        k = 2
+        "#,
+    );
+    for tenant in ["a", "b", "a", "c", "a", "b"] {
+        transform.record(log(tenant, "hello"));
+    }
+
+    let mut output = Vec::new();
+    transform.flush_into(&mut output);
+    assert_eq!(output.len(), 1);
+    assert_eq!(output[0].as_log().get("measure"), Some(&"count".into()));
+    assert!(output[0].as_log().get("timestamp").is_some());
+    assert_eq!(
+        top(&output[0]),
+        json!([
+            {"key": "a", "value": 3, "error": 0},
+            {"key": "b", "value": 2, "error": 0},
+        ])
+    );
+
+    // Values are reset after each emission
+    output.clear();
+    transform.flush_into(&mut output);
+    assert!(output.is_empty());
+}
+
+#[test]
+fn emits_top_keys_by_bytes() {
+    let mut transform = top_k(
+        r#"
+        key = "{{ tenant }}"
+        k = 1
+        measure = "bytes"
+        "#,
+    );
+    transform.record(log("a", "short"));
+    transform.record(log("a", "short"));
+    transform.record(log("b", &"long".repeat(100)));
+
+    let mut output = Vec::new();
+    transform.flush_into(&mut output);
+    assert_eq!(top(&output[0])[0]["key"], "b");
+}
+
+#[test]
+fn emits_gauges() {
+    let mut transform = top_k(
+        r#"
+        key = "{{ tenant }}"
+        output_type = "metric"
+        metric_name = "noisy_tenants"
+        "#,
+    );
+    transform.record(log("a", "hello"));
+    transform.record(log("a", "hello"));
+    // Events without a key are not tracked
+    transform.record(Event::Log(LogEvent::from("no tenant")));
+
+    let mut output = Vec::new();
+    transform.flush_into(&mut output);
+    assert_eq!(output.len(), 1);
+    let metric = output[0].as_metric();
+    assert_eq!(metric.name(), "noisy_tenants");
+    assert_eq!(metric.value(), &MetricValue::Gauge { value: 2.0 });
+    assert_eq!(metric.tag_value("key"), Some("a".into()));
+    assert_eq!(metric.tag_value("measure"), Some("count".into()));
+}
+
+#[test]
+fn rejects_invalid_sizes() {
+    let build = |config: &str| TopK::new(&toml::from_str::<TopKConfig>(config).unwrap());
+
+    assert!(build("key = \"{{ tenant }}\"\nk = 0").is_err());
+    assert!(build("key = \"{{ tenant }}\"\nk = 10\ncapacity = 5").is_err());
+}
+
+#[tokio::test]
+async fn emits_top_keys_when_input_ends() {
+    let transform = Box::new(top_k(r#"key = "{{ tenant }}""#));
+    let output = transform
+        .transform_events(Box::pin(stream::iter(vec![
+            log("a", "hello"),
+            log("b", "hello"),
+        ])))
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(output.len(), 1);
+    assert_eq!(top(&output[0]).as_array().unwrap().len(), 2);
+}
@@ -0,0 +1,155 @@
+use std::{
+    num::{NonZeroU32, NonZeroUsize},
+    time::Duration,
+};
+
+use serde_with::serde_as;
+use vector_config::configurable_component;
+use vector_core::config::{clone_input_definitions, LogNamespace};
+
+use crate::{
+    conditions::AnyCondition,
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema,
+    transforms::{trace_sampler::TraceSampler, Transform},
+};
+
+/// Configuration for the `trace_sampler` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "trace_sampler",
+    "Sample complete traces based on a set of policies."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceSamplerConfig {
+    /// The amount of time to wait after the first span of a trace is received before deciding
+    /// whether to keep it, in milliseconds.
+    ///
+    /// Spans of a trace received after the decision follow that same decision.
+    #[serde(default = "default_decision_wait_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Decision Wait"))]
+    pub decision_wait_ms: Duration,
+
+    /// The interval to check for traces ready for a decision, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Flush Period"))]
+    pub flush_period_ms: Duration,
+
+    /// The maximum number of traces awaiting a decision.
+    ///
+    /// When a new trace would exceed this limit, the decision for the oldest trace is made
+    /// immediately, without waiting for the rest of its spans.
+    #[serde(default = "default_max_traces")]
+    #[configurable(metadata(docs::type_unit = "traces"))]
+    pub max_traces: NonZeroUsize,
+
+    /// The policies used to decide whether to keep a trace.
+    ///
+    /// A trace is kept if any of the policies decides to keep it, and dropped otherwise.
+    pub policies: Vec<SamplingPolicy>,
+}
+
+/// A policy deciding whether to keep a trace.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(tag = "type", rename_all = "snake_case", deny_unknown_fields)]
+#[configurable(metadata(docs::enum_tag_description = "The type of sampling policy."))]
+pub enum SamplingPolicy {
+    /// Keeps traces containing at least one span with an error.
+    Error,
+
+    /// Keeps traces lasting at least a given amount of time.
+    Latency {
+        /// The minimum duration of a trace to keep, in milliseconds.
+        ///
+        /// The duration of a trace spans from the start of its earliest span to the end of its
+        /// latest span.
+        #[configurable(metadata(docs::type_unit = "milliseconds"))]
+        threshold_ms: u64,
+    },
+
+    /// Keeps traces with at least one event matching a condition.
+    Condition {
+        /// The condition to match against each event of a trace.
+        condition: AnyCondition,
+    },
+
+    /// Keeps a fraction of traces.
+    ///
+    /// Traces are chosen by hashing their ID, so the same traces are kept by every instance.
+    Probabilistic {
+        /// The fraction of traces to keep, between `0.0` and `1.0`.
+        #[configurable(validation(range(min = 0.0, max = 1.0)))]
+        rate: f64,
+    },
+
+    /// Keeps up to a number of traces per second for each service.
+    ///
+    /// The service of a trace is the service of its root span.
+    RateLimiting {
+        /// The maximum number of traces to keep per second, for each service.
+        #[configurable(metadata(docs::type_unit = "traces"))]
+        traces_per_second: NonZeroU32,
+    },
+}
+
+const fn default_decision_wait_ms() -> Duration {
+    Duration::from_secs(30)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn default_max_traces() -> NonZeroUsize {
+    NonZeroUsize::new(50_000).unwrap()
+}
+
+impl GenerateConfig for TraceSamplerConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[[policies]]
+type = "error"
+
+[[policies]]
+type = "latency"
+threshold_ms = 1000
+
+[[policies]]
+type = "probabilistic"
+rate = 0.1"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_sampler")]
+impl TransformConfig for TraceSamplerConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        TraceSampler::new(self, &context.enrichment_tables).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The event is not modified, so the definition is passed through as-is
+        vec![TransformOutput::new(
+            DataType::Trace,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
@@ -0,0 +1,403 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    num::NonZeroU32,
+    pin::Pin,
+    time::{Duration, Instant},
+};
+
+use futures::Stream;
+use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
+use vector_core::stream::expiration_map::{map_with_expiration, Emitter};
+use vrl::event_path;
+
+use crate::{
+    conditions::Condition,
+    event::{Event, Value},
+    internal_events::TraceSamplerEventsDropped,
+    transforms::TaskTransform,
+};
+
+mod config;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::{SamplingPolicy, TraceSamplerConfig};
+
+/// The status code of OpenTelemetry spans that failed.
+const STATUS_CODE_ERROR: i64 = 2;
+
+/// The period over which the `rate_limiting` policy counts kept traces.
+const RATE_LIMITING_WINDOW: Duration = Duration::from_secs(1);
+
+/// The events of a trace awaiting a decision.
+struct PendingTrace {
+    events: Vec<Event>,
+    first_seen: Instant,
+}
+
+enum Policy {
+    Error,
+    Latency(Duration),
+    Condition(Condition),
+    Probabilistic(f64),
+    RateLimiting {
+        traces_per_second: NonZeroU32,
+        /// The start of the current one second window, and the traces kept in it, per service.
+        windows: HashMap<String, (Instant, u32)>,
+        /// The last time windows of idle services were removed.
+        pruned: Instant,
+    },
+}
+
+impl Policy {
+    fn build(
+        policy: &SamplingPolicy,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        Ok(match policy {
+            SamplingPolicy::Error => Self::Error,
+            SamplingPolicy::Latency { threshold_ms } => {
+                Self::Latency(Duration::from_millis(*threshold_ms))
+            }
+            SamplingPolicy::Condition { condition } => {
+                Self::Condition(condition.build(enrichment_tables)?)
+            }
+            SamplingPolicy::Probabilistic { rate } => Self::Probabilistic(*rate),
+            SamplingPolicy::RateLimiting { traces_per_second } => Self::RateLimiting {
+                traces_per_second: *traces_per_second,
+                windows: HashMap::new(),
+                pruned: Instant::now(),
+            },
+        })
+    }
+
+    fn keep(&mut self, trace_id: &str, events: &mut Vec<Event>, now: Instant) -> bool {
+        match self {
+            Self::Error => spans(events).any(is_error),
+            Self::Latency(threshold) => trace_duration(events) >= *threshold,
+            Self::Condition(condition) => {
+                let mut matched = false;
+                *events = std::mem::take(events)
+                    .into_iter()
+                    .map(|event| {
+                        if matched {
+                            return event;
+                        }
+                        let (result, event) = condition.check(event);
+                        matched = result;
+                        event
+                    })
+                    .collect();
+                matched
+            }
+            Self::Probabilistic(rate) => {
+                (seahash::hash(trace_id.as_bytes()) as f64) < *rate * u64::MAX as f64
+            }
+            Self::RateLimiting {
+                traces_per_second,
+                windows,
+                pruned,
+            } => {
+                if now.duration_since(*pruned) >= RATE_LIMITING_WINDOW {
+                    windows.retain(|_, (window_start, _)| {
+                        now.duration_since(*window_start) < RATE_LIMITING_WINDOW
+                    });
+                    *pruned = now;
+                }
+
+                let (window_start, kept) = windows
+                    .entry(root_service(events).unwrap_or_default())
+                    .or_insert((now, 0));
+                if now.duration_since(*window_start) >= RATE_LIMITING_WINDOW {
+                    *window_start = now;
+                    *kept = 0;
+                }
+                if *kept < traces_per_second.get() {
+                    *kept += 1;
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+}
+
+/// Iterates over the spans of all the events of a trace.
+///
+/// Events received from the Datadog Agent hold their spans in a `spans` array, while events
+/// received over OTLP are a single span each.
+pub(crate) fn spans(events: &[Event]) -> impl Iterator<Item = &BTreeMap<String, Value>> {
+    events.iter().flat_map(|event| {
+        let trace = event.as_trace();
+        let (nested, flat) = match trace.get(event_path!("spans")) {
+            Some(Value::Array(spans)) => (Some(spans), None),
+            _ => (None, Some(trace.as_map())),
+        };
+        nested
+            .into_iter()
+            .flatten()
+            .filter_map(|span| match span {
+                Value::Object(span) => Some(span),
+                _ => None,
+            })
+            .chain(flat)
+    })
+}
+
+/// Returns whether a span has an error, either through its Datadog `error` flag or through its
+/// OpenTelemetry `status`.
+pub(crate) fn is_error(span: &BTreeMap<String, Value>) -> bool {
+    match (span.get("error"), span.get("status")) {
+        (Some(Value::Integer(error)), _) => *error != 0,
+        (Some(Value::Boolean(error)), _) => *error,
+        (_, Some(Value::Object(status))) => {
+            status.get("code") == Some(&Value::Integer(STATUS_CODE_ERROR))
+        }
+        _ => false,
+    }
+}
+
+/// Gets the start time of a span, and its duration in nanoseconds.
+pub(crate) fn span_timing(
+    span: &BTreeMap<String, Value>,
+) -> (Option<chrono::DateTime<chrono::Utc>>, Option<i64>) {
+    match (
+        span.get("start_time_unix_nano"),
+        span.get("end_time_unix_nano"),
+    ) {
+        // OpenTelemetry spans
+        (Some(Value::Timestamp(start)), Some(Value::Timestamp(end))) => {
+            let duration = (*end - *start).num_nanoseconds().unwrap_or(i64::MAX);
+            (Some(*start), Some(duration.max(0)))
+        }
+        // Datadog spans
+        _ => {
+            let start = match span.get("start") {
+                Some(Value::Timestamp(start)) => Some(*start),
+                _ => None,
+            };
+            let duration = match span.get("duration") {
+                Some(Value::Integer(duration)) => Some((*duration).max(0)),
+                _ => None,
+            };
+            (start, duration)
+        }
+    }
+}
+
+/// Gets the time between the start of the earliest span and the end of the latest span.
+fn trace_duration(events: &[Event]) -> Duration {
+    let mut start = None;
+    let mut end = None;
+    let mut longest = 0;
+    for span in spans(events) {
+        let (span_start, duration) = span_timing(span);
+        let duration = duration.unwrap_or(0);
+        longest = longest.max(duration);
+        if let Some(span_start) = span_start {
+            let span_end = span_start + chrono::Duration::nanoseconds(duration);
+            start = Some(start.map_or(span_start, |start: chrono::DateTime<_>| {
+                start.min(span_start)
+            }));
+            end = Some(end.map_or(span_end, |end: chrono::DateTime<_>| end.max(span_end)));
+        }
+    }
+
+    let nanos = match (start, end) {
+        (Some(start), Some(end)) => (end - start).num_nanoseconds().unwrap_or(i64::MAX),
+        _ => longest,
+    };
+    Duration::from_nanos(nanos.max(0) as u64)
+}
+
+/// Gets the service of a span, from either its Datadog `service` or its OpenTelemetry
+/// `service.name` resource.
+pub(crate) fn span_service(span: &BTreeMap<String, Value>) -> Option<&Value> {
+    span.get("service").or_else(|| match span.get("resources") {
+        Some(Value::Object(resources)) => resources.get("service.name"),
+        _ => None,
+    })
+}
+
+/// Gets the service of the root span of a trace, or of its first span if the root span wasn't
+/// received.
+fn root_service(events: &[Event]) -> Option<String> {
+    let is_root = |span: &&BTreeMap<String, Value>| match span.get("parent_span_id") {
+        // OpenTelemetry root spans have an empty parent ID
+        Some(Value::Bytes(parent_span_id)) => parent_span_id.is_empty(),
+        Some(_) => false,
+        None => matches!(span.get("parent_id"), None | Some(Value::Integer(0))),
+    };
+    spans(events)
+        .find(is_root)
+        .or_else(|| spans(events).next())
+        .and_then(span_service)
+        .map(|service| service.to_string_lossy().into_owned())
+}
+
+/// Gets the ID of the trace an event belongs to.
+fn trace_id(event: &Event) -> Option<String> {
+    event
+        .as_trace()
+        .get(event_path!("trace_id"))
+        .or_else(|| spans(std::slice::from_ref(event)).find_map(|span| span.get("trace_id")))
+        .map(|trace_id| trace_id.to_string_lossy().into_owned())
+}
+
+pub struct TraceSampler {
+    decision_wait: Duration,
+    flush_period: Duration,
+    max_traces: usize,
+    policies: Vec<Policy>,
+    pending: HashMap<String, PendingTrace>,
+    /// The IDs of pending traces, in the order they were first seen.
+    pending_order: VecDeque<String>,
+    /// The decisions made for recent traces, applied to their spans that arrive late.
+    decisions: HashMap<String, bool>,
+    decisions_order: VecDeque<String>,
+    events_dropped: Registered<TraceSamplerEventsDropped>,
+}
+
+impl TraceSampler {
+    pub fn new(
+        config: &TraceSamplerConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        let policies = config
+            .policies
+            .iter()
+            .map(|policy| Policy::build(policy, enrichment_tables))
+            .collect::<crate::Result<_>>()?;
+
+        Ok(Self {
+            decision_wait: config.decision_wait_ms,
+            flush_period: config.flush_period_ms,
+            max_traces: config.max_traces.get(),
+            policies,
+            pending: HashMap::new(),
+            pending_order: VecDeque::new(),
+            decisions: HashMap::new(),
+            decisions_order: VecDeque::new(),
+            events_dropped: register!(TraceSamplerEventsDropped),
+        })
+    }
+
+    fn record(&mut self, event: Event, output: &mut Vec<Event>) {
+        let Some(trace_id) = trace_id(&event) else {
+            // Without an ID, the event can't be grouped with the rest of its trace
+            output.push(event);
+            return;
+        };
+
+        if let Some(keep) = self.decisions.get(&trace_id) {
+            if *keep {
+                output.push(event);
+            } else {
+                self.events_dropped.emit(Count(1));
+            }
+            return;
+        }
+
+        if let Some(pending) = self.pending.get_mut(&trace_id) {
+            pending.events.push(event);
+            return;
+        }
+
+        if self.pending.len() >= self.max_traces {
+            self.decide_oldest(output);
+        }
+        self.pending.insert(
+            trace_id.clone(),
+            PendingTrace {
+                events: vec![event],
+                first_seen: Instant::now(),
+            },
+        );
+        self.pending_order.push_back(trace_id);
+    }
+
+    /// Decides for all traces that have waited long enough.
+    fn flush_into(&mut self, output: &mut Vec<Event>) {
+        while let Some(trace_id) = self.pending_order.front() {
+            let waited = self.pending.get(trace_id).map_or(true, |pending| {
+                pending.first_seen.elapsed() >= self.decision_wait
+            });
+            if !waited {
+                break;
+            }
+            self.decide_oldest(output);
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        while !self.pending_order.is_empty() {
+            self.decide_oldest(output);
+        }
+    }
+
+    fn decide_oldest(&mut self, output: &mut Vec<Event>) {
+        let Some(trace_id) = self.pending_order.pop_front() else {
+            return;
+        };
+        let Some(PendingTrace { mut events, .. }) = self.pending.remove(&trace_id) else {
+            return;
+        };
+
+        let now = Instant::now();
+        let keep = self
+            .policies
+            .iter_mut()
+            .any(|policy| policy.keep(&trace_id, &mut events, now));
+        if keep {
+            output.extend(events);
+        } else {
+            self.events_dropped.emit(Count(events.len()));
+        }
+
+        self.decisions.insert(trace_id.clone(), keep);
+        self.decisions_order.push_back(trace_id);
+        if self.decisions_order.len() > self.max_traces {
+            if let Some(expired) = self.decisions_order.pop_front() {
+                self.decisions.remove(&expired);
+            }
+        }
+    }
+}
+
+impl TaskTransform<Event> for TraceSampler {
+    fn transform(
+        self: Box<Self>,
+        input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let flush_period = self.flush_period;
+
+        Box::pin(map_with_expiration(
+            self,
+            input_rx,
+            flush_period,
+            |me: &mut Box<TraceSampler>, event, emitter: &mut Emitter<Event>| {
+                // called for each event
+                let mut output = Vec::new();
+                me.record(event, &mut output);
+                output.into_iter().for_each(|event| emitter.emit(event));
+            },
+            |me: &mut Box<TraceSampler>, emitter: &mut Emitter<Event>| {
+                // called periodically to decide for traces that have waited long enough
+                let mut output = Vec::new();
+                me.flush_into(&mut output);
+                output.into_iter().for_each(|event| emitter.emit(event));
+            },
+            |me: &mut Box<TraceSampler>, emitter: &mut Emitter<Event>| {
+                // called when the input stream ends
+                let mut output = Vec::new();
+                me.flush_all_into(&mut output);
+                output.into_iter().for_each(|event| emitter.emit(event));
+            },
+        ))
+    }
+}
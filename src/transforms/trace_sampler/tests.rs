@@ -0,0 +1,418 @@
+use std::collections::BTreeMap;
+
+use chrono::{TimeZone, Utc};
+use futures::{stream, StreamExt};
+use vrl::event_path;
+
+use super::*;
+use crate::event::TraceEvent;
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<TraceSamplerConfig>();
+}
+
+fn sampler(config: &str) -> TraceSampler {
+    let config = toml::from_str::<TraceSamplerConfig>(config).unwrap();
+    TraceSampler::new(&config, &Default::default()).unwrap()
+}
+
+struct Span {
+    trace_id: i64,
+    parent_id: i64,
+    service: &'static str,
+    start_ms: i64,
+    duration_ms: i64,
+    error: i64,
+}
+
+impl Default for Span {
+    fn default() -> Self {
+        Self {
+            trace_id: 1,
+            parent_id: 0,
+            service: "web",
+            start_ms: 0,
+            duration_ms: 10,
+            error: 0,
+        }
+    }
+}
+
+fn trace(spans: Vec<Span>) -> Event {
+    let spans = spans
+        .into_iter()
+        .map(|span| {
+            Value::from(BTreeMap::from([
+                ("trace_id".to_string(), Value::from(span.trace_id)),
+                ("parent_id".to_string(), Value::from(span.parent_id)),
+                ("service".to_string(), Value::from(span.service)),
+                (
+                    "start".to_string(),
+                    Value::from(Utc.timestamp_millis_opt(span.start_ms).unwrap()),
+                ),
+                (
+                    "duration".to_string(),
+                    Value::from(span.duration_ms * 1_000_000),
+                ),
+                ("error".to_string(), Value::from(span.error)),
+            ]))
+        })
+        .collect::<Vec<_>>();
+
+    let mut trace = TraceEvent::default();
+    trace.insert(event_path!("spans"), spans);
+    Event::Trace(trace)
+}
+
+fn span_trace(span: Span) -> Event {
+    trace(vec![span])
+}
+
+/// Builds a span as received over OTLP, with the given status code.
+fn otlp_span(trace_id: &str, start_ms: i64, end_ms: i64, status_code: i64) -> Event {
+    let mut span = TraceEvent::default();
+    span.insert(event_path!("trace_id"), trace_id);
+    span.insert(event_path!("parent_span_id"), "");
+    span.insert(
+        event_path!("start_time_unix_nano"),
+        Utc.timestamp_millis_opt(start_ms).unwrap(),
+    );
+    span.insert(
+        event_path!("end_time_unix_nano"),
+        Utc.timestamp_millis_opt(end_ms).unwrap(),
+    );
+    span.insert(
+        event_path!("status"),
+        BTreeMap::from([("code".to_string(), Value::from(status_code))]),
+    );
+    Event::Trace(span)
+}
+
+fn run(sampler: &mut TraceSampler, events: Vec<Event>) -> Vec<Event> {
+    let mut output = Vec::new();
+    for event in events {
+        sampler.record(event, &mut output);
+    }
+    sampler.flush_all_into(&mut output);
+    output
+}
+
+#[test]
+fn error_policy_keeps_traces_with_errors() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "error"
+        "#,
+    );
+
+    let kept = span_trace(Span {
+        trace_id: 1,
+        error: 1,
+        ..Default::default()
+    });
+    let dropped = span_trace(Span {
+        trace_id: 2,
+        ..Default::default()
+    });
+
+    assert_eq!(run(&mut sampler, vec![kept.clone(), dropped]), vec![kept]);
+}
+
+#[test]
+fn latency_policy_uses_whole_trace_duration() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "latency"
+        threshold_ms = 100
+        "#,
+    );
+
+    // Neither span is slow, but the trace spans 150ms
+    let slow = trace(vec![
+        Span {
+            trace_id: 1,
+            start_ms: 0,
+            duration_ms: 50,
+            ..Default::default()
+        },
+        Span {
+            trace_id: 1,
+            parent_id: 1,
+            start_ms: 100,
+            duration_ms: 50,
+            ..Default::default()
+        },
+    ]);
+    let fast = span_trace(Span {
+        trace_id: 2,
+        duration_ms: 50,
+        ..Default::default()
+    });
+
+    assert_eq!(run(&mut sampler, vec![slow.clone(), fast]), vec![slow]);
+}
+
+#[test]
+fn policies_support_otlp_spans() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "error"
+
+        [[policies]]
+        type = "latency"
+        threshold_ms = 100
+        "#,
+    );
+
+    let failed = otlp_span("a", 0, 10, STATUS_CODE_ERROR);
+    let slow = otlp_span("b", 0, 150, 0);
+    let fast = otlp_span("c", 0, 10, 1);
+
+    assert_eq!(
+        run(&mut sampler, vec![failed.clone(), slow.clone(), fast]),
+        vec![failed, slow]
+    );
+}
+
+#[test]
+fn condition_policy_matches_any_event() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "condition"
+        condition = '.spans[0].service == "checkout"'
+        "#,
+    );
+
+    let first = span_trace(Span {
+        trace_id: 1,
+        ..Default::default()
+    });
+    let second = span_trace(Span {
+        trace_id: 1,
+        parent_id: 1,
+        service: "checkout",
+        ..Default::default()
+    });
+    let other = span_trace(Span {
+        trace_id: 2,
+        ..Default::default()
+    });
+
+    assert_eq!(
+        run(&mut sampler, vec![first.clone(), other, second.clone()]),
+        vec![first, second]
+    );
+}
+
+#[test]
+fn probabilistic_policy_bounds() {
+    let events = || {
+        (0..10)
+            .map(|trace_id| {
+                span_trace(Span {
+                    trace_id,
+                    ..Default::default()
+                })
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut none = sampler(
+        r#"
+        [[policies]]
+        type = "probabilistic"
+        rate = 0.0
+        "#,
+    );
+    assert!(run(&mut none, events()).is_empty());
+
+    let mut all = sampler(
+        r#"
+        [[policies]]
+        type = "probabilistic"
+        rate = 1.0
+        "#,
+    );
+    assert_eq!(run(&mut all, events()).len(), 10);
+}
+
+#[test]
+fn rate_limiting_policy_is_per_service() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "rate_limiting"
+        traces_per_second = 1
+        "#,
+    );
+
+    let web_1 = span_trace(Span {
+        trace_id: 1,
+        ..Default::default()
+    });
+    let web_2 = span_trace(Span {
+        trace_id: 2,
+        ..Default::default()
+    });
+    let db = span_trace(Span {
+        trace_id: 3,
+        service: "db",
+        ..Default::default()
+    });
+
+    assert_eq!(
+        run(&mut sampler, vec![web_1.clone(), web_2, db.clone()]),
+        vec![web_1, db]
+    );
+}
+
+#[test]
+fn rate_limiting_policy_forgets_idle_services() {
+    let mut policy = Policy::build(
+        &SamplingPolicy::RateLimiting {
+            traces_per_second: NonZeroU32::new(1).unwrap(),
+        },
+        &Default::default(),
+    )
+    .unwrap();
+    let windows = |policy: &Policy| match policy {
+        Policy::RateLimiting { windows, .. } => windows.len(),
+        _ => unreachable!(),
+    };
+
+    let now = Instant::now();
+    for service in ["web", "db"] {
+        let mut events = vec![span_trace(Span {
+            service,
+            ..Default::default()
+        })];
+        assert!(policy.keep("1", &mut events, now));
+    }
+    assert_eq!(windows(&policy), 2);
+
+    let mut events = vec![span_trace(Span::default())];
+    assert!(policy.keep("2", &mut events, now + Duration::from_secs(2)));
+    assert_eq!(windows(&policy), 1);
+}
+
+#[test]
+fn max_traces_forces_oldest_decision() {
+    let mut sampler = sampler(
+        r#"
+        max_traces = 1
+
+        [[policies]]
+        type = "error"
+        "#,
+    );
+
+    let first = span_trace(Span {
+        trace_id: 1,
+        error: 1,
+        ..Default::default()
+    });
+    let second = span_trace(Span {
+        trace_id: 2,
+        error: 1,
+        ..Default::default()
+    });
+
+    let mut output = Vec::new();
+    sampler.record(first.clone(), &mut output);
+    assert!(output.is_empty());
+    sampler.record(second, &mut output);
+    assert_eq!(output, vec![first]);
+}
+
+#[test]
+fn late_spans_follow_decision() {
+    let mut sampler = sampler(
+        r#"
+        [[policies]]
+        type = "error"
+        "#,
+    );
+
+    let root = span_trace(Span {
+        trace_id: 1,
+        error: 1,
+        ..Default::default()
+    });
+    let late = span_trace(Span {
+        trace_id: 1,
+        parent_id: 1,
+        ..Default::default()
+    });
+    let dropped_root = span_trace(Span {
+        trace_id: 2,
+        ..Default::default()
+    });
+    let dropped_late = span_trace(Span {
+        trace_id: 2,
+        parent_id: 1,
+        error: 1,
+        ..Default::default()
+    });
+
+    let mut output = Vec::new();
+    sampler.record(root.clone(), &mut output);
+    sampler.record(dropped_root, &mut output);
+    sampler.flush_all_into(&mut output);
+    sampler.record(late.clone(), &mut output);
+    sampler.record(dropped_late, &mut output);
+    assert_eq!(output, vec![root, late]);
+}
+
+#[test]
+fn waits_for_decision() {
+    let mut sampler = sampler(
+        r#"
+        decision_wait_ms = 60000
+
+        [[policies]]
+        type = "error"
+        "#,
+    );
+
+    let mut output = Vec::new();
+    sampler.record(
+        span_trace(Span {
+            error: 1,
+            ..Default::default()
+        }),
+        &mut output,
+    );
+    sampler.flush_into(&mut output);
+    assert!(output.is_empty());
+}
+
+#[tokio::test]
+async fn emits_kept_traces_on_shutdown() {
+    let sampler = sampler(
+        r#"
+        [[policies]]
+        type = "error"
+        "#,
+    );
+
+    let kept = span_trace(Span {
+        trace_id: 1,
+        error: 1,
+        ..Default::default()
+    });
+    let dropped = span_trace(Span {
+        trace_id: 2,
+        ..Default::default()
+    });
+
+    let output = Box::new(sampler)
+        .transform(Box::pin(stream::iter(vec![kept.clone(), dropped])))
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(output, vec![kept]);
+}
@@ -0,0 +1,443 @@
+use std::{collections::HashMap, pin::Pin};
+
+use futures::{stream, Stream, StreamExt};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+
+use crate::{
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{
+        metric::{Metric, MetricKind, MetricTags, MetricValue, StatisticKind},
+        Event, Value,
+    },
+    schema,
+    transforms::{
+        tag_cardinality_limit::{TagCardinalityLimit, TagCardinalityLimitConfig},
+        trace_sampler::{is_error, span_service, span_timing, spans},
+        TaskTransform, Transform,
+    },
+};
+
+/// Configuration for the `trace_to_metric` transform.
+#[configurable_component(transform(
+    "trace_to_metric",
+    "Derive request rate, error rate, and latency metrics from the spans of traces."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct TraceToMetricConfig {
+    /// The span fields used as tags on the generated metrics.
+    ///
+    /// Each field is looked up on the span itself, then in its `meta`, `attributes`, and
+    /// `resources` objects. The `service` field also falls back to the `service.name` resource of
+    /// OpenTelemetry spans. Spans missing a field don't get the corresponding tag.
+    #[serde(default = "default_dimensions")]
+    #[configurable(metadata(docs::examples = "service", docs::examples = "resource"))]
+    pub dimensions: Vec<String>,
+
+    /// The namespace of the generated metrics.
+    #[configurable(metadata(docs::examples = "apm"))]
+    pub namespace: Option<String>,
+
+    /// The upper limits of the buckets of the span duration histogram, in seconds.
+    #[serde(default = "default_buckets")]
+    pub buckets: Vec<f64>,
+
+    /// Limits the number of distinct values of each dimension.
+    ///
+    /// When not set, the cardinality of dimensions is not limited.
+    #[configurable(derived)]
+    pub cardinality_limit: Option<TagCardinalityLimitConfig>,
+}
+
+fn default_dimensions() -> Vec<String> {
+    vec!["service".to_string(), "name".to_string()]
+}
+
+fn default_buckets() -> Vec<f64> {
+    vec![
+        0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+    ]
+}
+
+impl GenerateConfig for TraceToMetricConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"dimensions = ["service", "name"]
+buckets = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+
+[cardinality_limit]
+mode = "exact"
+value_limit = 500"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "trace_to_metric")]
+impl TransformConfig for TraceToMetricConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::event_task(TraceToMetric::new(self)))
+    }
+
+    fn input(&self) -> Input {
+        Input::trace()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        _: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // Converting the trace to metrics means we lose all incoming `Definition`s.
+        vec![TransformOutput::new(DataType::Metric, HashMap::new())]
+    }
+}
+
+pub struct TraceToMetric {
+    dimensions: Vec<String>,
+    namespace: Option<String>,
+    buckets: Vec<f64>,
+    cardinality_limit: Option<TagCardinalityLimit>,
+}
+
+impl TraceToMetric {
+    pub fn new(config: &TraceToMetricConfig) -> Self {
+        let mut buckets = config.buckets.clone();
+        buckets.sort_by(f64::total_cmp);
+        buckets.dedup();
+
+        Self {
+            dimensions: config.dimensions.clone(),
+            namespace: config.namespace.clone(),
+            buckets,
+            cardinality_limit: config
+                .cardinality_limit
+                .clone()
+                .map(TagCardinalityLimit::new),
+        }
+    }
+
+    fn metrics(&self, event: &Event) -> Vec<Event> {
+        let mut metrics = Vec::new();
+        for span in spans(std::slice::from_ref(event)) {
+            let tags = self
+                .dimensions
+                .iter()
+                .filter_map(|dimension| {
+                    let value = span
+                        .get(dimension)
+                        .or_else(|| {
+                            ["meta", "attributes", "resources"]
+                                .into_iter()
+                                .find_map(|field| match span.get(field) {
+                                    Some(Value::Object(fields)) => fields.get(dimension),
+                                    _ => None,
+                                })
+                        })
+                        .or_else(|| match dimension.as_str() {
+                            "service" => span_service(span),
+                            _ => None,
+                        })?;
+                    Some((dimension.clone(), value.to_string_lossy().into_owned()))
+                })
+                .collect::<MetricTags>();
+            let (timestamp, duration) = span_timing(span);
+            let metric = |name: &str, value: MetricValue| {
+                Event::Metric(
+                    Metric::new_with_metadata(
+                        name,
+                        MetricKind::Incremental,
+                        value,
+                        event.metadata().clone(),
+                    )
+                    .with_namespace(self.namespace.clone())
+                    .with_tags(tags.clone().as_option())
+                    .with_timestamp(timestamp),
+                )
+            };
+
+            metrics.push(metric("spans_total", MetricValue::Counter { value: 1.0 }));
+
+            if is_error(span) {
+                metrics.push(metric(
+                    "span_errors_total",
+                    MetricValue::Counter { value: 1.0 },
+                ));
+            }
+
+            // Span durations are in nanoseconds
+            if let Some(duration) = duration {
+                let distribution = MetricValue::Distribution {
+                    samples: vector_core::samples![duration as f64 / 1e9 => 1],
+                    statistic: StatisticKind::Histogram,
+                };
+                if let Some(histogram) = distribution.distribution_to_agg_histogram(&self.buckets) {
+                    metrics.push(metric("span_duration_seconds", histogram));
+                }
+            }
+        }
+        metrics
+    }
+
+    fn transform_one(&mut self, event: Event) -> Vec<Event> {
+        let metrics = self.metrics(&event);
+        match &mut self.cardinality_limit {
+            Some(limit) => metrics
+                .into_iter()
+                .filter_map(|metric| limit.transform_one(metric))
+                .collect(),
+            None => metrics,
+        }
+    }
+}
+
+impl TaskTransform<Event> for TraceToMetric {
+    fn transform(
+        self: Box<Self>,
+        task: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut inner = self;
+        Box::pin(task.flat_map(move |event| stream::iter(inner.transform_one(event))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{TimeZone, Utc};
+
+    use vrl::event_path;
+
+    use super::*;
+    use crate::event::{metric::Bucket, TraceEvent};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<TraceToMetricConfig>();
+    }
+
+    fn trace_to_metric(config: &str) -> TraceToMetric {
+        TraceToMetric::new(&toml::from_str::<TraceToMetricConfig>(config).unwrap())
+    }
+
+    fn span(service: &str, name: &str, duration_ms: i64, error: i64) -> Value {
+        Value::from(BTreeMap::from([
+            ("service".to_string(), Value::from(service)),
+            ("name".to_string(), Value::from(name)),
+            (
+                "start".to_string(),
+                Value::from(Utc.timestamp_opt(1_700_000_000, 0).unwrap()),
+            ),
+            ("duration".to_string(), Value::from(duration_ms * 1_000_000)),
+            ("error".to_string(), Value::from(error)),
+            (
+                "meta".to_string(),
+                Value::from(BTreeMap::from([(
+                    "http.method".to_string(),
+                    Value::from("GET"),
+                )])),
+            ),
+        ]))
+    }
+
+    fn trace(spans: Vec<Value>) -> Event {
+        let mut trace = TraceEvent::default();
+        trace.insert(event_path!("spans"), spans);
+        Event::Trace(trace)
+    }
+
+    fn summarize(events: Vec<Event>) -> Vec<(String, Vec<(String, String)>)> {
+        events
+            .into_iter()
+            .map(|event| {
+                let metric = event.into_metric();
+                let tags = metric
+                    .tags()
+                    .map(|tags| {
+                        tags.iter_single()
+                            .map(|(key, value)| (key.to_string(), value.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                (metric.name().to_string(), tags)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn emits_red_metrics_per_span() {
+        let mut transform = trace_to_metric(
+            r#"
+            buckets = [0.1, 0.01, 1.0]
+            "#,
+        );
+
+        let metrics = transform.transform_one(trace(vec![
+            span("web", "request", 50, 1),
+            span("db", "query", 5, 0),
+        ]));
+
+        let tags = |service: &str, name: &str| {
+            vec![
+                ("name".to_string(), name.to_string()),
+                ("service".to_string(), service.to_string()),
+            ]
+        };
+        assert_eq!(
+            summarize(metrics.clone()),
+            vec![
+                ("spans_total".to_string(), tags("web", "request")),
+                ("span_errors_total".to_string(), tags("web", "request")),
+                ("span_duration_seconds".to_string(), tags("web", "request")),
+                ("spans_total".to_string(), tags("db", "query")),
+                ("span_duration_seconds".to_string(), tags("db", "query")),
+            ]
+        );
+
+        let metric = metrics[2].as_metric();
+        assert_eq!(metric.kind(), MetricKind::Incremental);
+        assert_eq!(
+            metric.timestamp(),
+            Some(Utc.timestamp_opt(1_700_000_000, 0).unwrap())
+        );
+        assert_eq!(
+            metric.value(),
+            &MetricValue::AggregatedHistogram {
+                buckets: vec![
+                    Bucket {
+                        upper_limit: 0.01,
+                        count: 0
+                    },
+                    Bucket {
+                        upper_limit: 0.1,
+                        count: 1
+                    },
+                    Bucket {
+                        upper_limit: 1.0,
+                        count: 0
+                    },
+                ],
+                count: 1,
+                sum: 0.05,
+            }
+        );
+    }
+
+    #[test]
+    fn emits_red_metrics_for_opentelemetry_spans() {
+        let mut transform = trace_to_metric("");
+
+        let start = Utc.timestamp_opt(1_700_000_000, 0).unwrap();
+        let mut span = TraceEvent::default();
+        span.insert(event_path!("name"), "checkout");
+        span.insert(
+            event_path!("resources"),
+            BTreeMap::from([("service.name".to_string(), Value::from("web"))]),
+        );
+        span.insert(event_path!("start_time_unix_nano"), start);
+        span.insert(
+            event_path!("end_time_unix_nano"),
+            start + chrono::Duration::milliseconds(50),
+        );
+        span.insert(
+            event_path!("status"),
+            BTreeMap::from([
+                ("code".to_string(), Value::from(2)),
+                ("message".to_string(), Value::from("failed")),
+            ]),
+        );
+
+        let metrics = transform.transform_one(Event::Trace(span));
+        let tags = vec![
+            ("name".to_string(), "checkout".to_string()),
+            ("service".to_string(), "web".to_string()),
+        ];
+        assert_eq!(
+            summarize(metrics.clone()),
+            vec![
+                ("spans_total".to_string(), tags.clone()),
+                ("span_errors_total".to_string(), tags.clone()),
+                ("span_duration_seconds".to_string(), tags),
+            ]
+        );
+
+        let metric = metrics[2].as_metric();
+        assert_eq!(metric.timestamp(), Some(start));
+        match metric.value() {
+            MetricValue::AggregatedHistogram { count, sum, .. } => {
+                assert_eq!((*count, *sum), (1, 0.05));
+            }
+            value => panic!("unexpected value: {value:?}"),
+        }
+    }
+
+    #[test]
+    fn reads_dimensions_from_meta() {
+        let mut transform = trace_to_metric(
+            r#"
+            dimensions = ["service", "http.method", "missing"]
+            namespace = "apm"
+            "#,
+        );
+
+        let metrics = transform.transform_one(trace(vec![span("web", "request", 50, 0)]));
+        let metric = metrics[0].as_metric();
+        assert_eq!(metric.namespace(), Some("apm"));
+        assert_eq!(
+            summarize(metrics)[0].1,
+            vec![
+                ("http.method".to_string(), "GET".to_string()),
+                ("service".to_string(), "web".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn limits_dimension_cardinality() {
+        let mut transform = trace_to_metric(
+            r#"
+            dimensions = ["service"]
+
+            [cardinality_limit]
+            mode = "exact"
+            value_limit = 1
+            limit_exceeded_action = "drop_tag"
+            "#,
+        );
+
+        let metrics = transform.transform_one(trace(vec![
+            span("web", "request", 50, 0),
+            span("db", "query", 5, 0),
+        ]));
+        let summary = summarize(metrics);
+        assert_eq!(
+            summary[0].1,
+            vec![("service".to_string(), "web".to_string())]
+        );
+        assert!(summary[2].1.is_empty());
+    }
+
+    #[tokio::test]
+    async fn emits_metrics_for_each_trace() {
+        let transform = trace_to_metric("");
+
+        let output = Box::new(transform)
+            .transform(Box::pin(stream::iter(vec![
+                trace(vec![span("web", "request", 50, 0)]),
+                trace(vec![span("web", "request", 50, 1)]),
+            ])))
+            .collect::<Vec<_>>()
+            .await;
+        assert_eq!(output.len(), 5);
+    }
+}
@@ -0,0 +1,224 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::PathBuf,
+};
+
+use indexmap::IndexMap;
+use jsonschema::JSONSchema;
+use lookup::owned_value_path;
+use snafu::{ResultExt, Snafu};
+use vector_config::configurable_component;
+use vector_core::config::LogNamespace;
+use vrl::value::{kind::Collection, Kind};
+
+use crate::{
+    config::{
+        log_schema, DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    schema::{self, Definition},
+    template::Template,
+    transforms::{
+        validate::{Validate, REJECTED},
+        Transform,
+    },
+};
+
+#[derive(Debug, Snafu)]
+pub enum BuildError {
+    #[snafu(display("At least one schema must be configured"))]
+    NoSchemas,
+
+    #[snafu(display("`schema` must be set when more than one schema is configured"))]
+    MissingSchemaSelector,
+
+    #[snafu(display("Schema {:?} must provide exactly one of `inline` or `file`", name))]
+    InlineAndOrFile { name: String },
+
+    #[snafu(display("Could not read schema {:?} from {:?}: {}", name, path, source))]
+    FileReadFailed {
+        name: String,
+        path: PathBuf,
+        source: std::io::Error,
+    },
+
+    #[snafu(display("Schema {:?} is not valid JSON: {}", name, source))]
+    InvalidJson {
+        name: String,
+        source: serde_json::Error,
+    },
+
+    #[snafu(display("Schema {:?} is not a valid JSON Schema: {}", name, message))]
+    InvalidSchema { name: String, message: String },
+}
+
+/// Configuration for the `validate` transform.
+#[configurable_component(transform(
+    "validate",
+    "Validate log events against JSON Schema documents and reroute non-conforming events."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct ValidateConfig {
+    /// The JSON Schema documents events are validated against, by name.
+    #[configurable(metadata(docs::additional_props_description = "A JSON Schema document."))]
+    pub schemas: IndexMap<String, SchemaConfig>,
+
+    /// The name of the schema to validate each event against.
+    ///
+    /// Required if more than one schema is configured. Events for which the name can't be rendered,
+    /// or doesn't match a configured schema, are rejected.
+    #[configurable(metadata(docs::examples = "{{ kind }}", docs::examples = "{{ service }}-v1"))]
+    pub schema: Option<Template>,
+
+    /// Reroutes rejected events to a named output instead of dropping them.
+    ///
+    /// When set to `true`, events that don't conform to their schema are forwarded to a
+    /// specially-named output, `rejected`, annotated with the reason they were rejected.
+    /// Otherwise, they are dropped.
+    #[serde(default = "crate::serde::default_false")]
+    #[configurable(metadata(docs::human_name = "Reroute Rejected Events"))]
+    pub reroute_dropped: bool,
+}
+
+/// A JSON Schema document.
+#[configurable_component]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct SchemaConfig {
+    /// The JSON Schema document, as a JSON string.
+    ///
+    /// Required if `file` is missing.
+    #[configurable(metadata(docs::examples = r#"{"type": "object", "required": ["message"]}"#))]
+    pub inline: Option<String>,
+
+    /// File path to the JSON Schema document.
+    ///
+    /// Required if `inline` is missing.
+    #[configurable(metadata(docs::examples = "./my/schema.json"))]
+    pub file: Option<PathBuf>,
+}
+
+impl SchemaConfig {
+    fn compile(&self, name: &str) -> Result<JSONSchema, BuildError> {
+        let document = match (&self.inline, &self.file) {
+            (Some(inline), None) => inline.clone(),
+            (None, Some(path)) => fs::read_to_string(path).context(FileReadFailedSnafu {
+                name,
+                path: path.clone(),
+            })?,
+            _ => return Err(BuildError::InlineAndOrFile { name: name.into() }),
+        };
+        let document = serde_json::from_str(&document).context(InvalidJsonSnafu { name })?;
+
+        JSONSchema::compile(&document).map_err(|error| BuildError::InvalidSchema {
+            name: name.into(),
+            message: error.to_string(),
+        })
+    }
+}
+
+impl ValidateConfig {
+    pub(super) fn compile_schemas(&self) -> Result<IndexMap<String, JSONSchema>, BuildError> {
+        if self.schemas.is_empty() {
+            return Err(BuildError::NoSchemas);
+        }
+        if self.schema.is_none() && self.schemas.len() > 1 {
+            return Err(BuildError::MissingSchemaSelector);
+        }
+
+        self.schemas
+            .iter()
+            .map(|(name, schema)| Ok((name.clone(), schema.compile(name)?)))
+            .collect()
+    }
+}
+
+impl GenerateConfig for ValidateConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"[schemas.default]
+inline = '{"type": "object", "required": ["message"]}'"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "validate")]
+impl TransformConfig for ValidateConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Ok(Transform::synchronous(Validate::new(self, context)?))
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        let rejected_kind = Kind::object(BTreeMap::from([
+            ("reason".into(), Kind::bytes()),
+            ("message".into(), Kind::bytes()),
+            ("schema".into(), Kind::bytes().or_null()),
+            (
+                "errors".into(),
+                Kind::array(Collection::from_unknown(Kind::object(BTreeMap::from([
+                    ("instance_path".into(), Kind::bytes()),
+                    ("schema_path".into(), Kind::bytes()),
+                    ("message".into(), Kind::bytes()),
+                ])))),
+            ),
+            ("component_id".into(), Kind::bytes().or_null()),
+            ("component_type".into(), Kind::bytes()),
+            ("component_kind".into(), Kind::bytes()),
+        ]));
+
+        let mut default_definitions = HashMap::new();
+        let mut rejected_definitions = HashMap::new();
+
+        for (output_id, input_definition) in input_definitions {
+            // Conforming events are not modified, while rejected events are annotated with the
+            // reason they were rejected.
+            let rejected_definition = Definition::combine_log_namespaces(
+                input_definition.log_namespaces(),
+                input_definition.clone().with_event_field(
+                    &log_schema()
+                        .metadata_key()
+                        .expect("valid metadata key")
+                        .with_field_appended("rejected"),
+                    rejected_kind.clone(),
+                    None,
+                ),
+                input_definition.clone().with_metadata_field(
+                    &owned_value_path!("vector", "rejected"),
+                    rejected_kind.clone(),
+                    None,
+                ),
+            );
+
+            default_definitions.insert(output_id.clone(), input_definition.clone());
+            rejected_definitions.insert(output_id.clone(), rejected_definition);
+        }
+
+        let default_output = TransformOutput::new(DataType::Log, default_definitions);
+
+        if self.reroute_dropped {
+            vec![
+                default_output,
+                TransformOutput::new(DataType::Log, rejected_definitions).with_port(REJECTED),
+            ]
+        } else {
+            vec![default_output]
+        }
+    }
+
+    fn enable_concurrency(&self) -> bool {
+        true
+    }
+}
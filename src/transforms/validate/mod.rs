@@ -0,0 +1,156 @@
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use jsonschema::JSONSchema;
+use lookup::{metadata_path, PathPrefix};
+use vector_core::config::LogNamespace;
+use vrl::path;
+use vrl::path::ValuePath;
+
+use crate::{
+    config::{log_schema, ComponentKey, TransformContext},
+    event::{Event, LogEvent},
+    internal_events::{TemplateRenderingError, ValidationFailed},
+    template::Template,
+    transforms::{SyncTransform, TransformOutputsBuf},
+};
+
+mod config;
+
+#[cfg(test)]
+mod tests;
+
+pub use config::ValidateConfig;
+
+pub(crate) const REJECTED: &str = "rejected";
+
+#[derive(Clone)]
+pub struct Validate {
+    schemas: Arc<IndexMap<String, JSONSchema>>,
+    selector: Option<Template>,
+    reroute_dropped: bool,
+    component_key: Option<ComponentKey>,
+}
+
+/// The reason an event was rejected.
+struct Rejection<'a> {
+    reason: &'static str,
+    message: String,
+    schema: Option<&'a str>,
+    errors: Vec<serde_json::Value>,
+}
+
+impl Validate {
+    pub fn new(config: &ValidateConfig, context: &TransformContext) -> crate::Result<Self> {
+        Ok(Self {
+            schemas: Arc::new(config.compile_schemas()?),
+            selector: config.schema.clone(),
+            reroute_dropped: config.reroute_dropped,
+            component_key: context.key.clone(),
+        })
+    }
+
+    fn check(&self, log: &LogEvent) -> Result<(), Rejection<'_>> {
+        let (name, schema) = match &self.selector {
+            None => self.schemas.get_index(0).expect("at least one schema"),
+            Some(selector) => {
+                let name = selector.render_string(log).map_err(|error| {
+                    let message = error.to_string();
+                    emit!(TemplateRenderingError {
+                        field: Some("schema"),
+                        drop_event: false,
+                        error,
+                    });
+                    Rejection {
+                        reason: "template_failed",
+                        message,
+                        schema: None,
+                        errors: Vec::new(),
+                    }
+                })?;
+                self.schemas
+                    .get_key_value(name.as_str())
+                    .ok_or_else(|| Rejection {
+                        reason: "unknown_schema",
+                        message: format!("No schema named {:?} is configured.", name),
+                        schema: None,
+                        errors: Vec::new(),
+                    })?
+            }
+        };
+        let name = name.as_str();
+
+        let instance = serde_json::to_value(log.value()).map_err(|error| Rejection {
+            reason: "invalid",
+            message: error.to_string(),
+            schema: Some(name),
+            errors: Vec::new(),
+        })?;
+
+        schema.validate(&instance).map_err(|errors| Rejection {
+            reason: "invalid",
+            message: format!("Event does not conform to schema {:?}.", name),
+            schema: Some(name),
+            errors: errors
+                .map(|error| {
+                    serde_json::json!({
+                        "instance_path": error.instance_path.to_string(),
+                        "schema_path": error.schema_path.to_string(),
+                        "message": error.to_string(),
+                    })
+                })
+                .collect(),
+        })
+    }
+
+    fn rejected_data(&self, rejection: Rejection) -> serde_json::Value {
+        serde_json::json!({
+            "reason": rejection.reason,
+            "message": rejection.message,
+            "schema": rejection.schema,
+            "errors": rejection.errors,
+            "component_id": self.component_key,
+            "component_type": "validate",
+            "component_kind": "transform",
+        })
+    }
+
+    fn annotate_rejected(&self, log: &mut LogEvent, rejection: Rejection) {
+        match log.namespace() {
+            LogNamespace::Legacy => {
+                if let Some(metadata_key) = log_schema().metadata_key() {
+                    log.insert(
+                        (PathPrefix::Event, metadata_key.concat(path!("rejected"))),
+                        self.rejected_data(rejection),
+                    );
+                }
+            }
+            LogNamespace::Vector => {
+                log.insert(
+                    metadata_path!("vector", "rejected"),
+                    self.rejected_data(rejection),
+                );
+            }
+        }
+    }
+}
+
+impl SyncTransform for Validate {
+    fn transform(&mut self, mut event: Event, output: &mut TransformOutputsBuf) {
+        let log = event.as_mut_log();
+        match self.check(log) {
+            Ok(()) => output.push(None, event),
+            Err(rejection) => {
+                emit!(ValidationFailed {
+                    reason: rejection.reason,
+                    error_count: rejection.errors.len(),
+                    event_dropped: !self.reroute_dropped,
+                });
+                if self.reroute_dropped {
+                    self.annotate_rejected(log, rejection);
+                    output.push(Some(REJECTED), event);
+                }
+            }
+        }
+    }
+}
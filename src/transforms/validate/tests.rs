@@ -0,0 +1,169 @@
+use std::{collections::HashMap, io::Write};
+
+use serde_json::json;
+use vector_core::config::LogNamespace;
+
+use super::*;
+use crate::{
+    config::{DataType, TransformConfig, TransformOutput},
+    schema,
+};
+
+#[test]
+fn generate_config() {
+    crate::test_util::test_generate_config::<ValidateConfig>();
+}
+
+const USER_SCHEMA: &str = r#"{
+    "type": "object",
+    "required": ["name", "age"],
+    "properties": {
+        "name": {"type": "string"},
+        "age": {"type": "integer", "minimum": 0}
+    }
+}"#;
+
+fn validate(config: &str) -> Validate {
+    let config = format!("reroute_dropped = true\n{config}");
+    let config = toml::from_str::<ValidateConfig>(&config).unwrap();
+    Validate::new(&config, &TransformContext::default()).unwrap()
+}
+
+/// Runs an event through the transform, returning the output it was sent to and the event.
+fn run(validate: &mut Validate, event: serde_json::Value) -> (Option<&'static str>, Event) {
+    let mut outputs = TransformOutputsBuf::new_with_capacity(
+        vec![
+            TransformOutput::new(DataType::all(), HashMap::new()),
+            TransformOutput::new(DataType::all(), HashMap::new()).with_port(REJECTED),
+        ],
+        1,
+    );
+    let event = Event::from_json_value(event, LogNamespace::Legacy).unwrap();
+    validate.transform(event, &mut outputs);
+
+    let mut primary = outputs.take_primary().into_events();
+    let mut rejected = outputs
+        .take_all_named()
+        .remove(REJECTED)
+        .unwrap()
+        .into_events();
+    match (primary.next(), rejected.next()) {
+        (Some(event), None) => (None, event),
+        (None, Some(event)) => (Some(REJECTED), event),
+        outputs => panic!("unexpected outputs: {:?}", outputs),
+    }
+}
+
+fn rejected(event: &Event) -> serde_json::Value {
+    serde_json::to_value(event.as_log().get("metadata.rejected").unwrap()).unwrap()
+}
+
+#[test]
+fn passes_conforming_events_through() {
+    let mut validate = validate(&format!("schemas.user.inline = '''{USER_SCHEMA}'''"));
+
+    let (output, event) = run(&mut validate, json!({"name": "jane", "age": 42}));
+    assert_eq!(output, None);
+    assert_eq!(
+        serde_json::to_value(event.as_log().value()).unwrap(),
+        json!({"name": "jane", "age": 42})
+    );
+}
+
+#[test]
+fn rejects_non_conforming_events_with_error_paths() {
+    let mut validate = validate(&format!("schemas.user.inline = '''{USER_SCHEMA}'''"));
+
+    let (output, event) = run(&mut validate, json!({"name": "jane", "age": -1}));
+    assert_eq!(output, Some(REJECTED));
+    assert_eq!(event.as_log().get("name"), Some(&"jane".into()));
+
+    let rejected = rejected(&event);
+    assert_eq!(rejected["reason"], "invalid");
+    assert_eq!(rejected["schema"], "user");
+    assert_eq!(rejected["component_type"], "validate");
+    assert_eq!(rejected["errors"].as_array().unwrap().len(), 1);
+    assert_eq!(rejected["errors"][0]["instance_path"], "/age");
+    assert_eq!(
+        rejected["errors"][0]["schema_path"],
+        "/properties/age/minimum"
+    );
+}
+
+#[test]
+fn drops_non_conforming_events_unless_rerouted() {
+    let config = format!("schemas.user.inline = '''{USER_SCHEMA}'''");
+    let config = toml::from_str::<ValidateConfig>(&config).unwrap();
+    let outputs = config.outputs(
+        enrichment::TableRegistry::default(),
+        &[("test".into(), schema::Definition::any())],
+        LogNamespace::Legacy,
+    );
+    assert_eq!(outputs.len(), 1);
+
+    let mut validate = Validate::new(&config, &TransformContext::default()).unwrap();
+    let mut outputs = TransformOutputsBuf::new_with_capacity(
+        vec![TransformOutput::new(DataType::all(), HashMap::new())],
+        1,
+    );
+    let event = Event::from_json_value(json!({"name": "jane"}), LogNamespace::Legacy).unwrap();
+    validate.transform(event, &mut outputs);
+    assert!(outputs.take_primary().is_empty());
+}
+
+#[test]
+fn selects_schemas_per_event() {
+    let mut validate = validate(&format!(
+        r#"
+        schema = "{{{{ kind }}}}"
+        schemas.user.inline = '''{USER_SCHEMA}'''
+        schemas.order.inline = '{{"required": ["order_id"]}}'
+        "#
+    ));
+
+    let (output, _) = run(&mut validate, json!({"kind": "order", "order_id": 1}));
+    assert_eq!(output, None);
+
+    let (output, event) = run(&mut validate, json!({"kind": "user", "order_id": 1}));
+    assert_eq!(output, Some(REJECTED));
+    assert_eq!(rejected(&event)["errors"].as_array().unwrap().len(), 2);
+
+    let (output, event) = run(&mut validate, json!({"kind": "invoice"}));
+    assert_eq!(output, Some(REJECTED));
+    assert_eq!(rejected(&event)["reason"], "unknown_schema");
+
+    let (output, event) = run(&mut validate, json!({}));
+    assert_eq!(output, Some(REJECTED));
+    assert_eq!(rejected(&event)["reason"], "template_failed");
+}
+
+#[test]
+fn loads_schemas_from_files() {
+    let mut file = tempfile::NamedTempFile::new().unwrap();
+    file.write_all(USER_SCHEMA.as_bytes()).unwrap();
+    let mut validate = validate(&format!("schemas.user.file = {:?}", file.path()));
+
+    let (output, _) = run(&mut validate, json!({"name": "jane"}));
+    assert_eq!(output, Some(REJECTED));
+}
+
+#[test]
+fn rejects_invalid_configurations() {
+    let build = |config: &str| {
+        let config = toml::from_str::<ValidateConfig>(config).unwrap();
+        Validate::new(&config, &TransformContext::default())
+    };
+
+    // No schemas
+    assert!(build("schemas = {}").is_err());
+    // Several schemas without a selector
+    assert!(build("schemas.a.inline = '{}'\nschemas.b.inline = '{}'").is_err());
+    // Both or neither of `inline` and `file`
+    assert!(build("schemas.a = { inline = '{}', file = 'schema.json' }").is_err());
+    assert!(build("schemas.a = {}").is_err());
+    // Invalid JSON and invalid schemas
+    assert!(build("schemas.a.inline = '{'").is_err());
+    assert!(build(r#"schemas.a.inline = '{"type": 42}'"#).is_err());
+    // Missing files
+    assert!(build("schemas.a.file = '/nonexistent/schema.json'").is_err());
+}
@@ -0,0 +1,461 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+use serde_with::serde_as;
+use vector_common::internal_event::{Count, InternalEventHandle as _, Registered};
+use vector_config::configurable_component;
+use vector_core::config::{clone_input_definitions, LogNamespace};
+
+use crate::{
+    conditions::{AnyCondition, Condition},
+    config::{
+        DataType, GenerateConfig, Input, OutputId, TransformConfig, TransformContext,
+        TransformOutput,
+    },
+    event::{discriminant::Discriminant, Event},
+    internal_events::WindowEventsDropped,
+    schema,
+    transforms::{FunctionTransform, OutputBuffer, Transform},
+};
+
+/// Configuration for the `window` transform.
+#[serde_as]
+#[configurable_component(transform(
+    "window",
+    "Forward the events surrounding a triggering event, and drop the rest."
+))]
+#[derive(Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct WindowConfig {
+    /// The condition that triggers a window.
+    ///
+    /// When an event matches, the buffered events preceding it are forwarded, followed by the
+    /// event itself and the next `num_events_after` events.
+    pub trigger: AnyCondition,
+
+    /// A condition for events that are always forwarded.
+    ///
+    /// Matching events bypass the window entirely: they are neither buffered nor counted towards
+    /// `num_events_after`.
+    pub forward_when: Option<AnyCondition>,
+
+    /// The number of events preceding a triggering event to forward.
+    ///
+    /// Events are buffered until a trigger occurs, and the oldest event is dropped once the buffer
+    /// is full.
+    #[serde(default = "default_num_events_before")]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub num_events_before: usize,
+
+    /// The number of events following a triggering event to forward.
+    #[serde(default)]
+    #[configurable(metadata(docs::type_unit = "events"))]
+    pub num_events_after: usize,
+
+    /// An ordered list of fields by which to group events.
+    ///
+    /// Each group keeps its own buffer and is triggered independently, which allows keeping the
+    /// context of independent event streams separate. When no fields are specified, all events
+    /// share a single buffer.
+    #[serde(default)]
+    #[configurable(metadata(docs::examples = "service", docs::examples = "host",))]
+    pub group_by: Vec<String>,
+
+    /// The maximum period of time a group can go without receiving events, in milliseconds.
+    ///
+    /// Once a group has been idle for this long, its buffered events are dropped and its state is
+    /// removed, which bounds the memory used by groups that are no longer active.
+    #[serde(default = "default_expire_after_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    #[configurable(metadata(docs::human_name = "Expire After"))]
+    pub expire_after_ms: Duration,
+}
+
+const fn default_num_events_before() -> usize {
+    100
+}
+
+const fn default_expire_after_ms() -> Duration {
+    Duration::from_secs(300)
+}
+
+impl GenerateConfig for WindowConfig {
+    fn generate_config() -> toml::Value {
+        toml::from_str(
+            r#"trigger = '.level == "error"'
+num_events_before = 100
+num_events_after = 0"#,
+        )
+        .unwrap()
+    }
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "window")]
+impl TransformConfig for WindowConfig {
+    async fn build(&self, context: &TransformContext) -> crate::Result<Transform> {
+        Window::new(self, &context.enrichment_tables).map(Transform::function)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        // The event is not modified, so the definition is passed through as-is
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+#[derive(Clone, Debug)]
+struct WindowState {
+    /// The events preceding the next trigger.
+    buffer: VecDeque<Event>,
+
+    /// The number of events still to be forwarded after the last trigger.
+    remaining_after: usize,
+
+    /// The last time an event of the group was received.
+    last_seen: Instant,
+}
+
+impl WindowState {
+    fn new(now: Instant) -> Self {
+        Self {
+            buffer: VecDeque::new(),
+            remaining_after: 0,
+            last_seen: now,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Window {
+    trigger: Condition,
+    forward_when: Option<Condition>,
+    num_events_before: usize,
+    num_events_after: usize,
+    group_by: Vec<String>,
+    expire_after: Duration,
+    states: HashMap<Discriminant, WindowState>,
+    last_expiry: Instant,
+    events_dropped: Registered<WindowEventsDropped>,
+}
+
+impl Window {
+    pub fn new(
+        config: &WindowConfig,
+        enrichment_tables: &enrichment::TableRegistry,
+    ) -> crate::Result<Self> {
+        Ok(Self {
+            trigger: config.trigger.build(enrichment_tables)?,
+            forward_when: config
+                .forward_when
+                .as_ref()
+                .map(|condition| condition.build(enrichment_tables))
+                .transpose()?,
+            num_events_before: config.num_events_before,
+            num_events_after: config.num_events_after,
+            group_by: config.group_by.clone(),
+            expire_after: config.expire_after_ms,
+            states: HashMap::new(),
+            last_expiry: Instant::now(),
+            events_dropped: register!(WindowEventsDropped),
+        })
+    }
+
+    /// Removes the groups that have been idle for longer than `expire_after`, dropping their
+    /// buffered events.
+    ///
+    /// This only runs once per `expire_after` period, so idle groups are removed within twice that
+    /// period.
+    fn expire(&mut self, now: Instant) {
+        if now.duration_since(self.last_expiry) < self.expire_after {
+            return;
+        }
+        self.last_expiry = now;
+
+        let expire_after = self.expire_after;
+        let mut dropped = 0;
+        self.states.retain(|_, state| {
+            let idle = now.duration_since(state.last_seen) >= expire_after;
+            if idle {
+                dropped += state.buffer.len();
+            }
+            !idle
+        });
+        if dropped > 0 {
+            self.events_dropped.emit(Count(dropped));
+        }
+    }
+
+    fn transform_at(&mut self, output: &mut OutputBuffer, event: Event, now: Instant) {
+        self.expire(now);
+
+        let (forward, event) = match &self.forward_when {
+            Some(condition) => condition.check(event),
+            None => (false, event),
+        };
+        if forward {
+            output.push(event);
+            return;
+        }
+
+        let (triggered, event) = self.trigger.check(event);
+        let discriminant = Discriminant::from_log_event(event.as_log(), &self.group_by);
+        let state = self
+            .states
+            .entry(discriminant)
+            .or_insert_with(|| WindowState::new(now));
+        state.last_seen = now;
+
+        if triggered {
+            output.extend(state.buffer.drain(..));
+            output.push(event);
+            state.remaining_after = self.num_events_after;
+        } else if state.remaining_after > 0 {
+            state.remaining_after -= 1;
+            output.push(event);
+        } else if self.num_events_before > 0 {
+            if state.buffer.len() >= self.num_events_before {
+                state.buffer.pop_front();
+                self.events_dropped.emit(Count(1));
+            }
+            state.buffer.push_back(event);
+        } else {
+            self.events_dropped.emit(Count(1));
+        }
+    }
+}
+
+impl FunctionTransform for Window {
+    fn transform(&mut self, output: &mut OutputBuffer, event: Event) {
+        self.transform_at(output, event, Instant::now());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+    use tokio_stream::wrappers::ReceiverStream;
+    use vector_common::config::ComponentKey;
+
+    use super::*;
+    use crate::{
+        config::schema::Definition, event::LogEvent,
+        test_util::components::assert_transform_compliance, transforms::test::create_topology,
+    };
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<WindowConfig>();
+    }
+
+    fn window(config: &str) -> Window {
+        let config = toml::from_str::<WindowConfig>(config).unwrap();
+        Window::new(&config, &Default::default()).unwrap()
+    }
+
+    fn log(message: &str, level: &str, service: &str) -> Event {
+        let mut log = LogEvent::from(message);
+        log.insert("level", level);
+        log.insert("service", service);
+        Event::Log(log)
+    }
+
+    fn run(window: &mut Window, events: Vec<Event>) -> Vec<String> {
+        let mut output = OutputBuffer::default();
+        for event in events {
+            window.transform(&mut output, event);
+        }
+        output
+            .into_events()
+            .map(|event| {
+                event
+                    .as_log()
+                    .get("message")
+                    .unwrap()
+                    .to_string_lossy()
+                    .into_owned()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forwards_events_around_trigger() {
+        let mut window = window(
+            r#"
+trigger = '.level == "error"'
+num_events_before = 2
+num_events_after = 1
+"#,
+        );
+
+        let output = run(
+            &mut window,
+            vec![
+                log("1", "debug", "a"),
+                log("2", "debug", "a"),
+                log("3", "debug", "a"),
+                log("4", "error", "a"),
+                log("5", "debug", "a"),
+                log("6", "debug", "a"),
+            ],
+        );
+        assert_eq!(output, vec!["2", "3", "4", "5"]);
+    }
+
+    #[test]
+    fn does_not_forward_buffered_events_twice() {
+        let mut window = window(
+            r#"
+trigger = '.level == "error"'
+num_events_before = 5
+"#,
+        );
+
+        let output = run(
+            &mut window,
+            vec![
+                log("1", "debug", "a"),
+                log("2", "error", "a"),
+                log("3", "error", "a"),
+                log("4", "debug", "a"),
+            ],
+        );
+        assert_eq!(output, vec!["1", "2", "3"]);
+    }
+
+    #[test]
+    fn forward_when_bypasses_window() {
+        let mut window = window(
+            r#"
+trigger = '.level == "error"'
+forward_when = '.level == "info"'
+num_events_before = 1
+"#,
+        );
+
+        let output = run(
+            &mut window,
+            vec![
+                log("1", "debug", "a"),
+                log("2", "info", "a"),
+                log("3", "error", "a"),
+            ],
+        );
+        assert_eq!(output, vec!["2", "1", "3"]);
+    }
+
+    #[test]
+    fn groups_have_separate_buffers() {
+        let mut window = window(
+            r#"
+trigger = '.level == "error"'
+num_events_before = 1
+num_events_after = 1
+group_by = ["service"]
+"#,
+        );
+
+        let output = run(
+            &mut window,
+            vec![
+                log("1", "debug", "a"),
+                log("2", "debug", "b"),
+                log("3", "error", "a"),
+                log("4", "debug", "b"),
+                log("5", "debug", "a"),
+            ],
+        );
+        assert_eq!(output, vec!["1", "3", "5"]);
+    }
+
+    #[test]
+    fn expires_idle_groups() {
+        let mut window = window(
+            r#"
+trigger = '.level == "error"'
+num_events_before = 1
+group_by = ["service"]
+expire_after_ms = 1000
+"#,
+        );
+
+        let now = Instant::now();
+        let mut output = OutputBuffer::default();
+        window.transform_at(&mut output, log("1", "debug", "a"), now);
+        window.transform_at(&mut output, log("2", "debug", "b"), now);
+        assert_eq!(window.states.len(), 2);
+
+        // The buffered event of the idle group is dropped along with its state
+        let later = now + Duration::from_secs(2);
+        window.transform_at(&mut output, log("3", "debug", "b"), later);
+        window.transform_at(&mut output, log("4", "error", "a"), later);
+        assert_eq!(window.states.len(), 2);
+        assert_eq!(
+            output
+                .into_events()
+                .map(|event| {
+                    event
+                        .as_log()
+                        .get("message")
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned()
+                })
+                .collect::<Vec<_>>(),
+            vec!["4"]
+        );
+    }
+
+    #[tokio::test]
+    async fn window_topology() {
+        assert_transform_compliance(async {
+            let transform_config = toml::from_str::<WindowConfig>(
+                r#"
+trigger = '.level == "error"'
+num_events_before = 1
+"#,
+            )
+            .unwrap();
+
+            let (tx, rx) = mpsc::channel(1);
+            let (topology, mut out) =
+                create_topology(ReceiverStream::new(rx), transform_config).await;
+
+            let mut before = log("before", "debug", "a");
+            let mut trigger = log("trigger", "error", "a");
+            tx.send(before.clone()).await.unwrap();
+            tx.send(trigger.clone()).await.unwrap();
+
+            for event in [&mut before, &mut trigger] {
+                event.set_source_id(Arc::new(ComponentKey::from("in")));
+                event.set_upstream_id(Arc::new(OutputId::from("transform")));
+                event
+                    .metadata_mut()
+                    .set_schema_definition(&Arc::new(Definition::default_legacy_namespace()));
+            }
+
+            assert_eq!(out.recv().await.unwrap(), before);
+            assert_eq!(out.recv().await.unwrap(), trigger);
+
+            drop(tx);
+            topology.stop().await;
+            assert_eq!(out.recv().await, None);
+        })
+        .await;
+    }
+}
@@ -0,0 +1,70 @@
+package metadata
+
+components: _mqtt: {
+	features: {
+		collect: from: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					port:      1883
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+
+		send: to: {
+			service: services.mqtt
+			interface: {
+				socket: {
+					api: {
+						title: "MQTT protocol"
+						url:   urls.mqtt
+					}
+					direction: "outgoing"
+					protocols: ["tcp"]
+					ssl: "optional"
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		notices: []
+		warnings: []
+	}
+
+	how_it_works: {
+		rumqttc: {
+			title: "rumqttc"
+			body:  """
+				The `mqtt` source/sink uses [`rumqttc`](\(urls.rumqttc)) under the hood and speaks
+				MQTT 3.1.1.
+				"""
+		}
+		sessions: {
+			title: "Persistent sessions"
+			body:  """
+				By default, a clean session is started on every connection. When `clean_session` is
+				disabled, the broker keeps the session, including subscriptions and unacknowledged QoS 1
+				and QoS 2 messages, while Vector is disconnected and resumes it on reconnection. The
+				broker identifies the session by `client_id`, which must then be set explicitly.
+				"""
+		}
+		tls: {
+			title: "TLS"
+			body:  """
+				When TLS is enabled, the broker's certificate is verified against the system's trusted
+				certificates and the `tls.ca_file` certificate, if any. The certificate and its hostname
+				are always verified, so `tls.verify_certificate` and `tls.verify_hostname` can't be
+				disabled. The client key in `tls.key_file` can be an RSA, EC or PKCS#8 key.
+				"""
+		}
+	}
+}
@@ -330,6 +330,55 @@ base: components: sinks: nats: configuration: {
 			}
 		}
 	}
+	headers_key: {
+		description: """
+			The log field name to use for the NATS message headers.
+
+			The field must contain a map of string values. If omitted, no headers are written.
+			"""
+		required: false
+		type: string: examples: ["headers"]
+	}
+	jetstream: {
+		description: """
+			Options for publishing messages through [NATS JetStream][nats_jetstream].
+
+			When set, each message is published to JetStream and the sink waits for the server's
+			acknowledgement (`PubAck`) before considering the message delivered. Failed or timed out
+			acknowledgements are retried according to the `request` settings.
+
+			[nats_jetstream]: https://docs.nats.io/nats-concepts/jetstream
+			"""
+		required: false
+		type: object: options: {
+			domain: {
+				description: """
+					The [JetStream domain][nats_jetstream_domain] to publish to.
+
+					If omitted, the default domain of the account is used.
+
+					[nats_jetstream_domain]: https://docs.nats.io/running-a-nats-service/configuration/leafnodes/jetstream_leafnodes
+					"""
+				required: false
+				type: string: examples: ["hub"]
+			}
+			message_id: {
+				description: """
+					The template used to set the `Nats-Msg-Id` header of each message.
+
+					JetStream uses this identifier to discard duplicate messages published within the
+					stream's duplicate window, which makes retried publishes idempotent.
+
+					If omitted, no message ID is set.
+					"""
+				required: false
+				type: string: {
+					examples: ["{{ id }}", "{{ host }}-{{ offset }}"]
+					syntax: "template"
+				}
+			}
+		}
+	}
 	request: {
 		description: """
 			Middleware settings for outbound requests.
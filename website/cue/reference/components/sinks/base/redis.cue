@@ -77,6 +77,12 @@ base: components: sinks: redis: configuration: {
 
 					This is the default.
 					"""
+				stream: """
+					The Redis `stream` type.
+
+					Messages are appended to the stream as entries, which consumer groups can read and
+					acknowledge independently of each other.
+					"""
 			}
 		}
 	}
@@ -482,4 +488,54 @@ base: components: sinks: redis: configuration: {
 			}
 		}
 	}
+	stream_option: {
+		description: "Stream-specific options."
+		required:    false
+		type: object: options: {
+			field: {
+				description: "The field of each stream entry that holds the encoded event."
+				required:    false
+				type: string: {
+					default: "message"
+					examples: ["message"]
+				}
+			}
+			fields: {
+				description: """
+					Additional fields to add to each stream entry, mapped from fields of the event.
+
+					The value of each event field is added as-is for strings, and in its string representation
+					otherwise. Event fields that don't exist are not added to the entry. The mapped fields are
+					read before `encoding.except_fields` and `encoding.only_fields` are applied, so they can be
+					excluded from the encoded event.
+					"""
+				required: false
+				type: object: {
+					examples: [{
+						host:  "host"
+						level: "log.level"
+					}]
+					options: "*": {
+						description: "The event field to map to this stream entry field."
+						required:    true
+						type: string: {}
+					}
+				}
+			}
+			maxlen: {
+				description: """
+					The approximate maximum number of entries to keep in the stream.
+
+					When set, the stream is trimmed with `MAXLEN ~` as entries are added. Redis trims the stream
+					in whole nodes, so it can hold slightly more entries than this. By default, the stream is not
+					trimmed.
+					"""
+				required: false
+				type: uint: {
+					examples: [100000]
+					unit: "entries"
+				}
+			}
+		}
+	}
 }
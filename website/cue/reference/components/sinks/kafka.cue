@@ -66,7 +66,38 @@ components: sinks: kafka: {
 		traces: false
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		transactions: {
+			title: "Exactly-once delivery"
+			body:  """
+				By default, the sink produces each event on its own, and an event can be written more
+				than once if a delivery is retried. When `transaction.enabled` is set, events are
+				grouped into batches according to the `batch` settings, and each batch is produced
+				within a Kafka transaction using the `transactional.id` of the sink. If any message of
+				a batch fails, the transaction is aborted and the whole batch is produced again. Events
+				are only acknowledged once their transaction has been committed.
+
+				Consumers reading the topic with `isolation.level` set to `read_committed` only see the
+				messages of committed transactions, giving exactly-once delivery from Vector into
+				Kafka. The `transactional.id` is set with `transaction.transactional_id`, which is
+				required when transactions are enabled. It must not be shared by two running sinks, so
+				when several Vector replicas run the same configuration, it should include a value
+				specific to each replica, such as `vector-${HOSTNAME}`.
+				"""
+		}
+		schema_registry: {
+			title: "Confluent Schema Registry"
+			body:  """
+				When `schema_registry` is set, the Avro schema of the `avro` encoding is registered
+				under a subject in the registry, and every message is prefixed with the magic byte and
+				the ID of the schema, so that consumers using the Confluent wire format can decode it.
+				With the `topic_name` strategy, the subject is `<topic>-value` for each topic the sink
+				writes to, while with the `record_name` strategy it is the fully-qualified name of the
+				Avro record. Schema IDs are cached per subject. When `auto_register_schemas` is
+				disabled, the schema is looked up under the subject instead of being registered.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		kafka_queue_messages:                components.sources.internal_metrics.output.metrics.kafka_queue_messages
@@ -61,7 +61,21 @@ components: sinks: nats: {
 		traces:  false
 	}
 
-	how_it_works: components._nats.how_it_works
+	how_it_works: components._nats.how_it_works & {
+		jetstream: {
+			title: "JetStream"
+			body:  """
+				By default, messages are published with core NATS, so a message is considered delivered
+				once it has been written to the connection. When `jetstream` is configured, messages are
+				published to JetStream instead and the sink waits for the server's acknowledgement before
+				marking the events as delivered. Publishes that are not acknowledged are retried.
+
+				Setting `jetstream.message_id` sets the `Nats-Msg-Id` header, which lets the server discard
+				messages that were already stored within the stream's duplicate window, so retries do not
+				produce duplicates.
+				"""
+		}
+	}
 
 	telemetry: metrics: {
 		send_errors_total: components.sources.internal_metrics.output.metrics.send_errors_total
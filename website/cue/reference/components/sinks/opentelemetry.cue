@@ -0,0 +1,122 @@
+package metadata
+
+components: sinks: opentelemetry: {
+	title: "OpenTelemetry"
+
+	description: """
+		Exports logs, metrics, and traces to an OpenTelemetry collector, or any other backend that
+		accepts the OpenTelemetry protocol (OTLP), over gRPC or HTTP.
+		"""
+
+	classes: {
+		commonly_used: false
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "batch"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			batch: {
+				enabled:      true
+				common:       false
+				max_bytes:    10_000_000
+				timeout_secs: 1.0
+			}
+			compression: enabled: false
+			encoding: enabled:    false
+			request: {
+				enabled: true
+				headers: false
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+				enabled_by_scheme:      true
+			}
+			to: {
+				service: services.opentelemetry
+
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["http"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	configuration: base.components.sinks.opentelemetry.configuration
+
+	how_it_works: {
+		protocols: {
+			title: "Protocols"
+			body: """
+				The `protocol` option selects between OTLP/gRPC (`grpc`), which conventionally listens on
+				port 4317, and OTLP/HTTP with either binary protobuf (`http_protobuf`) or JSON
+				(`http_json`) payloads, which conventionally listens on port 4318. With the HTTP
+				protocols, each signal is sent to its own path (`/v1/logs`, `/v1/metrics`, and
+				`/v1/traces`) below the configured endpoint.
+				"""
+		}
+
+		mapping: {
+			title: "Event mapping"
+			body: """
+				Events produced by the `opentelemetry` source are exported unchanged: resource and scope
+				attributes are restored from the fields (or metric tags prefixed with `resource.` and
+				`scope.`) that the source decoded them into, and records that share a resource and scope
+				are grouped together.
+
+				Other log events are exported with their `message` as the log body and their remaining
+				fields as attributes. Counters are exported as monotonic sums, gauges as gauges,
+				aggregated histograms as histograms, and distributions, sketches, and aggregated
+				summaries as summaries. Sets are exported as a gauge of their size. Metrics that can't be
+				represented in OTLP are dropped, and reported by the `component_discarded_events_total`
+				metric.
+				"""
+		}
+
+		retries: {
+			title: "Retries and throttling"
+			body: """
+				Requests are retried when the backend reports a transient failure, as described by the
+				[OTLP specification](\(urls.opentelemetry_protocol)). When the backend throttles the
+				sink, either through a gRPC `RetryInfo` detail or an HTTP `Retry-After` header, the
+				request is delayed by the requested amount, up to 30 seconds, before it is retried.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
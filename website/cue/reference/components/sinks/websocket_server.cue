@@ -0,0 +1,100 @@
+package metadata
+
+components: sinks: websocket_server: {
+	title: "WebSocket Server"
+
+	classes: {
+		commonly_used: false
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		service_providers: []
+		stateful: false
+	}
+
+	features: {
+		acknowledgements: true
+		auto_generated:   true
+		healthcheck: enabled: false
+		send: {
+			compression: enabled: false
+			encoding: {
+				enabled: true
+				codec: {
+					enabled: true
+					enum: ["json", "text"]
+				}
+			}
+			request: enabled: false
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    false
+				enabled_default:        false
+			}
+			to: {
+				service: services.websocket
+				interface: {
+					socket: {
+						direction: "incoming"
+						port:      8080
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+		}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.sinks.websocket_server.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			summary:      true
+			set:          true
+		}
+		traces: true
+	}
+
+	how_it_works: {
+		filters: {
+			title: "Client filters"
+			body:  """
+				Every connected client receives all events by default. A client can narrow down the
+				events it receives with query parameters on the URL it connects to: each
+				`field.<path>=<value>` parameter only lets log events through whose field at `<path>`
+				has the given value, and a `condition` parameter sets a [VRL boolean
+				expression](\(urls.vrl_boolean_expression)) that events must match. For example, connecting to
+				`ws://localhost:8080/?field.service=api&condition=.status%20%3E%3D%20500` streams the
+				events of the `api` service with a status of at least 500. Connections with an invalid
+				filter are rejected with a `400 Bad Request` response.
+				"""
+		}
+		slow_clients: {
+			title: "Slow clients"
+			body:  """
+				Events are queued separately for each client, up to `client_queue_size` events. When a
+				client doesn't read its events fast enough and its queue is full, further events are
+				dropped for that client and counted in the `component_discarded_events_total` metric,
+				so a slow client never holds back the pipeline or the other clients.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		open_connections:             components.sources.internal_metrics.output.metrics.open_connections
+		connection_established_total: components.sources.internal_metrics.output.metrics.connection_established_total
+		connection_shutdown_total:    components.sources.internal_metrics.output.metrics.connection_shutdown_total
+	}
+}
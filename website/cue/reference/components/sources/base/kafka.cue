@@ -192,6 +192,20 @@ base: components: sources: kafka: configuration: {
 			}
 		}
 	}
+	end_timestamp: {
+		description: """
+			Stops reading each partition at the first message whose timestamp is at or after this time.
+
+			The offsets of the messages are resolved when the source starts, and all partitions of
+			`topics` are assigned explicitly instead of through the consumer group. Offsets given in
+			`partitions` take precedence.
+
+			Once every partition reached its end, and all events were acknowledged, the source
+			finishes.
+			"""
+		required: false
+		type: timestamp: examples: ["2023-10-02T00:00:00Z"]
+	}
 	fetch_wait_max_ms: {
 		description: "Maximum time the broker may wait to fill the response."
 		required:    false
@@ -293,8 +307,14 @@ base: components: sources: kafka: configuration: {
 		}
 	}
 	group_id: {
-		description: "The consumer group name to be used to consume events from Kafka."
-		required:    true
+		description: """
+			The consumer group name to be used to consume events from Kafka.
+
+			This must be set to subscribe to `topics`. When partitions are assigned explicitly, through
+			`partitions`, `start_timestamp`, or `end_timestamp`, the offsets are only committed to the
+			group if it is set, and are never used to decide where to start reading.
+			"""
+		required: false
 		type: string: examples: ["consumer-group-name"]
 	}
 	headers_key: {
@@ -384,6 +404,49 @@ base: components: sources: kafka: configuration: {
 			examples: ["partition"]
 		}
 	}
+	partitions: {
+		description: """
+			The partitions to read events from, assigned explicitly instead of through the consumer
+			group.
+
+			Either `topics` or `partitions` must be set.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: object: options: {
+				end_offset: {
+					description: """
+						The offset to stop reading at. The message at this offset is not read.
+
+						If not set, reading stops at `end_timestamp` if that is set, and never stops otherwise.
+						"""
+					required: false
+					type: uint: examples: [2000]
+				}
+				partition: {
+					description: "The number of the partition."
+					required:    true
+					type: uint: examples: [0]
+				}
+				start_offset: {
+					description: """
+						The offset of the first message to read.
+
+						If not set, reading starts at `start_timestamp` if that is set, and at the position given by
+						`auto_offset_reset` otherwise.
+						"""
+					required: false
+					type: uint: examples: [1000]
+				}
+				topic: {
+					description: "The name of the topic the partition belongs to."
+					required:    true
+					type: string: examples: ["topic-1"]
+				}
+			}
+		}
+	}
 	sasl: {
 		description: "Configuration for SASL authentication when interacting with Kafka."
 		required:    false
@@ -422,6 +485,159 @@ base: components: sources: kafka: configuration: {
 			}
 		}
 	}
+	schema_registry: {
+		description: """
+			The Confluent Schema Registry used to decode messages in its wire format.
+
+			Messages starting with the magic byte and schema ID of the wire format are decoded with the
+			schema resolved from the registry. Avro and JSON Schema payloads are decoded directly, while
+			Protobuf payloads are decoded with the configured `decoding` once their message indexes are
+			skipped. Other messages are decoded with `decoding` as usual.
+
+			While the registry is unavailable, decoding is retried with a backoff and no further messages
+			are read. Messages that fail to decode with their schema are dropped.
+			"""
+		required: false
+		type: object: options: {
+			auth: {
+				description: """
+					Configuration of the authentication strategy for HTTP requests.
+
+					HTTP authentication should be used with HTTPS only, as the authentication credentials are passed as an
+					HTTP header without any additional encryption beyond what is provided by the transport itself.
+					"""
+				required: false
+				type: object: options: {
+					password: {
+						description:   "The basic authentication password."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${PASSWORD}", "password"]
+					}
+					strategy: {
+						description: "The authentication strategy to use."
+						required:    true
+						type: string: enum: {
+							basic: """
+								Basic authentication.
+
+								The username and password are concatenated and encoded via [base64][base64].
+
+								[base64]: https://en.wikipedia.org/wiki/Base64
+								"""
+							bearer: """
+								Bearer authentication.
+
+								The bearer token value (OAuth2, JWT, etc.) is passed as-is.
+								"""
+						}
+					}
+					token: {
+						description:   "The bearer authentication token."
+						relevant_when: "strategy = \"bearer\""
+						required:      true
+						type: string: {}
+					}
+					user: {
+						description:   "The basic authentication username."
+						relevant_when: "strategy = \"basic\""
+						required:      true
+						type: string: examples: ["${USERNAME}", "username"]
+					}
+				}
+			}
+			tls: {
+				description: "TLS configuration."
+				required:    false
+				type: object: options: {
+					alpn_protocols: {
+						description: """
+							Sets the list of supported ALPN protocols.
+
+							Declare the supported ALPN protocols, which are used during negotiation with peer. They are prioritized in the order
+							that they are defined.
+							"""
+						required: false
+						type: array: items: type: string: examples: ["h2"]
+					}
+					ca_file: {
+						description: """
+							Absolute path to an additional CA certificate file.
+
+							The certificate must be in the DER or PEM (X.509) format. Additionally, the certificate can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/certificate_authority.crt"]
+					}
+					crt_file: {
+						description: """
+							Absolute path to a certificate file used to identify this server.
+
+							The certificate must be in DER, PEM (X.509), or PKCS#12 format. Additionally, the certificate can be provided as
+							an inline string in PEM format.
+
+							If this is set, and is not a PKCS#12 archive, `key_file` must also be set.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.crt"]
+					}
+					key_file: {
+						description: """
+							Absolute path to a private key file used to identify this server.
+
+							The key must be in DER or PEM (PKCS#8) format. Additionally, the key can be provided as an inline string in PEM format.
+							"""
+						required: false
+						type: string: examples: ["/path/to/host_certificate.key"]
+					}
+					key_pass: {
+						description: """
+							Passphrase used to unlock the encrypted key file.
+
+							This has no effect unless `key_file` is set.
+							"""
+						required: false
+						type: string: examples: ["${KEY_PASS_ENV_VAR}", "PassWord1"]
+					}
+					verify_certificate: {
+						description: """
+							Enables certificate verification.
+
+							If enabled, certificates must not be expired and must be issued by a trusted
+							issuer. This verification operates in a hierarchical manner, checking that the leaf certificate (the
+							certificate presented by the client/server) is not only valid, but that the issuer of that certificate is also valid, and
+							so on until the verification process reaches a root certificate.
+
+							Relevant for both incoming and outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the validity of certificates.
+							"""
+						required: false
+						type: bool: {}
+					}
+					verify_hostname: {
+						description: """
+							Enables hostname verification.
+
+							If enabled, the hostname used to connect to the remote host must be present in the TLS certificate presented by
+							the remote host, either as the Common Name or as an entry in the Subject Alternative Name extension.
+
+							Only relevant for outgoing connections.
+
+							Do NOT set this to `false` unless you understand the risks of not verifying the remote hostname.
+							"""
+						required: false
+						type: bool: {}
+					}
+				}
+			}
+			url: {
+				description: "The URL of the schema registry."
+				required:    true
+				type: string: examples: ["http://localhost:8081"]
+			}
+		}
+	}
 	session_timeout_ms: {
 		description: "The Kafka session timeout."
 		required:    false
@@ -440,6 +656,17 @@ base: components: sources: kafka: configuration: {
 			unit: "milliseconds"
 		}
 	}
+	start_timestamp: {
+		description: """
+			Starts reading each partition at the first message whose timestamp is at or after this time.
+
+			The offsets of the messages are resolved when the source starts, and all partitions of
+			`topics` are assigned explicitly instead of through the consumer group. Offsets given in
+			`partitions` take precedence.
+			"""
+		required: false
+		type: timestamp: examples: ["2023-10-01T00:00:00Z"]
+	}
 	tls: {
 		description: "Configures the TLS options for incoming/outgoing connections."
 		required:    false
@@ -556,8 +783,13 @@ base: components: sources: kafka: configuration: {
 			The Kafka topics names to read events from.
 
 			Regular expression syntax is supported if the topic begins with `^`.
+
+			Either `topics` or `partitions` must be set.
 			"""
-		required: true
-		type: array: items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["^(prefix1|prefix2)-.+", "topic-1", "topic-2"]
+		}
 	}
 }
@@ -1,8 +1,29 @@
 package metadata
 
 base: components: sources: redis: configuration: {
+	acknowledgements: {
+		deprecated: true
+		description: """
+			Controls how acknowledgements are handled by this source.
+
+			This setting is **deprecated** in favor of enabling `acknowledgements` at the [global][global_acks] or sink level.
+
+			Enabling or disabling acknowledgements at the source level has **no effect** on acknowledgement behavior.
+
+			See [End-to-end Acknowledgements][e2e_acks] for more information on how event acknowledgement is handled.
+
+			[global_acks]: https://vector.dev/docs/reference/configuration/global-options/#acknowledgements
+			[e2e_acks]: https://vector.dev/docs/about/under-the-hood/architecture/end-to-end-acknowledgements/
+			"""
+		required: false
+		type: object: options: enabled: {
+			description: "Whether or not end-to-end acknowledgements are enabled for this source."
+			required:    false
+			type: bool: {}
+		}
+	}
 	data_type: {
-		description: "The Redis data type (`list` or `channel`) to use."
+		description: "The Redis data type (`list`, `channel`, or `stream`) to use."
 		required:    false
 		type: string: {
 			default: "list"
@@ -13,6 +34,12 @@ base: components: sources: redis: configuration: {
 					This is based on Redis' Pub/Sub capabilities.
 					"""
 				list: "The `list` data type."
+				stream: """
+					The `stream` data type.
+
+					Entries are read as part of a consumer group, and acknowledged once their events have been
+					processed.
+					"""
 			}
 		}
 	}
@@ -274,6 +301,72 @@ base: components: sources: redis: configuration: {
 		required: false
 		type: string: examples: ["redis_key"]
 	}
+	stream: {
+		description: "Options for the Redis `stream` data type."
+		required:    false
+		type: object: options: {
+			batch_size: {
+				description: "The maximum number of entries to read from the stream at once."
+				required:    false
+				type: uint: {
+					default: 100
+					unit:    "entries"
+				}
+			}
+			claim_min_idle_secs: {
+				description: """
+					The minimum amount of time, in seconds, that an entry must have been pending before it is
+					claimed by this consumer.
+
+					Entries are pending when they were read by a consumer of the group but not acknowledged, for
+					example because the consumer stopped or their events failed to be delivered. Claiming them
+					makes sure they are eventually processed. Set to `0` to never claim pending entries.
+					"""
+				required: false
+				type: uint: {
+					default: 60
+					unit:    "seconds"
+				}
+			}
+			consumer: {
+				description: """
+					The name of this consumer within the consumer group.
+
+					Each instance reading from the same group must use a distinct name. By default, the hostname
+					is used.
+					"""
+				required: false
+				type: string: examples: ["vector-01"]
+			}
+			field: {
+				description: "The field of each stream entry that holds the message to decode."
+				required:    false
+				type: string: {
+					default: "message"
+					examples: ["message"]
+				}
+			}
+			group: {
+				description: """
+					The consumer group to read the stream as.
+
+					The group, and the stream itself, are created if they don't exist yet. A newly created group
+					only receives entries added to the stream after its creation.
+					"""
+				required: true
+				type: string: examples: ["vector"]
+			}
+			id_key: {
+				description: """
+					Sets the name of the log field to use to add the entry ID to each event.
+
+					By default, this is not set and the field is not automatically added.
+					"""
+				required: false
+				type: string: examples: ["redis_id"]
+			}
+		}
+	}
 	url: {
 		description: """
 			The Redis URL to connect to.
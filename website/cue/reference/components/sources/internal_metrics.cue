@@ -850,6 +850,17 @@ components: sources: internal_metrics: {
 			default_namespace: "vector"
 			tags:              _component_tags
 		}
+		redactions_total: {
+			description:       "The total number of sensitive values redacted."
+			type:              "counter"
+			default_namespace: "vector"
+			tags:              _component_tags & {
+				detector: {
+					description: "The detector that found the sensitive values."
+					required:    true
+				}
+			}
+		}
 		request_automatic_decode_errors_total: {
 			description:       "The total number of request errors for this component when it attempted to automatically discover and handle the content-encoding of incoming request data."
 			type:              "counter"
@@ -101,5 +101,34 @@ components: sources: kafka: {
 		kafka_consumer_lag:                   components.sources.internal_metrics.output.metrics.kafka_consumer_lag
 	}
 
-	how_it_works: components._kafka.how_it_works
+	how_it_works: components._kafka.how_it_works & {
+		replays: {
+			title: "Replaying a range of messages"
+			body:  """
+				By default, the source subscribes to `topics` with the consumer group named by `group_id`,
+				and resumes from the offsets committed to that group. To read a specific range of
+				messages instead, the partitions can be assigned explicitly: either by listing them in
+				`partitions` with the offsets to read them from and up to, or by setting
+				`start_timestamp` and `end_timestamp` to read all partitions of `topics` between two
+				points in time. The timestamps are resolved to offsets with the broker's time index when
+				the source starts. No consumer group is needed in that case.
+
+				When every partition has an end, the source finishes once all of them were read up to
+				their end and the events read were acknowledged, which lets Vector exit after a
+				backfill.
+				"""
+		}
+		schema_registry: {
+			title: "Confluent Schema Registry"
+			body:  """
+				When `schema_registry` is set, the source detects messages in the Confluent wire format
+				by their leading magic byte, and resolves the schema identified by the following schema
+				ID from the registry. Schemas are cached, so the registry is only queried once per
+				schema. Avro and JSON Schema payloads are decoded into events directly, while Protobuf
+				payloads are passed to the configured `decoding`, which must be the `protobuf` codec
+				with a descriptor of the message type. Messages that are not in the wire format are
+				decoded with `decoding` as usual.
+				"""
+		}
+	}
 }
@@ -39,11 +39,7 @@ components: sources: opentelemetry: {
 
 	support: {
 		requirements: []
-		warnings: [
-			"""
-				The `opentelemetry` source only supports log events at this time.
-				""",
-		]
+		warnings: []
 		notices: []
 	}
 
@@ -60,6 +56,18 @@ components: sources: opentelemetry: {
 				Received log events will go to this output stream. Use `<component_id>.logs` as an input to downstream transforms and sinks.
 				"""
 		},
+		{
+			name: "metrics"
+			description: """
+				Received metric events will go to this output stream. Use `<component_id>.metrics` as an input to downstream transforms and sinks.
+				"""
+		},
+		{
+			name: "traces"
+			description: """
+				Received trace events will go to this output stream. Use `<component_id>.traces` as an input to downstream transforms and sinks.
+				"""
+		},
 	]
 
 	output: {
@@ -192,9 +200,108 @@ components: sources: opentelemetry: {
 				}
 			}
 		}
+		metrics: {
+			_extra_tags: {
+				"resource.*": {
+					description: "The attributes of the resource the metric originates from."
+					required:    false
+					examples: ["resource.service.name"]
+				}
+				"scope.*": {
+					description: "The name, version, and attributes of the instrumentation scope."
+					required:    false
+					examples: ["scope.name"]
+				}
+			}
+			counter: output._passthrough_counter & {
+				tags: _extra_tags
+			}
+			gauge: output._passthrough_gauge & {
+				tags: _extra_tags
+			}
+			histogram: output._passthrough_histogram & {
+				tags: _extra_tags
+			}
+			summary: output._passthrough_summary & {
+				tags: _extra_tags
+			}
+		}
+		traces: {
+			description: "A span received through an OTLP request."
+			fields: {
+				trace_id: {
+					description: "The hex-encoded identifier of the trace the span belongs to."
+					required:    true
+					type: string: {
+						examples: ["4ac52aadf321c2e531db005df08792f5"]
+					}
+				}
+				span_id: {
+					description: "The hex-encoded identifier of the span."
+					required:    true
+					type: string: {
+						examples: ["0b9e4bda2a55530d"]
+					}
+				}
+				parent_span_id: {
+					description: "The hex-encoded identifier of the parent span, empty for root spans."
+					required:    true
+					type: string: {
+						examples: ["1a2b3c4d5e6f7a8b"]
+					}
+				}
+				name: {
+					description: "The name of the operation the span represents."
+					required:    true
+					type: string: {
+						examples: ["GET /users"]
+					}
+				}
+				resources: {
+					description: "Set of attributes that describe the resource."
+					required:    false
+					type: object: options: {}
+				}
+				scope: {
+					description: "The instrumentation scope that produced the span."
+					required:    false
+					type: object: options: {}
+				}
+			}
+		}
 	}
 
 	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body:  """
+				Each OTLP data point is converted into a Vector metric named after the OTLP metric:
+
+				- Gauges become `gauge` metrics.
+				- Monotonic sums become `counter` metrics, and non-monotonic sums become `gauge` metrics.
+				- Histograms and exponential histograms become `histogram` metrics. The buckets of
+				  exponential histograms are converted to their explicit boundaries.
+				- Summaries become `summary` metrics.
+
+				Data points with the `delta` aggregation temporality are incremental, while data points
+				with the `cumulative` temporality are absolute. Data points flagged as having no recorded
+				value are dropped.
+
+				The attributes of each data point are added as tags. Resource attributes are added as tags
+				prefixed with `resource.`, and the instrumentation scope name, version, and attributes as
+				tags prefixed with `scope.`.
+				"""
+		}
+		traces: {
+			title: "Traces"
+			body:  """
+				Each OTLP span is converted into a Vector trace event holding the span fields, such as
+				`trace_id`, `span_id`, `parent_span_id`, `name`, `kind`, `start_time_unix_nano`,
+				`end_time_unix_nano`, `attributes`, `events`, `links`, and `status`. Identifiers are
+				hex-encoded. The resource attributes are added under `resources`, and the instrumentation
+				scope under `scope`.
+				"""
+		}
 		tls: {
 			title: "Transport Layer Security (TLS)"
 			body:  """
@@ -0,0 +1,121 @@
+package metadata
+
+components: sources: pulsar: {
+	title: "Apache Pulsar"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: true
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.pulsar
+				interface: {
+					socket: {
+						api: {
+							title: "Pulsar protocol"
+							url:   urls.pulsar_protocol
+						}
+						direction: "incoming"
+						port:      6650
+						protocols: ["tcp"]
+						ssl: "disabled"
+					}
+				}
+			}
+			tls: enabled: false
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator"]
+		delivery:      "at_least_once"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.pulsar.configuration
+
+	output: logs: record: {
+		description: "An individual Pulsar message."
+		fields: {
+			message: {
+				description: "The raw payload of the Pulsar message."
+				required:    true
+				type: string: {
+					examples: ["53.126.150.246 - - [01/Oct/2020:11:25:58 -0400] \"GET /disintermediate HTTP/2.0\" 401 20308"]
+				}
+			}
+			message_key: {
+				description: "The partition key of the Pulsar message, if it has one."
+				required:    false
+				common:      true
+				type: string: {
+					default: null
+					examples: ["my-key"]
+				}
+			}
+			properties: {
+				description: "The properties of the Pulsar message."
+				required:    true
+				type: object: {}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["pulsar"]
+				}
+			}
+			timestamp: fields._current_timestamp
+			topic: {
+				description: "The fully qualified name of the topic the message was read from."
+				required:    true
+				type: string: {
+					examples: ["persistent://public/default/my-topic"]
+				}
+			}
+		}
+	}
+
+	how_it_works: {
+		acknowledgements: {
+			title: "Acknowledgements"
+			body:  """
+				When acknowledgements are enabled, each message is acknowledged to the broker once the
+				events decoded from it have been delivered by all connected sinks. Messages whose events
+				are rejected, or could not be delivered, are negatively acknowledged, which makes the broker
+				redeliver them after a delay. When acknowledgements are disabled, messages are acknowledged
+				as soon as their events have been sent downstream.
+				"""
+		}
+		subscriptions: {
+			title: "Subscriptions"
+			body:  """
+				Messages are consumed through the subscription named by `subscription_name`, which keeps
+				track of the acknowledged messages. The `subscription_type` option controls how messages
+				are distributed when several instances of Vector consume from the same subscription: an
+				`exclusive` subscription only admits a single consumer, `failover` delivers to one active
+				consumer at a time, `shared` distributes messages among all consumers, and `key_shared`
+				does so while keeping messages with the same key on the same consumer.
+				"""
+		}
+	}
+}
@@ -5,7 +5,7 @@ components: sources: redis: {
 
 	features: {
 		auto_generated:   true
-		acknowledgements: false
+		acknowledgements: true
 		collect: {
 			checkpoint: enabled: false
 			tls: enabled:        false
@@ -94,5 +94,16 @@ components: sources: redis: {
 				API.
 				"""
 		}
+		streams: {
+			title: "Streams"
+			body:  """
+				With the `stream` data type, the source reads entries as a member of a consumer group, so several
+				instances can share the entries of a stream. An entry is acknowledged with `XACK` once its events
+				have been delivered, or right away when acknowledgements are disabled. Entries that stay pending
+				for longer than `stream.claim_min_idle_secs`, for example because another consumer stopped, are
+				claimed with `XAUTOCLAIM` and processed again. The ID of each entry is available in the event
+				metadata, or in the field configured by `stream.id_key`.
+				"""
+		}
 	}
 }
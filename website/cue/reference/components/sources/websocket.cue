@@ -0,0 +1,97 @@
+package metadata
+
+components: sources: websocket: {
+	title: "WebSocket"
+
+	features: {
+		auto_generated:   true
+		acknowledgements: false
+		collect: {
+			checkpoint: enabled: false
+			from: {
+				service: services.websocket
+				interface: {
+					socket: {
+						direction: "outgoing"
+						protocols: ["tcp"]
+						ssl: "optional"
+					}
+				}
+			}
+			tls: {
+				enabled:                true
+				can_verify_certificate: true
+				can_verify_hostname:    true
+				enabled_default:        false
+			}
+		}
+		multiline: enabled: false
+		codecs: {
+			enabled:         true
+			default_framing: "bytes"
+		}
+	}
+
+	classes: {
+		commonly_used: false
+		deployment_roles: ["aggregator", "sidecar"]
+		delivery:      "best_effort"
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	installation: {
+		platform_name: null
+	}
+
+	configuration: base.components.sources.websocket.configuration
+
+	output: logs: record: {
+		description: "An individual WebSocket message."
+		fields: {
+			message: {
+				description: "The payload of the text or binary WebSocket message."
+				required:    true
+				type: string: {
+					examples: ["{\"level\": \"info\", \"message\": \"User logged in\"}"]
+				}
+			}
+			source_type: {
+				description: "The name of the source type."
+				required:    true
+				type: string: {
+					examples: ["websocket"]
+				}
+			}
+			timestamp: fields._current_timestamp
+		}
+	}
+
+	how_it_works: {
+		connection: {
+			title: "Connection and subscription"
+			body:  """
+				The source connects to the server at `uri` and decodes every text and binary message it
+				receives with the configured `framing` and `decoding`. If `initial_message` is set, it
+				is sent as a text message right after the connection is established, which lets the
+				source subscribe to servers that only start streaming on request.
+				"""
+		}
+		reconnects: {
+			title: "Keepalive and reconnects"
+			body:  """
+				When `ping_interval` is set, the source pings the server on that interval, and with
+				`ping_timeout` it considers the connection broken when no pong arrives in time. Whenever
+				the connection is closed or fails, the source reconnects, backing off exponentially
+				between failed attempts, and sends the `initial_message` again.
+				"""
+		}
+	}
+}
@@ -170,6 +170,12 @@ components: transforms: aggregate: {
 				values 93 and 95 would result in a single `absolute` `gauge` with the value of 95. More complex
 				types like `distribution`, `histogram`, `set`, and `summary` behave similarly with `incremental`
 				values being combined in a manner that makes sense based on their type.
+
+				This describes the default `auto` mode. Other modes can be selected with the `mode` option,
+				for example `mean`, `max`, or `stdev` to reduce a high-frequency `absolute` `gauge` to a
+				single value per interval, or `distribution` to convert its values into an `incremental`
+				`distribution` so that their spread is preserved. Metrics not supported by the selected mode
+				are dropped.
 				"""
 		}
 
@@ -1,11 +1,67 @@
 package metadata
 
-base: components: transforms: aggregate: configuration: interval_ms: {
-	description: """
-		The interval between flushes, in milliseconds.
-
-		During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
-		"""
-	required: false
-	type: uint: default: 10000
+base: components: transforms: aggregate: configuration: {
+	interval_ms: {
+		description: """
+			The interval between flushes, in milliseconds.
+
+			During this time frame, metrics with the same series data (name, namespace, tags, and so on) are aggregated.
+			"""
+		required: false
+		type: uint: default: 10000
+	}
+	mode: {
+		description: "The aggregation mode to apply to metrics within each interval."
+		required:    false
+		type: string: {
+			default: "auto"
+			enum: {
+				auto: "Sums incremental metrics and keeps the latest value of absolute metrics."
+				count: "Counts the metrics received for each series, emitted as an incremental counter."
+				diff: """
+					Emits the difference between the latest value of an absolute metric and its value in
+					the previous interval.
+
+					Counters are emitted as incremental metrics. Nothing is emitted for a series until it has
+					been seen in two consecutive intervals. Incremental metrics are dropped.
+					"""
+				distribution: """
+					Converts the values of an absolute gauge into an incremental distribution.
+
+					This allows downsampling high-frequency gauges while preserving their spread. Other
+					metrics are dropped.
+					"""
+				latest: """
+					Keeps the latest value of absolute metrics.
+
+					Incremental metrics are dropped.
+					"""
+				max: """
+					Emits the maximum value of an absolute gauge.
+
+					Other metrics are dropped.
+					"""
+				mean: """
+					Emits the mean value of an absolute gauge.
+
+					Other metrics are dropped.
+					"""
+				min: """
+					Emits the minimum value of an absolute gauge.
+
+					Other metrics are dropped.
+					"""
+				stdev: """
+					Emits the population standard deviation of an absolute gauge.
+
+					Other metrics are dropped.
+					"""
+				sum: """
+					Sums incremental metrics.
+
+					Absolute metrics are dropped.
+					"""
+			}
+		}
+	}
 }
@@ -4,12 +4,40 @@ base: components: transforms: dedupe: configuration: {
 	cache: {
 		description: "Caching configuration for deduplication."
 		required:    false
-		type: object: options: num_events: {
-			description: "Number of events to cache and use for comparing incoming events to previously seen events."
-			required:    false
-			type: uint: default: 5000
+		type: object: options: {
+			num_events: {
+				description: "Number of events to cache and use for comparing incoming events to previously seen events."
+				required:    false
+				type: uint: default: 5000
+			}
+			ttl_secs: {
+				description: """
+					The maximum time an event is cached for, in seconds.
+
+					Once an event has been cached for this long, the next matching event is no longer
+					considered a duplicate, and is cached in its place. By default, events are cached until
+					they are evicted to make room for new ones.
+					"""
+				required: false
+				type: uint: {
+					examples: [60]
+					unit: "seconds"
+				}
+			}
 		}
 	}
+	emit_summaries: {
+		description: """
+			Whether to emit a summary event when a cached event with suppressed duplicates expires.
+
+			The summary is a copy of the cached event, with its message replaced by
+			`message repeated N times`, its timestamp set to the time of the last duplicate, and a
+			`repeat_count` field holding the number of duplicates. Summaries are emitted when the cached
+			event reaches `cache.ttl_secs`, is evicted from the cache, or when Vector shuts down.
+			"""
+		required: false
+		type: bool: default: false
+	}
 	fields: {
 		description: """
 			Options to control what fields to match against.
@@ -0,0 +1,31 @@
+package metadata
+
+base: components: transforms: exclusive_route: configuration: routes: {
+	description: """
+		An ordered list of routes.
+
+		Conditions are checked in order, and each event is sent to the first route it matches only.
+		If an event doesn't match any route, it is sent to the `<transform_name>._unmatched` output.
+
+		Both `_unmatched`, as well as `_default`, are reserved output names and thus cannot be used
+		as a route name.
+		"""
+	required: true
+	type: array: items: type: object: options: {
+		condition: {
+			description: "The condition an event must match to be sent to this route."
+			required:    true
+			type: condition: {}
+		}
+		name: {
+			description: """
+				The name of the route.
+
+				The route can be referenced as an input by other components with the name
+				`<transform_name>.<name>`.
+				"""
+			required: true
+			type: string: examples: ["errors", "audit"]
+		}
+	}
+}
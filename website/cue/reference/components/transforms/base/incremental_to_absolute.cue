@@ -0,0 +1,24 @@
+package metadata
+
+base: components: transforms: incremental_to_absolute: configuration: {
+	max_series: {
+		description: """
+			The maximum number of series to track.
+
+			When a new series would exceed this limit, the least recently seen series is evicted, and its
+			running total starts over if it's seen again. By default, the number of series is unbounded.
+			"""
+		required: false
+		type: uint: unit: "series"
+	}
+	time_to_live_secs: {
+		description: """
+			The amount of time, in seconds, a series is tracked after it was last seen.
+
+			When an incremental metric arrives for a series that has expired, its running total starts over
+			from the value of that metric.
+			"""
+		required: false
+		type: uint: default: 300
+	}
+}
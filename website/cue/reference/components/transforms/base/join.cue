@@ -0,0 +1,106 @@
+package metadata
+
+base: components: transforms: join: configuration: {
+	flush_period_ms: {
+		description: "The interval to check for and flush any expired events, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	join_type: {
+		description: "The type of join."
+		required:    false
+		type: string: {
+			default: "inner"
+			enum: {
+				inner: """
+					Only joined events are sent to the default output.
+
+					Left events for which no matching right event was received are sent to the `unmatched`
+					output.
+					"""
+				left: """
+					Joined events, as well as left events for which no matching right event was received, are
+					sent to the default output.
+					"""
+			}
+		}
+	}
+	left: {
+		description: """
+			The input whose events are the left side of the join.
+
+			Must be the ID of one of the transform's inputs, such as `my_source` or `my_route.errors`.
+			"""
+		required: true
+		type: string: examples: ["http_requests"]
+	}
+	merge_strategies: {
+		description: """
+			A map of field names to custom merge strategies.
+
+			For each field specified, the given strategy is used for combining the values of the left
+			and right events. Otherwise, the value of the left event is kept when both events have the
+			field.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "An individual merge strategy."
+			required:    true
+			type: string: enum: {
+				array:          "Append each value to an array."
+				concat:         "Concatenate each string value, delimited with a space."
+				concat_newline: "Concatenate each string value, delimited with a newline."
+				concat_raw:     "Concatenate each string, without a delimiter."
+				discard:        "Discard all but the first value found."
+				flat_unique:    "Create a flattened array of all unique values."
+				longest_array:  "Keep the longest array seen."
+				max:            "Keep the maximum numeric value seen."
+				min:            "Keep the minimum numeric value seen."
+				retain: """
+					Discard all but the last value found.
+
+					Works as a way to coalesce by not retaining `null`.
+					"""
+				shortest_array: "Keep the shortest array seen."
+				sum:            "Sum all numeric values."
+			}
+		}
+	}
+	on: {
+		description: """
+			An ordered list of fields by which to join events.
+
+			Events from the left and right inputs are joined when they have the same values for all of
+			these fields. Events missing any of these fields are never joined, and are flushed right
+			away as unmatched events.
+			"""
+		required: true
+		type: array: items: type: string: examples: ["request_id", "client_ip"]
+	}
+	right: {
+		description: """
+			The input whose events are the right side of the join.
+
+			Must be the ID of one of the transform's inputs, such as `my_source` or `my_route.errors`.
+			"""
+		required: true
+		type: string: examples: ["http_responses"]
+	}
+	window_ms: {
+		description: """
+			The maximum period of time an event waits for a matching event from the other input, in
+			milliseconds.
+
+			Right events are kept for this period of time, and are joined with every matching left event
+			received in the meantime.
+			"""
+		required: false
+		type: uint: {
+			default: 30000
+			unit:    "milliseconds"
+		}
+	}
+}
@@ -0,0 +1,97 @@
+package metadata
+
+base: components: transforms: log_patterns: configuration: {
+	data_dir: {
+		description: """
+			The directory used to persist patterns.
+
+			By default, the global `data_dir` option is used. Make sure the running user has write
+			permissions to this directory.
+			"""
+		required: false
+		type: string: examples: ["/var/lib/vector"]
+	}
+	depth: {
+		description: """
+			The depth of the tree used to match messages to patterns.
+
+			Messages are matched by their number of tokens, then by their first `depth - 2` tokens, before
+			being compared to the patterns they were matched to. Must be at least `3`.
+			"""
+		required: false
+		type: uint: default: 4
+	}
+	emit_metrics: {
+		description: """
+			Whether to periodically emit the number of events of each pattern as metrics.
+
+			An incremental `log_pattern_events_total` counter is emitted every `flush_period_secs` for
+			each pattern that matched events, tagged with `pattern_id` and `pattern`.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	flush_period_secs: {
+		description: "The interval at which metrics are emitted and patterns are persisted, in seconds."
+		required:    false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
+	}
+	max_children: {
+		description: """
+			The maximum number of children of each node of the tree used to match messages to patterns.
+
+			Once a node is full, messages with new tokens are matched together.
+			"""
+		required: false
+		type: uint: default: 100
+	}
+	max_clusters: {
+		description: """
+			The maximum number of patterns.
+
+			Once this limit is reached, the least recently matched pattern is forgotten to make room
+			for new ones.
+			"""
+		required: false
+		type: uint: {
+			default: 1000
+			unit:    "patterns"
+		}
+	}
+	message_field: {
+		description: """
+			The field containing the message to cluster.
+
+			By default, the message of the event is used.
+			"""
+		required: false
+		type: string: examples: ["message", "parent.child"]
+	}
+	pattern_field: {
+		description: """
+			The field to store the pattern of the event in.
+
+			The variable parts of the pattern are replaced with `<*>`.
+			"""
+		required: false
+		type: string: default: "pattern"
+	}
+	pattern_id_field: {
+		description: "The field to store the ID of the pattern of the event in."
+		required:    false
+		type: string: default: "pattern_id"
+	}
+	persist: {
+		description: "Whether to persist patterns, so that their IDs are stable across restarts."
+		required:    false
+		type: bool: default: false
+	}
+	similarity_threshold: {
+		description: "The fraction of tokens a message must share with a pattern to be part of it."
+		required:    false
+		type: float: default: 0.4
+	}
+}
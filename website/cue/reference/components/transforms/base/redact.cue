@@ -0,0 +1,76 @@
+package metadata
+
+base: components: transforms: redact: configuration: {
+	detectors: {
+		description: "The detectors used to find sensitive values."
+		required:    true
+		type: array: items: type: object: options: {
+			pattern: {
+				description:   "The regular expression matching sensitive values."
+				relevant_when: "type = \"regex\""
+				required:      true
+				type: string: examples: ["user-\\d+"]
+			}
+			type: {
+				description: "The type of sensitive values to detect."
+				required:    true
+				type: string: enum: {
+					aws_access_key: "AWS access key IDs."
+					credit_card:    "Credit card numbers, validated with the Luhn algorithm."
+					email:          "Email addresses."
+					iban:           "International bank account numbers, validated with their check digits."
+					ipv4:           "IPv4 addresses."
+					ipv6:           "IPv6 addresses."
+					jwt:            "JSON Web Tokens."
+					regex:          "Values matching a regular expression."
+				}
+			}
+		}
+	}
+	fields: {
+		description: """
+			The fields to redact.
+
+			When no fields are specified, all string values of the event are redacted, including the
+			ones nested in objects and arrays.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["message", "user.email"]
+		}
+	}
+	strategy: {
+		description: "The strategy used to redact sensitive values."
+		required:    false
+		type: object: options: {
+			placeholder: {
+				description:   "The placeholder replacing sensitive values."
+				relevant_when: "type = \"replace\""
+				required:      false
+				type: string: default: "[REDACTED]"
+			}
+			salt: {
+				description:   "The salt prepended to sensitive values before hashing them."
+				relevant_when: "type = \"hash\""
+				required:      false
+				type: string: {}
+			}
+			type: {
+				description: "The strategy used to redact sensitive values."
+				required:    true
+				type: string: enum: {
+					drop: "Removes the fields containing sensitive values."
+					hash: """
+						Replaces sensitive values with the hex-encoded SHA-256 hash of the salt followed by the
+						value.
+
+						Hashing keeps equal values correlated across events without revealing them.
+						"""
+					mask:    "Replaces each character of sensitive values with `*`."
+					replace: "Replaces sensitive values with a placeholder."
+				}
+			}
+		}
+	}
+}
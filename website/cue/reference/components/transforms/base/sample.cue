@@ -1,6 +1,56 @@
 package metadata
 
 base: components: transforms: sample: configuration: {
+	adaptive: {
+		description: """
+			Configuration of the adaptive sampling mode.
+
+			Instead of a fixed `rate`, each unique value of `key_field` gets its own sample rate, adjusted
+			periodically so that the events forwarded across all keys stay within a budget. Keys with few
+			events are forwarded in full, while the most frequent keys are sampled harder.
+			"""
+		required: false
+		type: object: options: {
+			adjustment_interval_secs: {
+				description: """
+					The interval at which sample rates are adjusted, in seconds.
+
+					Sample rates are based on the volume of each key during the previous interval.
+					"""
+				required: false
+				type: uint: {
+					default: 10
+					unit:    "seconds"
+				}
+			}
+			max_keys: {
+				description: """
+					The maximum number of keys with their own sample rate.
+
+					Once this limit is reached, events with new keys share a single sample rate until the next
+					adjustment.
+					"""
+				required: false
+				type: uint: default: 10000
+			}
+			measure: {
+				description: "The volume targeted by `target_per_sec`."
+				required:    false
+				type: string: {
+					default: "count"
+					enum: {
+						bytes: "The estimated JSON-encoded size of events, in bytes."
+						count: "The number of events."
+					}
+				}
+			}
+			target_per_sec: {
+				description: "The number of events, or of bytes, per second to forward across all keys."
+				required:    true
+				type: float: examples: [1000.0]
+			}
+		}
+	}
 	exclude: {
 		description: "A logical condition used to exclude events from sampling."
 		required:    false
@@ -19,6 +69,8 @@ base: components: transforms: sample: configuration: {
 
 			This can be useful to, for example, ensure that all logs for a given transaction are
 			sampled together, but that overall `1/N` transactions are sampled.
+
+			When `adaptive` is set, each unique value for the key gets its own sample rate instead.
 			"""
 		required: false
 		type: string: examples: ["message"]
@@ -29,8 +81,10 @@ base: components: transforms: sample: configuration: {
 
 			For example, `rate = 10` means 1 out of every 10 events are forwarded and the rest are
 			dropped.
+
+			Required unless `adaptive` is set.
 			"""
-		required: true
+		required: false
 		type: uint: {}
 	}
 }
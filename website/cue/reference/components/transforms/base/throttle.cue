@@ -19,14 +19,92 @@ base: components: transforms: throttle: configuration: {
 			syntax: "template"
 		}
 	}
+	overrides: {
+		description: """
+			Thresholds for specific buckets, keyed by the value of `key_field`.
+
+			These take precedence over `threshold` and `overrides_table`.
+			"""
+		required: false
+		type: object: options: "*": {
+			description: "The threshold of the bucket with this key."
+			required:    true
+			type: {
+				object: options: {
+					events: {
+						description: "The number of events allowed."
+						required:    false
+						type: uint: {}
+					}
+					json_bytes: {
+						description: "The number of bytes allowed, measured by the estimated JSON-encoded size of events."
+						required:    false
+						type: uint: {}
+					}
+				}
+				uint: {}
+			}
+		}
+	}
+	overrides_table: {
+		description: """
+			An enrichment table from which the thresholds of specific buckets are read.
+
+			The row whose `key_column` is equal to the value of `key_field` holds the threshold of the
+			bucket. Buckets without a row, or whose row has no valid limit, use `threshold`.
+			"""
+		required: false
+		type: object: options: {
+			events_column: {
+				description: "The column holding the number of events allowed."
+				required:    false
+				type: string: default: "events"
+			}
+			json_bytes_column: {
+				description: "The column holding the number of bytes allowed."
+				required:    false
+				type: string: default: "json_bytes"
+			}
+			key_column: {
+				description: "The column matched against the value of `key_field`."
+				required:    false
+				type: string: default: "key"
+			}
+			table: {
+				description: "The name of the enrichment table."
+				required:    true
+				type: string: examples: ["throttle_overrides"]
+			}
+		}
+	}
+	reroute_dropped: {
+		description: "Whether to send throttled events to the `dropped` output instead of discarding them."
+		required:    false
+		type: bool: default: false
+	}
 	threshold: {
 		description: """
-			The number of events allowed for a given bucket per configured `window_secs`.
+			The number of events, or of bytes, allowed for a given bucket per configured `window_secs`.
 
-			Each unique key has its own `threshold`.
+			Each unique key has its own `threshold`. When both events and bytes are limited, events are
+			throttled as soon as either limit is exceeded.
 			"""
 		required: true
-		type: uint: {}
+		type: {
+			object: options: {
+				events: {
+					description: "The number of events allowed."
+					required:    false
+					type: uint: {}
+				}
+				json_bytes: {
+					description: "The number of bytes allowed, measured by the estimated JSON-encoded size of events."
+					required:    false
+					type: uint: {}
+				}
+			}
+			uint: {}
+		}
 	}
 	window_secs: {
 		description: "The time window in which the configured `threshold` is applied, in seconds."
@@ -0,0 +1,68 @@
+package metadata
+
+base: components: transforms: top_k: configuration: {
+	capacity: {
+		description: """
+			The maximum number of keys tracked at once.
+
+			This bounds the memory used by the transform, regardless of the number of distinct keys.
+			The more keys are tracked, the more accurate the estimated values are. Defaults to ten times
+			`k`.
+			"""
+		required: false
+		type: uint: {}
+	}
+	interval_secs: {
+		description: """
+			The interval at which the top keys are emitted, in seconds.
+
+			The values are reset after each emission, so that the top keys of each interval are
+			emitted.
+			"""
+		required: false
+		type: uint: {
+			default: 60
+			unit:    "seconds"
+		}
+	}
+	k: {
+		description: "The number of keys to emit."
+		required:    false
+		type: uint: default: 10
+	}
+	key: {
+		description: "The key to track for each event."
+		required:    true
+		type: string: {
+			examples: ["{{ tenant_id }}", "{{ host }}"]
+			syntax: "template"
+		}
+	}
+	measure: {
+		description: "The value tracked for each key."
+		required:    false
+		type: string: {
+			default: "count"
+			enum: {
+				bytes: "The estimated JSON-encoded size of events, in bytes."
+				count: "The number of events."
+			}
+		}
+	}
+	metric_name: {
+		description: "The name of the gauges emitted when `output_type` is `metric`."
+		required:    false
+		type: string: default: "top_k"
+	}
+	output_type: {
+		description: "The type of events emitted."
+		required:    false
+		type: string: {
+			default: "log"
+			enum: {
+				log:    "A log event listing the top keys."
+				metric: "A gauge for each top key, tagged with the key."
+			}
+		}
+	}
+}
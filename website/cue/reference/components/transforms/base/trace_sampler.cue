@@ -0,0 +1,96 @@
+package metadata
+
+base: components: transforms: trace_sampler: configuration: {
+	decision_wait_ms: {
+		description: """
+			The amount of time to wait after the first span of a trace is received before deciding
+			whether to keep it, in milliseconds.
+
+			Spans of a trace received after the decision follow that same decision.
+			"""
+		required: false
+		type: uint: {
+			default: 30000
+			unit:    "milliseconds"
+		}
+	}
+	flush_period_ms: {
+		description: "The interval to check for traces ready for a decision, in milliseconds."
+		required:    false
+		type: uint: {
+			default: 1000
+			unit:    "milliseconds"
+		}
+	}
+	max_traces: {
+		description: """
+			The maximum number of traces awaiting a decision.
+
+			When a new trace would exceed this limit, the decision for the oldest trace is made
+			immediately, without waiting for the rest of its spans.
+			"""
+		required: false
+		type: uint: {
+			default: 50000
+			unit:    "traces"
+		}
+	}
+	policies: {
+		description: """
+			The policies used to decide whether to keep a trace.
+
+			A trace is kept if any of the policies decides to keep it, and dropped otherwise.
+			"""
+		required: true
+		type: array: items: type: object: options: {
+			condition: {
+				description:   "The condition to match against each event of a trace."
+				relevant_when: "type = \"condition\""
+				required:      true
+				type: condition: {}
+			}
+			rate: {
+				description:   "The fraction of traces to keep, between `0.0` and `1.0`."
+				relevant_when: "type = \"probabilistic\""
+				required:      true
+				type: float: {}
+			}
+			threshold_ms: {
+				description: """
+					The minimum duration of a trace to keep, in milliseconds.
+
+					The duration of a trace spans from the start of its earliest span to the end of its
+					latest span.
+					"""
+				relevant_when: "type = \"latency\""
+				required:      true
+				type: uint: unit: "milliseconds"
+			}
+			traces_per_second: {
+				description:   "The maximum number of traces to keep per second, for each service."
+				relevant_when: "type = \"rate_limiting\""
+				required:      true
+				type: uint: unit: "traces"
+			}
+			type: {
+				description: "The type of sampling policy."
+				required:    true
+				type: string: enum: {
+					condition: "Keeps traces with at least one event matching a condition."
+					error:     "Keeps traces containing at least one span with an error."
+					latency:   "Keeps traces lasting at least a given amount of time."
+					probabilistic: """
+						Keeps a fraction of traces.
+
+						Traces are chosen by hashing their ID, so the same traces are kept by every instance.
+						"""
+					rate_limiting: """
+						Keeps up to a number of traces per second for each service.
+
+						The service of a trace is the service of its root span.
+						"""
+				}
+			}
+		}
+	}
+}
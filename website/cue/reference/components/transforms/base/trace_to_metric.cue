@@ -0,0 +1,91 @@
+package metadata
+
+base: components: transforms: trace_to_metric: configuration: {
+	buckets: {
+		description: "The upper limits of the buckets of the span duration histogram, in seconds."
+		required:    false
+		type: array: {
+			default: [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0]
+			items: type: float: {}
+		}
+	}
+	cardinality_limit: {
+		description: """
+			Limits the number of distinct values of each dimension.
+
+			When not set, the cardinality of dimensions is not limited.
+			"""
+		required: false
+		type: object: options: {
+			cache_size_per_key: {
+				description: """
+					The size of the cache for detecting duplicate tags, in bytes.
+
+					The larger the cache size, the less likely it is to have a false positive, or a case where
+					we allow a new value for tag even after we have reached the configured limits.
+					"""
+				relevant_when: "mode = \"probabilistic\""
+				required:      false
+				type: uint: default: 5120000
+			}
+			limit_exceeded_action: {
+				description: """
+					Possible actions to take when an event arrives that would exceed the cardinality limit for one
+					or more of its tags.
+					"""
+				required: false
+				type: string: {
+					default: "drop_tag"
+					enum: {
+						drop_event: "Drop the entire event itself."
+						drop_tag:   "Drop the tag(s) that would exceed the configured limit."
+					}
+				}
+			}
+			mode: {
+				description: "Controls the approach taken for tracking tag cardinality."
+				required:    true
+				type: string: enum: {
+					exact: """
+						Tracks cardinality exactly.
+
+						This mode has higher memory requirements than `probabilistic`, but never falsely outputs
+						metrics with new tags after the limit has been hit.
+						"""
+					probabilistic: """
+						Tracks cardinality probabilistically.
+
+						This mode has lower memory requirements than `exact`, but may occasionally allow metric
+						events to pass through the transform even when they contain new tags that exceed the
+						configured limit. The rate at which this happens can be controlled by changing the value of
+						`cache_size_per_tag`.
+						"""
+				}
+			}
+			value_limit: {
+				description: "How many distinct values to accept for any given key."
+				required:    false
+				type: uint: default: 500
+			}
+		}
+	}
+	dimensions: {
+		description: """
+			The span fields used as tags on the generated metrics.
+
+			Each field is looked up on the span itself, then in its `meta`, `attributes`, and
+			`resources` objects. The `service` field also falls back to the `service.name` resource of
+			OpenTelemetry spans. Spans missing a field don't get the corresponding tag.
+			"""
+		required: false
+		type: array: {
+			default: ["service", "name"]
+			items: type: string: examples: ["service", "resource"]
+		}
+	}
+	namespace: {
+		description: "The namespace of the generated metrics."
+		required:    false
+		type: string: examples: ["apm"]
+	}
+}
@@ -0,0 +1,56 @@
+package metadata
+
+base: components: transforms: validate: configuration: {
+	reroute_dropped: {
+		description: """
+			Reroutes rejected events to a named output instead of dropping them.
+
+			When set to `true`, events that don't conform to their schema are forwarded to a
+			specially-named output, `rejected`, annotated with the reason they were rejected.
+			Otherwise, they are dropped.
+			"""
+		required: false
+		type: bool: default: false
+	}
+	schema: {
+		description: """
+			The name of the schema to validate each event against.
+
+			Required if more than one schema is configured. Events for which the name can't be rendered,
+			or doesn't match a configured schema, are rejected.
+			"""
+		required: false
+		type: string: {
+			examples: ["{{ kind }}", "{{ service }}-v1"]
+			syntax: "template"
+		}
+	}
+	schemas: {
+		description: "The JSON Schema documents events are validated against, by name."
+		required:    true
+		type: object: options: "*": {
+			description: "A JSON Schema document."
+			required:    true
+			type: object: options: {
+				file: {
+					description: """
+						File path to the JSON Schema document.
+
+						Required if `inline` is missing.
+						"""
+					required: false
+					type: string: examples: ["./my/schema.json"]
+				}
+				inline: {
+					description: """
+						The JSON Schema document, as a JSON string.
+
+						Required if `file` is missing.
+						"""
+					required: false
+					type: string: examples: ["{\"type\": \"object\", \"required\": [\"message\"]}"]
+				}
+			}
+		}
+	}
+}
@@ -0,0 +1,72 @@
+package metadata
+
+base: components: transforms: window: configuration: {
+	expire_after_ms: {
+		description: """
+			The maximum period of time a group can go without receiving events, in milliseconds.
+
+			Once a group has been idle for this long, its buffered events are dropped and its state is
+			removed, which bounds the memory used by groups that are no longer active.
+			"""
+		required: false
+		type: uint: {
+			default: 300000
+			unit:    "milliseconds"
+		}
+	}
+	forward_when: {
+		description: """
+			A condition for events that are always forwarded.
+
+			Matching events bypass the window entirely: they are neither buffered nor counted towards
+			`num_events_after`.
+			"""
+		required: false
+		type: condition: {}
+	}
+	group_by: {
+		description: """
+			An ordered list of fields by which to group events.
+
+			Each group keeps its own buffer and is triggered independently, which allows keeping the
+			context of independent event streams separate. When no fields are specified, all events
+			share a single buffer.
+			"""
+		required: false
+		type: array: {
+			default: []
+			items: type: string: examples: ["service", "host"]
+		}
+	}
+	num_events_after: {
+		description: "The number of events following a triggering event to forward."
+		required:    false
+		type: uint: {
+			default: 0
+			unit:    "events"
+		}
+	}
+	num_events_before: {
+		description: """
+			The number of events preceding a triggering event to forward.
+
+			Events are buffered until a trigger occurs, and the oldest event is dropped once the buffer
+			is full.
+			"""
+		required: false
+		type: uint: {
+			default: 100
+			unit:    "events"
+		}
+	}
+	trigger: {
+		description: """
+			The condition that triggers a window.
+
+			When an event matches, the buffered events preceding it are forwarded, followed by the
+			event itself and the next `num_events_after` events.
+			"""
+		required: true
+		type: condition: {}
+	}
+}
@@ -47,6 +47,29 @@ components: transforms: dedupe: {
 				"""
 		}
 
+		expiry: {
+			title: "Expiry"
+			body: """
+				When `cache.ttl_secs` is set, entries are also removed from the
+				cache once they have been cached for that long, whether or not
+				duplicates were received in the meantime. The next matching Event
+				is then passed through, and cached in its place.
+				"""
+		}
+
+		repeat_summaries: {
+			title: "Repeat Summaries"
+			body: """
+				When `emit_summaries` is enabled, an Event is emitted when an entry
+				for which duplicates were suppressed is removed from the cache,
+				similar to syslog's "message repeated N times" lines. The summary
+				is a copy of the first Event of the entry, with its message replaced
+				by `message repeated N times` and a `repeat_count` field holding the
+				number of suppressed duplicates. Since the first Event is kept in
+				memory, this increases the memory usage of each cache entry.
+				"""
+		}
+
 		memory_usage_details: {
 			title: "Memory Usage Details"
 			body: """
@@ -0,0 +1,92 @@
+package metadata
+
+components: transforms: exclusive_route: {
+	title: "Exclusive Route"
+
+	description: """
+		Splits a stream of events into multiple sub-streams based on an ordered list of
+		conditions. Unlike the `route` transform, each event is sent to the first route it
+		matches only.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		route: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.exclusive_route.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	examples: [
+		{
+			title: "Route by log level"
+
+			configuration: {
+				routes: [
+					{name: "errors", condition: #".level == "error""#},
+					{name: "important", condition: #".level == "error" || .level == "warn""#},
+				]
+			}
+
+			input: log: {
+				level: "error"
+			}
+			output: log: {
+				level: "error"
+			}
+		},
+	]
+
+	how_it_works: {
+		first_match: {
+			title: "First Match"
+			body: """
+				Routes are evaluated in the order they are defined, and evaluation stops at the first
+				route whose condition matches. This replaces chains of negated conditions that the
+				`route` transform requires to emulate `else if` semantics.
+				"""
+		}
+		unmatched: {
+			title: "Unmatched Events"
+			body: """
+				Events that don't match any route are sent to the `<transform_name>._unmatched`
+				output. If no component consumes that output, the events are discarded.
+				"""
+		}
+	}
+
+	outputs: [
+		{
+			name:        "<route_name>"
+			description: "Each route can be referenced as an input by other components with the name `<transform_name>.<route_name>`."
+		},
+		{
+			name:        "_unmatched"
+			description: "Events that don't match any route can be referenced as an input by other components with the name `<transform_name>._unmatched`."
+		},
+	]
+}
@@ -0,0 +1,106 @@
+package metadata
+
+components: transforms: incremental_to_absolute: {
+	title: "Incremental to Absolute"
+
+	description: """
+		Converts incremental metrics into absolute metrics by keeping a running total for each
+		series. This is useful when sending metrics produced as increments, for example by the
+		`statsd` source or the `log_to_metric` transform, to systems that expect cumulative values.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.incremental_to_absolute.configuration
+
+	input: {
+		logs: false
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: false
+	}
+
+	examples: [
+		{
+			title: "Accumulate an incremental counter"
+			configuration: {}
+			input: [
+				{
+					metric: {
+						kind: "incremental"
+						name: "requests"
+						counter: {
+							value: 2.0
+						}
+					}
+				},
+				{
+					metric: {
+						kind: "incremental"
+						name: "requests"
+						counter: {
+							value: 3.0
+						}
+					}
+				},
+			]
+			output: [
+				{
+					metric: {
+						kind: "absolute"
+						name: "requests"
+						counter: {
+							value: 2.0
+						}
+					}
+				},
+				{
+					metric: {
+						kind: "absolute"
+						name: "requests"
+						counter: {
+							value: 5.0
+						}
+					}
+				},
+			]
+		},
+	]
+
+	how_it_works: {
+		state: {
+			title: "State"
+			body: """
+				The transform keeps the running total of every series in memory, and emits the updated
+				total as soon as each incremental metric arrives. Absolute metrics are passed through
+				unchanged.
+
+				To bound memory usage, a series is forgotten once it hasn't been seen for
+				`time_to_live_secs`, and `max_series` caps the number of series tracked at once by
+				evicting the least recently seen series. A forgotten series starts over from zero, so
+				downstream systems observe it as a counter reset.
+				"""
+		}
+	}
+}
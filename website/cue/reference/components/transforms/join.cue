@@ -0,0 +1,97 @@
+package metadata
+
+components: transforms: join: {
+	title: "Join"
+
+	description: """
+		Correlates log events from two inputs, such as the start and end of requests, by joining
+		events that share the same values for a set of fields within a time window.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		reduce: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.join.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "unmatched"
+			description: """
+				Events for which no matching event was received from the other input within the
+				window are sent to the `unmatched` output when the window expires. For a transform
+				component named `foo`, this output can be accessed by specifying `foo.unmatched` as
+				the input to another component. With a `left` join, unmatched left events are sent to
+				the default output instead.
+				"""
+		},
+	]
+
+	examples: [
+		{
+			title: "Correlate request start and end logs"
+			configuration: {
+				left:  "request_end"
+				right: "request_start"
+				on: ["request_id"]
+			}
+			input: [
+				{log: {request_id: "abc", path: "/login", message: "Request started"}},
+				{log: {request_id: "abc", status: 200, message: "Request completed"}},
+			]
+			output: [
+				{log: {request_id: "abc", path: "/login", status: 200, message: "Request completed"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		inputs: {
+			title: "Left and right inputs"
+			body: """
+				Each event is attributed to the left or right side of the join by the input it was
+				received from, so both `left` and `right` must be listed in the transform's `inputs`,
+				otherwise the transform fails to build. Events received from any other input are sent to the `unmatched` output as-is.
+				"""
+		}
+		matching: {
+			title: "Matching"
+			body: """
+				Right events are kept for `window_ms`, and each left event received in the meantime
+				with the same values for the `on` fields is joined with the latest of them. Left
+				events received before their matching right event wait for it for up to `window_ms`.
+				Right events that were never joined are sent to the `unmatched` output when they
+				expire, as are left events that were never joined, unless `join_type` is `left`.
+				Events still waiting when Vector stops are flushed the same way.
+				"""
+		}
+		merging: {
+			title: "Merging"
+			body: """
+				The fields of the right event are merged into the left event. When both events have
+				a field, the value of the left event is kept, unless a strategy is configured for the
+				field in `merge_strategies`.
+				"""
+		}
+	}
+}
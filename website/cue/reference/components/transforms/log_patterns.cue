@@ -0,0 +1,96 @@
+package metadata
+
+components: transforms: log_patterns: {
+	title: "Log Patterns"
+
+	description: """
+		Clusters log messages into patterns as they flow through, and annotates each event with the
+		ID and text of its pattern. This makes it possible to find the noisiest log producers, and to
+		reduce volume by sampling or aggregating events per pattern downstream.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		shape: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.log_patterns.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	output: metrics: {
+		log_pattern_events_total: {
+			description:       "The number of events of a pattern since the last flush. Only emitted when `emit_metrics` is enabled."
+			type:              "counter"
+			default_namespace: ""
+			tags: {
+				pattern_id: {
+					description: "The ID of the pattern."
+					required:    true
+					examples: ["12"]
+				}
+				pattern: {
+					description: "The pattern."
+					required:    true
+					examples: ["user <*> logged in from <*>"]
+				}
+			}
+		}
+	}
+
+	examples: [
+		{
+			title: "Annotate events with their pattern"
+			configuration: {}
+			input: [
+				{log: {message: "user 42 logged in from 10.0.0.1"}},
+				{log: {message: "user 7 logged in from 10.0.0.2"}},
+			]
+			output: [
+				{log: {message: "user 42 logged in from 10.0.0.1", pattern_id: 0, pattern: "user 42 logged in from 10.0.0.1"}},
+				{log: {message: "user 7 logged in from 10.0.0.2", pattern_id: 0, pattern: "user <*> logged in from <*>"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		clustering: {
+			title: "Clustering"
+			body: """
+				Patterns are mined with the [Drain](https://jiemingzhu.github.io/pub/pjhe_icws2017.pdf)
+				algorithm. Messages are split into whitespace separated tokens, and matched against the
+				existing patterns with the same number of tokens and the same leading tokens. A message
+				joins the most similar pattern if at least `similarity_threshold` of its tokens are equal
+				to the pattern's, replacing the tokens that differ with `<*>`. Otherwise, it starts a new
+				pattern.
+
+				Patterns evolve as events are received, so the pattern an event is annotated with is the
+				pattern as it was when the event was processed, while its ID remains the same.
+				"""
+		}
+		persistence: {
+			title: "Persistence"
+			body: """
+				When `persist` is enabled, patterns are saved to the data directory every
+				`flush_period_secs` and when Vector shuts down, and loaded again on startup, so that
+				pattern IDs remain the same across restarts.
+				"""
+		}
+	}
+}
@@ -0,0 +1,89 @@
+package metadata
+
+components: transforms: redact: {
+	title: "Redact"
+
+	description: """
+		Redacts sensitive values, such as email addresses, credit card numbers, or access tokens,
+		from log events using built-in detectors, without having to maintain regular expressions in
+		`remap` programs.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		sanitize: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.redact.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Redact emails and credit cards"
+			configuration: {
+				detectors: [{type: "email"}, {type: "credit_card"}]
+			}
+			input: log: {
+				message: "Order by jane@example.com paid with 4111 1111 1111 1111"
+			}
+			output: log: {
+				message: "Order by [REDACTED] paid with [REDACTED]"
+			}
+		},
+		{
+			title: "Hash user identifiers"
+			configuration: {
+				fields: ["user"]
+				detectors: [{type: "regex", pattern: #"user-\d+"#}]
+				strategy: {type: "hash", salt: "pepper"}
+			}
+			input: log: {
+				user: "user-42"
+			}
+			output: log: {
+				user: "78126829e9d59f54da027fdc286e75a02736dd09d28b8c2a84190f0277705850"
+			}
+		},
+	]
+
+	how_it_works: {
+		detection: {
+			title: "Detection"
+			body: """
+				Each string value is scanned by all detectors. Detectors with a checksum, such as
+				`credit_card` and `iban`, only match values with valid check digits, which reduces
+				false positives. When the values found by several detectors overlap, the value starting
+				first, then the longest value, is redacted.
+				"""
+		}
+		strategies: {
+			title: "Strategies"
+			body: """
+				The `replace`, `mask` and `hash` strategies replace the sensitive part of a string,
+				keeping the rest of it. The `drop` strategy removes the whole field containing a
+				sensitive value instead.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		redactions_total: components.sources.internal_metrics.output.metrics.redactions_total
+	}
+}
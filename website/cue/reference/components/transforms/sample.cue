@@ -4,14 +4,14 @@ components: transforms: sample: {
 	title: "Sample"
 
 	description: """
-		Samples events at a configurable rate.
+		Samples events at a configurable rate, or at rates adjusted per key to stay within a budget.
 		"""
 
 	classes: {
 		commonly_used: false
 		development:   "stable"
 		egress_method: "stream"
-		stateful:      false
+		stateful:      true
 	}
 
 	features: {
@@ -31,4 +31,30 @@ components: transforms: sample: {
 		metrics: null
 		traces:  true
 	}
+
+	how_it_works: {
+		adaptive_sampling: {
+			title: "Adaptive Sampling"
+			body: """
+				When `adaptive` is set, the volume of each unique value of `key_field`, in events or in
+				bytes, is measured over each `adaptive.adjustment_interval_secs`. At the end of each interval,
+				the budget of the interval, `adaptive.target_per_sec` times its duration, is split between
+				the keys: keys whose volume fits in an even share of the budget keep their whole volume, and
+				the rest of the budget is split evenly between the others. Each key is then sampled at
+				`1/N` during the next interval, where `N` is the smallest integer bringing the key's volume
+				within its share.
+
+				Keys that weren't seen during the previous interval are forwarded in full until the next
+				adjustment, so rare keys are always represented.
+				"""
+		}
+		sample_rate: {
+			title: "Sample Rate"
+			body: """
+				Each forwarded event is stamped with the `sample_rate` it was sampled at, so that counts
+				computed downstream can be re-weighted by multiplying each event by its rate. Events
+				excluded from sampling with `exclude` aren't stamped.
+				"""
+		}
+	}
 }
@@ -32,6 +32,18 @@ components: transforms: throttle: {
 		traces:  false
 	}
 
+	outputs: [
+		components._default_output,
+		{
+			name: "dropped"
+			description: """
+				When `reroute_dropped` is enabled, throttled events are sent to the `dropped` output
+				instead of being discarded. For a transform component named `foo`, this output can be
+				accessed by specifying `foo.dropped` as the input to another component.
+				"""
+		},
+	]
+
 	telemetry: metrics: {
 		events_discarded_total: components.sources.internal_metrics.output.metrics.events_discarded_total
 	}
@@ -101,13 +113,33 @@ components: transforms: throttle: {
 						replenishes a cell every 6 seconds and allows a burst of up to 10 events.
 						"""
 				},
+				{
+					title: "Byte Limits"
+					body: """
+						When `threshold.json_bytes` is set, each event consumes as many cells as its estimated
+						JSON-encoded size in bytes, from a separate rate limiter allowing up to `json_bytes` bytes per
+						`window_secs`. Events larger than `json_bytes` are always rate limited. When both
+						`threshold.events` and `threshold.json_bytes` are set, an event is rate limited as soon as
+						either limit is reached.
+						"""
+				},
+				{
+					title: "Per-Key Thresholds"
+					body: """
+						Buckets can be given their own threshold with `overrides`, keyed by the value of `key_field`, or
+						with `overrides_table`, an enrichment table with a row per key. Thresholds from `overrides`
+						take precedence over the ones from `overrides_table`, and buckets without an override use
+						`threshold`.
+						"""
+				},
 				{
 					title: "Rate Limited Events"
 					body: """
 						The rate limiter will allow up to `threshold` number of events through and drop any further events
 						for that particular bucket when the rate limiter is at capacity. Any event passed when the rate
 						limiter is at capacity will be discarded and tracked by an `events_discarded_total` metric tagged
-						by the bucket's `key`.
+						by the bucket's `key`, unless `reroute_dropped` is enabled, in which case it is sent to the
+						`dropped` output instead.
 						"""
 				},
 			]
@@ -0,0 +1,93 @@
+package metadata
+
+components: transforms: top_k: {
+	title: "Top K"
+
+	description: """
+		Tracks the keys with the most events, or the most bytes, and periodically emits them. This
+		makes it possible to find the noisiest tenants, hosts, or services without the memory cost of
+		counting every distinct key.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "batch"
+		stateful:      true
+	}
+
+	features: {
+		aggregate: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.top_k.configuration
+
+	input: {
+		logs: true
+		metrics: {
+			counter:      true
+			distribution: true
+			gauge:        true
+			histogram:    true
+			set:          true
+			summary:      true
+		}
+		traces: true
+	}
+
+	examples: [
+		{
+			title: "Emit the noisiest tenants"
+			configuration: {
+				key: "{{ tenant }}"
+				k:   2
+			}
+			input: [
+				{log: {tenant: "acme", message: "a"}},
+				{log: {tenant: "globex", message: "b"}},
+				{log: {tenant: "acme", message: "c"}},
+				{log: {tenant: "initech", message: "d"}},
+			]
+			output: [
+				{log: {
+					measure: "count"
+					top: [
+						{key: "acme", value: 2, error: 0},
+						{key: "globex", value: 1, error: 0},
+					]
+					timestamp: "2020-10-07T12:33:21.223543Z"
+				}},
+			]
+		},
+	]
+
+	how_it_works: {
+		accuracy: {
+			title: "Accuracy"
+			body: """
+				Keys are tracked with the
+				[Space-Saving](https://www.cs.ucsb.edu/sites/default/files/documents/2005-23.pdf)
+				algorithm. At most `capacity` keys are tracked at once. When a new key is received while
+				all of them are in use, it replaces the key with the lowest value, and inherits that value.
+
+				As a result, values are never underestimated. The `error` field of each key is the
+				maximum amount by which its value may be overestimated. Any key whose actual value is
+				higher than the total value of the interval divided by `capacity` is guaranteed to be
+				emitted if it's among the top `k`.
+				"""
+		}
+		intervals: {
+			title: "Intervals"
+			body: """
+				The top keys are emitted every `interval_secs`, and when Vector shuts down. Values are
+				reset after each emission, and nothing is emitted for intervals without events.
+				"""
+		}
+	}
+}
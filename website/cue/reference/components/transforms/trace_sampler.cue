@@ -0,0 +1,84 @@
+package metadata
+
+components: transforms: trace_sampler: {
+	title: "Trace Sampler"
+
+	description: """
+		Samples complete traces, deciding whether to keep each trace once all of its spans have had
+		time to arrive. Unlike sampling individual events, this keeps or drops every span of a trace
+		together, and allows keeping traces based on properties of the whole trace, such as errors
+		or latency.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.trace_sampler.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	how_it_works: {
+		decision_wait: {
+			title: "Decision wait"
+			body: """
+				Events are grouped by trace ID, read from the `trace_id` field of the event or from its
+				first span. A trace is held for `decision_wait_ms` after its first span is received, then
+				the policies are evaluated against all of its spans at once. The trace is kept if any
+				policy keeps it. Events without a trace ID are forwarded immediately.
+
+				Pending traces are decided and flushed when Vector shuts down.
+				"""
+		}
+		late_spans: {
+			title: "Late spans"
+			body: """
+				Decisions are remembered for the most recent `max_traces` traces, so spans that arrive
+				after a decision are kept or dropped along with the rest of their trace.
+				"""
+		}
+		memory: {
+			title: "Memory usage"
+			body: """
+				At most `max_traces` traces are held awaiting a decision. When a new trace would exceed
+				this limit, the oldest trace is decided immediately, without waiting for
+				`decision_wait_ms` to elapse.
+				"""
+		}
+		span_formats: {
+			title: "Span formats"
+			body: """
+				Events received from the Datadog Agent hold the spans of a trace in a `spans` array,
+				while events received over OTLP are a single span each. For both formats:
+
+				- The `error` policy keeps traces with a span whose `error` field is set, or whose
+				  `status.code` is `2` (error).
+				- The `latency` policy measures the duration of traces from the `start` and `duration`
+				  fields of spans, or from their `start_time_unix_nano` and `end_time_unix_nano` fields.
+				- The `rate_limiting` policy reads the service of a trace from the `service` field of its
+				  root span, or from its `resources."service.name"` field.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
@@ -0,0 +1,87 @@
+package metadata
+
+components: transforms: trace_to_metric: {
+	title: "Trace to Metric"
+
+	description: """
+		Derives request rate, error rate, and latency (RED) metrics from the spans of traces, tagged
+		with configurable span fields such as the service and operation name.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		convert: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.trace_to_metric.configuration
+
+	input: {
+		logs:    false
+		metrics: null
+		traces:  true
+	}
+
+	_dimension_tags: {
+		"*": {
+			description: "The value of each of the configured `dimensions` found on the span."
+			examples: ["checkout"]
+			required: false
+		}
+	}
+
+	output: metrics: {
+		spans_total: {
+			description:       "The number of spans."
+			type:              "counter"
+			default_namespace: ""
+			tags:              _dimension_tags
+		}
+		span_errors_total: {
+			description:       "The number of spans with an error."
+			type:              "counter"
+			default_namespace: ""
+			tags:              _dimension_tags
+		}
+		span_duration_seconds: {
+			description:       "The duration of spans."
+			type:              "histogram"
+			default_namespace: ""
+			tags:              _dimension_tags
+		}
+	}
+
+	how_it_works: {
+		metrics: {
+			title: "Metrics"
+			body: """
+				Every span of an incoming trace produces an incremental `spans_total` counter and, when
+				its `duration` field is set, a `span_duration_seconds` histogram with the configured
+				`buckets`. Spans whose `error` field is set also produce a `span_errors_total` counter.
+				Metrics are timestamped with the start of their span.
+
+				The metrics are not aggregated, so an `aggregate` transform can be used to reduce their
+				volume before they are sent to a sink.
+				"""
+		}
+		cardinality: {
+			title: "Cardinality"
+			body: """
+				Each dimension becomes a tag on the generated metrics. Dimensions with unbounded values
+				can be limited with `cardinality_limit`, which behaves like the `tag_cardinality_limit`
+				transform applied to the generated metrics.
+				"""
+		}
+	}
+}
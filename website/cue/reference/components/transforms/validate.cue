@@ -0,0 +1,104 @@
+package metadata
+
+components: transforms: validate: {
+	title: "Validate"
+
+	description: """
+		Validates log events against [JSON Schema](\(urls.json_schema)) documents, forwarding
+		conforming events to the default output and dropping the other events, or rerouting them
+		to a `rejected` output, annotated with the reasons they were rejected.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      false
+	}
+
+	features: {
+		route: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.validate.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	outputs: [
+		components._default_output,
+		{
+			name: "rejected"
+			description: """
+				When `reroute_dropped` is enabled, events that don't conform to their schema are sent
+				to the `rejected` output instead of being dropped. For a transform component named
+				`foo`, this output can be accessed by specifying `foo.rejected` as the input to another
+				component. Events sent to this output are annotated with the reason they were rejected.
+				"""
+		},
+	]
+
+	examples: [
+		{
+			title: "Reject events missing required fields"
+			configuration: {
+				reroute_dropped: true
+				schemas: user: inline: #"{"type": "object", "required": ["name", "age"]}"#
+			}
+			input: log: {
+				name: "jane"
+			}
+			output: log: {
+				name: "jane"
+				metadata: rejected: {
+					reason:  "invalid"
+					message: "Event does not conform to schema \"user\"."
+					schema:  "user"
+					errors: [{
+						instance_path: ""
+						schema_path:   "/required"
+						message:       "\"age\" is a required property"
+					}]
+					component_id:   "my_transform_id"
+					component_type: "validate"
+					component_kind: "transform"
+				}
+			}
+		},
+	]
+
+	how_it_works: {
+		schema_selection: {
+			title: "Schema selection"
+			body: """
+				When a single schema is configured, all events are validated against it. Otherwise,
+				the `schema` template is rendered for each event to select the schema by name. Events
+				for which the template can't be rendered, or which select an unknown schema, are
+				rejected with the `template_failed` and `unknown_schema` reasons respectively.
+				"""
+		}
+		rejected_events: {
+			title: "Rejected events"
+			body: """
+				Rejected events are annotated with a `rejected` object under the `metadata` field,
+				or under the `vector` metadata namespace when the Vector log namespace is used. Its
+				`errors` array lists each validation error, with the JSON pointers to the invalid value
+				(`instance_path`) and to the failing keyword of the schema (`schema_path`).
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+		component_errors_total:           components.sources.internal_metrics.output.metrics.component_errors_total
+	}
+}
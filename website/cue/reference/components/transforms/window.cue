@@ -0,0 +1,87 @@
+package metadata
+
+components: transforms: window: {
+	title: "Window"
+
+	description: """
+		Buffers events and forwards the ones surrounding an event that matches a trigger
+		condition, dropping all others. This keeps the context leading up to and following an
+		error, for example, without shipping the debug logs that are not related to it.
+		"""
+
+	classes: {
+		commonly_used: false
+		development:   "beta"
+		egress_method: "stream"
+		stateful:      true
+	}
+
+	features: {
+		filter: {}
+	}
+
+	support: {
+		requirements: []
+		warnings: []
+		notices: []
+	}
+
+	configuration: base.components.transforms.window.configuration
+
+	input: {
+		logs:    true
+		metrics: null
+		traces:  false
+	}
+
+	examples: [
+		{
+			title: "Forward the context of an error"
+			configuration: {
+				trigger:           #".level == "error""#
+				num_events_before: 1
+				num_events_after:  1
+			}
+			input: [
+				{log: {message: "Starting request", level: "debug"}},
+				{log: {message: "Connecting to database", level: "debug"}},
+				{log: {message: "Connection refused", level: "error"}},
+				{log: {message: "Retrying", level: "debug"}},
+				{log: {message: "Request completed", level: "debug"}},
+			]
+			output: [
+				{log: {message: "Connecting to database", level: "debug"}},
+				{log: {message: "Connection refused", level: "error"}},
+				{log: {message: "Retrying", level: "debug"}},
+			]
+		},
+	]
+
+	how_it_works: {
+		buffering: {
+			title: "Buffering"
+			body: """
+				Each group of events keeps a buffer of up to `num_events_before` events. When an event
+				matches `trigger`, the buffer is flushed, followed by the triggering event and the next
+				`num_events_after` events of the same group. Events that are not forwarded are dropped,
+				either because they are evicted from a full buffer or because `num_events_before` is
+				zero.
+
+				Buffered events are only forwarded when a trigger occurs, so they are not flushed when
+				Vector shuts down.
+				"""
+		}
+		expiration: {
+			title: "Group expiration"
+			body: """
+				Groups that receive no events for `expire_after_ms` are removed, along with their
+				buffered events, so that the number of groups held in memory stays bounded when
+				`group_by` fields have many distinct values over time.
+				"""
+		}
+	}
+
+	telemetry: metrics: {
+		component_discarded_events_total: components.sources.internal_metrics.output.metrics.component_discarded_events_total
+	}
+}
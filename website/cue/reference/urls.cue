@@ -284,6 +284,7 @@ urls: {
 	journalctl:                                 "https://www.freedesktop.org/software/systemd/man/journalctl.html"
 	journald:                                   "https://www.freedesktop.org/software/systemd/man/systemd-journald.service.html"
 	json:                                       "\(wikipedia)/wiki/JSON"
+	json_schema:                                "https://json-schema.org/"
 	json_types:                                 "\(wikipedia)/wiki/JSON#Data_types_and_syntax"
 	jsonnet:                                    "https://jsonnet.org/"
 	kafka:                                      "https://kafka.apache.org/"
@@ -360,6 +361,8 @@ urls: {
 	mongodb:                                    "https://www.mongodb.com"
 	mongodb_command_server_status:              "https://docs.mongodb.com/manual/reference/command/serverStatus/"
 	mongodb_connection_string_uri_format:       "https://docs.mongodb.com/manual/reference/connection-string/"
+	mqtt:                                       "https://mqtt.org/"
+	mqtt_topics:                                "https://docs.oasis-open.org/mqtt/mqtt/v3.1.1/os/mqtt-v3.1.1-os.html#_Toc398718106"
 	musl_builder_docker_image:                  "\(vector_repo)/blob/master/scripts/ci-docker-images/builder-x86_64-unknown-linux-musl/Dockerfile"
 	native_proto_schema:                        "\(vector_repo)/blob/master/lib/vector-core/proto/event.proto"
 	native_json_schema:                         "\(vector_repo)/blob/master/lib/codecs/tests/data/native_encoding/schema.cue"
@@ -443,6 +446,7 @@ urls: {
 	rfc_6891:                                   "https://tools.ietf.org/html/rfc6891"
 	rhel:                                       "https://www.redhat.com/en/technologies/linux-platforms/enterprise-linux"
 	rpm:                                        "https://rpm.org/"
+	rumqttc:                                    "\(github)/bytebeamio/rumqtt"
 	rust:                                       "https://www.rust-lang.org/"
 	rust_date_time:                             "https://docs.rs/chrono/latest/chrono/struct.DateTime.html"
 	rust_grok_library:                          "\(github)/daschl/grok"